@@ -41,7 +41,9 @@ fn default_config() -> cbindgen::Config {
                 ("VoidArg".into(), "void".into()),
                 ("KeyEventArg".into(), "KeyEvent".into()),
                 ("PointerEventArg".into(), "PointerEvent".into()),
+                ("DropEventArg".into(), "DropEvent".into()),
                 ("PointArg".into(), "Point".into()),
+                ("StringArg".into(), "SharedString".into()),
             ]
             .iter()
             .cloned()
@@ -91,6 +93,9 @@ fn gen_corelib(root_dir: &Path, include_dir: &Path) -> anyhow::Result<()> {
         "ImageItem",
         "ClippedImage",
         "TouchArea",
+        "DragArea",
+        "DropArea",
+        "GestureArea",
         "FocusScope",
         "Flickable",
         "Text",
@@ -101,6 +106,7 @@ fn gen_corelib(root_dir: &Path, include_dir: &Path) -> anyhow::Result<()> {
         "BoxShadow",
         "Rotate",
         "Opacity",
+        "TransformItem",
     ];
 
     config.export.include = [
@@ -113,11 +119,17 @@ fn gen_corelib(root_dir: &Path, include_dir: &Path) -> anyhow::Result<()> {
         "TextVerticalAlignment",
         "TextOverflow",
         "TextWrap",
+        "InputType",
+        "TextDecoration",
+        "LayoutDirection",
+        "BoundsBehavior",
         "ImageFit",
         "FillRule",
         "MouseCursor",
         "StandardButtonKind",
         "DialogButtonRole",
+        "AccessibleRole",
+        "AccessibleStringProperty",
         "PointerEventKind",
         "PointerEventButton",
         "PointerEvent",
@@ -150,7 +162,9 @@ fn gen_corelib(root_dir: &Path, include_dir: &Path) -> anyhow::Result<()> {
         "VoidArg",
         "KeyEventArg",
         "PointerEventArg",
+        "DropEventArg",
         "PointArg",
+        "StringArg",
         "Point",
         "sixtyfps_color_brighter",
         "sixtyfps_color_darker",
@@ -327,6 +341,21 @@ fn gen_corelib(root_dir: &Path, include_dir: &Path) -> anyhow::Result<()> {
         .body
         .insert("Flickable".to_owned(), "    inline Flickable(); inline ~Flickable();".into());
     config.export.pre_body.insert("FlickableDataBox".to_owned(), "struct FlickableData;".into());
+    config.export.body.insert("Text".to_owned(), "    inline Text(); inline ~Text();".into());
+    config.export.pre_body.insert("TextDataBox".to_owned(), "struct TextData;".into());
+    config
+        .export
+        .body
+        .insert("TextInput".to_owned(), "    inline TextInput(); inline ~TextInput();".into());
+    config.export.pre_body.insert("TextInputDataBox".to_owned(), "struct TextInputData;".into());
+    config.export.body.insert(
+        "GestureArea".to_owned(),
+        "    inline GestureArea(); inline ~GestureArea();".into(),
+    );
+    config
+        .export
+        .pre_body
+        .insert("GestureAreaDataBox".to_owned(), "struct GestureAreaData;".into());
     config.export.include.push("StandardListViewItem".into());
     cbindgen::Builder::new()
         .with_config(config)
@@ -358,7 +387,7 @@ fn gen_corelib(root_dir: &Path, include_dir: &Path) -> anyhow::Result<()> {
     namespace cbindgen_private {
         using sixtyfps::private_api::WindowRc;
         using namespace vtable;
-        struct KeyEvent; struct PointerEvent;
+        struct KeyEvent; struct PointerEvent; struct DropEvent;
         using private_api::Property;
         using private_api::PathData;
         using private_api::Point;
@@ -20,6 +20,7 @@ LICENSE END */
 #include <iostream> // FIXME: remove: iostream always bring it lots of code so we should not have it in this header
 #include <chrono>
 #include <optional>
+#include <functional>
 #include <thread>
 #include <mutex>
 #include <condition_variable>
@@ -75,6 +76,7 @@ using ItemTreeNode = cbindgen_private::ItemTreeNode<uint8_t>;
 using cbindgen_private::KeyboardModifiers;
 using cbindgen_private::KeyEvent;
 using cbindgen_private::PointerEvent;
+using cbindgen_private::DropEvent;
 
 /// Internal function that checks that the API that must be called from the main
 /// thread is indeed called from the main thread, or abort the program otherwise
@@ -125,6 +127,7 @@ public:
 
     float scale_factor() const { return sixtyfps_windowrc_get_scale_factor(&inner); }
     void set_scale_factor(float value) const { sixtyfps_windowrc_set_scale_factor(&inner, value); }
+    bool is_right_to_left() const { return sixtyfps_windowrc_is_right_to_left(&inner); }
 
     template<typename Component, typename ItemTree>
     void free_graphics_resources(Component *c, ItemTree items) const
@@ -139,6 +142,25 @@ public:
         cbindgen_private::sixtyfps_windowrc_set_focus_item(&inner, &item_rc);
     }
 
+    void text_input_undo(const ComponentRc &component_rc, uintptr_t item_index)
+    {
+        cbindgen_private::ItemRc item_rc { component_rc, item_index };
+        cbindgen_private::sixtyfps_windowrc_text_input_undo(&inner, &item_rc);
+    }
+
+    void text_input_redo(const ComponentRc &component_rc, uintptr_t item_index)
+    {
+        cbindgen_private::ItemRc item_rc { component_rc, item_index };
+        cbindgen_private::sixtyfps_windowrc_text_input_redo(&inner, &item_rc);
+    }
+
+    void flickable_scroll_to(const ComponentRc &component_rc, uintptr_t item_index, float x,
+                             float y, bool animated)
+    {
+        cbindgen_private::ItemRc item_rc { component_rc, item_index };
+        cbindgen_private::sixtyfps_windowrc_flickable_scroll_to(&inner, &item_rc, x, y, animated);
+    }
+
     template<typename Component, typename ItemTree>
     void init_items(Component *c, ItemTree items) const
     {
@@ -238,6 +260,52 @@ template<>
 struct ReturnWrapper<void>
 {
 };
+
+/// Wraps the value of a property in the type returned by the element_property function
+/// of the ComponentVTable.
+inline cbindgen_private::ElementPropertyValue element_property_value(float value)
+{
+    cbindgen_private::ElementPropertyValue result {};
+    result.kind = cbindgen_private::ElementPropertyKind::Number;
+    result.number = value;
+    return result;
+}
+inline cbindgen_private::ElementPropertyValue element_property_value(int value)
+{
+    return element_property_value(int64_t(value));
+}
+inline cbindgen_private::ElementPropertyValue element_property_value(int64_t value)
+{
+    cbindgen_private::ElementPropertyValue result {};
+    result.kind = cbindgen_private::ElementPropertyKind::Number;
+    result.number = double(value);
+    return result;
+}
+inline cbindgen_private::ElementPropertyValue element_property_value(bool value)
+{
+    cbindgen_private::ElementPropertyValue result {};
+    result.kind = cbindgen_private::ElementPropertyKind::Bool;
+    result.boolean = value;
+    return result;
+}
+inline cbindgen_private::ElementPropertyValue element_property_value(const SharedString &value)
+{
+    cbindgen_private::ElementPropertyValue result {};
+    result.kind = cbindgen_private::ElementPropertyKind::String;
+    result.string = value;
+    return result;
+}
+inline cbindgen_private::ElementPropertyValue element_property_value(const Brush &value)
+{
+    cbindgen_private::ElementPropertyValue result {};
+    result.kind = cbindgen_private::ElementPropertyKind::Brush;
+    result.brush = value;
+    return result;
+}
+inline cbindgen_private::ElementPropertyValue element_property_value(const Color &value)
+{
+    return element_property_value(Brush(value));
+}
 } // namespace private_api
 
 template<typename T>
@@ -433,9 +501,25 @@ struct AbstractRepeaterView
     virtual void row_added(int index, int count) = 0;
     virtual void row_removed(int index, int count) = 0;
     virtual void row_changed(int index) = 0;
+    virtual void row_moved(int from, int to, int count) = 0;
+    virtual void reset() = 0;
 };
 using ModelPeer = std::weak_ptr<AbstractRepeaterView>;
 
+/// Returns the new index of \a row after the \a count rows starting at \a from were moved to
+/// \a to
+inline int moved_row(int row, int from, int to, int count)
+{
+    if (row >= from && row < from + count)
+        return row - from + to;
+    else if (from < to && row >= from + count && row < to + count)
+        return row - count;
+    else if (to < from && row >= to && row < from)
+        return row + count;
+    else
+        return row;
+}
+
 } // namespace private_api
 
 /// \rst
@@ -495,6 +579,19 @@ protected:
         model_dirty_property.mark_dirty();
         for_each_peers([=](auto peer) { peer->row_removed(index, count); });
     }
+    /// Notify the views that \a count rows starting at \a from were moved so that they now
+    /// start at the row \a to (as an index in the model after the move)
+    void row_moved(int from, int to, int count)
+    {
+        for_each_peers([=](auto peer) { peer->row_moved(from, to, count); });
+    }
+    /// Notify the views that the model has changed completely, so that all the rows need to be
+    /// queried again
+    void reset()
+    {
+        model_dirty_property.mark_dirty();
+        for_each_peers([=](auto peer) { peer->reset(); });
+    }
 
 private:
     template<typename F>
@@ -585,8 +682,374 @@ public:
     }
 };
 
+/// A Model that only exposes the rows of a source model which are accepted by a filter function.
+///
+/// The FilterModel listens to the changes of the source model and updates itself accordingly.
+/// Call reset() when the filter function itself changes its behavior.
+template<typename ModelData>
+class FilterModel : public Model<ModelData>
+{
+    struct Listener : private_api::AbstractRepeaterView
+    {
+        FilterModel *model;
+        explicit Listener(FilterModel *model) : model(model) { }
+        void row_added(int index, int count) override { model->source_row_added(index, count); }
+        void row_removed(int index, int count) override
+        {
+            model->source_row_removed(index, count);
+        }
+        void row_changed(int index) override { model->source_row_changed(index); }
+        void row_moved(int from, int to, int count) override
+        {
+            model->source_row_moved(from, to, count);
+        }
+        void reset() override { model->reset(); }
+    };
+
+public:
+    /// Constructs a new FilterModel containing the rows of \a source for which \a filter
+    /// returns true.
+    FilterModel(std::shared_ptr<Model<ModelData>> source,
+                std::function<bool(const ModelData &)> filter)
+        : source(std::move(source)),
+          filter(std::move(filter)),
+          listener(std::make_shared<Listener>(this))
+    {
+        mapping = build_mapping();
+        this->source->attach_peer(listener);
+    }
+
+    int row_count() const override { return int(mapping.size()); }
+    ModelData row_data(int i) const override { return source->row_data(mapping[i]); }
+    void set_row_data(int i, const ModelData &value) override
+    {
+        source->set_row_data(mapping[i], value);
+    }
+
+    /// Applies the filter again on every row of the source model.
+    /// This must be called when the filter function changes its behavior.
+    void reset()
+    {
+        mapping = build_mapping();
+        Model<ModelData>::reset();
+    }
+
+    /// Returns the row in the source model that corresponds to the row \a filtered_row
+    /// of this model.
+    int unfiltered_row(int filtered_row) const { return mapping[filtered_row]; }
+
+    /// Returns the source model
+    const std::shared_ptr<Model<ModelData>> &source_model() const { return source; }
+
+private:
+    bool accepts(int row) const { return filter(source->row_data(row)); }
+
+    std::vector<int> build_mapping() const
+    {
+        std::vector<int> result;
+        for (int row = 0; row < source->row_count(); ++row) {
+            if (accepts(row))
+                result.push_back(row);
+        }
+        return result;
+    }
+
+    void source_row_added(int index, int count)
+    {
+        std::vector<int> added;
+        for (int row = index; row < index + count; ++row) {
+            if (accepts(row))
+                added.push_back(row);
+        }
+        auto it = std::lower_bound(mapping.begin(), mapping.end(), index);
+        std::for_each(it, mapping.end(), [=](int &row) { row += count; });
+        int position = int(it - mapping.begin());
+        mapping.insert(it, added.begin(), added.end());
+        if (!added.empty())
+            this->row_added(position, int(added.size()));
+    }
+
+    void source_row_removed(int index, int count)
+    {
+        auto begin = std::lower_bound(mapping.begin(), mapping.end(), index);
+        auto end = std::lower_bound(begin, mapping.end(), index + count);
+        int position = int(begin - mapping.begin());
+        int removed = int(end - begin);
+        auto it = mapping.erase(begin, end);
+        std::for_each(it, mapping.end(), [=](int &row) { row -= count; });
+        if (removed > 0)
+            this->row_removed(position, removed);
+    }
+
+    void source_row_moved(int from, int to, int count)
+    {
+        auto begin = std::lower_bound(mapping.begin(), mapping.end(), from);
+        auto end = std::lower_bound(begin, mapping.end(), from + count);
+        int position = int(begin - mapping.begin());
+        int moved = int(end - begin);
+        for (auto &row : mapping)
+            row = private_api::moved_row(row, from, to, count);
+        std::sort(mapping.begin(), mapping.end());
+        int new_position =
+                int(std::lower_bound(mapping.begin(), mapping.end(), to) - mapping.begin());
+        if (moved > 0 && position != new_position)
+            this->row_moved(position, new_position, moved);
+    }
+
+    void source_row_changed(int row)
+    {
+        auto it = std::lower_bound(mapping.begin(), mapping.end(), row);
+        int position = int(it - mapping.begin());
+        bool was_accepted = it != mapping.end() && *it == row;
+        bool accepted = accepts(row);
+        if (was_accepted && accepted) {
+            this->row_changed(position);
+        } else if (was_accepted) {
+            mapping.erase(it);
+            this->row_removed(position, 1);
+        } else if (accepted) {
+            mapping.insert(it, row);
+            this->row_added(position, 1);
+        }
+    }
+
+    std::shared_ptr<Model<ModelData>> source;
+    std::function<bool(const ModelData &)> filter;
+    /// The rows of the source model that are accepted by the filter, in increasing order
+    std::vector<int> mapping;
+    std::shared_ptr<Listener> listener;
+};
+
+/// A Model that exposes the rows of a source model sorted with a comparison function.
+///
+/// The comparison function returns true if its first argument must be placed before its
+/// second argument, like `std::less`. The sort is stable.
+///
+/// The SortModel listens to the changes of the source model and updates itself accordingly.
+/// Call reset() when the comparison function itself changes its behavior.
+template<typename ModelData>
+class SortModel : public Model<ModelData>
+{
+    struct Listener : private_api::AbstractRepeaterView
+    {
+        SortModel *model;
+        explicit Listener(SortModel *model) : model(model) { }
+        void row_added(int index, int count) override { model->source_row_added(index, count); }
+        void row_removed(int index, int count) override
+        {
+            model->source_row_removed(index, count);
+        }
+        void row_changed(int index) override { model->source_row_changed(index); }
+        void row_moved(int from, int to, int count) override
+        {
+            model->source_row_moved(from, to, count);
+        }
+        void reset() override { model->reset(); }
+    };
+
+public:
+    /// Constructs a new SortModel containing the rows of \a source sorted according to \a less.
+    SortModel(std::shared_ptr<Model<ModelData>> source,
+              std::function<bool(const ModelData &, const ModelData &)> less)
+        : source(std::move(source)),
+          less(std::move(less)),
+          listener(std::make_shared<Listener>(this))
+    {
+        mapping = build_mapping();
+        this->source->attach_peer(listener);
+    }
+
+    int row_count() const override { return int(mapping.size()); }
+    ModelData row_data(int i) const override { return source->row_data(mapping[i]); }
+    void set_row_data(int i, const ModelData &value) override
+    {
+        source->set_row_data(mapping[i], value);
+    }
+
+    /// Sorts all the rows of the source model again.
+    /// This must be called when the comparison function changes its behavior.
+    void reset()
+    {
+        mapping = build_mapping();
+        Model<ModelData>::reset();
+    }
+
+    /// Returns the row in the source model that corresponds to the row \a sorted_row
+    /// of this model.
+    int unsorted_row(int sorted_row) const { return mapping[sorted_row]; }
+
+    /// Returns the source model
+    const std::shared_ptr<Model<ModelData>> &source_model() const { return source; }
+
+private:
+    std::vector<int> build_mapping() const
+    {
+        std::vector<std::pair<int, ModelData>> rows;
+        for (int row = 0; row < source->row_count(); ++row)
+            rows.emplace_back(row, source->row_data(row));
+        std::stable_sort(rows.begin(), rows.end(),
+                         [&](const auto &a, const auto &b) { return less(a.second, b.second); });
+        std::vector<int> result;
+        for (const auto &row : rows)
+            result.push_back(row.first);
+        return result;
+    }
+
+    /// Returns the position at which a row with the given data needs to be inserted,
+    /// after all the rows that compare equal to it.
+    int sorted_position(const ModelData &data) const
+    {
+        auto it = std::upper_bound(mapping.begin(), mapping.end(), data,
+                                   [&](const ModelData &data, int row) {
+                                       return less(data, source->row_data(row));
+                                   });
+        return int(it - mapping.begin());
+    }
+
+    void source_row_added(int index, int count)
+    {
+        for (auto &row : mapping) {
+            if (row >= index)
+                row += count;
+        }
+        for (int row = index; row < index + count; ++row) {
+            int position = sorted_position(source->row_data(row));
+            mapping.insert(mapping.begin() + position, row);
+            this->row_added(position, 1);
+        }
+    }
+
+    void source_row_removed(int index, int count)
+    {
+        std::vector<int> removed;
+        for (int position = 0; position < int(mapping.size()); ++position) {
+            int &row = mapping[position];
+            if (row >= index + count)
+                row -= count;
+            else if (row >= index)
+                removed.push_back(position);
+        }
+        for (auto it = removed.rbegin(); it != removed.rend(); ++it) {
+            mapping.erase(mapping.begin() + *it);
+            this->row_removed(*it, 1);
+        }
+    }
+
+    void source_row_moved(int from, int to, int count)
+    {
+        // The data didn't change, so the sorted order stays the same
+        for (auto &row : mapping)
+            row = private_api::moved_row(row, from, to, count);
+    }
+
+    void source_row_changed(int row)
+    {
+        auto it = std::find(mapping.begin(), mapping.end(), row);
+        if (it == mapping.end())
+            return;
+        int old_position = int(it - mapping.begin());
+        mapping.erase(it);
+        int new_position = sorted_position(source->row_data(row));
+        mapping.insert(mapping.begin() + new_position, row);
+        if (new_position == old_position) {
+            this->row_changed(new_position);
+        } else {
+            this->row_removed(old_position, 1);
+            this->row_added(new_position, 1);
+        }
+    }
+
+    std::shared_ptr<Model<ModelData>> source;
+    std::function<bool(const ModelData &, const ModelData &)> less;
+    /// The rows of the source model, in sorted order
+    std::vector<int> mapping;
+    std::shared_ptr<Listener> listener;
+};
+
+/// A Model that exposes the rows of a source model transformed by a mapping function.
+template<typename SourceData, typename MappedData>
+class MapModel : public Model<MappedData>
+{
+    struct Listener : private_api::AbstractRepeaterView
+    {
+        MapModel *model;
+        explicit Listener(MapModel *model) : model(model) { }
+        void row_added(int index, int count) override { model->row_added(index, count); }
+        void row_removed(int index, int count) override { model->row_removed(index, count); }
+        void row_changed(int index) override { model->row_changed(index); }
+        void row_moved(int from, int to, int count) override
+        {
+            model->row_moved(from, to, count);
+        }
+        void reset() override { model->reset(); }
+    };
+
+public:
+    /// Constructs a new MapModel whose rows are the rows of \a source passed through \a map.
+    MapModel(std::shared_ptr<Model<SourceData>> source,
+             std::function<MappedData(const SourceData &)> map)
+        : source(std::move(source)), map(std::move(map)), listener(std::make_shared<Listener>(this))
+    {
+        this->source->attach_peer(listener);
+    }
+
+    int row_count() const override { return source->row_count(); }
+    MappedData row_data(int i) const override { return map(source->row_data(i)); }
+
+    /// Returns the source model
+    const std::shared_ptr<Model<SourceData>> &source_model() const { return source; }
+
+private:
+    std::shared_ptr<Model<SourceData>> source;
+    std::function<MappedData(const SourceData &)> map;
+    std::shared_ptr<Listener> listener;
+};
+
 namespace private_api {
 
+/// Splits \a s at each occurrence of \a separator. An empty separator splits the string into
+/// its individual characters.
+inline std::shared_ptr<Model<SharedString>> string_split(const SharedString &s,
+                                                         const SharedString &separator)
+{
+    std::vector<SharedString> result;
+    std::string_view view(s);
+    std::string_view sep(separator);
+    if (sep.empty()) {
+        for (std::size_t i = 0; i < view.size();) {
+            std::size_t len = 1;
+            // skip the UTF-8 continuation bytes
+            while (i + len < view.size() && (view[i + len] & 0xC0) == 0x80)
+                ++len;
+            result.emplace_back(view.substr(i, len));
+            i += len;
+        }
+    } else {
+        std::size_t pos = 0;
+        while (true) {
+            auto next = view.find(sep, pos);
+            result.emplace_back(view.substr(pos, next - pos));
+            if (next == std::string_view::npos)
+                break;
+            pos = next + sep.size();
+        }
+    }
+    return std::make_shared<VectorModel<SharedString>>(std::move(result));
+}
+
+/// Translates \a text with the currently loaded translations, and substitutes the placeholders
+/// with \a n and \a args. This is what `@tr(...)` compiles to.
+inline SharedString translate(const SharedString &context, const SharedString &text,
+                              const SharedString &plural, int n,
+                              std::initializer_list<SharedString> args)
+{
+    SharedString result;
+    cbindgen_private::sixtyfps_translate(
+            &result, &context, &text, &plural, n,
+            { const_cast<SharedString *>(args.begin()), args.size() });
+    return result;
+}
+
 template<typename C, typename ModelData>
 class Repeater
 {
@@ -623,6 +1086,37 @@ class Repeater
                 data[i].state = State::Dirty;
             }
         }
+        void row_moved(int from, int to, int count) override
+        {
+            int start = std::min(from, to);
+            int end = std::max(from, to) + count;
+            if (count == 0 || from == to)
+                return;
+            is_dirty.set(true);
+            if (end > int(data.size())) {
+                // the moved rows are not all known: update all the components, like a reset
+                for (auto &c : data)
+                    c.state = State::Dirty;
+                return;
+            }
+            // keep the components with their rows
+            if (from < to)
+                std::rotate(data.begin() + start, data.begin() + start + count,
+                            data.begin() + end);
+            else
+                std::rotate(data.begin() + start, data.begin() + end - count, data.begin() + end);
+            for (int i = start; i < end; ++i) {
+                // all the indexes are dirty
+                data[i].state = State::Dirty;
+            }
+        }
+        void reset() override
+        {
+            is_dirty.set(true);
+            // the components are kept to be re-used with the new data
+            for (auto &c : data)
+                c.state = State::Dirty;
+        }
     };
 
 public:
@@ -743,6 +1237,33 @@ cbindgen_private::Flickable::~Flickable()
     sixtyfps_flickable_data_free(&data);
 }
 
+cbindgen_private::Text::Text()
+{
+    sixtyfps_text_data_init(&data);
+}
+cbindgen_private::Text::~Text()
+{
+    sixtyfps_text_data_free(&data);
+}
+
+cbindgen_private::TextInput::TextInput()
+{
+    sixtyfps_textinput_data_init(&data);
+}
+cbindgen_private::TextInput::~TextInput()
+{
+    sixtyfps_textinput_data_free(&data);
+}
+
+cbindgen_private::GestureArea::GestureArea()
+{
+    sixtyfps_gesturearea_data_init(&data);
+}
+cbindgen_private::GestureArea::~GestureArea()
+{
+    sixtyfps_gesturearea_data_free(&data);
+}
+
 cbindgen_private::NativeStyleMetrics::NativeStyleMetrics()
 {
     sixtyfps_native_style_metrics_init(this);
@@ -910,6 +1431,23 @@ auto blocking_invoke_from_event_loop(Functor f) -> void {
     cv.wait(lock, [&] { return ok; });
 }
 
+/// Loads the translations from the content of a gettext `.po` file and uses them for all the
+/// strings marked with `@tr(...)`. Bindings depending on these strings are updated.
+/// \returns false if the content could not be parsed
+inline bool load_translations_from_po(std::string_view po_source)
+{
+    return cbindgen_private::sixtyfps_translations_load_po(
+            { const_cast<uint8_t *>(reinterpret_cast<const uint8_t *>(po_source.data())),
+              po_source.size() });
+}
+
+/// Removes the translations previously loaded with load_translations_from_po(), so that the
+/// strings marked with `@tr(...)` are shown untranslated.
+inline void clear_translations()
+{
+    cbindgen_private::sixtyfps_translations_clear();
+}
+
 namespace private_api {
 
 /// Registers a font by the specified path. The path must refer to an existing
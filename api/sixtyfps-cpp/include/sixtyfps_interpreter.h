@@ -317,6 +317,8 @@ public:
         Brush,
         /// Correspond to `image` type in .60.
         Image,
+        /// Correspond to an enum type in .60.
+        Enumeration,
         /// The type is not a public type but something internal.
         Other = -1,
     };
@@ -490,6 +492,15 @@ inline Value::Value(const std::shared_ptr<sixtyfps::Model<Value>> &model)
         {
             cbindgen_private::sixtyfps_interpreter_model_notify_row_removed(&notify, index, count);
         }
+        void row_moved(int from, int to, int count) override
+        {
+            cbindgen_private::sixtyfps_interpreter_model_notify_row_moved(&notify, from, to,
+                                                                          count);
+        }
+        void reset() override
+        {
+            cbindgen_private::sixtyfps_interpreter_model_notify_reset(&notify);
+        }
     };
 
     auto wrapper = std::make_shared<ModelWrapper>();
@@ -211,6 +211,73 @@ inline cbindgen_private::Slice<uint8_t> string_to_slice(std::string_view str)
         const_cast<unsigned char *>(reinterpret_cast<const unsigned char *>(str.data())), str.size()
     };
 }
+
+inline int string_length(const SharedString &s)
+{
+    return cbindgen_private::sixtyfps_shared_string_length(&s);
+}
+
+inline SharedString string_to_uppercase(const SharedString &s)
+{
+    SharedString result;
+    cbindgen_private::sixtyfps_shared_string_to_uppercase(&result, &s);
+    return result;
+}
+
+inline SharedString string_to_lowercase(const SharedString &s)
+{
+    SharedString result;
+    cbindgen_private::sixtyfps_shared_string_to_lowercase(&result, &s);
+    return result;
+}
+
+inline SharedString string_trim(const SharedString &s)
+{
+    SharedString result;
+    cbindgen_private::sixtyfps_shared_string_trim(&result, &s);
+    return result;
+}
+
+inline bool string_contains(const SharedString &s, const SharedString &needle)
+{
+    return std::string_view(s).find(std::string_view(needle)) != std::string_view::npos;
+}
+
+inline SharedString string_substring(const SharedString &s, int start, int length)
+{
+    SharedString result;
+    cbindgen_private::sixtyfps_shared_string_substring(&result, &s, start, length);
+    return result;
+}
+
+inline SharedString string_replace(const SharedString &s, const SharedString &from,
+                                   const SharedString &to)
+{
+    SharedString result;
+    cbindgen_private::sixtyfps_shared_string_replace(&result, &s, &from, &to);
+    return result;
+}
+
+inline SharedString format_number(double n, const SharedString &spec)
+{
+    SharedString result;
+    cbindgen_private::sixtyfps_shared_string_format_number(&result, n, &spec);
+    return result;
+}
+
+inline SharedString format_integer(int64_t n, const SharedString &spec)
+{
+    SharedString result;
+    cbindgen_private::sixtyfps_shared_string_format_integer(&result, n, &spec);
+    return result;
+}
+
+inline SharedString format_string(const SharedString &s, const SharedString &spec)
+{
+    SharedString result;
+    cbindgen_private::sixtyfps_shared_string_format_string(&result, &s, &spec);
+    return result;
+}
 }
 
 }
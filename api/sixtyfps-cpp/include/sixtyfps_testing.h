@@ -10,6 +10,8 @@ LICENSE END */
 #pragma once
 #include "sixtyfps.h"
 #include <iostream>
+#include <optional>
+#include <vector>
 
 namespace sixtyfps::testing {
 
@@ -18,10 +20,18 @@ inline void init()
     cbindgen_private::sixtyfps_testing_init_backend();
 }
 
-inline void mock_elapsed_time(int64_t time_in_ms)
+inline void mock_elapsed_time(uint64_t time_in_ms)
 {
     cbindgen_private::sixtyfps_mock_elapsed_time(time_in_ms);
 }
+
+/// Advances the virtual time of the testing backend, firing the timers that expire in order
+/// and running the events posted with `invoke_from_event_loop`.
+inline void advance_time(uint64_t time_in_ms)
+{
+    cbindgen_private::sixtyfps_testing_advance_time(time_in_ms);
+}
+
 template<typename Component>
 inline void send_mouse_click(const Component *component, float x, float y)
 {
@@ -29,6 +39,29 @@ inline void send_mouse_click(const Component *component, float x, float y)
     cbindgen_private::sixtyfps_send_mouse_click(&crc, x, y, &component->m_window.window_handle());
 }
 
+/// Simulates a drag with the left mouse button, from `(from_x, from_y)` to `(to_x, to_y)`.
+/// The mocked time advances by `duration_ms` milliseconds between the press and the release.
+template<typename Component>
+inline void send_mouse_drag(const Component *component, float from_x, float from_y, float to_x,
+                            float to_y, uint64_t duration_ms)
+{
+    auto crc = *component->self_weak.into_dyn().lock();
+    cbindgen_private::sixtyfps_send_mouse_drag(&crc, from_x, from_y, to_x, to_y, duration_ms,
+                                               &component->m_window.window_handle());
+}
+
+using cbindgen_private::TouchPhase;
+
+/// Simulates a finger that touches, moves on, or leaves a touch screen at `(x, y)`. `id`
+/// identifies the finger while it touches the screen.
+template<typename Component>
+inline void send_touch_event(const Component *component, uint64_t id, float x, float y,
+                             TouchPhase phase)
+{
+    cbindgen_private::sixtyfps_send_touch_event(id, x, y, phase,
+                                                &component->m_window.window_handle());
+}
+
 template<typename Component>
 inline void send_keyboard_string_sequence(const Component *component,
                                           const sixtyfps::SharedString &str,
@@ -38,6 +71,137 @@ inline void send_keyboard_string_sequence(const Component *component,
                                                     &component->m_window.window_handle());
 }
 
+/// Simulates the input method composing text: the preedit text is shown at the cursor position
+/// of the focused text input, with the cursor of the input method at the byte offset `cursor`
+template<typename Component>
+inline void send_composition_update(const Component *component,
+                                    const sixtyfps::SharedString &preedit_text, uintptr_t cursor)
+{
+    cbindgen_private::sixtyfps_send_composition_update(&preedit_text, cursor,
+                                                       &component->m_window.window_handle());
+}
+
+/// Simulates the input method committing the composed text, which replaces the preedit text
+template<typename Component>
+inline void send_composition_commit(const Component *component, const sixtyfps::SharedString &text)
+{
+    cbindgen_private::sixtyfps_send_composition_commit(&text,
+                                                       &component->m_window.window_handle());
+}
+
+using cbindgen_private::ElementPropertyKind;
+using cbindgen_private::ElementPropertyValue;
+
+/// A handle to an element of a component, as returned by find_elements_by_id(),
+/// find_elements_by_type() and find_elements_by_text().
+class ElementHandle
+{
+public:
+    explicit ElementHandle(cbindgen_private::ItemRc item) : item(std::move(item)) { }
+
+    /// The id of the element, as written in the .60 file. It is empty if the element has no id.
+    SharedString id() const
+    {
+        SharedString id, type_name;
+        cbindgen_private::sixtyfps_testing_element_info(&item, &id, &type_name);
+        return id;
+    }
+
+    /// The type of the element, as written in the .60 file. For example `Text` or `Button`.
+    SharedString type_name() const
+    {
+        SharedString id, type_name;
+        cbindgen_private::sixtyfps_testing_element_info(&item, &id, &type_name);
+        return type_name;
+    }
+
+    /// Returns the geometry of the element relative to the window, in logical pixels.
+    cbindgen_private::Rect absolute_geometry() const
+    {
+        return cbindgen_private::sixtyfps_testing_element_absolute_geometry(&item);
+    }
+
+    /// Returns true if a part of the element is on screen: it has a non-empty area that is not
+    /// clipped away by the window or by one of its ancestors, and none of its ancestors is fully
+    /// transparent.
+    bool is_visible() const { return cbindgen_private::sixtyfps_testing_element_is_visible(&item); }
+
+    /// Returns the text of the element if it is a `Text` or a `TextInput`.
+    std::optional<SharedString> text() const
+    {
+        SharedString text;
+        if (cbindgen_private::sixtyfps_testing_element_text(&item, &text))
+            return text;
+        return {};
+    }
+
+    /// Returns the value of the property \a name of the element, as it is named in the .60 file.
+    /// The kind of the result is ElementPropertyKind::None if the element has no such property or
+    /// if its type is not supported. The properties of the elements that are inside of a component
+    /// used by the component, such as the `Text` inside of a `Button`, are not available.
+    ElementPropertyValue property(const SharedString &name) const
+    {
+        ElementPropertyValue result {};
+        cbindgen_private::sixtyfps_testing_element_property(&item, &name, &result);
+        return result;
+    }
+
+    /// The item in the item tree.
+    const cbindgen_private::ItemRc &item_rc() const { return item; }
+
+private:
+    cbindgen_private::ItemRc item;
+};
+
+namespace private_api {
+template<typename Component>
+std::vector<ElementHandle> find_elements(const Component *component,
+                                         cbindgen_private::ElementQuery query,
+                                         const SharedString &value)
+{
+    auto crc = *component->self_weak.into_dyn().lock();
+    SharedVector<cbindgen_private::ItemRc> items;
+    cbindgen_private::sixtyfps_testing_find_elements(
+            &crc, &component->m_window.window_handle(), query, &value, &items);
+    return std::vector<ElementHandle>(items.begin(), items.end());
+}
+}
+
+/// Returns the elements of the component with the given id, in the order of the item tree.
+template<typename Component>
+std::vector<ElementHandle> find_elements_by_id(const Component *component, const SharedString &id)
+{
+    return private_api::find_elements(component, cbindgen_private::ElementQuery::Id, id);
+}
+
+/// Returns the elements of the component with the given type, such as `Text` or `Button`, in
+/// the order of the item tree.
+template<typename Component>
+std::vector<ElementHandle> find_elements_by_type(const Component *component,
+                                                 const SharedString &type_name)
+{
+    return private_api::find_elements(component, cbindgen_private::ElementQuery::TypeName,
+                                      type_name);
+}
+
+/// Returns the `Text` and `TextInput` elements of the component that show the given text, in
+/// the order of the item tree.
+template<typename Component>
+std::vector<ElementHandle> find_elements_by_text(const Component *component,
+                                                 const SharedString &text)
+{
+    return private_api::find_elements(component, cbindgen_private::ElementQuery::Text, text);
+}
+
+/// Simulates a click in the middle of the element.
+template<typename Component>
+inline void send_mouse_click(const Component *component, const ElementHandle &element)
+{
+    auto geometry = element.absolute_geometry();
+    send_mouse_click(component, geometry.x + geometry.width / 2,
+                     geometry.y + geometry.height / 2);
+}
+
 #define assert_eq(A, B)                                                                            \
     sixtyfps::testing::private_api::assert_eq_impl(A, B, #A, #B, __FILE__, __LINE__)
 
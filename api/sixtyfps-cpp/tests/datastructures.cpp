@@ -116,6 +116,39 @@ TEST_CASE("Model row changes")
     }) == 1);
 }
 
+TEST_CASE("Model adapters")
+{
+    using namespace sixtyfps;
+
+    auto model = std::make_shared<VectorModel<int>>(std::vector<int> { 3, 1, 4, 2 });
+    auto all_rows = [](const auto &model) {
+        std::vector<int> result;
+        for (int i = 0; i < model.row_count(); ++i)
+            result.push_back(model.row_data(i));
+        return result;
+    };
+
+    auto filtered = FilterModel<int>(model, [](int x) { return x % 2 == 0; });
+    auto sorted = SortModel<int>(model, [](int a, int b) { return a < b; });
+    auto mapped = MapModel<int, int>(model, [](int x) { return x * 10; });
+    REQUIRE(all_rows(filtered) == std::vector<int> { 4, 2 });
+    REQUIRE(all_rows(sorted) == std::vector<int> { 1, 2, 3, 4 });
+    REQUIRE(all_rows(mapped) == std::vector<int> { 30, 10, 40, 20 });
+
+    model->push_back(0);
+    model->erase(2);
+    model->set_row_data(0, 6);
+    REQUIRE(all_rows(filtered) == std::vector<int> { 6, 2, 0 });
+    REQUIRE(filtered.unfiltered_row(1) == 2);
+    REQUIRE(all_rows(sorted) == std::vector<int> { 0, 1, 2, 6 });
+    REQUIRE(sorted.unsorted_row(0) == 3);
+    REQUIRE(all_rows(mapped) == std::vector<int> { 60, 10, 20, 0 });
+
+    filtered.set_row_data(2, 8);
+    REQUIRE(all_rows(*model) == std::vector<int> { 6, 1, 2, 8 });
+    REQUIRE(all_rows(sorted) == std::vector<int> { 1, 2, 6, 8 });
+}
+
 TEST_CASE("Image")
 {
     using namespace sixtyfps;
@@ -127,6 +127,19 @@ interface ModelPeer {
      * @param count
      */
     rowRemoved(row: number, count: number): void;
+    /**
+     * Call this function from your own model to notify that the specified number of
+     * rows starting at `from` were moved, so that they now start at the row `to`.
+     * @param from
+     * @param to
+     * @param count
+     */
+    rowMoved(from: number, to: number, count: number): void;
+    /**
+     * Call this function from your own model to notify that the model changed
+     * completely and that all the rows need to be read again.
+     */
+    reset(): void;
 }
 
 /**
@@ -167,6 +180,8 @@ class NullPeer implements ModelPeer {
     rowDataChanged(row: number): void { }
     rowAdded(row: number, count: number): void { }
     rowRemoved(row: number, count: number): void { }
+    rowMoved(from: number, to: number, count: number): void { }
+    reset(): void { }
 }
 
 /**
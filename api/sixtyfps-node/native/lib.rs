@@ -224,7 +224,13 @@ fn to_eval_value<'cx>(
                     .collect::<Result<_, _>>()?,
             ))
         }
-        Type::Enumeration(_) => todo!(),
+        Type::Enumeration(e) => {
+            let value = val.to_string(cx)?.value().replace('_', "-");
+            if !e.values.contains(&value) {
+                return cx.throw_error(format!("'{}' is not a value of enum {}", value, e.name));
+            }
+            Ok(Value::EnumerationValue(e.name.clone(), value))
+        }
         Type::Invalid
         | Type::Void
         | Type::InferredProperty
@@ -282,6 +288,9 @@ fn to_js_value<'cx>(
             &format!("#{:02x}{:02x}{:02x}{:02x}", c.red(), c.green(), c.blue(), c.alpha()),
         )
         .as_value(cx),
+        Value::EnumerationValue(_, value) => {
+            JsString::new(cx, value.replace('-', "_").as_str()).as_value(cx)
+        }
         _ => todo!("converting {:?} to js has not been implemented", val),
     })
 }
@@ -220,11 +220,13 @@ struct MyStruct {
     Brush, Color, Image, LoadImageError, Rgb8Pixel, Rgba8Pixel, RgbaColor, SharedPixelBuffer,
 };
 pub use sixtyfps_corelib::model::{
-    Model, ModelHandle, ModelNotify, ModelPeer, ModelTracker, StandardListViewItem, VecModel,
+    FilterModel, LazyModel, MapModel, Model, ModelHandle, ModelNotify, ModelPeer, ModelTracker,
+    SortModel, StandardListViewItem, VecModel,
 };
 pub use sixtyfps_corelib::sharedvector::SharedVector;
 pub use sixtyfps_corelib::string::SharedString;
 pub use sixtyfps_corelib::timers::{Timer, TimerMode};
+pub use sixtyfps_corelib::translations::{set_translations, Translations, TranslationsError};
 
 /// This function can be used to register a custom TrueType font with SixtyFPS,
 /// for use with the `font-family` property. The provided slice must be a valid TrueType
@@ -267,7 +269,8 @@ pub mod re_exports {
     };
     pub use sixtyfps_corelib::graphics::*;
     pub use sixtyfps_corelib::input::{
-        FocusEvent, InputEventResult, KeyEvent, KeyEventResult, KeyboardModifiers, MouseEvent,
+        DropEvent, FocusEvent, InputEventResult, KeyEvent, KeyEventResult, KeyboardModifiers,
+        MouseEvent,
     };
     pub use sixtyfps_corelib::item_tree::{
         visit_item_tree, ItemTreeNode, ItemVisitorRefMut, ItemVisitorVTable, TraversalOrder,
@@ -280,6 +283,11 @@ pub mod re_exports {
         set_state_binding, Property, PropertyTracker, StateInfo,
     };
     pub use sixtyfps_corelib::slice::Slice;
+    pub use sixtyfps_corelib::string::{
+        format_integer, format_number, format_string, string_length, string_split, string_substring,
+    };
+    pub use sixtyfps_corelib::tests::ElementPropertyValue;
+    pub use sixtyfps_corelib::translations::translate;
     pub use sixtyfps_corelib::window::{Window, WindowHandleAccess, WindowRc};
     pub use sixtyfps_corelib::Color;
     pub use sixtyfps_corelib::ComponentVTable_static;
@@ -671,7 +679,9 @@ pub mod testing {
 
     use super::ComponentHandle;
 
+    pub use sixtyfps_corelib::input::TouchPhase;
     pub use sixtyfps_corelib::tests::sixtyfps_mock_elapsed_time as mock_elapsed_time;
+    pub use sixtyfps_corelib::tests::{advance_time, process_posted_events};
 
     /// Simulate a mouse click
     pub fn send_mouse_click<
@@ -694,6 +704,57 @@ pub fn send_mouse_click<
         );
     }
 
+    /// Simulate a drag with the left mouse button, from `(from_x, from_y)` to `(to_x, to_y)`.
+    /// The mocked time advances by `duration` between the press and the release.
+    pub fn send_mouse_drag<
+        X: vtable::HasStaticVTable<sixtyfps_corelib::component::ComponentVTable>
+            + crate::re_exports::WindowHandleAccess
+            + 'static,
+        Component: Into<vtable::VRc<sixtyfps_corelib::component::ComponentVTable, X>> + ComponentHandle,
+    >(
+        component: &Component,
+        from_x: f32,
+        from_y: f32,
+        to_x: f32,
+        to_y: f32,
+        duration: core::time::Duration,
+    ) {
+        let rc = component.clone_strong().into();
+        let dyn_rc = vtable::VRc::into_dyn(rc.clone());
+        sixtyfps_corelib::tests::sixtyfps_send_mouse_drag(
+            &dyn_rc,
+            from_x,
+            from_y,
+            to_x,
+            to_y,
+            duration.as_millis() as u64,
+            &rc.window_handle().clone(),
+        );
+    }
+
+    /// Simulate a finger that touches, moves on, or leaves a touch screen at `(x, y)`.
+    /// `id` identifies the finger while it touches the screen.
+    pub fn send_touch_event<
+        X: vtable::HasStaticVTable<sixtyfps_corelib::component::ComponentVTable>
+            + crate::re_exports::WindowHandleAccess,
+        Component: Into<vtable::VRc<sixtyfps_corelib::component::ComponentVTable, X>> + ComponentHandle,
+    >(
+        component: &Component,
+        id: u64,
+        x: f32,
+        y: f32,
+        phase: TouchPhase,
+    ) {
+        let component = component.clone_strong().into();
+        sixtyfps_corelib::tests::sixtyfps_send_touch_event(
+            id,
+            x,
+            y,
+            phase,
+            &component.window_handle().clone(),
+        )
+    }
+
     /// Simulate a change in keyboard modifiers being pressed
     pub fn set_current_keyboard_modifiers<
         X: vtable::HasStaticVTable<sixtyfps_corelib::component::ComponentVTable>
@@ -723,6 +784,42 @@ pub fn send_keyboard_string_sequence<
         )
     }
 
+    /// Simulate the input method composing text: `preedit_text` is shown at the cursor position
+    /// of the focused text input, with the cursor of the input method at the `cursor` byte offset
+    /// in it.
+    pub fn send_composition_update<
+        X: vtable::HasStaticVTable<sixtyfps_corelib::component::ComponentVTable>
+            + crate::re_exports::WindowHandleAccess,
+        Component: Into<vtable::VRc<sixtyfps_corelib::component::ComponentVTable, X>> + ComponentHandle,
+    >(
+        component: &Component,
+        preedit_text: &str,
+        cursor: usize,
+    ) {
+        let component = component.clone_strong().into();
+        sixtyfps_corelib::tests::sixtyfps_send_composition_update(
+            &super::SharedString::from(preedit_text),
+            cursor,
+            &component.window_handle().clone(),
+        )
+    }
+
+    /// Simulate the input method committing the composed text, which replaces the preedit text.
+    pub fn send_composition_commit<
+        X: vtable::HasStaticVTable<sixtyfps_corelib::component::ComponentVTable>
+            + crate::re_exports::WindowHandleAccess,
+        Component: Into<vtable::VRc<sixtyfps_corelib::component::ComponentVTable, X>> + ComponentHandle,
+    >(
+        component: &Component,
+        text: &str,
+    ) {
+        let component = component.clone_strong().into();
+        sixtyfps_corelib::tests::sixtyfps_send_composition_commit(
+            &super::SharedString::from(text),
+            &component.window_handle().clone(),
+        )
+    }
+
     /// Applies the specified scale factor to the window that's associated with the given component.
     /// This overrides the value provided by the windowing system.
     pub fn set_window_scale_factor<
@@ -736,6 +833,96 @@ pub fn set_window_scale_factor<
         let component = component.clone_strong().into();
         component.window_handle().set_scale_factor(factor)
     }
+
+    /// Renders the window of the component into a buffer of RGBA pixels, which can be compared
+    /// with a reference image.
+    ///
+    /// Returns None if the backend can't render into a buffer. Only the testing backend supports it.
+    pub fn take_snapshot<
+        X: vtable::HasStaticVTable<sixtyfps_corelib::component::ComponentVTable>
+            + crate::re_exports::WindowHandleAccess,
+        Component: Into<vtable::VRc<sixtyfps_corelib::component::ComponentVTable, X>> + ComponentHandle,
+    >(
+        component: &Component,
+    ) -> Option<crate::SharedPixelBuffer<crate::Rgba8Pixel>> {
+        let component = component.clone_strong().into();
+        component.window_handle().take_snapshot()
+    }
+
+    #[cfg(feature = "testing")]
+    pub use sixtyfps_rendering_backend_testing::{
+        compare_with_reference, UPDATE_REFERENCE_ENV_VAR,
+    };
+
+    pub use sixtyfps_corelib::tests::{ElementHandle, ElementPropertyKind, ElementPropertyValue};
+
+    /// Returns all the elements of the component for which `predicate` returns true, in the order
+    /// of the item tree. This includes the elements created by `for` and `if`.
+    pub fn find_elements<
+        X: vtable::HasStaticVTable<sixtyfps_corelib::component::ComponentVTable>
+            + crate::re_exports::WindowHandleAccess
+            + 'static,
+        Component: Into<vtable::VRc<sixtyfps_corelib::component::ComponentVTable, X>> + ComponentHandle,
+    >(
+        component: &Component,
+        predicate: impl FnMut(&ElementHandle) -> bool,
+    ) -> Vec<ElementHandle> {
+        let rc = component.clone_strong().into();
+        let dyn_rc = vtable::VRc::into_dyn(rc.clone());
+        sixtyfps_corelib::tests::find_elements(&dyn_rc, rc.window_handle(), predicate)
+    }
+
+    /// Returns the elements of the component that have the given id in the .60 file.
+    pub fn find_elements_by_id<
+        X: vtable::HasStaticVTable<sixtyfps_corelib::component::ComponentVTable>
+            + crate::re_exports::WindowHandleAccess
+            + 'static,
+        Component: Into<vtable::VRc<sixtyfps_corelib::component::ComponentVTable, X>> + ComponentHandle,
+    >(
+        component: &Component,
+        id: &str,
+    ) -> Vec<ElementHandle> {
+        find_elements(component, |element| element.id() == id)
+    }
+
+    /// Returns the elements of the component of the given type, for example `Text` or `Button`.
+    pub fn find_elements_by_type<
+        X: vtable::HasStaticVTable<sixtyfps_corelib::component::ComponentVTable>
+            + crate::re_exports::WindowHandleAccess
+            + 'static,
+        Component: Into<vtable::VRc<sixtyfps_corelib::component::ComponentVTable, X>> + ComponentHandle,
+    >(
+        component: &Component,
+        type_name: &str,
+    ) -> Vec<ElementHandle> {
+        find_elements(component, |element| element.type_name() == type_name)
+    }
+
+    /// Returns the `Text` and `TextInput` elements of the component that display the given text.
+    pub fn find_elements_by_text<
+        X: vtable::HasStaticVTable<sixtyfps_corelib::component::ComponentVTable>
+            + crate::re_exports::WindowHandleAccess
+            + 'static,
+        Component: Into<vtable::VRc<sixtyfps_corelib::component::ComponentVTable, X>> + ComponentHandle,
+    >(
+        component: &Component,
+        text: &str,
+    ) -> Vec<ElementHandle> {
+        find_elements(component, |element| element.text().map_or(false, |t| t.as_str() == text))
+    }
+
+    pub use sixtyfps_corelib::accessibility::AccessibilityNode;
+
+    /// Builds the accessibility tree of the component, from its `accessible-*` properties.
+    pub fn accessibility_tree<
+        X: vtable::HasStaticVTable<sixtyfps_corelib::component::ComponentVTable> + 'static,
+        Component: Into<vtable::VRc<sixtyfps_corelib::component::ComponentVTable, X>> + ComponentHandle,
+    >(
+        component: &Component,
+    ) -> AccessibilityNode {
+        let rc = component.clone_strong().into();
+        sixtyfps_corelib::accessibility::build_accessibility_tree(&vtable::VRc::into_dyn(rc))
+    }
 }
 
 /// Include the code generated with the sixtyfps-build crate from the build script. After calling `sixtyfps_build::compile`
@@ -187,24 +187,27 @@ fn fill_token_vec(stream: impl Iterator<Item = TokenTree>, vec: &mut Vec<parser:
                         SyntaxKind::AndAnd
                     }
                     '|' => {
-                        // Since the '|' alone does not exist or cannot be part of any other token that ||
-                        // just consider it as '||' and skip the joint ones.  FIXME. do that properly
+                        // Two joint '|' form a '||'
                         if let Some(last) = vec.last_mut() {
-                            if last.kind == SyntaxKind::OrOr && prev_spacing == Spacing::Joint {
+                            if last.kind == SyntaxKind::Pipe && prev_spacing == Spacing::Joint {
+                                last.kind = SyntaxKind::OrOr;
+                                last.text = "||".into();
                                 continue;
                             }
                         }
-                        SyntaxKind::OrOr
+                        SyntaxKind::Pipe
                     }
                     '%' => {
-                        // % can only exist after number literal
+                        // a '%' right after a number literal is part of that literal
                         if let Some(last) = vec.last_mut() {
-                            if last.kind == SyntaxKind::NumberLiteral {
+                            if last.kind == SyntaxKind::NumberLiteral
+                                && are_token_touching(prev_span, p.span())
+                            {
                                 last.text = format!("{}%", last.text).into();
                                 continue;
                             }
                         }
-                        SyntaxKind::Error
+                        SyntaxKind::Percent
                     }
                     '$' => SyntaxKind::Dollar,
                     '@' => SyntaxKind::At,
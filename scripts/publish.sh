@@ -8,6 +8,7 @@
 # This file is also available under commercial licensing terms.
 # Please contact info@sixtyfps.io for more information.
 # LICENSE END
+cargo publish --manifest-path sixtyfps_runtime/common/Cargo.toml
 cargo publish --manifest-path sixtyfps_runtime/corelib_macros/Cargo.toml
 cargo publish --manifest-path sixtyfps_compiler/Cargo.toml
 cargo publish --manifest-path sixtyfps_runtime/corelib/Cargo.toml
@@ -15,6 +16,7 @@ cargo publish --manifest-path api/sixtyfps-rs/sixtyfps-macros/Cargo.toml
 cargo publish --manifest-path sixtyfps_runtime/rendering_backends/gl/Cargo.toml --features x11
 cargo publish --manifest-path api/sixtyfps-rs/sixtyfps-build/Cargo.toml
 cargo publish --manifest-path sixtyfps_runtime/rendering_backends/qt/Cargo.toml
+cargo publish --manifest-path sixtyfps_runtime/rendering_backends/testing/Cargo.toml
 sleep 30
 cargo publish --manifest-path sixtyfps_runtime/rendering_backends/default/Cargo.toml
 sleep 30
@@ -24,6 +24,8 @@
 /// A function built into the run-time
 pub enum BuiltinFunction {
     GetWindowScaleFactor,
+    /// Whether the `layout-direction` of the window is `rtl`
+    WindowIsRightToLeft,
     Debug,
     Mod,
     Round,
@@ -39,10 +41,30 @@ pub enum BuiltinFunction {
     ATan,
     SetFocusItem,
     ShowPopupWindow,
+    TextInputUndo,
+    TextInputRedo,
+    FlickableScrollTo,
     /// the "42".to_float()
     StringToFloat,
     /// the "42".is_float()
     StringIsFloat,
+    StringLength,
+    StringToUppercase,
+    StringToLowercase,
+    StringContains,
+    StringStartsWith,
+    StringSubstring,
+    StringTrim,
+    StringReplace,
+    StringSplit,
+    /// Format a number according to a format specifier: `"\{value:spec}"`
+    FormatNumber,
+    /// Format an integer according to a format specifier: `"\{value:spec}"`
+    FormatInteger,
+    /// Format a string according to a format specifier: `"\{value:spec}"`
+    FormatString,
+    /// `@tr(...)`: arguments are the context, the text, the plural form, the count, and the arguments
+    Translate,
     ColorBrighter,
     ColorDarker,
     ImageSize,
@@ -70,6 +92,9 @@ pub fn ty(&self) -> Type {
                 return_type: Box::new(Type::UnitProduct(vec![(Unit::Phx, 1), (Unit::Px, -1)])),
                 args: vec![],
             },
+            BuiltinFunction::WindowIsRightToLeft => {
+                Type::Function { return_type: Box::new(Type::Bool), args: vec![] }
+            }
             BuiltinFunction::Debug => {
                 Type::Function { return_type: Box::new(Type::Void), args: vec![Type::String] }
             }
@@ -93,16 +118,73 @@ pub fn ty(&self) -> Type {
                 return_type: Box::new(Type::Void),
                 args: vec![Type::ElementReference],
             },
-            BuiltinFunction::ShowPopupWindow => Type::Function {
+            BuiltinFunction::ShowPopupWindow
+            | BuiltinFunction::TextInputUndo
+            | BuiltinFunction::TextInputRedo => Type::Function {
                 return_type: Box::new(Type::Void),
                 args: vec![Type::ElementReference],
             },
+            BuiltinFunction::FlickableScrollTo => Type::Function {
+                return_type: Box::new(Type::Void),
+                args: vec![
+                    Type::ElementReference,
+                    Type::LogicalLength,
+                    Type::LogicalLength,
+                    Type::Bool,
+                ],
+            },
             BuiltinFunction::StringToFloat => {
                 Type::Function { return_type: Box::new(Type::Float32), args: vec![Type::String] }
             }
             BuiltinFunction::StringIsFloat => {
                 Type::Function { return_type: Box::new(Type::Bool), args: vec![Type::String] }
             }
+            BuiltinFunction::StringLength => {
+                Type::Function { return_type: Box::new(Type::Int32), args: vec![Type::String] }
+            }
+            BuiltinFunction::StringToUppercase
+            | BuiltinFunction::StringToLowercase
+            | BuiltinFunction::StringTrim => {
+                Type::Function { return_type: Box::new(Type::String), args: vec![Type::String] }
+            }
+            BuiltinFunction::StringContains | BuiltinFunction::StringStartsWith => Type::Function {
+                return_type: Box::new(Type::Bool),
+                args: vec![Type::String, Type::String],
+            },
+            BuiltinFunction::StringSubstring => Type::Function {
+                return_type: Box::new(Type::String),
+                args: vec![Type::String, Type::Int32, Type::Int32],
+            },
+            BuiltinFunction::StringReplace => Type::Function {
+                return_type: Box::new(Type::String),
+                args: vec![Type::String, Type::String, Type::String],
+            },
+            BuiltinFunction::StringSplit => Type::Function {
+                return_type: Box::new(Type::Array(Box::new(Type::String))),
+                args: vec![Type::String, Type::String],
+            },
+            BuiltinFunction::FormatNumber => Type::Function {
+                return_type: Box::new(Type::String),
+                args: vec![Type::Float32, Type::String],
+            },
+            BuiltinFunction::FormatInteger => Type::Function {
+                return_type: Box::new(Type::String),
+                args: vec![Type::Int32, Type::String],
+            },
+            BuiltinFunction::FormatString => Type::Function {
+                return_type: Box::new(Type::String),
+                args: vec![Type::String, Type::String],
+            },
+            BuiltinFunction::Translate => Type::Function {
+                return_type: Box::new(Type::String),
+                args: vec![
+                    Type::String,
+                    Type::String,
+                    Type::String,
+                    Type::Int32,
+                    Type::Array(Box::new(Type::String)),
+                ],
+            },
             BuiltinFunction::ImplicitLayoutInfo(_) => Type::Function {
                 return_type: Box::new(crate::layout::layout_info_type()),
                 args: vec![Type::ElementReference],
@@ -147,6 +229,7 @@ pub fn ty(&self) -> Type {
     fn is_pure(&self) -> bool {
         match self {
             BuiltinFunction::GetWindowScaleFactor => false,
+            BuiltinFunction::WindowIsRightToLeft => false,
             // Even if it is not pure, we optimize it away anyway
             BuiltinFunction::Debug => true,
             BuiltinFunction::Mod
@@ -163,7 +246,23 @@ fn is_pure(&self) -> bool {
             | BuiltinFunction::ATan => true,
             BuiltinFunction::SetFocusItem => false,
             BuiltinFunction::ShowPopupWindow => false,
+            BuiltinFunction::TextInputUndo | BuiltinFunction::TextInputRedo => false,
+            BuiltinFunction::FlickableScrollTo => false,
             BuiltinFunction::StringToFloat | BuiltinFunction::StringIsFloat => true,
+            BuiltinFunction::StringLength
+            | BuiltinFunction::StringToUppercase
+            | BuiltinFunction::StringToLowercase
+            | BuiltinFunction::StringContains
+            | BuiltinFunction::StringStartsWith
+            | BuiltinFunction::StringSubstring
+            | BuiltinFunction::StringTrim
+            | BuiltinFunction::StringReplace
+            | BuiltinFunction::StringSplit
+            | BuiltinFunction::FormatNumber
+            | BuiltinFunction::FormatInteger
+            | BuiltinFunction::FormatString => true,
+            // Depends on the currently loaded translations
+            BuiltinFunction::Translate => false,
             BuiltinFunction::ColorBrighter | BuiltinFunction::ColorDarker => true,
             // ImageSize is pure, except when loading images via the network. Then the initial size will be 0/0 and
             // we need to make sure that calls to this function stay within a binding, so that the property
@@ -180,6 +279,21 @@ fn is_pure(&self) -> bool {
             | BuiltinFunction::RegisterCustomFontByMemory => false,
         }
     }
+
+    /// Whether the function changes the state of the elements, and can therefore not be called
+    /// from a function declared in the .60 file, as these must be pure.
+    pub fn has_side_effects(&self) -> bool {
+        matches!(
+            self,
+            BuiltinFunction::SetFocusItem
+                | BuiltinFunction::ShowPopupWindow
+                | BuiltinFunction::TextInputUndo
+                | BuiltinFunction::TextInputRedo
+                | BuiltinFunction::FlickableScrollTo
+                | BuiltinFunction::RegisterCustomFontByPath
+                | BuiltinFunction::RegisterCustomFontByMemory
+        )
+    }
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -311,6 +425,9 @@ pub enum Expression {
     /// Reference to the callback <name> in the <element>
     PropertyReference(NamedReference),
 
+    /// Reference to the function <name> declared with `function` in the <element>
+    FunctionReference(NamedReference),
+
     /// Reference to a function built into the run-time, implemented natively
     BuiltinFunctionReference(BuiltinFunction, Option<SourceLocation>),
 
@@ -441,6 +558,17 @@ pub enum Expression {
         stops: Vec<(Expression, Expression)>,
     },
 
+    RadialGradient {
+        /// First expression in the tuple is a color, second expression is the stop position
+        stops: Vec<(Expression, Expression)>,
+    },
+
+    ConicGradient {
+        /// First expression in the tuple is a color, second expression is the stop position
+        /// as a fraction of a full turn
+        stops: Vec<(Expression, Expression)>,
+    },
+
     EnumerationValue(EnumerationValue),
 
     ReturnStatement(Option<Box<Expression>>),
@@ -474,6 +602,7 @@ pub fn ty(&self) -> Type {
             Expression::NumberLiteral(_, unit) => unit.ty(),
             Expression::BoolLiteral(_) => Type::Bool,
             Expression::CallbackReference(nr) => nr.ty(),
+            Expression::FunctionReference(nr) => nr.ty(),
             Expression::PropertyReference(nr) => nr.ty(),
             Expression::BuiltinFunctionReference(funcref, _) => funcref.ty(),
             Expression::MemberFunction { member, .. } => member.ty(),
@@ -588,6 +717,8 @@ pub fn ty(&self) -> Type {
             Expression::ReadLocalVariable { ty, .. } => ty.clone(),
             Expression::EasingCurve(_) => Type::Easing,
             Expression::LinearGradient { .. } => Type::Brush,
+            Expression::RadialGradient { .. } => Type::Brush,
+            Expression::ConicGradient { .. } => Type::Brush,
             Expression::EnumerationValue(value) => Type::Enumeration(value.enumeration.clone()),
             // invalid because the expression is unreachable
             Expression::ReturnStatement(_) => Type::Invalid,
@@ -607,6 +738,7 @@ pub fn visit(&self, mut visitor: impl FnMut(&Self)) {
             Expression::BoolLiteral(_) => {}
             Expression::CallbackReference { .. } => {}
             Expression::PropertyReference { .. } => {}
+            Expression::FunctionReference { .. } => {}
             Expression::FunctionParameterReference { .. } => {}
             Expression::BuiltinFunctionReference { .. } => {}
             Expression::MemberFunction { base, member, .. } => {
@@ -668,6 +800,12 @@ pub fn visit(&self, mut visitor: impl FnMut(&Self)) {
                     visitor(s);
                 }
             }
+            Expression::RadialGradient { stops } | Expression::ConicGradient { stops } => {
+                for (c, s) in stops {
+                    visitor(c);
+                    visitor(s);
+                }
+            }
             Expression::EnumerationValue(_) => {}
             Expression::ReturnStatement(expr) => {
                 expr.as_deref().map(visitor);
@@ -689,6 +827,7 @@ pub fn visit_mut(&mut self, mut visitor: impl FnMut(&mut Self)) {
             Expression::BoolLiteral(_) => {}
             Expression::CallbackReference { .. } => {}
             Expression::PropertyReference { .. } => {}
+            Expression::FunctionReference { .. } => {}
             Expression::FunctionParameterReference { .. } => {}
             Expression::BuiltinFunctionReference { .. } => {}
             Expression::MemberFunction { base, member, .. } => {
@@ -753,6 +892,12 @@ pub fn visit_mut(&mut self, mut visitor: impl FnMut(&mut Self)) {
                     visitor(s);
                 }
             }
+            Expression::RadialGradient { stops } | Expression::ConicGradient { stops } => {
+                for (c, s) in stops {
+                    visitor(c);
+                    visitor(s);
+                }
+            }
             Expression::EnumerationValue(_) => {}
             Expression::ReturnStatement(expr) => {
                 expr.as_deref_mut().map(visitor);
@@ -786,6 +931,7 @@ pub fn is_constant(&self) -> bool {
             Expression::BoolLiteral(_) => true,
             Expression::CallbackReference { .. } => false,
             Expression::PropertyReference(nr) => nr.is_constant(),
+            Expression::FunctionReference { .. } => false,
             Expression::BuiltinFunctionReference(func, _) => func.is_pure(),
             Expression::MemberFunction { .. } => false,
             Expression::ElementReference(_) => false,
@@ -825,6 +971,9 @@ pub fn is_constant(&self) -> bool {
             Expression::LinearGradient { angle, stops } => {
                 angle.is_constant() && stops.iter().all(|(c, s)| c.is_constant() && s.is_constant())
             }
+            Expression::RadialGradient { stops } | Expression::ConicGradient { stops } => {
+                stops.iter().all(|(c, s)| c.is_constant() && s.is_constant())
+            }
             Expression::EnumerationValue(_) => true,
             Expression::ReturnStatement(expr) => {
                 expr.as_ref().map_or(true, |expr| expr.is_constant())
@@ -1223,6 +1372,7 @@ pub fn pretty_print(f: &mut dyn std::fmt::Write, expression: &Expression) -> std
         Expression::BoolLiteral(b) => write!(f, "{:?}", b),
         Expression::CallbackReference(a) => write!(f, "{:?}", a),
         Expression::PropertyReference(a) => write!(f, "{:?}", a),
+        Expression::FunctionReference(a) => write!(f, "{:?}", a),
         Expression::BuiltinFunctionReference(a, _) => write!(f, "{:?}", a),
         Expression::MemberFunction { base, base_node: _, member } => {
             pretty_print(f, base)?;
@@ -1329,6 +1479,30 @@ pub fn pretty_print(f: &mut dyn std::fmt::Write, expression: &Expression) -> std
             }
             write!(f, ")")
         }
+        Expression::RadialGradient { stops } => {
+            write!(f, "@radial-gradient(circle")?;
+            for (c, s) in stops {
+                write!(f, ", ")?;
+                pretty_print(f, c)?;
+                write!(f, "  ")?;
+                pretty_print(f, s)?;
+            }
+            write!(f, ")")
+        }
+        Expression::ConicGradient { stops } => {
+            write!(f, "@conic-gradient(")?;
+            let mut first = true;
+            for (c, s) in stops {
+                if !first {
+                    write!(f, ", ")?;
+                }
+                first = false;
+                pretty_print(f, c)?;
+                write!(f, "  ")?;
+                pretty_print(f, s)?;
+            }
+            write!(f, ")")
+        }
         Expression::EnumerationValue(e) => match e.enumeration.values.get(e.value as usize) {
             Some(val) => write!(f, "{}.{}", e.enumeration.name, val),
             None => write!(f, "{}.{}", e.enumeration.name, e.value),
@@ -112,12 +112,17 @@ fn push_repeated_item(
         parent_index: u32,
         component_state: &Self::SubComponentState,
     );
+    /// `source_element` is the element as written in the .60 file. It differs from `item` when
+    /// `item` is the root of a sub-component (and then `source_element` is the element using it).
+    /// `source_component_state` is the state of the component of `source_element`.
     fn push_native_item(
         &mut self,
         item: &ElementRc,
+        source_element: &ElementRc,
         children_offset: u32,
         parent_index: u32,
         component_state: &Self::SubComponentState,
+        source_component_state: &Self::SubComponentState,
     );
     /// Called when a component is entered, this allow to change the component_state.
     /// The returned SubComponentState will be used for all the items within that component
@@ -143,6 +148,22 @@ pub fn build_item_tree<T: ItemTreeBuilder>(
     root_component: &Rc<Component>,
     initial_state: &T::SubComponentState,
     builder: &mut T,
+) {
+    build_item_tree_impl(
+        root_component,
+        &root_component.root_element,
+        initial_state,
+        initial_state,
+        builder,
+    )
+}
+
+fn build_item_tree_impl<T: ItemTreeBuilder>(
+    root_component: &Rc<Component>,
+    source_root: &ElementRc,
+    source_root_state: &T::SubComponentState,
+    initial_state: &T::SubComponentState,
+    builder: &mut T,
 ) {
     if let Some(sub_component) = root_component.root_element.borrow().sub_component() {
         assert!(root_component.root_element.borrow().children.is_empty());
@@ -154,10 +175,25 @@ pub fn build_item_tree<T: ItemTreeBuilder>(
             initial_state,
             &sub_compo_state,
         );
-        build_item_tree::<T>(sub_component, &sub_compo_state, builder);
+        build_item_tree_impl::<T>(
+            sub_component,
+            source_root,
+            source_root_state,
+            &sub_compo_state,
+            builder,
+        );
     } else {
         let mut repeater_count = 0;
-        visit_item(initial_state, &root_component.root_element, 1, &mut repeater_count, 0, builder);
+        visit_item(
+            initial_state,
+            &root_component.root_element,
+            source_root,
+            source_root_state,
+            1,
+            &mut repeater_count,
+            0,
+            builder,
+        );
 
         visit_children(
             initial_state,
@@ -254,6 +290,8 @@ fn visit_children<T: ItemTreeBuilder>(
                 visit_item(
                     &sub_component_state,
                     &sub_component.root_element,
+                    child,
+                    state,
                     offset,
                     repeater_count,
                     parent_index,
@@ -261,7 +299,16 @@ fn visit_children<T: ItemTreeBuilder>(
                 );
                 sub_component_states.push_back(sub_component_state);
             } else {
-                visit_item(state, child, offset, repeater_count, parent_index, builder);
+                visit_item(
+                    state,
+                    child,
+                    child,
+                    state,
+                    offset,
+                    repeater_count,
+                    parent_index,
+                    builder,
+                );
             }
             offset += item_sub_tree_size(child) as u32;
         }
@@ -313,6 +360,8 @@ fn visit_children<T: ItemTreeBuilder>(
     fn visit_item<T: ItemTreeBuilder>(
         component_state: &T::SubComponentState,
         item: &ElementRc,
+        source_element: &ElementRc,
+        source_component_state: &T::SubComponentState,
         children_offset: u32,
         repeater_count: &mut u32,
         parent_index: u32,
@@ -324,24 +373,81 @@ fn visit_item<T: ItemTreeBuilder>(
             return;
         } else {
             let mut item = item.clone();
-            let mut component_state = component_state.clone();
+            let mut item_component_state = component_state.clone();
             while let Some((base, state)) = {
                 let base = item.borrow().sub_component().map(|c| {
                     (
                         c.root_element.clone(),
-                        builder.enter_component(&item, &c, children_offset, &component_state),
+                        builder.enter_component(&item, &c, children_offset, &item_component_state),
                     )
                 });
                 base
             } {
                 item = base;
-                component_state = state;
+                item_component_state = state;
             }
-            builder.push_native_item(&item, children_offset, parent_index, &component_state)
+            builder.push_native_item(
+                &item,
+                source_element,
+                children_offset,
+                parent_index,
+                &item_component_state,
+                source_component_state,
+            )
         }
     }
 }
 
+/// Returns the accessible properties of the native item `item`, by their name in
+/// `Element::accessibility_props`. The references are relative to the component of `source_element`.
+///
+/// When `item` is the root of a sub-component, the properties can be set on any element of the
+/// chain from `source_element` down to `item`, and the one that is set the highest in the chain wins.
+pub fn item_accessibility_props(
+    item: &ElementRc,
+    source_element: &ElementRc,
+) -> Vec<(String, NamedReference)> {
+    let mut result = source_element.borrow().accessibility_props.clone();
+    let mut elem = source_element.clone();
+    while !Rc::ptr_eq(&elem, item) {
+        let next = elem.borrow().sub_component().unwrap().root_element.clone();
+        for what in next.borrow().accessibility_props.keys() {
+            if !result.contains_key(what) {
+                // The property is declared in the sub-component, and the bindings of the elements
+                // using it apply to it, so it can be accessed by its name from the outer element
+                let nr = NamedReference::new(source_element, &format!("accessible-{}", what));
+                result.insert(what.clone(), nr);
+            }
+        }
+        elem = next;
+    }
+    result.into_iter().collect()
+}
+
+/// Returns the properties of `element` that the testing API can read through the
+/// `element_property` function of the component vtable, by their name in the .60 file.
+pub fn queryable_properties(element: &ElementRc) -> Vec<(String, NamedReference)> {
+    crate::object_tree::source_properties(element)
+        .into_iter()
+        .filter(|(_, nr)| {
+            matches!(
+                nr.ty(),
+                Type::Float32
+                    | Type::Int32
+                    | Type::Duration
+                    | Type::PhysicalLength
+                    | Type::LogicalLength
+                    | Type::Percent
+                    | Type::Angle
+                    | Type::Bool
+                    | Type::String
+                    | Type::Color
+                    | Type::Brush
+            )
+        })
+        .collect()
+}
+
 /// Will call the `handle_property` callback for every property that needs to be initialized.
 /// This function makes sure to call them in order so that if constant binding need to access
 /// constant properties, these are already initialized
@@ -389,6 +495,13 @@ fn handle_property_inner(
     let mut processed = HashSet::new();
     crate::object_tree::recurse_elem(&component.root_element, &(), &mut |elem: &ElementRc, ()| {
         for (prop_name, binding_expression) in &elem.borrow().bindings {
+            if matches!(
+                elem.borrow().lookup_property(prop_name).property_type,
+                Type::Function { .. }
+            ) {
+                // The body of functions is generated separately
+                continue;
+            }
             handle_property_inner(
                 &Rc::downgrade(component),
                 elem,
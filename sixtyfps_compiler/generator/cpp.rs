@@ -60,6 +60,7 @@ pub enum Declaration {
         Function(Function),
         Var(Var),
         TypeAlias(TypeAlias),
+        Enum(Enum),
     }
 
     #[derive(Debug, Copy, Clone, Eq, PartialEq)]
@@ -218,6 +219,26 @@ fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         }
     }
 
+    /// An `enum class`
+    #[derive(Default, Debug)]
+    pub struct Enum {
+        pub name: String,
+        pub values: Vec<String>,
+    }
+
+    impl Display for Enum {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            indent(f)?;
+            writeln!(f, "enum class {} {{", self.name)?;
+            for value in &self.values {
+                indent(f)?;
+                writeln!(f, "    {},", value)?;
+            }
+            indent(f)?;
+            writeln!(f, "}};")
+        }
+    }
+
     pub trait CppType {
         fn cpp_type(&self) -> Option<String>;
     }
@@ -292,6 +313,9 @@ fn cpp_type(&self) -> Option<String> {
             Type::Array(i) => Some(format!("std::shared_ptr<sixtyfps::Model<{}>>", i.cpp_type()?)),
             Type::Image => Some("sixtyfps::Image".to_owned()),
             Type::Builtin(elem) => elem.native_class.cpp_type.clone(),
+            Type::Enumeration(enumeration) if enumeration.node.is_some() => {
+                Some(ident(&enumeration.name).into_owned())
+            }
             Type::Enumeration(enumeration) => {
                 Some(format!("sixtyfps::cbindgen_private::{}", ident(&enumeration.name)))
             }
@@ -644,6 +668,13 @@ pub fn generate(doc: &Document, diag: &mut BuildDiagnostics) -> Option<impl std:
         ..Default::default()
     }));
 
+    for en in doc.root_component.used_types.borrow().enums.iter() {
+        file.declarations.push(Declaration::Enum(Enum {
+            name: ident(&en.name).into_owned(),
+            values: en.values.iter().map(|v| ident(v).into_owned()).collect(),
+        }));
+    }
+
     for ty in doc.root_component.used_types.borrow().structs.iter() {
         if let Type::Struct { fields, name: Some(name), node: Some(_) } = ty {
             generate_struct(&mut file, name, fields, diag);
@@ -825,6 +856,46 @@ fn generate_component(
 
     for (prop_name, property_decl) in component.root_element.borrow().property_declarations.iter() {
         let cpp_name = ident(prop_name);
+
+        if let Type::Function { args, return_type } = &property_decl.property_type {
+            let param_types =
+                args.iter().map(|t| get_cpp_type(t, property_decl, diag)).collect::<Vec<_>>();
+            let return_type = if **return_type == Type::Void {
+                "void".into()
+            } else {
+                get_cpp_type(return_type, property_decl, diag)
+            };
+            let code = component
+                .root_element
+                .borrow()
+                .bindings
+                .get(prop_name)
+                .map_or_else(String::new, |b| {
+                    compile_expression_wrap_return(&b.borrow(), component)
+                });
+            component_struct.members.push((
+                field_access,
+                Declaration::Function(Function {
+                    name: format!("fn_{}", cpp_name),
+                    signature: format!(
+                        "({}) const -> {}",
+                        param_types
+                            .iter()
+                            .enumerate()
+                            .map(|(i, ty)| format!("[[maybe_unused]] {} arg_{}", ty, i))
+                            .join(", "),
+                        return_type
+                    ),
+                    statements: Some(vec![
+                        "[[maybe_unused]] auto self = this;".into(),
+                        format!("return {};", code),
+                    ]),
+                    ..Default::default()
+                }),
+            ));
+            continue;
+        }
+
         let access = if let Some(alias) = &property_decl.is_alias {
             access_named_reference(alias, component, "this")
         } else {
@@ -1134,6 +1205,10 @@ fn generate_component(
 
     struct TreeBuilder<'a> {
         tree_array: Vec<String>,
+        element_infos: Vec<String>,
+        accessible_role_cases: Vec<String>,
+        accessible_string_property_cases: Vec<String>,
+        element_property_cases: Vec<String>,
         children_visitor_cases: Vec<String>,
         constructor_member_initializers: &'a mut Vec<String>,
         root_ptr: String,
@@ -1190,14 +1265,39 @@ fn push_repeated_item(
                 "sixtyfps::private_api::make_dyn_node({}, {})",
                 repeater_count, parent_index
             ));
+            push_element_info(&mut self.element_infos, item_rc);
         }
         fn push_native_item(
             &mut self,
             item_rc: &ElementRc,
+            source_element: &ElementRc,
             children_offset: u32,
             parent_index: u32,
             component_state: &Self::SubComponentState,
+            source_component_state: &Self::SubComponentState,
         ) {
+            push_element_info(&mut self.element_infos, source_element);
+            self.push_accessibility_props(
+                item_rc,
+                source_element,
+                source_component_state,
+                self.tree_array.len(),
+            );
+            if Rc::ptr_eq(
+                &source_element.borrow().enclosing_component.upgrade().unwrap(),
+                self.component,
+            ) && !source_element.borrow().source_id_and_type_name().1.is_empty()
+            {
+                let index = self.tree_array.len();
+                for (name, nr) in crate::generator::queryable_properties(source_element) {
+                    self.element_property_cases.push(format!(
+                        "if (index == {} && std::string_view(*name) == \"{}\") {{ *result = sixtyfps::private_api::element_property_value({}.get()); return; }}",
+                        index,
+                        escape_string(&name),
+                        access_named_reference(&nr, self.component, "self")
+                    ));
+                }
+            }
             let item = item_rc.borrow();
             if component_state.is_empty() {
                 handle_item(item_rc, self.field_access, &mut self.component_struct);
@@ -1302,12 +1402,52 @@ fn enter_component_children(
         }
     }
 
+    impl<'a> TreeBuilder<'a> {
+        fn push_accessibility_props(
+            &mut self,
+            item_rc: &ElementRc,
+            source_element: &ElementRc,
+            source_component_state: &str,
+            index: usize,
+        ) {
+            let source_component = source_element.borrow().enclosing_component.upgrade().unwrap();
+            let source_component_cpp = if source_component_state.is_empty() {
+                "self".to_owned()
+            } else {
+                // The state is a sum of offsets followed by a `+`
+                format!(
+                    "reinterpret_cast<const {}*>(reinterpret_cast<const char *>(self) + {}0)",
+                    self::component_id(&source_component),
+                    source_component_state
+                )
+            };
+            for (what, nr) in crate::generator::item_accessibility_props(item_rc, source_element) {
+                let prop = access_named_reference(&nr, &source_component, &source_component_cpp);
+                if what == "role" {
+                    self.accessible_role_cases
+                        .push(format!("case {}: return {}.get();", index, prop));
+                } else {
+                    self.accessible_string_property_cases.push(format!(
+                        "if (index == {} && what == sixtyfps::cbindgen_private::AccessibleStringProperty::{}) {{ *result = {}.get(); return; }}",
+                        index,
+                        ident(&what),
+                        prop
+                    ));
+                }
+            }
+        }
+    }
+
     // For children of sub-components, the item index generated by the generate_item_indices pass
     // starts at 1 (0 is the root element).
     let item_index_base = if is_sub_component { "tree_index_of_first_child - 1 + " } else { "" };
 
     let mut builder = TreeBuilder {
         tree_array: vec![],
+        element_infos: vec![],
+        accessible_role_cases: vec![],
+        accessible_string_property_cases: vec![],
+        element_property_cases: vec![],
         children_visitor_cases: vec![],
         constructor_member_initializers: &mut constructor_member_initializers,
         root_ptr: access_root_tokens(component),
@@ -1326,6 +1466,11 @@ fn enter_component_children(
     }
 
     let tree_array = std::mem::take(&mut builder.tree_array);
+    let element_infos = std::mem::take(&mut builder.element_infos);
+    let accessible_role_cases = std::mem::take(&mut builder.accessible_role_cases);
+    let accessible_string_property_cases =
+        std::mem::take(&mut builder.accessible_string_property_cases);
+    let element_property_cases = std::mem::take(&mut builder.element_property_cases);
     let children_visitor_cases = std::mem::take(&mut builder.children_visitor_cases);
     drop(builder);
 
@@ -1395,6 +1540,10 @@ fn enter_component_children(
             component,
             children_visitor_cases,
             tree_array,
+            element_infos,
+            accessible_role_cases,
+            accessible_string_property_cases,
+            element_property_cases,
             file,
         );
     } else if is_sub_component {
@@ -1613,6 +1762,10 @@ fn generate_component_vtable(
     component: &Rc<Component>,
     children_visitor_cases: Vec<String>,
     tree_array: Vec<String>,
+    element_infos: Vec<String>,
+    accessible_role_cases: Vec<String>,
+    accessible_string_property_cases: Vec<String>,
+    element_property_cases: Vec<String>,
     file: &mut File,
 ) {
     component_struct
@@ -1706,6 +1859,63 @@ fn generate_component_vtable(
             ..Default::default()
         }),
     ));
+    component_struct.members.push((
+        Access::Private,
+        Declaration::Function(Function {
+            name: "item_element_info".into(),
+            signature: "(sixtyfps::private_api::ComponentRef, uintptr_t index, sixtyfps::SharedString *id, sixtyfps::SharedString *type_name) -> void".into(),
+            is_static: true,
+            statements: Some(vec![
+                "static const char *const element_infos[][2] {".to_owned(),
+                format!("    {} }};", element_infos.join(", \n")),
+                "*id = element_infos[index][0];".into(),
+                "*type_name = element_infos[index][1];".into(),
+            ]),
+            ..Default::default()
+        }),
+    ));
+    component_struct.members.push((
+        Access::Private,
+        Declaration::Function(Function {
+            name: "accessible_role".into(),
+            signature: "([[maybe_unused]] sixtyfps::private_api::ComponentRef component, uintptr_t index) -> sixtyfps::cbindgen_private::AccessibleRole".into(),
+            is_static: true,
+            statements: Some(vec![
+                format!("[[maybe_unused]] auto self = reinterpret_cast<const {}*>(component.instance);", component_id),
+                format!("switch (index) {{ {} }}", accessible_role_cases.join(" ")),
+                "return sixtyfps::cbindgen_private::AccessibleRole::none;".into(),
+            ]),
+            ..Default::default()
+        }),
+    ));
+    component_struct.members.push((
+        Access::Private,
+        Declaration::Function(Function {
+            name: "accessible_string_property".into(),
+            signature: "([[maybe_unused]] sixtyfps::private_api::ComponentRef component, [[maybe_unused]] uintptr_t index, [[maybe_unused]] sixtyfps::cbindgen_private::AccessibleStringProperty what, [[maybe_unused]] sixtyfps::SharedString *result) -> void".into(),
+            is_static: true,
+            statements: Some(
+                std::iter::once(format!("[[maybe_unused]] auto self = reinterpret_cast<const {}*>(component.instance);", component_id))
+                    .chain(accessible_string_property_cases)
+                    .collect(),
+            ),
+            ..Default::default()
+        }),
+    ));
+    component_struct.members.push((
+        Access::Private,
+        Declaration::Function(Function {
+            name: "element_property".into(),
+            signature: "([[maybe_unused]] sixtyfps::private_api::ComponentRef component, [[maybe_unused]] uintptr_t index, [[maybe_unused]] const sixtyfps::SharedString *name, [[maybe_unused]] sixtyfps::cbindgen_private::ElementPropertyValue *result) -> void".into(),
+            is_static: true,
+            statements: Some(
+                std::iter::once(format!("[[maybe_unused]] auto self = reinterpret_cast<const {}*>(component.instance);", component_id))
+                    .chain(element_property_cases)
+                    .collect(),
+            ),
+            ..Default::default()
+        }),
+    ));
     component_struct.members.push((
         Access::Public,
         Declaration::Var(Var {
@@ -1745,13 +1955,22 @@ fn generate_component_vtable(
         ty: "const sixtyfps::private_api::ComponentVTable".to_owned(),
         name: format!("{}::static_vtable", component_id),
         init: Some(format!(
-            "{{ visit_children, get_item_ref, parent_item,  layout_info, sixtyfps::private_api::drop_in_place<{}>, sixtyfps::private_api::dealloc }}",
+            "{{ visit_children, get_item_ref, parent_item,  layout_info, item_element_info, accessible_role, accessible_string_property, element_property, sixtyfps::private_api::drop_in_place<{}>, sixtyfps::private_api::dealloc }}",
             component_id)
         ),
         ..Default::default()
     }));
 }
 
+fn push_element_info(element_infos: &mut Vec<String>, item_rc: &ElementRc) {
+    let (id, type_name) = item_rc.borrow().source_id_and_type_name();
+    element_infos.push(format!(
+        "{{ \"{}\", \"{}\" }}",
+        escape_string(id.as_str()),
+        escape_string(type_name.as_str())
+    ));
+}
+
 /// Retruns the tokens needed to access the root component (where global singletons are located).
 /// This is needed for the `init()` calls on sub-components, that take the root as a parameter.
 fn access_root_tokens(component: &Rc<Component>) -> String {
@@ -1888,6 +2107,55 @@ fn access_member(
     }
 }
 
+/// Returns the code to access the member function generated for the function `name` declared on `element`
+fn access_function(
+    element: &ElementRc,
+    name: &str,
+    component: &Rc<Component>,
+    component_cpp: &str,
+) -> String {
+    let e = element.borrow();
+    let enclosing_component = e.enclosing_component.upgrade().unwrap();
+    if Rc::ptr_eq(component, &enclosing_component) {
+        match e.sub_component().filter(|_| !e.property_declarations.contains_key(name)) {
+            Some(sub_component) => access_function(
+                &sub_component.root_element,
+                name,
+                sub_component,
+                &format!("(&{}->{})", component_cpp, ident(&e.id)),
+            ),
+            None => format!("{}->fn_{}", component_cpp, ident(name)),
+        }
+    } else if enclosing_component.is_global() {
+        let mut top_level_component = component.clone();
+        let mut component_cpp = component_cpp.to_owned();
+        while let Some(p) = top_level_component.parent_element.upgrade() {
+            top_level_component = p.borrow().enclosing_component.upgrade().unwrap();
+            component_cpp = format!("{}->parent", component_cpp);
+        }
+        if top_level_component.is_sub_component() {
+            component_cpp = format!("{}->m_root", component_cpp);
+        }
+        let global_comp =
+            format!("{}->global_{}", component_cpp, component_id(&enclosing_component));
+        access_function(element, name, &enclosing_component, &global_comp)
+    } else {
+        access_function(
+            element,
+            name,
+            &component
+                .parent_element
+                .upgrade()
+                .unwrap()
+                .borrow()
+                .enclosing_component
+                .upgrade()
+                .unwrap(),
+            &format!("{}->parent", component_cpp),
+        )
+    }
+}
+
 /// Call access_member  for a NamedReference
 fn access_named_reference(
     nr: &NamedReference,
@@ -1973,10 +2241,16 @@ fn compile_expression(
             "{}.call",
             access_named_reference(nr, component, "self")
         ),
+        Expression::FunctionReference(nr) => {
+            access_function(&nr.element(), nr.name(), component, "self")
+        }
         Expression::BuiltinFunctionReference(funcref, _) => match funcref {
             BuiltinFunction::GetWindowScaleFactor => {
                 "self->m_window.window_handle().scale_factor".into()
             }
+            BuiltinFunction::WindowIsRightToLeft => {
+                "self->m_window.window_handle().is_right_to_left".into()
+            }
             BuiltinFunction::Debug => {
                 "[](auto... args){ (std::cout << ... << args) << std::endl; return nullptr; }"
                     .into()
@@ -1999,6 +2273,15 @@ fn compile_expression(
             BuiltinFunction::ShowPopupWindow => {
                 "self->m_window.window_handle().show_popup".into()
             }
+            BuiltinFunction::TextInputUndo => {
+                "self->m_window.window_handle().text_input_undo".into()
+            }
+            BuiltinFunction::TextInputRedo => {
+                "self->m_window.window_handle().text_input_redo".into()
+            }
+            BuiltinFunction::FlickableScrollTo => {
+                "self->m_window.window_handle().flickable_scroll_to".into()
+            }
 
            /*  std::from_chars is unfortunately not yet implemented in gcc
             BuiltinFunction::StringIsFloat => {
@@ -2017,6 +2300,20 @@ fn compile_expression(
                 "[](const auto &a){ auto e1 = std::end(a); auto e2 = const_cast<char*>(e1); auto r = std::strtod(std::begin(a), &e2); return e1 == e2 ? r : 0; }"
                     .into()
             }
+            BuiltinFunction::StringLength => "sixtyfps::private_api::string_length".into(),
+            BuiltinFunction::StringToUppercase => "sixtyfps::private_api::string_to_uppercase".into(),
+            BuiltinFunction::StringToLowercase => "sixtyfps::private_api::string_to_lowercase".into(),
+            BuiltinFunction::StringContains => "sixtyfps::private_api::string_contains".into(),
+            BuiltinFunction::StringStartsWith => {
+                "[](const sixtyfps::SharedString &a, const sixtyfps::SharedString &b) { return a.starts_with(b); }".into()
+            }
+            BuiltinFunction::StringSubstring => "sixtyfps::private_api::string_substring".into(),
+            BuiltinFunction::StringTrim => "sixtyfps::private_api::string_trim".into(),
+            BuiltinFunction::StringReplace => "sixtyfps::private_api::string_replace".into(),
+            BuiltinFunction::StringSplit => "sixtyfps::private_api::string_split".into(),
+            BuiltinFunction::FormatNumber => "sixtyfps::private_api::format_number".into(),
+            BuiltinFunction::FormatInteger => "sixtyfps::private_api::format_integer".into(),
+            BuiltinFunction::FormatString => "sixtyfps::private_api::format_string".into(),
             BuiltinFunction::ImplicitLayoutInfo(_) => {
                 unreachable!()
             }
@@ -2035,6 +2332,9 @@ fn compile_expression(
             BuiltinFunction::Rgb => {
                 "[](int r, int g, int b, float a) {{ return sixtyfps::Color::from_argb_uint8(std::clamp(a * 255., 0., 255.), std::clamp(r, 0, 255), std::clamp(g, 0, 255), std::clamp(b, 0, 255)); }}".into()
             }
+            BuiltinFunction::Translate => {
+                panic!("internal error: Translate can only be evaluated from within a FunctionCall expression")
+            }
             BuiltinFunction::RegisterCustomFontByPath => {
                 panic!("internal error: RegisterCustomFontByPath can only be evaluated from within a FunctionCall expression")
             }
@@ -2141,6 +2441,39 @@ fn compile_expression(
                     panic!("internal error: argument to SetFocusItem must be an element")
                 }
             }
+            Expression::BuiltinFunctionReference(f, _) if matches!(f, BuiltinFunction::TextInputUndo | BuiltinFunction::TextInputRedo) => {
+                if arguments.len() != 1 {
+                    panic!("internal error: incorrect argument count to {:?} call", f);
+                }
+                if let Expression::ElementReference(text_input) = &arguments[0] {
+                    let text_input = text_input.upgrade().unwrap();
+                    let component_ref = access_element_component(&text_input, component, "self");
+                    format!("{}({}->self_weak.lock()->into_dyn(), {});",
+                        compile_expression(function, component),
+                        component_ref,
+                        absolute_element_item_index_expression(&text_input))
+                } else {
+                    panic!("internal error: argument to {:?} must be an element", f)
+                }
+            }
+            Expression::BuiltinFunctionReference(BuiltinFunction::FlickableScrollTo, _) => {
+                if arguments.len() != 4 {
+                    panic!("internal error: incorrect argument count to FlickableScrollTo call");
+                }
+                if let Expression::ElementReference(flickable) = &arguments[0] {
+                    let flickable = flickable.upgrade().unwrap();
+                    let component_ref = access_element_component(&flickable, component, "self");
+                    format!("{}({}->self_weak.lock()->into_dyn(), {}, {}, {}, {});",
+                        compile_expression(function, component),
+                        component_ref,
+                        absolute_element_item_index_expression(&flickable),
+                        compile_expression(&arguments[1], component),
+                        compile_expression(&arguments[2], component),
+                        compile_expression(&arguments[3], component))
+                } else {
+                    panic!("internal error: argument to FlickableScrollTo must be an element")
+                }
+            }
             Expression::BuiltinFunctionReference(BuiltinFunction::ShowPopupWindow, _) => {
                 if arguments.len() != 1 {
                     panic!("internal error: incorrect argument count to SetFocusItem call");
@@ -2190,6 +2523,24 @@ fn compile_expression(
                     panic!("internal error: argument to ImplicitLayoutInfo must be an element")
                 }
             }
+            Expression::BuiltinFunctionReference(BuiltinFunction::Translate, _) => {
+                if arguments.len() != 5 {
+                    panic!("internal error: incorrect argument count to Translate call");
+                }
+                if let Expression::Array { values, .. } = &arguments[4] {
+                    let mut args = arguments[..4].iter().map(|e| compile_expression(e, component));
+                    format!(
+                        "sixtyfps::private_api::translate({}, {}, {}, {}, {{ {} }})",
+                        args.next().unwrap(),
+                        args.next().unwrap(),
+                        args.next().unwrap(),
+                        args.next().unwrap(),
+                        values.iter().map(|e| format!("sixtyfps::SharedString({})", compile_expression(e, component))).join(", ")
+                    )
+                } else {
+                    panic!("internal error: the arguments of Translate must be an array")
+                }
+            }
             Expression::BuiltinFunctionReference(BuiltinFunction::RegisterCustomFontByPath, _) => {
                 if arguments.len() != 1 {
                     panic!("internal error: incorrect argument count to RegisterCustomFontByPath call");
@@ -2319,6 +2670,24 @@ fn compile_expression(
                 stops_it.join(", "), angle, stops.len()
             )
         }
+        Expression::RadialGradient{stops} | Expression::ConicGradient{stops} => {
+            let class = if matches!(expr, Expression::RadialGradient{..}) { "RadialGradientBrush" } else { "ConicGradientBrush" };
+            if stops.is_empty() {
+                return format!("sixtyfps::Brush(sixtyfps::private_api::{}(nullptr, 0))", class);
+            }
+            let mut stops_it = stops.iter().map(|(color, stop)| {
+                let color = compile_expression(color, component);
+                let position = compile_expression(stop, component);
+                format!("sixtyfps::private_api::GradientStop{{ {}, {}, }}", color, position)
+            });
+            format!(
+                "[&] {{ const sixtyfps::private_api::GradientStop stops[] = {{ {} }}; return sixtyfps::Brush(sixtyfps::private_api::{}(stops, {})); }}()",
+                stops_it.join(", "), class, stops.len()
+            )
+        }
+        Expression::EnumerationValue(value) if value.enumeration.node.is_some() => {
+            format!("{}::{}", ident(&value.enumeration.name), ident(&value.to_string()))
+        }
         Expression::EnumerationValue(value) => {
             format!("sixtyfps::cbindgen_private::{}::{}", value.enumeration.name, ident(&value.to_string()))
         }
@@ -21,7 +21,7 @@
     BindingExpression, BuiltinFunction, EasingCurve, Expression, NamedReference, OperatorClass,
     Path,
 };
-use crate::langtype::Type;
+use crate::langtype::{Enumeration, Type};
 use crate::layout::{Layout, LayoutGeometry, LayoutRect, Orientation};
 use crate::object_tree::{Component, Document, ElementRc};
 use proc_macro2::{Ident, TokenStream};
@@ -36,6 +36,11 @@ fn ident(ident: &str) -> proc_macro2::Ident {
     }
 }
 
+/// The identifier of the method generated for a function declared in the .60 file
+fn function_ident(name: &str) -> proc_macro2::Ident {
+    format_ident!("fn_{}", name.replace('-', "_"))
+}
+
 impl quote::ToTokens for Orientation {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let tks = match self {
@@ -82,8 +87,12 @@ fn rust_type(ty: &Type) -> Option<proc_macro2::TokenStream> {
             Some(quote!(sixtyfps::re_exports::ModelHandle<#inner>))
         }
         Type::Enumeration(e) => {
-            let e = ident(&e.name);
-            Some(quote!(sixtyfps::re_exports::#e))
+            let name = ident(&e.name);
+            if e.node.is_some() {
+                Some(quote!(#name))
+            } else {
+                Some(quote!(sixtyfps::re_exports::#name))
+            }
         }
         Type::Brush => Some(quote!(sixtyfps::Brush)),
         Type::LayoutCache => Some(quote!(SharedVector<f32>)),
@@ -126,6 +135,15 @@ pub fn generate(doc: &Document, diag: &mut BuildDiagnostics) -> Option<TokenStre
         })
         .unzip();
 
+    let (enums_ids, enums): (Vec<_>, Vec<_>) = doc
+        .root_component
+        .used_types
+        .borrow()
+        .enums
+        .iter()
+        .map(|e| (ident(&e.name), generate_enum(e)))
+        .unzip();
+
     let mut sub_compos = Vec::new();
     for sub_comp in doc.root_component.used_types.borrow().sub_components.iter() {
         sub_compos.push(generate_component(&sub_comp, &doc.root_component, diag)?);
@@ -172,13 +190,14 @@ pub fn generate(doc: &Document, diag: &mut BuildDiagnostics) -> Option<TokenStre
         #[allow(unused_braces)]
         mod #compo_module {
             use sixtyfps::re_exports::*;
+            #(#enums)*
             #(#structs)*
             #(#globals)*
             #(#sub_compos)*
             #compo
             const _THE_SAME_VERSION_MUST_BE_USED_FOR_THE_COMPILER_AND_THE_RUNTIME : sixtyfps::#version_check = sixtyfps::#version_check;
         }
-        pub use #compo_module::{#compo_id #(,#structs_ids)* #(,#globals_ids)* };
+        pub use #compo_module::{#compo_id #(,#enums_ids)* #(,#structs_ids)* #(,#globals_ids)* };
         pub use sixtyfps::{ComponentHandle, Global};
     })
 }
@@ -204,6 +223,23 @@ pub struct #component_id {
     }
 }
 
+fn generate_enum(en: &Enumeration) -> TokenStream {
+    let enum_id = ident(&en.name);
+    let values = en.values.iter().map(|v| ident(v)).collect::<Vec<_>>();
+    let default_value = &values[en.default_value];
+    quote! {
+        #[derive(Copy, Clone, PartialEq, Eq, Debug)]
+        pub enum #enum_id {
+            #(#values),*
+        }
+        impl Default for #enum_id {
+            fn default() -> Self {
+                Self::#default_value
+            }
+        }
+    }
+}
+
 fn handle_property_binding(
     component: &Rc<Component>,
     item_rc: &ElementRc,
@@ -354,7 +390,37 @@ fn generate_component(
     let mut declared_callbacks_types = vec![];
     let mut declared_callbacks_ret = vec![];
     let mut property_and_callback_accessors: Vec<TokenStream> = vec![];
+    let mut declared_functions: Vec<TokenStream> = vec![];
     for (prop_name, property_decl) in component.root_element.borrow().property_declarations.iter() {
+        if let Type::Function { args, return_type } = &property_decl.property_type {
+            let function_ident = function_ident(prop_name);
+            let args_types = args
+                .iter()
+                .map(|a| get_rust_type(a, &property_decl.type_node(), diag))
+                .collect::<Vec<_>>();
+            let return_type = if **return_type == Type::Void {
+                quote!(())
+            } else {
+                get_rust_type(return_type, &property_decl.type_node(), diag)
+            };
+            let body = component
+                .root_element
+                .borrow()
+                .bindings
+                .get(prop_name)
+                .map(|b| compile_expression(&b.borrow(), component));
+            declared_functions.push(quote!(
+                #[allow(dead_code, unused)]
+                fn #function_ident(self: ::core::pin::Pin<&Self>, args: (#(#args_types,)*)) -> #return_type {
+                    #[allow(unused_imports)]
+                    use sixtyfps::re_exports::*;
+                    let _self = self;
+                    (#body) as _
+                }
+            ));
+            continue;
+        }
+
         let prop_ident = ident(prop_name);
 
         let make_prop_getter = |self_accessor| {
@@ -470,6 +536,10 @@ pub fn #setter_ident(&self, value: #rust_property_type) {
 
     struct TreeBuilder<'a> {
         tree_array: Vec<TokenStream>,
+        element_infos: Vec<TokenStream>,
+        accessible_role_branches: Vec<TokenStream>,
+        accessible_string_property_branches: Vec<TokenStream>,
+        element_property_branches: Vec<TokenStream>,
         item_names: Vec<Ident>,
         sub_component_names: Vec<Ident>,
         sub_component_types: Vec<Ident>,
@@ -516,14 +586,25 @@ fn push_repeated_item(
                     parent_index: #parent_index,
                 }
             ));
+            self.push_element_info(item_rc);
         }
         fn push_native_item(
             &mut self,
             item_rc: &ElementRc,
+            source_element: &ElementRc,
             children_index: u32,
             parent_index: u32,
             component_state: &Self::SubComponentState,
+            source_component_state: &Self::SubComponentState,
         ) {
+            self.push_element_info(source_element);
+            self.push_element_properties(source_element, self.tree_array.len());
+            self.push_accessibility_props(
+                item_rc,
+                source_element,
+                source_component_state,
+                self.tree_array.len(),
+            );
             let item = item_rc.borrow();
             let children_count = item.children.len() as u32;
             let inner_component_id =
@@ -640,6 +721,59 @@ fn enter_component_children(
     }
 
     impl<'a> TreeBuilder<'a> {
+        fn push_element_info(&mut self, item_rc: &ElementRc) {
+            let (id, type_name) = item_rc.borrow().source_id_and_type_name();
+            self.element_infos.push(quote!((#id, #type_name)));
+        }
+
+        fn push_element_properties(&mut self, source_element: &ElementRc, index: usize) {
+            let in_generating_component = Rc::ptr_eq(
+                &source_element.borrow().enclosing_component.upgrade().unwrap(),
+                self.generating_component,
+            );
+            if !in_generating_component
+                || source_element.borrow().source_id_and_type_name().1.is_empty()
+            {
+                return;
+            }
+            for (name, nr) in crate::generator::queryable_properties(source_element) {
+                let prop = access_named_reference(&nr, self.generating_component, quote!(_self));
+                self.element_property_branches
+                    .push(quote!((#index, #name) => *result = #prop.get().into(),));
+            }
+        }
+
+        fn push_accessibility_props(
+            &mut self,
+            item_rc: &ElementRc,
+            source_element: &ElementRc,
+            source_component_state: &TokenStream,
+            index: usize,
+        ) {
+            let source_component = source_element.borrow().enclosing_component.upgrade().unwrap();
+            let source_component_rust = if source_component_state.is_empty() {
+                quote!(_self)
+            } else {
+                // The state is a sum of field offsets followed by a `+`
+                let mut offsets = source_component_state.clone().into_iter().collect::<Vec<_>>();
+                offsets.pop();
+                let offsets = offsets.into_iter().collect::<TokenStream>();
+                quote!((#offsets).apply_pin(_self))
+            };
+            for (what, nr) in crate::generator::item_accessibility_props(item_rc, source_element) {
+                let prop =
+                    access_named_reference(&nr, &source_component, source_component_rust.clone());
+                if what == "role" {
+                    self.accessible_role_branches.push(quote!(#index => #prop.get(),));
+                } else {
+                    let what = ident(&what);
+                    self.accessible_string_property_branches.push(quote!(
+                        (#index, sixtyfps::re_exports::AccessibleStringProperty::#what) => *result = #prop.get(),
+                    ));
+                }
+            }
+        }
+
         fn handle_repeater(
             &mut self,
             repeated: &crate::object_tree::RepeatedElementInfo,
@@ -797,6 +931,10 @@ fn update(&self, index: usize, data: Self::Data) {
 
     let mut builder = TreeBuilder {
         tree_array: vec![],
+        element_infos: vec![],
+        accessible_role_branches: vec![],
+        accessible_string_property_branches: vec![],
+        element_property_branches: vec![],
         item_names: vec![],
         item_types: vec![],
         sub_component_names: vec![],
@@ -829,6 +967,10 @@ fn update(&self, index: usize, data: Self::Data) {
 
     let TreeBuilder {
         tree_array: item_tree_array,
+        element_infos,
+        accessible_role_branches,
+        accessible_string_property_branches,
+        element_property_branches,
         item_names,
         item_types,
         sub_component_names,
@@ -1047,6 +1189,40 @@ fn parent_item(self: ::core::pin::Pin<&Self>, index: usize, result: &mut sixtyfp
                         let self_rc = self.self_weak.get().unwrap().clone().into_dyn().upgrade().unwrap();
                         *result = ItemRc::new(self_rc, parent_index as _).downgrade()
                     }
+
+                    fn item_element_info(self: ::core::pin::Pin<&Self>, index: usize, id: &mut sixtyfps::SharedString, type_name: &mut sixtyfps::SharedString) {
+                        const ELEMENT_INFOS: [(&str, &str); #item_tree_array_len] = [#(#element_infos),*];
+                        let (element_id, element_type_name) = ELEMENT_INFOS[index];
+                        *id = element_id.into();
+                        *type_name = element_type_name.into();
+                    }
+
+                    fn accessible_role(self: ::core::pin::Pin<&Self>, index: usize) -> sixtyfps::re_exports::AccessibleRole {
+                        #![allow(unused)]
+                        let _self = self;
+                        match index {
+                            #(#accessible_role_branches)*
+                            _ => sixtyfps::re_exports::AccessibleRole::none,
+                        }
+                    }
+
+                    fn accessible_string_property(self: ::core::pin::Pin<&Self>, index: usize, what: sixtyfps::re_exports::AccessibleStringProperty, result: &mut sixtyfps::SharedString) {
+                        #![allow(unused)]
+                        let _self = self;
+                        match (index, what) {
+                            #(#accessible_string_property_branches)*
+                            _ => {}
+                        }
+                    }
+
+                    fn element_property(self: ::core::pin::Pin<&Self>, index: usize, name: &sixtyfps::SharedString, result: &mut sixtyfps::re_exports::ElementPropertyValue) {
+                        #![allow(unused)]
+                        let _self = self;
+                        match (index, name.as_str()) {
+                            #(#element_property_branches)*
+                            _ => {}
+                        }
+                    }
                 }
             }),
         )
@@ -1331,6 +1507,7 @@ pub fn new(#(parent: #parent_component_type)* #window_parent_param)
         impl #inner_component_id{
             #inner_impl
             #item_tree_impl
+            #(#declared_functions)*
         }
 
         #public_interface
@@ -1518,6 +1695,61 @@ fn access_member(
     }
 }
 
+/// Returns the tokens needed to call the function `name` declared in the .60 file on `element`.
+/// The arguments must be passed as a tuple.
+fn access_function(
+    element: &ElementRc,
+    name: &str,
+    component: &Rc<Component>,
+    component_rust: TokenStream,
+) -> TokenStream {
+    let e = element.borrow();
+
+    let enclosing_component = e.enclosing_component.upgrade().unwrap();
+    if Rc::ptr_eq(component, &enclosing_component) {
+        if let Some(sub_component) =
+            e.sub_component().filter(|_| !e.property_declarations.contains_key(name))
+        {
+            let inner_component_id = inner_component_id(&enclosing_component);
+            let subcomp_ident = ident(&e.id);
+            let subcomp_field = access_component_field_offset(&inner_component_id, &subcomp_ident);
+            let subcomp = quote!(#subcomp_field.apply_pin(#component_rust));
+            access_function(&sub_component.root_element, name, sub_component, subcomp)
+        } else {
+            let function_ident = function_ident(name);
+            quote!(#component_rust.#function_ident)
+        }
+    } else if enclosing_component.is_global() {
+        let mut top_level_component = component.clone();
+        let mut component_rust = component_rust;
+        while let Some(p) = top_level_component.parent_element.upgrade() {
+            top_level_component = p.borrow().enclosing_component.upgrade().unwrap();
+            component_rust = quote!(#component_rust.parent.upgrade().unwrap().as_pin_ref());
+        }
+        if top_level_component.is_sub_component() {
+            component_rust =
+                quote!(#component_rust.root.get().unwrap().upgrade().unwrap().as_pin_ref());
+        }
+        let global_id = format_ident!("global_{}", public_component_id(&enclosing_component));
+        let global_comp = quote!(#component_rust.#global_id.as_ref());
+        access_function(element, name, &enclosing_component, global_comp)
+    } else {
+        access_function(
+            element,
+            name,
+            &component
+                .parent_element
+                .upgrade()
+                .unwrap()
+                .borrow()
+                .enclosing_component
+                .upgrade()
+                .unwrap(),
+            quote!(#component_rust.parent.upgrade().unwrap().as_pin_ref()),
+        )
+    }
+}
+
 /// Call access_member  for a NamedReference
 fn access_named_reference(
     nr: &NamedReference,
@@ -1630,6 +1862,10 @@ fn compile_expression(expr: &Expression, component: &Rc<Component>) -> TokenStre
                 let window_tokens = access_window_field(component, quote!(_self));
                 quote!(#window_tokens.window_handle().scale_factor)
             }
+            BuiltinFunction::WindowIsRightToLeft => {
+                let window_tokens = access_window_field(component, quote!(_self));
+                quote!(#window_tokens.window_handle().is_right_to_left)
+            }
             BuiltinFunction::Debug => quote!((|x| println!("{:?}", x))),
             BuiltinFunction::Mod => quote!((|a1, a2| (a1 as i32) % (a2 as i32))),
             BuiltinFunction::Round => quote!((|a| (a as f64).round())),
@@ -1643,7 +1879,7 @@ fn compile_expression(expr: &Expression, component: &Rc<Component>) -> TokenStre
             BuiltinFunction::ASin => quote!((|a| (a as f64).asin().to_degrees())),
             BuiltinFunction::ACos => quote!((|a| (a as f64).acos().to_degrees())),
             BuiltinFunction::ATan => quote!((|a| (a as f64).atan().to_degrees())),
-            BuiltinFunction::SetFocusItem | BuiltinFunction::ShowPopupWindow | BuiltinFunction::ImplicitLayoutInfo(_) => {
+            BuiltinFunction::SetFocusItem | BuiltinFunction::ShowPopupWindow | BuiltinFunction::TextInputUndo | BuiltinFunction::TextInputRedo | BuiltinFunction::FlickableScrollTo | BuiltinFunction::ImplicitLayoutInfo(_) => {
                 panic!("internal error: should be handled directly in CallFunction")
             }
             BuiltinFunction::StringToFloat => {
@@ -1652,6 +1888,46 @@ fn compile_expression(expr: &Expression, component: &Rc<Component>) -> TokenStre
             BuiltinFunction::StringIsFloat => {
                 quote!((|x: SharedString| { <f64 as ::core::str::FromStr>::from_str(x.as_str()).is_ok() } ))
             }
+            BuiltinFunction::StringLength => {
+                quote!((|x: SharedString| -> i32 { sixtyfps::re_exports::string_length(&x) }))
+            }
+            BuiltinFunction::StringToUppercase => {
+                quote!((|x: SharedString| -> SharedString { x.to_uppercase().into() }))
+            }
+            BuiltinFunction::StringToLowercase => {
+                quote!((|x: SharedString| -> SharedString { x.to_lowercase().into() }))
+            }
+            BuiltinFunction::StringContains => {
+                quote!((|x: SharedString, y: SharedString| -> bool { x.contains(y.as_str()) }))
+            }
+            BuiltinFunction::StringStartsWith => {
+                quote!((|x: SharedString, y: SharedString| -> bool { x.starts_with(y.as_str()) }))
+            }
+            BuiltinFunction::StringSubstring => {
+                quote!((|x: SharedString, start: i32, length: i32| -> SharedString { sixtyfps::re_exports::string_substring(&x, start, length) }))
+            }
+            BuiltinFunction::StringTrim => {
+                quote!((|x: SharedString| -> SharedString { x.trim().into() }))
+            }
+            BuiltinFunction::StringReplace => {
+                quote!((|x: SharedString, from: SharedString, to: SharedString| -> SharedString { x.replace(from.as_str(), &to).into() }))
+            }
+            BuiltinFunction::StringSplit => {
+                quote!((|x: SharedString, sep: SharedString| -> ModelHandle<SharedString> {
+                    sixtyfps::re_exports::ModelHandle::new(sixtyfps::re_exports::Rc::new(
+                        sixtyfps::re_exports::VecModel::from(sixtyfps::re_exports::string_split(&x, &sep))
+                    ))
+                }))
+            }
+            BuiltinFunction::FormatNumber => {
+                quote!((|x: f32, spec: SharedString| -> SharedString { sixtyfps::re_exports::format_number(x as f64, &spec) }))
+            }
+            BuiltinFunction::FormatInteger => {
+                quote!((|x, spec: SharedString| -> SharedString { sixtyfps::re_exports::format_integer(x as i64, &spec) }))
+            }
+            BuiltinFunction::FormatString => {
+                quote!((|x: SharedString, spec: SharedString| -> SharedString { sixtyfps::re_exports::format_string(&x, &spec) }))
+            }
             BuiltinFunction::ColorBrighter => {
                 quote!((|x: Color, factor| -> Color { x.brighter(factor as f32) }))
             }
@@ -1674,6 +1950,9 @@ fn compile_expression(expr: &Expression, component: &Rc<Component>) -> TokenStre
                     sixtyfps::re_exports::Color::from_argb_u8(a, r, g, b)
                 }))
             }
+            BuiltinFunction::Translate => {
+                panic!("internal error: BuiltinFunction::Translate can only be compiled as part of a FunctionCall expression")
+            }
             BuiltinFunction::RegisterCustomFontByPath => {
                 panic!("internal error: BuiltinFunction::RegisterCustomFontByPath can only be compiled as part of a FunctionCall expression")
             }
@@ -1734,6 +2013,7 @@ fn compile_expression(expr: &Expression, component: &Rc<Component>) -> TokenStre
             component,
             quote!(_self),
         ),
+        Expression::FunctionReference(_) => panic!("function reference outside of a function call"),
         Expression::FunctionCall { function, arguments,  source_location: _ } => {
             match &**function {
                 Expression::BuiltinFunctionReference(BuiltinFunction::SetFocusItem, _) => {
@@ -1779,6 +2059,47 @@ fn compile_expression(expr: &Expression, component: &Rc<Component>) -> TokenStre
                         panic!("internal error: argument to SetFocusItem must be an element")
                     }
                 }
+                Expression::BuiltinFunctionReference(f, _) if matches!(f, BuiltinFunction::TextInputUndo | BuiltinFunction::TextInputRedo) => {
+                    if arguments.len() != 1 {
+                        panic!("internal error: incorrect argument count to {:?} call", f);
+                    }
+                    if let Expression::ElementReference(text_input) = &arguments[0] {
+                        let text_input = text_input.upgrade().unwrap();
+                        let component_vrc = element_component_vrc(&text_input, component);
+                        let item_index_tokens = absolute_element_item_index_expression(&text_input);
+                        let window_tokens = access_window_field(component, quote!(_self));
+                        let method = if matches!(f, BuiltinFunction::TextInputUndo) { quote!(undo) } else { quote!(redo) };
+                        quote!({
+                            let item_rc = ItemRc::new(#component_vrc, #item_index_tokens);
+                            if let Some(text_input) = ItemRef::downcast_pin::<TextInput>(item_rc.borrow()) {
+                                text_input.#method(&#window_tokens.window_handle());
+                            }
+                        })
+                    } else {
+                        panic!("internal error: argument to {:?} must be an element", f)
+                    }
+                }
+                Expression::BuiltinFunctionReference(BuiltinFunction::FlickableScrollTo, _) => {
+                    if arguments.len() != 4 {
+                        panic!("internal error: incorrect argument count to FlickableScrollTo call");
+                    }
+                    if let Expression::ElementReference(flickable) = &arguments[0] {
+                        let flickable = flickable.upgrade().unwrap();
+                        let component_vrc = element_component_vrc(&flickable, component);
+                        let item_index_tokens = absolute_element_item_index_expression(&flickable);
+                        let x = compile_expression(&arguments[1], component);
+                        let y = compile_expression(&arguments[2], component);
+                        let animated = compile_expression(&arguments[3], component);
+                        quote!({
+                            let item_rc = ItemRc::new(#component_vrc, #item_index_tokens);
+                            if let Some(flickable) = ItemRef::downcast_pin::<Flickable>(item_rc.borrow()) {
+                                flickable.scroll_to(#x as _, #y as _, #animated);
+                            }
+                        })
+                    } else {
+                        panic!("internal error: argument to FlickableScrollTo must be an element")
+                    }
+                }
                 Expression::BuiltinFunctionReference(BuiltinFunction::ImplicitLayoutInfo(orient), _) => {
                     if arguments.len() != 1 {
                         panic!("internal error: incorrect argument count to ImplicitLayoutInfo call");
@@ -1796,6 +2117,21 @@ fn compile_expression(expr: &Expression, component: &Rc<Component>) -> TokenStre
                         panic!("internal error: argument to ImplicitLayoutInfo must be an element")
                     }
                 }
+                Expression::BuiltinFunctionReference(BuiltinFunction::Translate, _) => {
+                    if arguments.len() != 5 {
+                        panic!("internal error: incorrect argument count to Translate call");
+                    }
+                    if let Expression::Array { values, .. } = &arguments[4] {
+                        let context = compile_expression(&arguments[0], component);
+                        let text = compile_expression(&arguments[1], component);
+                        let plural = compile_expression(&arguments[2], component);
+                        let n = compile_expression(&arguments[3], component);
+                        let args = values.iter().map(|a| compile_expression(a, component));
+                        quote!(sixtyfps::re_exports::translate(&#context, &#text, &#plural, (#n) as i32, &[#(#args),*]))
+                    } else {
+                        panic!("internal error: the arguments of Translate must be an array")
+                    }
+                }
                 Expression::BuiltinFunctionReference(BuiltinFunction::RegisterCustomFontByPath, _) => {
                     if arguments.len() != 1 {
                         panic!("internal error: incorrect argument count to RegisterCustomFontByPath call");
@@ -1818,6 +2154,21 @@ fn compile_expression(expr: &Expression, component: &Rc<Component>) -> TokenStre
                         panic!("internal error: argument to RegisterCustomFontByMemory must be a number")
                     }
                 }
+                Expression::FunctionReference(nr) => {
+                    let f = access_function(&nr.element(), nr.name(), component, quote!(_self));
+                    let a = arguments.iter().map(|a| compile_expression(a, component));
+                    let args = match nr.ty() {
+                        Type::Function { args, .. } => args,
+                        _ => panic!("not calling a function"),
+                    };
+                    let cast = args.iter().map(|ty| match ty {
+                        Type::Bool => quote!(as bool),
+                        Type::Int32 => quote!(as i32),
+                        Type::Float32 => quote!(as f32),
+                        _ => quote!(.clone()),
+                    });
+                    quote! { #f((#((#a)#cast,)*)) }
+                }
                 _ => {
                     let f = compile_expression(function, component);
                     let a = arguments.iter().map(|a| compile_expression(a, component));
@@ -1998,10 +2349,34 @@ fn compile_expression(expr: &Expression, component: &Rc<Component>) -> TokenStre
                 sixtyfps::re_exports::LinearGradientBrush::new(#angle as _, [#(#stops),*].iter().cloned())
             ))
         }
+        Expression::RadialGradient{stops} => {
+            let stops = stops.iter().map(|(color, stop)| {
+                let color = compile_expression(color, component);
+                let position = compile_expression(stop, component);
+                quote!(sixtyfps::re_exports::GradientStop{ color: #color, position: #position as _ })
+            });
+            quote!(sixtyfps::Brush::RadialGradient(
+                sixtyfps::re_exports::RadialGradientBrush::new_circle([#(#stops),*].iter().cloned())
+            ))
+        }
+        Expression::ConicGradient{stops} => {
+            let stops = stops.iter().map(|(color, stop)| {
+                let color = compile_expression(color, component);
+                let position = compile_expression(stop, component);
+                quote!(sixtyfps::re_exports::GradientStop{ color: #color, position: #position as _ })
+            });
+            quote!(sixtyfps::Brush::ConicGradient(
+                sixtyfps::re_exports::ConicGradientBrush::new([#(#stops),*].iter().cloned())
+            ))
+        }
         Expression::EnumerationValue(value) => {
             let base_ident = ident(&value.enumeration.name);
             let value_ident = ident(&value.to_string());
-            quote!(sixtyfps::re_exports::#base_ident::#value_ident)
+            if value.enumeration.node.is_some() {
+                quote!(#base_ident::#value_ident)
+            } else {
+                quote!(sixtyfps::re_exports::#base_ident::#value_ident)
+            }
         }
         Expression::ReturnStatement(expr) => {
             let return_expr = expr.as_ref().map(|expr| compile_expression(expr, component));
@@ -15,9 +15,18 @@
 
 #[derive(Default)]
 pub struct LexState {
-    /// The top of the stack is the level of embedded braces `{`.
+    /// The top of the stack is the state of the innermost template string expression.
+    template_string_stack: Vec<TemplateStringState>,
+}
+
+#[derive(Default)]
+struct TemplateStringState {
+    /// The level of embedded braces `{`.
     /// So we must still lex so many '}' before re-entering into a string mode and pop the stack.
-    template_string_stack: Vec<u32>,
+    brace_level: u32,
+    /// The number of `?` of conditional expressions that still expect their `:`.
+    /// Any other `:` at brace level 0 starts a format specifier.
+    pending_conditions: u32,
 }
 
 /// This trait is used by the `crate::parser::lex_next_token` function and is implemented
@@ -94,17 +103,32 @@ pub fn lex_comment(text: &str, _: &mut LexState) -> usize {
 }
 
 pub fn lex_string(text: &str, state: &mut LexState) -> usize {
-    if let Some(brace_level) = state.template_string_stack.last_mut() {
+    let mut end = 1; // skip the '"' or the '}'
+    if let Some(template) = state.template_string_stack.last_mut() {
         if text.starts_with('{') {
-            *brace_level += 1;
+            template.brace_level += 1;
             return 0;
         } else if text.starts_with('}') {
-            if *brace_level > 0 {
-                *brace_level -= 1;
+            if template.brace_level > 0 {
+                template.brace_level -= 1;
                 return 0;
             } else {
                 state.template_string_stack.pop();
             }
+        } else if template.brace_level == 0 && text.starts_with('?') {
+            template.pending_conditions += 1;
+            return 0;
+        } else if template.brace_level == 0 && text.starts_with(':') {
+            if template.pending_conditions > 0 {
+                template.pending_conditions -= 1;
+                return 0;
+            }
+            // A format specifier, which extends up to the closing brace
+            match text.find(&['}', '"', '\\', '\n'][..]) {
+                Some(stop) if text.as_bytes()[stop] == b'}' => end = stop + 1,
+                _ => return 0,
+            }
+            state.template_string_stack.pop();
         } else if !text.starts_with('"') {
             return 0;
         }
@@ -112,7 +136,6 @@ pub fn lex_string(text: &str, state: &mut LexState) -> usize {
         return 0;
     }
     let text_len = text.as_bytes().len();
-    let mut end = 1; // skip the '"'
     loop {
         let stop = match text[end..].find(&['"', '\\'][..]) {
             Some(stop) => end + stop,
@@ -129,7 +152,7 @@ pub fn lex_string(text: &str, state: &mut LexState) -> usize {
                     return 0;
                 }
                 if text.as_bytes()[stop + 1] == b'{' {
-                    state.template_string_stack.push(0);
+                    state.template_string_stack.push(Default::default());
                     return stop + 2;
                 }
                 end = stop + 1 + text[stop + 1..].chars().next().map_or(0, |c| c.len_utf8())
@@ -315,6 +338,24 @@ fn compare(source: &str, expected: &[(crate::parser::SyntaxKind, &str)]) {
             (crate::parser::SyntaxKind::Identifier, "j"),
         ],
     );
+    compare(
+        r#""a\{b:.2}c\{d?e:{f:g}}h""#,
+        &[
+            (crate::parser::SyntaxKind::StringLiteral, r#""a\{"#),
+            (crate::parser::SyntaxKind::Identifier, "b"),
+            (crate::parser::SyntaxKind::StringLiteral, r#":.2}c\{"#),
+            (crate::parser::SyntaxKind::Identifier, "d"),
+            (crate::parser::SyntaxKind::Question, "?"),
+            (crate::parser::SyntaxKind::Identifier, "e"),
+            (crate::parser::SyntaxKind::Colon, ":"),
+            (crate::parser::SyntaxKind::LBrace, "{"),
+            (crate::parser::SyntaxKind::Identifier, "f"),
+            (crate::parser::SyntaxKind::Colon, ":"),
+            (crate::parser::SyntaxKind::Identifier, "g"),
+            (crate::parser::SyntaxKind::RBrace, "}"),
+            (crate::parser::SyntaxKind::StringLiteral, r#"}h""#),
+        ],
+    );
 
     // Fuzzer tests:
     compare(
@@ -130,6 +130,104 @@ fn test_unescape_string() {
     assert_eq!(unescape_string(r#""xxx\u{1234567890}""#), None);
 }
 
+/// Check the format specifier of a string template `"\{value:spec}"`, which has the syntax
+/// `[[fill]align][+][0][width][,][.precision]`.
+/// The sign, the zero padding and the thousands separator can only be used with numbers.
+pub fn check_format_specifier(spec: &str, is_number: bool) -> Result<(), String> {
+    use sixtyfps_common::format::{FormatSpec, FormatSpecError};
+    match FormatSpec::parse(spec) {
+        Err(FormatSpecError::MissingPrecision) => {
+            Err(format!("Missing precision in format specifier '{}'", spec))
+        }
+        Err(FormatSpecError::Invalid) => Err(format!("Invalid format specifier '{}'", spec)),
+        Ok(spec_value) => match spec_value.number_only_flag() {
+            Some(flag) if !is_number => Err(format!(
+                "'{}' in format specifier '{}' can only be used with numbers",
+                flag, spec
+            )),
+            _ => Ok(()),
+        },
+    }
+}
+
+#[test]
+fn test_check_format_specifier() {
+    assert_eq!(check_format_specifier("", true), Ok(()));
+    assert_eq!(check_format_specifier(".2", true), Ok(()));
+    assert_eq!(check_format_specifier("05", true), Ok(()));
+    assert_eq!(check_format_specifier("*^+010,.3", true), Ok(()));
+    assert_eq!(check_format_specifier(">>10", false), Ok(()));
+    assert_eq!(check_format_specifier("-<8.4", false), Ok(()));
+    assert!(check_format_specifier(".", true).is_err());
+    assert!(check_format_specifier("5x", true).is_err());
+    assert!(check_format_specifier(".2.", true).is_err());
+    assert!(check_format_specifier("05", false).is_err());
+    assert!(check_format_specifier(",", false).is_err());
+    assert!(check_format_specifier("+", false).is_err());
+}
+
+/// Check the placeholders of a string in `@tr(...)`: `{}` and `{0}` refer to the arguments,
+/// `{n}` to the count of the plural form, and `{{` and `}}` are the escaped braces.
+pub fn check_translation_placeholders(
+    text: &str,
+    arg_count: usize,
+    has_plural: bool,
+) -> Result<(), String> {
+    let mut next_arg = 0;
+    let mut rest = text;
+    while let Some(pos) = rest.find(|c| c == '{' || c == '}') {
+        let brace = &rest[pos..pos + 1];
+        rest = &rest[pos + 1..];
+        if let Some(r) = rest.strip_prefix(brace) {
+            rest = r;
+            continue;
+        }
+        let end = match rest.find('}') {
+            Some(end) if brace == "{" => end,
+            _ => return Err(format!("Unescaped '{}' in translated string", brace)),
+        };
+        let placeholder = &rest[..end];
+        rest = &rest[end + 1..];
+        let index = if placeholder.is_empty() {
+            next_arg += 1;
+            next_arg - 1
+        } else if placeholder == "n" {
+            if !has_plural {
+                return Err(
+                    "'{n}' can only be used in a translated string with a plural form".into()
+                );
+            }
+            continue;
+        } else if let Ok(index) = placeholder.parse::<usize>() {
+            index
+        } else {
+            return Err(format!("Invalid placeholder '{{{}}}' in translated string", placeholder));
+        };
+        if index >= arg_count {
+            return Err(format!(
+                "Placeholder '{{{}}}' refers to argument {}, but only {} argument(s) were given",
+                placeholder, index, arg_count
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_check_translation_placeholders() {
+    assert_eq!(check_translation_placeholders("hello", 0, false), Ok(()));
+    assert_eq!(check_translation_placeholders("{{hello}}", 0, false), Ok(()));
+    assert_eq!(check_translation_placeholders("{} and {}", 2, false), Ok(()));
+    assert_eq!(check_translation_placeholders("{1} and {0}", 2, false), Ok(()));
+    assert_eq!(check_translation_placeholders("{n} items", 0, true), Ok(()));
+    assert!(check_translation_placeholders("{n} items", 0, false).is_err());
+    assert!(check_translation_placeholders("{} and {}", 1, false).is_err());
+    assert!(check_translation_placeholders("{2}", 2, false).is_err());
+    assert!(check_translation_placeholders("{foo}", 1, false).is_err());
+    assert!(check_translation_placeholders("{", 1, false).is_err());
+    assert!(check_translation_placeholders("}", 1, false).is_err());
+}
+
 pub fn parse_number_literal(s: String) -> Result<Expression, String> {
     let bytes = s.as_bytes();
     let mut end = 0;
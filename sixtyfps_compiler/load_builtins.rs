@@ -89,15 +89,15 @@ pub fn load_builtins(register: &mut TypeRegister) {
                         register,
                     ));
 
-                    if let Some(e) = p.BindingExpression() {
-                        if e.Expression()
-                            .and_then(|e| e.QualifiedName())
-                            .and_then(|q| q.child_text(SyntaxKind::Identifier))
-                            .map(|s| s == "native_output")
-                            == Some(true)
-                        {
-                            info.is_native_output = true;
-                        } else {
+                    if let Some(e) = p.BindingExpression().and_then(|e| e.Expression()) {
+                        let default_value = match native_output_binding(&e) {
+                            Some(default_value) => {
+                                info.is_native_output = true;
+                                default_value
+                            }
+                            None => Some(e),
+                        };
+                        if let Some(e) = default_value {
                             let ty = info.ty.clone();
                             info.default_value = Some(compiled(e, register, ty));
                         }
@@ -222,15 +222,29 @@ enum Base {
     }
 }
 
+/// Returns `Some(None)` if the expression is `native_output`, or `Some(Some(default_value))`
+/// if it is `native_output(default_value)`
+fn native_output_binding(e: &syntax_nodes::Expression) -> Option<Option<syntax_nodes::Expression>> {
+    let is_native_output = |e: &syntax_nodes::Expression| {
+        e.QualifiedName()
+            .and_then(|q| q.child_text(SyntaxKind::Identifier))
+            .map_or(false, |s| s == "native_output")
+    };
+    if is_native_output(e) {
+        return Some(None);
+    }
+    let mut arguments = e.FunctionCallExpression()?.Expression();
+    if !is_native_output(&arguments.next()?) {
+        return None;
+    }
+    Some(arguments.next())
+}
+
 /// Compile an expression, knowing that the expression is basic (does not have lookup to other things)
-fn compiled(
-    node: syntax_nodes::BindingExpression,
-    type_register: &TypeRegister,
-    ty: Type,
-) -> Expression {
+fn compiled(node: syntax_nodes::Expression, type_register: &TypeRegister, ty: Type) -> Expression {
     let mut diag = crate::diagnostics::BuildDiagnostics::default();
-    let e = Expression::from_binding_expression_node(
-        node.clone().into(),
+    let e = Expression::from_expression_node(
+        node.clone(),
         &mut crate::lookup::LookupCtx::empty_context(type_register, &mut diag),
     )
     .maybe_convert_to(ty, &node, &mut diag);
@@ -69,6 +69,8 @@ pub fn empty_context(type_register: &'a TypeRegister, diag: &'a mut BuildDiagnos
     pub fn return_type(&self) -> &Type {
         if let Type::Callback { return_type, .. } = &self.property_type {
             return_type.as_ref().map_or(&Type::Void, |b| &(**b))
+        } else if let Type::Function { return_type, .. } = &self.property_type {
+            return_type
         } else {
             &self.property_type
         }
@@ -350,6 +352,13 @@ fn lookup(&self, _ctx: &LookupCtx, name: &str) -> Option<LookupResult> {
 fn expression_from_reference(n: NamedReference, ty: &Type) -> Expression {
     if matches!(ty, Type::Callback { .. }) {
         Expression::CallbackReference(n)
+    } else if matches!(ty, Type::Function { .. })
+        && matches!(
+            n.element().borrow().base_type.lookup_member_function(n.name()),
+            Expression::Invalid
+        )
+    {
+        Expression::FunctionReference(n)
     } else {
         Expression::PropertyReference(n)
     }
@@ -686,8 +695,27 @@ fn for_each_entry<R>(
                 )),
             })
         };
+        let length = || {
+            LookupResult::from(Expression::FunctionCall {
+                function: Box::new(Expression::BuiltinFunctionReference(
+                    BuiltinFunction::StringLength,
+                    ctx.current_token.as_ref().map(|t| t.to_source_location()),
+                )),
+                source_location: ctx.current_token.as_ref().map(|t| t.to_source_location()),
+                arguments: vec![self.0.clone()],
+            })
+        };
         None.or_else(|| f("is-float", member_function(BuiltinFunction::StringIsFloat)))
             .or_else(|| f("to-float", member_function(BuiltinFunction::StringToFloat)))
+            .or_else(|| f("length", length()))
+            .or_else(|| f("to-uppercase", member_function(BuiltinFunction::StringToUppercase)))
+            .or_else(|| f("to-lowercase", member_function(BuiltinFunction::StringToLowercase)))
+            .or_else(|| f("contains", member_function(BuiltinFunction::StringContains)))
+            .or_else(|| f("starts-with", member_function(BuiltinFunction::StringStartsWith)))
+            .or_else(|| f("substring", member_function(BuiltinFunction::StringSubstring)))
+            .or_else(|| f("trim", member_function(BuiltinFunction::StringTrim)))
+            .or_else(|| f("replace", member_function(BuiltinFunction::StringReplace)))
+            .or_else(|| f("split", member_function(BuiltinFunction::StringSplit)))
     }
 }
 struct ColorExpression<'a>(&'a Expression);
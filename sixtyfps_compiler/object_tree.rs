@@ -16,7 +16,7 @@
 use crate::diagnostics::{BuildDiagnostics, SourceLocation, Spanned};
 use crate::expression_tree::{self, BindingExpression, Expression, Unit};
 use crate::langtype::PropertyLookupResult;
-use crate::langtype::{BuiltinElement, NativeClass, Type};
+use crate::langtype::{BuiltinElement, Enumeration, NativeClass, Type};
 use crate::layout::{LayoutConstraints, Orientation};
 use crate::namedreference::NamedReference;
 use crate::parser;
@@ -89,6 +89,42 @@ pub fn from_node(
                 local_registry.insert_type(ty.clone());
                 inner_structs.push(ty);
             };
+        let process_enum = |n: syntax_nodes::EnumDeclaration,
+                            diag: &mut BuildDiagnostics,
+                            local_registry: &mut TypeRegister| {
+            let name = match parser::identifier_text(&n.DeclaredIdentifier()) {
+                Some(name) => name,
+                None => {
+                    assert!(diag.has_error());
+                    return;
+                }
+            };
+            let mut values: Vec<String> = vec![];
+            for value in n.EnumValue() {
+                let value_name = parser::identifier_text(&value).unwrap_or_default();
+                if values.contains(&value_name) {
+                    diag.push_error(format!("Duplicated enum value '{}'", value_name), &value);
+                } else {
+                    values.push(value_name);
+                }
+            }
+            if values.is_empty() {
+                diag.push_error(
+                    format!("The enum '{}' must have at least one value", name),
+                    &n.DeclaredIdentifier(),
+                );
+                return;
+            }
+            local_registry.insert_type_with_name(
+                Type::Enumeration(Rc::new(Enumeration {
+                    name: name.clone(),
+                    values,
+                    default_value: 0,
+                    node: Some(n),
+                })),
+                name,
+            );
+        };
 
         for n in node.children() {
             match n.kind() {
@@ -96,6 +132,7 @@ pub fn from_node(
                 SyntaxKind::StructDeclaration => {
                     process_struct(n.into(), diag, &mut local_registry)
                 }
+                SyntaxKind::EnumDeclaration => process_enum(n.into(), diag, &mut local_registry),
                 SyntaxKind::ExportsList => {
                     for n in n.children() {
                         match n.kind() {
@@ -105,6 +142,9 @@ pub fn from_node(
                             SyntaxKind::StructDeclaration => {
                                 process_struct(n.into(), diag, &mut local_registry)
                             }
+                            SyntaxKind::EnumDeclaration => {
+                                process_enum(n.into(), diag, &mut local_registry)
+                            }
                             _ => {}
                         }
                     }
@@ -182,6 +222,8 @@ pub struct UsedSubTypes {
     pub globals: Vec<Rc<Component>>,
     /// All the structs used by the component and its children.
     pub structs: Vec<Type>,
+    /// All the enums declared in .60 files and used by the component and its children.
+    pub enums: Vec<Rc<Enumeration>>,
     /// All the sub components use by this components and its children,
     /// and the amount of time it is used
     pub sub_components: Vec<Rc<Component>>,
@@ -446,6 +488,13 @@ pub struct Element {
     pub child_of_layout: bool,
     /// The property pointing to the layout info. `(horizontal, vertical)`
     pub layout_info_prop: Option<(NamedReference, NamedReference)>,
+    /// The `accessible-*` properties set on this element, indexed by name without the
+    /// `accessible-` prefix. Filled by the lower_accessibility pass.
+    pub accessibility_props: BTreeMap<String, NamedReference>,
+    /// The properties declared in this element, by their name in the .60 file. The passes that
+    /// move or remove the declarations keep these references up to date, so they can be used
+    /// to find the properties at run-time. Filled by the move_declarations pass.
+    pub declared_properties: BTreeMap<String, NamedReference>,
 
     /// true if this Element is the fake Flickable viewport
     pub is_flickable_viewport: bool,
@@ -784,6 +833,39 @@ pub fn from_node(
             );
         }
 
+        for func in node.Function() {
+            let name =
+                unwrap_or_continue!(parser::identifier_text(&func.DeclaredIdentifier()); diag);
+            let PropertyLookupResult { resolved_name, property_type } = r.lookup_property(&name);
+            if !matches!(property_type, Type::Invalid) {
+                diag.push_error(
+                    format!("Cannot declare function '{}' because the name is already used", name),
+                    &func.DeclaredIdentifier(),
+                );
+                continue;
+            }
+
+            let args = func
+                .ArgumentDeclaration()
+                .map(|arg| type_from_node(arg.Type(), diag, tr))
+                .collect();
+            let return_type = Box::new(
+                func.ReturnType()
+                    .map_or(Type::Void, |ret_ty| type_from_node(ret_ty.Type(), diag, tr)),
+            );
+            r.property_declarations.insert(
+                resolved_name.to_string(),
+                PropertyDeclaration {
+                    property_type: Type::Function { return_type, args },
+                    ..Default::default()
+                },
+            );
+            r.bindings.insert(
+                resolved_name.into_owned(),
+                BindingExpression::new_uncompiled(func.into()).into(),
+            );
+        }
+
         for con_node in node.CallbackConnection() {
             let unresolved_name = unwrap_or_continue!(parser::identifier_text(&con_node); diag);
             let PropertyLookupResult { resolved_name, property_type } =
@@ -1224,6 +1306,83 @@ pub fn sub_component(&self) -> Option<&Rc<Component>> {
             None
         }
     }
+
+    /// Returns the id and the type name of this element as they were written in the .60 file.
+    ///
+    /// Unlike `self.id`, these are not changed by the passes, so they can be used to find the
+    /// element at run-time. Both are empty for elements that were generated by the compiler.
+    pub fn source_id_and_type_name(&self) -> (String, String) {
+        let node = match &self.node {
+            Some(node) if !self.is_flickable_viewport => node,
+            _ => return Default::default(),
+        };
+        let id = node
+            .parent()
+            .filter(|parent| parent.kind() == SyntaxKind::SubElement)
+            .and_then(|parent| parser::identifier_text(&parent))
+            .unwrap_or_default();
+        let type_name = node
+            .QualifiedName()
+            .map(|qn| QualifiedTypeName::from_node(qn).to_string())
+            .unwrap_or_default();
+        (id, type_name)
+    }
+}
+
+/// Returns a reference to the property `name` of `element`, as it was called in the .60 file.
+///
+/// The properties declared in the element are resolved to the property they were moved to by
+/// the passes. Returns None if the element has no such property.
+pub fn source_property_reference(element: &ElementRc, name: &str) -> Option<NamedReference> {
+    if let Some(nr) = element.borrow().declared_properties.get(name) {
+        return Some(nr.clone());
+    }
+    if element.borrow().lookup_property(name).property_type.is_property_type() {
+        Some(NamedReference::new(element, name))
+    } else {
+        None
+    }
+}
+
+/// Returns a reference to all the properties of `element`, by the name they had in the .60 file.
+///
+/// That is the properties declared in the element itself, and the ones of its base type.
+pub fn source_properties(element: &ElementRc) -> BTreeMap<String, NamedReference> {
+    let mut result = element.borrow().declared_properties.clone();
+    let mut base_type = element.borrow().base_type.clone();
+    loop {
+        base_type = match base_type {
+            Type::Component(c) => {
+                let root = c.root_element.borrow();
+                for name in root.declared_properties.keys() {
+                    if !result.contains_key(name)
+                        && root.lookup_property(name).property_type.is_property_type()
+                    {
+                        result.insert(name.clone(), NamedReference::new(element, name));
+                    }
+                }
+                root.base_type.clone()
+            }
+            Type::Native(n) => {
+                let mut native = Some(n);
+                while let Some(n) = native {
+                    for (name, info) in &n.properties {
+                        if info.ty.is_property_type()
+                            && !name.starts_with("viewport")
+                            && name != "commands"
+                            && !result.contains_key(name)
+                        {
+                            result.insert(name.clone(), NamedReference::new(element, name));
+                        }
+                    }
+                    native = n.parent.clone();
+                }
+                break;
+            }
+            _ => break,
+        }
+    }
+    result
 }
 
 /// Create a Type for this node
@@ -1560,7 +1719,9 @@ pub fn visit_all_named_references_in_element(
     fn recurse_expression(expr: &mut Expression, vis: &mut impl FnMut(&mut NamedReference)) {
         expr.visit_mut(|sub| recurse_expression(sub, vis));
         match expr {
-            Expression::PropertyReference(r) | Expression::CallbackReference(r) => vis(r),
+            Expression::PropertyReference(r)
+            | Expression::CallbackReference(r)
+            | Expression::FunctionReference(r) => vis(r),
             Expression::LayoutCacheAccess { layout_cache_prop, .. } => vis(layout_cache_prop),
             Expression::SolveLayout(l, _) => l.visit_named_references(vis),
             Expression::ComputeLayoutInfo(l, _) => l.visit_named_references(vis),
@@ -1606,6 +1767,12 @@ fn recurse_expression(expr: &mut Expression, vis: &mut impl FnMut(&mut NamedRefe
     let mut layout_info_prop = std::mem::take(&mut elem.borrow_mut().layout_info_prop);
     layout_info_prop.as_mut().map(|(h, b)| (vis(h), vis(b)));
     elem.borrow_mut().layout_info_prop = layout_info_prop;
+    let mut accessibility_props = std::mem::take(&mut elem.borrow_mut().accessibility_props);
+    accessibility_props.iter_mut().for_each(|(_, x)| vis(x));
+    elem.borrow_mut().accessibility_props = accessibility_props;
+    let mut declared_properties = std::mem::take(&mut elem.borrow_mut().declared_properties);
+    declared_properties.iter_mut().for_each(|(_, x)| vis(x));
+    elem.borrow_mut().declared_properties = declared_properties;
 
     // visit two way bindings
     for (_, expr) in &elem.borrow().bindings {
@@ -1765,6 +1932,21 @@ struct NamedExport {
                 }
             }),
         );
+        let exports_it = exports_it.chain(
+            doc.ExportsList().flat_map(|exports| exports.EnumDeclaration()).map(|en| {
+                let name_location: SyntaxNode = en.DeclaredIdentifier().into();
+                let name = parser::identifier_text(&en.DeclaredIdentifier()).unwrap_or_else(|| {
+                    debug_assert!(diag.has_error());
+                    String::new()
+                });
+                NamedExport {
+                    internal_name_ident: name_location.clone(),
+                    internal_name: name.clone(),
+                    external_name_ident: name_location,
+                    exported_name: name,
+                }
+            }),
+        );
 
         struct SeenExport {
             name_location: SyntaxNode,
@@ -1815,6 +1997,7 @@ struct SeenExport {
         let mut resolve_export_to_inner_component_or_import =
             |export: &NamedExport| match type_registry.lookup(export.internal_name.as_str()) {
                 ty @ Type::Component(_) | ty @ Type::Struct { .. } => Some(ty),
+                ty @ Type::Enumeration(_) => Some(ty),
                 Type::Invalid => {
                     diag.push_error(
                         format!("'{}' not found", export.internal_name),
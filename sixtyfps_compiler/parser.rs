@@ -306,6 +306,7 @@ fn source_file(&self) -> Option<&SourceFile> {
         Arrow -> "->",
         OrOr -> "||",
         AndAnd -> "&&",
+        Pipe -> "|",
         LBrace -> "{",
         RBrace -> "}",
         LParent -> "(",
@@ -318,6 +319,7 @@ fn source_file(&self) -> Option<&SourceFile> {
         Minus -> "-",
         Star -> "*",
         Div -> "/",
+        Percent -> "%",
         Equal -> "=",
         Colon -> ":",
         Comma -> ",",
@@ -330,14 +332,14 @@ fn source_file(&self) -> Option<&SourceFile> {
     }
     // syntax kind
     {
-        Document -> [ *Component, *ExportsList, *ImportSpecifier, *StructDeclaration ],
+        Document -> [ *Component, *ExportsList, *ImportSpecifier, *StructDeclaration, *EnumDeclaration ],
         /// `DeclaredIdentifier := Element { ... }`
         Component -> [ DeclaredIdentifier, Element ],
         /// `id := Element { ... }`
         SubElement -> [ Element ],
         Element -> [ ?QualifiedName, *PropertyDeclaration, *Binding, *CallbackConnection,
                      *CallbackDeclaration, *SubElement, *RepeatedElement, *PropertyAnimation,
-                     *TwoWayBinding, *States, *Transitions, ?ChildrenPlaceholder ],
+                     *TwoWayBinding, *States, *Transitions, ?ChildrenPlaceholder, *Function ],
         RepeatedElement -> [ ?DeclaredIdentifier, ?RepeatedIndex, Expression , SubElement],
         RepeatedIndex -> [],
         ConditionalElement -> [ Expression , SubElement],
@@ -345,6 +347,10 @@ fn source_file(&self) -> Option<&SourceFile> {
         /// `-> type`  (but without the ->)
         ReturnType -> [Type],
         CallbackConnection -> [ *DeclaredIdentifier,  CodeBlock ],
+        /// `function foo(a: int) -> int { ... }`
+        Function -> [ DeclaredIdentifier, *ArgumentDeclaration, ?ReturnType, CodeBlock ],
+        /// `a: int` in the argument list of a function
+        ArgumentDeclaration -> [ DeclaredIdentifier, Type ],
         /// Declaration of a property.
         PropertyDeclaration-> [ ?Type , DeclaredIdentifier, ?BindingExpression, ?TwoWayBinding ],
         /// QualifiedName are the properties name
@@ -365,13 +371,24 @@ fn source_file(&self) -> Option<&SourceFile> {
         // FIXME: the test should test that as alternative rather than several of them (but it can also be a literal)
         Expression-> [ ?Expression, ?FunctionCallExpression, ?SelfAssignment,
                        ?ConditionalExpression, ?QualifiedName, ?BinaryExpression, ?Array, ?ObjectLiteral,
-                       ?UnaryOpExpression, ?CodeBlock, ?StringTemplate, ?AtImageUrl, ?AtLinearGradient],
+                       ?UnaryOpExpression, ?CodeBlock, ?StringTemplate, ?AtImageUrl, ?AtLinearGradient, ?AtRadialGradient,
+                       ?AtConicGradient, ?AtTr],
         /// Concatenate the Expressions to make a string (usually expended from a template string)
         StringTemplate -> [*Expression],
         /// `@image-url("foo.png")`
         AtImageUrl -> [],
         /// `@linear-gradient(...)`
         AtLinearGradient -> [*Expression],
+        /// `@radial-gradient(...)`
+        AtRadialGradient -> [*Expression],
+        /// `@conic-gradient(...)`
+        AtConicGradient -> [*Expression],
+        /// `@tr("context" => "text {}" | "plural {}" % count, args...)`
+        AtTr -> [ ?TrContext, ?TrPlural, *Expression ],
+        /// `"context" =>` in `@tr(...)`
+        TrContext -> [],
+        /// `| "plural {}" % count` in `@tr(...)`
+        TrPlural -> [ Expression ],
         /// expression()
         FunctionCallExpression -> [*Expression],
         /// `expression += expression`
@@ -399,7 +416,7 @@ fn source_file(&self) -> Option<&SourceFile> {
         /// There is an identifier "in" or "out", the DeclaredIdentifier is the state name
         Transition -> [DeclaredIdentifier, *PropertyAnimation],
         /// Export a set of declared components by name
-        ExportsList -> [ *ExportSpecifier, ?Component, *StructDeclaration ],
+        ExportsList -> [ *ExportSpecifier, ?Component, *StructDeclaration, *EnumDeclaration ],
         /// Declare the first identifier to be exported, either under its name or instead
         /// under the name of the second identifier.
         ExportSpecifier -> [ ExportIdentifier, ?ExportName ],
@@ -422,6 +439,10 @@ fn source_file(&self) -> Option<&SourceFile> {
         ArrayType -> [ Type ],
         /// `struct Foo := { ... }
         StructDeclaration -> [DeclaredIdentifier, ObjectType],
+        /// `enum Foo { bar, baz }`
+        EnumDeclaration -> [DeclaredIdentifier, *EnumValue],
+        /// `bar` inside an EnumDeclaration
+        EnumValue -> [],
 
     }
 }
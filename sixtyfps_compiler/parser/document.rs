@@ -10,7 +10,7 @@
 
 use super::element::{parse_element, parse_element_content};
 use super::prelude::*;
-use super::r#type::parse_struct_declaration;
+use super::r#type::{parse_enum_declaration, parse_struct_declaration};
 
 #[cfg_attr(test, parser_test)]
 /// ```test,Document
@@ -20,6 +20,7 @@
 /// Type := Base {} export { Type }
 /// import { Base } from "somewhere"; Type := Base {}
 /// struct Foo := { foo: foo }
+/// enum Foo { bar, baz }
 /// /* empty */
 /// ```
 pub fn parse_document(p: &mut impl Parser) -> bool {
@@ -46,6 +47,11 @@ pub fn parse_document(p: &mut impl Parser) -> bool {
                     return false;
                 }
             }
+            "enum" => {
+                if !parse_enum_declaration(&mut *p) {
+                    return false;
+                }
+            }
             _ => {
                 if !parse_component(&mut *p) {
                     return false;
@@ -117,6 +123,7 @@ pub fn parse_qualified_name(p: &mut impl Parser) -> bool {
 /// export { Type as Foo, AnotherType }
 /// export Foo := Item { }
 /// export struct Foo := { foo: bar }
+/// export enum Foo { bar, baz }
 /// ```
 fn parse_export(p: &mut impl Parser) -> bool {
     debug_assert_eq!(p.peek().as_str(), "export");
@@ -145,6 +152,8 @@ fn parse_export(p: &mut impl Parser) -> bool {
         }
     } else if p.peek().as_str() == "struct" {
         parse_struct_declaration(&mut *p)
+    } else if p.peek().as_str() == "enum" {
+        parse_enum_declaration(&mut *p)
     } else {
         parse_component(&mut *p)
     }
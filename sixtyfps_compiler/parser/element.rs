@@ -44,6 +44,7 @@ pub fn parse_element(p: &mut impl Parser) -> bool {
 /// animate * { }
 /// @children
 /// double_binding <=> element.property;
+/// function foo() {}
 /// ```
 pub fn parse_element_content(p: &mut impl Parser) {
     let mut had_parse_error = false;
@@ -64,6 +65,9 @@ pub fn parse_element_content(p: &mut impl Parser) {
                 SyntaxKind::Identifier if p.peek().as_str() == "callback" => {
                     parse_callback_declaration(&mut *p);
                 }
+                SyntaxKind::Identifier if p.peek().as_str() == "function" => {
+                    parse_function(&mut *p);
+                }
                 SyntaxKind::Identifier | SyntaxKind::Star if p.peek().as_str() == "animate" => {
                     parse_property_animation(&mut *p);
                 }
@@ -326,6 +330,44 @@ fn parse_callback_declaration(p: &mut impl Parser) {
     p.expect(SyntaxKind::Semicolon);
 }
 
+#[cfg_attr(test, parser_test)]
+/// ```test,Function
+/// function foo() {}
+/// function bar(a: int, b: string) -> int { return a; }
+/// function with_coma(a: int,) { }
+/// ```
+fn parse_function(p: &mut impl Parser) {
+    debug_assert_eq!(p.peek().as_str(), "function");
+    let mut p = p.start_node(SyntaxKind::Function);
+    p.consume(); // "function"
+    {
+        let mut p = p.start_node(SyntaxKind::DeclaredIdentifier);
+        p.expect(SyntaxKind::Identifier);
+    }
+    if p.expect(SyntaxKind::LParent) {
+        while p.peek().kind() != SyntaxKind::RParent {
+            {
+                let mut p = p.start_node(SyntaxKind::ArgumentDeclaration);
+                {
+                    let mut p = p.start_node(SyntaxKind::DeclaredIdentifier);
+                    p.expect(SyntaxKind::Identifier);
+                }
+                p.expect(SyntaxKind::Colon);
+                parse_type(&mut *p);
+            }
+            if !p.test(SyntaxKind::Comma) {
+                break;
+            }
+        }
+        p.expect(SyntaxKind::RParent);
+    }
+    if p.test(SyntaxKind::Arrow) {
+        let mut p = p.start_node(SyntaxKind::ReturnType);
+        parse_type(&mut *p);
+    }
+    parse_code_block(&mut *p);
+}
+
 #[cfg_attr(test, parser_test)]
 /// ```test,PropertyDeclaration
 /// property<int> foobar;
@@ -190,6 +190,9 @@ fn parse_expression_helper(p: &mut impl Parser, precedence: OperatorPrecedence)
 /// ```test
 /// @image-url("/foo/bar.png")
 /// @linear-gradient(0deg, blue, red)
+/// @radial-gradient(circle, blue, red)
+/// @conic-gradient(blue, red)
+/// @tr("Hello {}", name)
 /// ```
 fn parse_at_keyword(p: &mut impl Parser) {
     debug_assert_eq!(p.peek().kind(), SyntaxKind::At);
@@ -205,9 +208,18 @@ fn parse_at_keyword(p: &mut impl Parser) {
         "linear-gradient" | "linear_gradient" => {
             parse_at_linear_gradient(p);
         }
+        "radial-gradient" | "radial_gradient" => {
+            parse_at_radial_gradient(p);
+        }
+        "conic-gradient" | "conic_gradient" => {
+            parse_at_conic_gradient(p);
+        }
+        "tr" => {
+            parse_tr(p);
+        }
         _ => {
             p.consume();
-            p.error("Expected 'image-url' or 'linear-gradient' after '@'");
+            p.error("Expected 'image-url', 'linear-gradient', 'radial-gradient', 'conic-gradient' or 'tr' after '@'");
         }
     }
 }
@@ -278,6 +290,8 @@ fn parse_function_arguments(p: &mut impl Parser) {
 /// ```test,StringTemplate
 /// "foo\{bar}"
 /// "foo\{4 + 5}foo"
+/// "foo\{bar:.2}"
+/// "foo\{a ? b : c:>5}foo\{d:05}"
 /// ```
 fn parse_template_string(p: &mut impl Parser) {
     let mut p = p.start_node(SyntaxKind::StringTemplate);
@@ -289,7 +303,10 @@ fn parse_template_string(p: &mut impl Parser) {
     loop {
         parse_expression(&mut *p);
         let peek = p.peek();
-        if peek.kind != SyntaxKind::StringLiteral || !peek.as_str().starts_with('}') {
+        // The continuation either starts with the closing brace, or with a format specifier
+        if peek.kind != SyntaxKind::StringLiteral
+            || !(peek.as_str().starts_with('}') || peek.as_str().starts_with(':'))
+        {
             p.error("Error while parsing string template")
         }
         let mut p = p.start_node(SyntaxKind::Expression);
@@ -301,6 +318,42 @@ fn parse_template_string(p: &mut impl Parser) {
     }
 }
 
+#[cfg_attr(test, parser_test)]
+/// ```test,AtTr
+/// @tr("foo")
+/// @tr("foo{0}", bar(42))
+/// @tr("context" => "foo{}", 42,)
+/// @tr("{n} foo" | "{n} foos" % 42)
+/// @tr("context" => "{} foo {n}" | "{} foos {n}" % count + 1, name)
+/// ```
+fn parse_tr(p: &mut impl Parser) {
+    let mut p = p.start_node(SyntaxKind::AtTr);
+    p.expect(SyntaxKind::At);
+    debug_assert_eq!(p.peek().as_str(), "tr");
+    p.consume(); // "tr"
+    p.expect(SyntaxKind::LParent);
+    if p.nth(1).kind() == SyntaxKind::FatArrow {
+        let mut p = p.start_node(SyntaxKind::TrContext);
+        p.expect(SyntaxKind::StringLiteral);
+        p.expect(SyntaxKind::FatArrow);
+    }
+    p.expect(SyntaxKind::StringLiteral);
+    if p.nth(0).kind() == SyntaxKind::Pipe {
+        let mut p = p.start_node(SyntaxKind::TrPlural);
+        p.consume();
+        p.expect(SyntaxKind::StringLiteral);
+        p.expect(SyntaxKind::Percent);
+        parse_expression(&mut *p);
+    }
+    while p.test(SyntaxKind::Comma) {
+        if p.nth(0).kind() == SyntaxKind::RParent {
+            break;
+        }
+        parse_expression(&mut *p);
+    }
+    p.expect(SyntaxKind::RParent);
+}
+
 #[cfg_attr(test, parser_test)]
 /// ```test,AtLinearGradient
 /// @linear-gradient(#e66465, #9198e5)
@@ -314,7 +367,39 @@ fn parse_at_linear_gradient(p: &mut impl Parser) {
     p.expect(SyntaxKind::At);
     debug_assert!(p.peek().as_str() == "linear-gradient" || p.peek().as_str() == "linear_gradient");
     p.consume(); //"linear-gradient"
+    parse_gradient_arguments(&mut *p);
+}
+
+#[cfg_attr(test, parser_test)]
+/// ```test,AtRadialGradient
+/// @radial-gradient(circle, #e66465, #9198e5)
+/// @radial-gradient(circle, #333, #333 50%, #eee 75%, #333 75%)
+/// @radial_gradient(circle, rgba(255,0,0,0.8) 20%, rgba(255,0,0,0))
+/// ```
+fn parse_at_radial_gradient(p: &mut impl Parser) {
+    let mut p = p.start_node(SyntaxKind::AtRadialGradient);
+    p.expect(SyntaxKind::At);
+    debug_assert!(p.peek().as_str() == "radial-gradient" || p.peek().as_str() == "radial_gradient");
+    p.consume(); //"radial-gradient"
+    parse_gradient_arguments(&mut *p);
+}
+
+#[cfg_attr(test, parser_test)]
+/// ```test,AtConicGradient
+/// @conic-gradient(#e66465, #9198e5)
+/// @conic-gradient(red 0deg, yellow 90deg, blue 0.5turn, red 100%)
+/// @conic_gradient(#333, #eee 25%, #333 50%)
+/// ```
+fn parse_at_conic_gradient(p: &mut impl Parser) {
+    let mut p = p.start_node(SyntaxKind::AtConicGradient);
+    p.expect(SyntaxKind::At);
+    debug_assert!(p.peek().as_str() == "conic-gradient" || p.peek().as_str() == "conic_gradient");
+    p.consume(); //"conic-gradient"
+    parse_gradient_arguments(&mut *p);
+}
 
+/// Parse the parentheses and the comma separated expressions of a gradient
+fn parse_gradient_arguments(p: &mut impl Parser) {
     p.expect(SyntaxKind::LParent);
 
     while !p.test(SyntaxKind::RParent) {
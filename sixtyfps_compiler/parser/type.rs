@@ -84,3 +84,34 @@ pub fn parse_struct_declaration(p: &mut impl Parser) -> bool {
     parse_type_object(&mut *p);
     true
 }
+
+#[cfg_attr(test, parser_test)]
+/// ```test,EnumDeclaration
+/// enum Foo { bar, baz }
+/// enum Foo { bar, baz-qux, }
+/// enum Foo {}
+/// ```
+pub fn parse_enum_declaration(p: &mut impl Parser) -> bool {
+    debug_assert_eq!(p.peek().as_str(), "enum");
+    let mut p = p.start_node(SyntaxKind::EnumDeclaration);
+    p.consume(); // "enum"
+    {
+        let mut p = p.start_node(SyntaxKind::DeclaredIdentifier);
+        p.expect(SyntaxKind::Identifier);
+    }
+    if !p.expect(SyntaxKind::LBrace) {
+        return false;
+    }
+    while p.nth(0).kind() != SyntaxKind::RBrace {
+        {
+            let mut p = p.start_node(SyntaxKind::EnumValue);
+            if !p.expect(SyntaxKind::Identifier) {
+                return false;
+            }
+        }
+        if !p.test(SyntaxKind::Comma) {
+            break;
+        }
+    }
+    p.expect(SyntaxKind::RBrace)
+}
@@ -28,6 +28,7 @@
 mod generate_item_indices;
 mod infer_aliases_types;
 mod inlining;
+mod lower_accessibility;
 mod lower_layout;
 mod lower_popups;
 mod lower_shadows;
@@ -89,6 +90,7 @@ pub async fn run_passes(
     }
 
     ensure_window::ensure_window(root_component, &doc.local_registry);
+    let can_be_right_to_left = lower_layout::window_can_be_right_to_left(root_component);
 
     for component in (root_component.used_types.borrow().sub_components.iter())
         .chain(std::iter::once(root_component))
@@ -103,7 +105,7 @@ pub async fn run_passes(
         lower_states::lower_states(component, &doc.local_registry, diag);
         repeater_component::process_repeater_components(component);
         lower_popups::lower_popups(component, &doc.local_registry, diag);
-        lower_layout::lower_layouts(component, &mut type_loader, diag).await;
+        lower_layout::lower_layouts(component, &mut type_loader, can_be_right_to_left, diag).await;
         z_order::reorder_by_z_order(component, diag);
         lower_shadows::lower_shadow_properties(component, &doc.local_registry, diag);
         clip::handle_clip(component, &global_type_registry.borrow(), diag);
@@ -114,6 +116,7 @@ pub async fn run_passes(
         );
         default_geometry::default_geometry(component, diag);
         visible::handle_visible(component, &global_type_registry.borrow());
+        lower_accessibility::lower_accessibility_properties(component, diag);
         materialize_fake_properties::materialize_fake_properties(component);
     }
     collect_globals::collect_globals(&doc, diag);
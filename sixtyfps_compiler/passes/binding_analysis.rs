@@ -71,6 +71,9 @@ fn analyse_binding(
     }
 
     if currently_analysing.contains(&nr) {
+        let is_function = |p: &NamedReference| matches!(p.ty(), Type::Function { .. });
+        // A loop made only of functions declared in the .60 file is a recursion
+        let is_recursion = currently_analysing.iter().skip_while(|p| **p != nr).all(is_function);
         for p in currently_analysing.iter().rev() {
             let elem = p.element();
             let elem = elem.borrow();
@@ -81,10 +84,14 @@ fn analyse_binding(
 
             let span =
                 binding.span.clone().or_else(|| elem.node.as_ref().map(|n| n.to_source_location()));
-            diag.push_error(
-                format!("The binding for the property '{}' is part of a binding loop", p.name()),
-                &span,
-            );
+            let message = if is_recursion {
+                format!("The function '{}' is called recursively", p.name())
+            } else if is_function(p) {
+                format!("The function '{}' is part of a binding loop", p.name())
+            } else {
+                format!("The binding for the property '{}' is part of a binding loop", p.name())
+            };
+            diag.push_error(message, &span);
 
             if *p == nr {
                 break;
@@ -172,7 +179,9 @@ fn analyse_binding(
 fn recurse_expression(expr: &Expression, vis: &mut impl FnMut(&NamedReference)) {
     expr.visit(|sub| recurse_expression(sub, vis));
     match expr {
-        Expression::PropertyReference(r) | Expression::CallbackReference(r) => vis(r),
+        Expression::PropertyReference(r)
+        | Expression::CallbackReference(r)
+        | Expression::FunctionReference(r) => vis(r),
         Expression::LayoutCacheAccess { layout_cache_prop, .. } => vis(layout_cache_prop),
         Expression::SolveLayout(l, o) | Expression::ComputeLayoutInfo(l, o) => {
             // we should only visit the layout geometry for the orientation
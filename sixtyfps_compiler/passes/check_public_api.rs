@@ -31,7 +31,9 @@ pub fn check_public_api(doc: &Document, diag: &mut BuildDiagnostics) {
 
 fn check_public_api_component(root_component: &Rc<Component>, diag: &mut BuildDiagnostics) {
     root_component.root_element.borrow_mut().property_declarations.values_mut().for_each(|d| {
-        if d.property_type.ok_for_public_api() {
+        if matches!(d.property_type, Type::Function { .. }) {
+            // Functions are private to the .60 file
+        } else if d.property_type.ok_for_public_api() {
             d.expose_in_public_api = true
         } else {
             diag.push_diagnostic(
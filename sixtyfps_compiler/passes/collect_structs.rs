@@ -8,25 +8,27 @@
     Please contact info@sixtyfps.io for more information.
 LICENSE END */
 
-//! Passes that fills the root component used_types.structs
+//! Passes that fills the root component used_types.structs and used_types.enums
 
 use crate::expression_tree::Expression;
-use crate::langtype::Type;
+use crate::langtype::{Enumeration, Type};
 use crate::object_tree::*;
 use std::collections::BTreeMap;
 use std::rc::Rc;
 
-/// Fill the root_component's used_types.structs
+/// Fill the root_component's used_types.structs and used_types.enums
 pub fn collect_structs(doc: &Document) {
     let mut hash = BTreeMap::new();
+    let mut enums = BTreeMap::new();
 
     for component in (doc.root_component.used_types.borrow().sub_components.iter())
         .chain(std::iter::once(&doc.root_component))
     {
-        collect_structs_in_component(component, &mut hash)
+        collect_structs_in_component(component, &mut hash, &mut enums)
     }
 
     let mut used_types = doc.root_component.used_types.borrow_mut();
+    used_types.enums = enums.into_iter().map(|(_, e)| e).collect();
     let used_struct = &mut used_types.structs;
     *used_struct = Vec::with_capacity(hash.len());
     while let Some(next) = hash.iter().next() {
@@ -36,10 +38,21 @@ pub fn collect_structs(doc: &Document) {
     }
 }
 
-fn collect_structs_in_component(root_component: &Rc<Component>, hash: &mut BTreeMap<String, Type>) {
+fn collect_structs_in_component(
+    root_component: &Rc<Component>,
+    hash: &mut BTreeMap<String, Type>,
+    enums: &mut BTreeMap<String, Rc<Enumeration>>,
+) {
     let mut maybe_collect_object = |ty: &Type| {
-        visit_named_object(ty, &mut |name, sub_ty| {
-            hash.entry(name.clone()).or_insert_with(|| sub_ty.clone());
+        visit_named_object(ty, &mut |name, sub_ty| match sub_ty {
+            Type::Enumeration(e) => {
+                if e.node.is_some() {
+                    enums.entry(name.clone()).or_insert_with(|| e.clone());
+                }
+            }
+            _ => {
+                hash.entry(name.clone()).or_insert_with(|| sub_ty.clone());
+            }
         });
     };
 
@@ -50,10 +63,12 @@ fn collect_structs_in_component(root_component: &Rc<Component>, hash: &mut BTree
     });
 
     visit_all_expressions(root_component, |expr, _| {
-        expr.visit_recursive(&mut |expr| {
-            if let Expression::Struct { ty, .. } = expr {
-                maybe_collect_object(ty)
+        expr.visit_recursive(&mut |expr| match expr {
+            Expression::Struct { ty, .. } => maybe_collect_object(ty),
+            Expression::EnumerationValue(value) => {
+                maybe_collect_object(&Type::Enumeration(value.enumeration.clone()))
             }
+            _ => (),
         })
     });
 }
@@ -76,8 +91,10 @@ fn sort_struct(hash: &mut BTreeMap<String, Type>, vec: &mut Vec<Type>, key: &str
     vec.push(ty)
 }
 
+/// Visit the named structs and the enums of a type
 fn visit_named_object(ty: &Type, visitor: &mut impl FnMut(&String, &Type)) {
     match ty {
+        Type::Enumeration(e) => visitor(&e.name, ty),
         Type::Struct { fields, name, .. } => {
             if let Some(struct_name) = name.as_ref() {
                 visitor(struct_name, ty);
@@ -95,6 +112,12 @@ fn visit_named_object(ty: &Type, visitor: &mut impl FnMut(&String, &Type)) {
                 visit_named_object(a, visitor);
             }
         }
+        Type::Function { return_type, args } => {
+            visit_named_object(return_type, visitor);
+            for a in args {
+                visit_named_object(a, visitor);
+            }
+        }
         _ => {}
     }
 }
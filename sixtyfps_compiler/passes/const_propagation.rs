@@ -15,7 +15,7 @@
 
 pub fn const_propagation(component: &Component) {
     visit_all_expressions(component, |expr, ty| {
-        if matches!(ty(), Type::Callback { .. }) {
+        if matches!(ty(), Type::Callback { .. } | Type::Function { .. }) {
             return;
         }
         simplify_expression(expr);
@@ -109,6 +109,7 @@ fn simplify_expression(expr: &mut Expression) -> bool {
             can_inline
         }
         Expression::CallbackReference { .. } => false,
+        Expression::FunctionReference { .. } => false,
         Expression::ElementReference { .. } => false,
         // FIXME
         Expression::LayoutCacheAccess { .. } => false,
@@ -17,9 +17,11 @@
 
 pub fn deduplicate_property_read(component: &Component) {
     visit_all_expressions(component, |expr, ty| {
-        if matches!(ty(), Type::Callback { .. }) {
+        if matches!(ty(), Type::Callback { .. } | Type::Function { .. }) {
             // Callback handler can't be optimizes because they can have side effect.
-            // But that's fine as they also do not register dependencies
+            // But that's fine as they also do not register dependencies.
+            // Functions are pure, but are only evaluated from the binding that calls them,
+            // which is optimized instead
             return;
         }
         process_expression(expr, &DedupPropState::default());
@@ -44,6 +44,8 @@ pub fn ensure_window(component: &Rc<Component>, type_register: &TypeRegister) {
         transitions: Default::default(),
         child_of_layout: false,
         layout_info_prop: Default::default(),
+        accessibility_props: Default::default(),
+        declared_properties: Default::default(),
         is_flickable_viewport: false,
         item_index: Default::default(),
         node: win_elem_mut.node.clone(),
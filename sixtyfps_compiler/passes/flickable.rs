@@ -61,7 +61,10 @@ fn create_viewport_element(flickable_elem: &ElementRc, native_rect: &Rc<NativeCl
         ..Element::default()
     }));
     for (prop, info) in &flickable.base_type.as_builtin().properties {
-        if let Some(vp_prop) = prop.strip_prefix("viewport-") {
+        if let Some(vp_prop) = prop
+            .strip_prefix("viewport-")
+            .filter(|vp_prop| native_rect.lookup_property(vp_prop).is_some())
+        {
             let nr = NamedReference::new(&viewport, vp_prop);
             flickable.property_declarations.insert(prop.to_owned(), info.ty.clone().into());
             match flickable.bindings.entry(prop.to_owned()) {
@@ -75,9 +75,11 @@ fn push_repeated_item(
     fn push_native_item(
         &mut self,
         item: &ElementRc,
+        _source_element: &ElementRc,
         _children_offset: u32,
         _parent_index: u32,
         component_state: &Self::SubComponentState,
+        _source_component_state: &Self::SubComponentState,
     ) {
         if !component_state {
             item.borrow().item_index.set(self.current_item_index).unwrap();
@@ -177,6 +177,10 @@ fn inline_element(
         }
     }
 
+    for (name, nr) in &inlined_component.root_element.borrow().accessibility_props {
+        elem_mut.accessibility_props.entry(name.clone()).or_insert_with(|| nr.clone());
+    }
+
     core::mem::drop(elem_mut);
 
     // Now fixup all binding and reference
@@ -224,6 +228,8 @@ fn duplicate_element_with_mapping(
             .collect(),
         child_of_layout: elem.child_of_layout,
         layout_info_prop: elem.layout_info_prop.clone(),
+        accessibility_props: elem.accessibility_props.clone(),
+        declared_properties: elem.declared_properties.clone(),
         named_references: Default::default(),
         item_index: Default::default(), // Not determined yet
         is_flickable_viewport: elem.is_flickable_viewport,
@@ -450,9 +456,14 @@ fn component_requires_inlining(component: &Rc<Component>) -> bool {
 
     for (prop, binding) in &root_element.borrow().bindings {
         let binding = binding.borrow();
-        // The passes that dp the drop shadow or the opacity currently won't allow this property
-        // on the top level of a component. This could be changed in the future.
-        if prop.starts_with("drop-shadow-") || prop == "opacity" {
+        // The passes that dp the drop shadow, the opacity or the transform currently won't allow
+        // this property on the top level of a component. This could be changed in the future.
+        if prop.starts_with("drop-shadow-")
+            || prop == "opacity"
+            || crate::typeregister::RESERVED_TRANSFORM_PROPERTIES
+                .iter()
+                .any(|(p, _)| *p == prop.as_str())
+        {
             return true;
         }
         if prop == "height" || prop == "width" {
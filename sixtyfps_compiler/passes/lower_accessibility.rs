@@ -0,0 +1,74 @@
+/* LICENSE BEGIN
+    This file is part of the SixtyFPS Project -- https://sixtyfps.io
+    Copyright (c) 2021 Olivier Goffart <olivier.goffart@sixtyfps.io>
+    Copyright (c) 2021 Simon Hausmann <simon.hausmann@sixtyfps.io>
+
+    SPDX-License-Identifier: GPL-3.0-only
+    This file is also available under commercial licensing terms.
+    Please contact info@sixtyfps.io for more information.
+LICENSE END */
+//! Pass that collects the `accessible-*` properties into `Element::accessibility_props`
+
+use std::rc::Rc;
+
+use crate::diagnostics::BuildDiagnostics;
+use crate::expression_tree::NamedReference;
+use crate::object_tree::{Component, ElementRc};
+use crate::typeregister::RESERVED_ACCESSIBILITY_STRING_PROPERTIES;
+
+pub fn lower_accessibility_properties(component: &Rc<Component>, diag: &mut BuildDiagnostics) {
+    crate::object_tree::recurse_elem_including_sub_components_no_borrow(
+        component,
+        &(),
+        &mut |elem: &ElementRc, _| {
+            if elem.borrow().repeated.is_some() {
+                // The properties are on the root element of the repeated component
+                return;
+            }
+            let is_set = |prop: &str| {
+                elem.borrow().bindings.get(prop).map_or(false, |b| b.borrow().has_binding())
+            };
+            let has_role = is_set("accessible-role");
+            if has_role {
+                elem.borrow_mut()
+                    .accessibility_props
+                    .insert("role".into(), NamedReference::new(elem, "accessible-role"));
+            }
+            // The role can also be set by the component this element is an instance of
+            let has_inherited_role = || {
+                let mut base = elem.borrow().sub_component().cloned();
+                while let Some(c) = base {
+                    let root = c.root_element.borrow();
+                    if root
+                        .bindings
+                        .get("accessible-role")
+                        .map_or(false, |b| b.borrow().has_binding())
+                    {
+                        return true;
+                    }
+                    base = root.sub_component().cloned();
+                }
+                false
+            };
+            for (prop, _) in RESERVED_ACCESSIBILITY_STRING_PROPERTIES {
+                if !is_set(prop) {
+                    continue;
+                }
+                if !has_role && !has_inherited_role() {
+                    diag.push_error(
+                        format!(
+                            "The {} property can only be set in combination with accessible-role",
+                            prop
+                        ),
+                        &*elem.borrow().bindings[*prop].borrow(),
+                    );
+                    continue;
+                }
+                elem.borrow_mut().accessibility_props.insert(
+                    prop.strip_prefix("accessible-").unwrap().into(),
+                    NamedReference::new(elem, prop),
+                );
+            }
+        },
+    )
+}
@@ -26,6 +26,7 @@
 pub async fn lower_layouts(
     component: &Rc<Component>,
     type_loader: &mut TypeLoader<'_>,
+    can_be_right_to_left: bool,
     diag: &mut BuildDiagnostics,
 ) {
     // Ignore import errors
@@ -46,6 +47,7 @@ pub async fn lower_layouts(
             elem,
             &type_loader.global_type_registry.borrow(),
             &style_metrics,
+            can_be_right_to_left,
             diag,
         );
         check_no_layout_properties(elem, diag);
@@ -57,6 +59,7 @@ fn lower_element_layout(
     elem: &ElementRc,
     type_register: &TypeRegister,
     style_metrics: &Option<Rc<Component>>,
+    can_be_right_to_left: bool,
     diag: &mut BuildDiagnostics,
 ) {
     let base_type = if let Type::Builtin(base_type) = &elem.borrow().base_type {
@@ -66,12 +69,14 @@ fn lower_element_layout(
     };
     match base_type.name.as_str() {
         "Row" => panic!("Error caught at element lookup time"),
-        "GridLayout" => lower_grid_layout(component, elem, diag),
-        "HorizontalLayout" => lower_box_layout(elem, diag, Orientation::Horizontal),
-        "VerticalLayout" => lower_box_layout(elem, diag, Orientation::Vertical),
+        "GridLayout" => lower_grid_layout(component, elem, can_be_right_to_left, diag),
+        "HorizontalLayout" => {
+            lower_box_layout(elem, diag, Orientation::Horizontal, can_be_right_to_left)
+        }
+        "VerticalLayout" => lower_box_layout(elem, diag, Orientation::Vertical, false),
         "PathLayout" => lower_path_layout(elem, diag),
         "Dialog" => {
-            lower_dialog_layout(elem, style_metrics, diag);
+            lower_dialog_layout(elem, style_metrics, can_be_right_to_left, diag);
             return; // the Dialog stays in the tree as a Dialog
         }
         _ => return,
@@ -102,6 +107,7 @@ pub fn is_layout_element(element: &ElementRc) -> bool {
 fn lower_grid_layout(
     component: &Rc<Component>,
     grid_layout_element: &ElementRc,
+    can_be_right_to_left: bool,
     diag: &mut BuildDiagnostics,
 ) {
     let mut grid = GridLayout {
@@ -142,6 +148,7 @@ fn lower_grid_layout(
                     (&mut row, &mut col),
                     &layout_cache_prop_h,
                     &layout_cache_prop_v,
+                    can_be_right_to_left,
                     diag,
                 );
                 col += 1;
@@ -158,6 +165,7 @@ fn lower_grid_layout(
                 (&mut row, &mut col),
                 &layout_cache_prop_h,
                 &layout_cache_prop_v,
+                can_be_right_to_left,
                 diag,
             );
             col += 1;
@@ -212,6 +220,7 @@ fn add_element(
         (row, col): (&mut u16, &mut u16),
         layout_cache_prop_h: &NamedReference,
         layout_cache_prop_v: &NamedReference,
+        can_be_right_to_left: bool,
         diag: &mut BuildDiagnostics,
     ) {
         let mut get_const_value = |name: &str| {
@@ -237,6 +246,7 @@ fn add_element(
             (rowspan, colspan),
             layout_cache_prop_h,
             layout_cache_prop_v,
+            can_be_right_to_left,
             diag,
         )
     }
@@ -248,6 +258,7 @@ fn add_element_with_coord(
         (rowspan, colspan): (u16, u16),
         layout_cache_prop_h: &NamedReference,
         layout_cache_prop_v: &NamedReference,
+        can_be_right_to_left: bool,
         diag: &mut BuildDiagnostics,
     ) {
         let index = self.elems.len();
@@ -263,6 +274,9 @@ fn add_element_with_coord(
 
             let e = &layout_item.elem;
             set_prop_from_cache(e, "x", layout_cache_prop_h, index * 2, &None, diag);
+            if can_be_right_to_left {
+                mirror_x_in_right_to_left(e, &layout_cache_prop_h.element());
+            }
             if !layout_item.item.constraints.fixed_width {
                 set_prop_from_cache(e, "width", layout_cache_prop_h, index * 2 + 1, &None, diag);
             }
@@ -282,10 +296,13 @@ fn add_element_with_coord(
     }
 }
 
+/// `mirror_in_right_to_left` is true if the x position of the elements of a horizontal layout
+/// needs to be mirrored when the window is right to left.
 fn lower_box_layout(
     layout_element: &ElementRc,
     diag: &mut BuildDiagnostics,
     orientation: Orientation,
+    mirror_in_right_to_left: bool,
 ) {
     let mut layout = BoxLayout {
         orientation,
@@ -338,6 +355,9 @@ fn lower_box_layout(
             };
             let actual_elem = &item.elem;
             set_prop_from_cache(actual_elem, pos, &layout_cache_prop, index, rep_idx, diag);
+            if mirror_in_right_to_left {
+                mirror_x_in_right_to_left(actual_elem, layout_element);
+            }
             if !fixed_size {
                 set_prop_from_cache(
                     actual_elem,
@@ -395,6 +415,7 @@ fn lower_box_layout(
 fn lower_dialog_layout(
     dialog_element: &ElementRc,
     style_metrics: &Option<Rc<Component>>,
+    can_be_right_to_left: bool,
     diag: &mut BuildDiagnostics,
 ) {
     let mut grid = GridLayout {
@@ -524,6 +545,7 @@ fn lower_dialog_layout(
                 (1, 1),
                 &layout_cache_prop_h,
                 &layout_cache_prop_v,
+                can_be_right_to_left,
                 diag,
             );
         } else if main_widget.is_some() {
@@ -544,6 +566,7 @@ fn lower_dialog_layout(
             (1, button_roles.len() as u16 + 1),
             &layout_cache_prop_h,
             &layout_cache_prop_v,
+            can_be_right_to_left,
             diag,
         );
     } else {
@@ -754,6 +777,80 @@ fn set_prop_from_cache(
     }
 }
 
+/// Returns true if the `layout-direction` of the window, which is the root element of the root
+/// component, is not always `ltr`. Otherwise, there is no need to mirror the layouts.
+pub fn window_can_be_right_to_left(root_component: &Rc<Component>) -> bool {
+    let layout_direction = NamedReference::new(&root_component.root_element, "layout-direction");
+    if layout_direction.is_externally_modified() {
+        return true;
+    }
+    // The binding can be in a base component of the window
+    let mut elem = root_component.root_element.clone();
+    loop {
+        let base = {
+            let e = elem.borrow();
+            if let Some(binding) = e.bindings.get("layout-direction") {
+                let binding = binding.borrow();
+                let is_ltr = matches!(
+                    &binding.expression,
+                    Expression::EnumerationValue(value) if value.to_string() == "ltr"
+                );
+                return !is_ltr || !binding.two_way_bindings.is_empty();
+            }
+            match &e.base_type {
+                Type::Component(base) => base.root_element.clone(),
+                _ => break,
+            }
+        };
+        elem = base;
+    }
+    // The property can also be changed through a two way binding with another property
+    let mut has_two_way_binding = false;
+    recurse_elem_including_sub_components(root_component, &(), &mut |elem, _| {
+        has_two_way_binding |= elem
+            .borrow()
+            .bindings
+            .values()
+            .any(|binding| binding.borrow().two_way_bindings.contains(&layout_direction));
+    });
+    has_two_way_binding
+}
+
+/// In a window whose `layout-direction` is `rtl`, the horizontal layouts are mirrored: the x
+/// position computed by the layout is the distance between the right edge of the layout and the
+/// right edge of the element.
+fn mirror_x_in_right_to_left(elem: &ElementRc, layout_element: &ElementRc) {
+    let layout_width = NamedReference::new(layout_element, "width");
+    let elem_width = NamedReference::new(elem, "width");
+    let mut elem = elem.borrow_mut();
+    let binding = match elem.bindings.get_mut("x") {
+        Some(binding) => binding.get_mut(),
+        None => return,
+    };
+    let ltr_x = std::mem::take(&mut binding.expression);
+    let rtl_x = Expression::BinaryExpression {
+        lhs: Box::new(Expression::BinaryExpression {
+            lhs: Box::new(Expression::PropertyReference(layout_width)),
+            rhs: Box::new(ltr_x.clone()),
+            op: '-',
+        }),
+        rhs: Box::new(Expression::PropertyReference(elem_width)),
+        op: '-',
+    };
+    binding.expression = Expression::Condition {
+        condition: Box::new(Expression::FunctionCall {
+            function: Box::new(Expression::BuiltinFunctionReference(
+                BuiltinFunction::WindowIsRightToLeft,
+                None,
+            )),
+            arguments: vec![],
+            source_location: None,
+        }),
+        true_expr: Box::new(rtl_x),
+        false_expr: Box::new(ltr_x),
+    };
+}
+
 fn eval_const_expr(
     expression: &Expression,
     name: &str,
@@ -139,11 +139,24 @@ fn initialize(elem: &ElementRc, name: &str) -> Option<Expression> {
         "vertical-stretch" => layout_constraint_prop(elem, "stretch", Orientation::Vertical),
         "opacity" => Expression::NumberLiteral(1., Unit::None),
         "visible" => Expression::BoolLiteral(true),
+        "scale-x" | "scale-y" => Expression::NumberLiteral(1., Unit::None),
+        "skew-x" | "skew-y" => Expression::NumberLiteral(0., Unit::Deg),
+        "transform-origin-x" => half_of(elem, "width"),
+        "transform-origin-y" => half_of(elem, "height"),
         _ => return None,
     };
     Some(expr)
 }
 
+/// The center of the element, which is the default transform origin
+fn half_of(elem: &ElementRc, prop: &str) -> Expression {
+    Expression::BinaryExpression {
+        lhs: Box::new(Expression::PropertyReference(NamedReference::new(elem, prop))),
+        rhs: Box::new(Expression::NumberLiteral(2., Unit::None)),
+        op: '/',
+    }
+}
+
 fn layout_constraint_prop(elem: &ElementRc, field: &str, orient: Orientation) -> Expression {
     let expr = match elem.borrow().layout_info_prop(orient) {
         Some(e) => Expression::PropertyReference(e.clone()),
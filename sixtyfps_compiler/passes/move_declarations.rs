@@ -29,6 +29,7 @@ fn take_from_element(e: &mut Element) -> Self {
 
 pub fn move_declarations(component: &Rc<Component>, diag: &mut BuildDiagnostics) {
     simplify_optimized_items(component.optimized_elements.borrow().as_slice());
+    recurse_elem(&component.root_element, &(), &mut |elem, _| remember_declared_properties(elem));
 
     let mut decl = Declarations::take_from_element(&mut *component.root_element.borrow_mut());
     component.popup_windows.borrow().iter().for_each(|f| move_declarations(&f.component, diag));
@@ -125,6 +126,24 @@ fn fixup_reference(nr: &mut NamedReference) {
     }
 }
 
+/// Fills `Element::declared_properties` for the elements written in the .60 file, before their
+/// declarations are moved to the root element and renamed.
+fn remember_declared_properties(elem: &ElementRc) {
+    if elem.borrow().source_id_and_type_name().1.is_empty() {
+        return;
+    }
+    let names = elem
+        .borrow()
+        .property_declarations
+        .iter()
+        .filter(|(_, decl)| decl.property_type.is_property_type())
+        .map(|(name, _)| name.clone())
+        .collect::<Vec<_>>();
+    let declared_properties =
+        names.into_iter().map(|name| (name.clone(), NamedReference::new(elem, &name))).collect();
+    elem.borrow_mut().declared_properties = declared_properties;
+}
+
 fn map_name(e: &ElementRc, s: &str) -> String {
     format!("{}-{}", e.borrow().id, s)
 }
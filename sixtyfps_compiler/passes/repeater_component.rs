@@ -47,6 +47,8 @@ fn create_repeater_components(component: &Rc<Component>) {
                 transitions: std::mem::take(&mut elem.transitions),
                 child_of_layout: elem.child_of_layout || is_listview.is_some(),
                 layout_info_prop: elem.layout_info_prop.take(),
+                accessibility_props: std::mem::take(&mut elem.accessibility_props),
+                declared_properties: std::mem::take(&mut elem.declared_properties),
                 is_flickable_viewport: elem.is_flickable_viewport,
                 item_index: Default::default(), // Not determined yet
             })),
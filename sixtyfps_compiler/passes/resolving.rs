@@ -56,6 +56,7 @@ fn resolve_expression(
                 //FIXME: proper callback support (node is a codeblock)
                 Expression::from_callback_connection(node.clone().into(), &mut lookup_ctx)
             }
+            SyntaxKind::Function => Expression::from_function(node.clone().into(), &mut lookup_ctx),
             SyntaxKind::Expression => {
                 //FIXME again: this happen for non-binding expression (i.e: model)
                 Expression::from_expression_node(node.clone().into(), &mut lookup_ctx)
@@ -238,11 +239,29 @@ fn from_callback_connection(
         )
     }
 
-    fn from_expression_node(node: syntax_nodes::Expression, ctx: &mut LookupCtx) -> Self {
+    fn from_function(node: syntax_nodes::Function, ctx: &mut LookupCtx) -> Expression {
+        ctx.arguments = node
+            .ArgumentDeclaration()
+            .map(|x| identifier_text(&x.DeclaredIdentifier()).unwrap_or_default())
+            .collect();
+        Self::from_codeblock_node(node.CodeBlock(), ctx).maybe_convert_to(
+            ctx.return_type().clone(),
+            &node,
+            &mut ctx.diag,
+        )
+    }
+
+    pub(crate) fn from_expression_node(
+        node: syntax_nodes::Expression,
+        ctx: &mut LookupCtx,
+    ) -> Self {
         node.Expression()
             .map(|n| Self::from_expression_node(n, ctx))
             .or_else(|| node.AtImageUrl().map(|n| Self::from_at_image_url_node(n, ctx)))
             .or_else(|| node.AtLinearGradient().map(|n| Self::from_at_linear_gradient(n, ctx)))
+            .or_else(|| node.AtRadialGradient().map(|n| Self::from_at_radial_gradient(n, ctx)))
+            .or_else(|| node.AtConicGradient().map(|n| Self::from_at_conic_gradient(n, ctx)))
+            .or_else(|| node.AtTr().map(|n| Self::from_at_tr(n, ctx)))
             .or_else(|| {
                 node.QualifiedName().map(|n| {
                     let exp = Self::from_qualified_name_node(n.clone(), ctx);
@@ -351,6 +370,71 @@ fn from_at_image_url_node(node: syntax_nodes::AtImageUrl, ctx: &mut LookupCtx) -
         }
     }
 
+    fn from_at_tr(node: syntax_nodes::AtTr, ctx: &mut LookupCtx) -> Self {
+        let unescape = |node: &SyntaxNode, diag: &mut BuildDiagnostics| {
+            node.child_text(SyntaxKind::StringLiteral)
+                .and_then(|x| crate::literals::unescape_string(&x))
+                .unwrap_or_else(|| {
+                    diag.push_error("Cannot parse string literal".into(), node);
+                    String::new()
+                })
+        };
+        let context = node.TrContext().map(|c| unescape(&c, ctx.diag)).unwrap_or_default();
+        let text = unescape(&node, ctx.diag);
+        let plural = node.TrPlural().map(|p| {
+            let plural = unescape(&p, ctx.diag);
+            let count = p.Expression();
+            let count = Expression::from_expression_node(count.clone(), ctx).maybe_convert_to(
+                Type::Int32,
+                &count,
+                &mut ctx.diag,
+            );
+            (plural, count, p)
+        });
+        let args = node
+            .Expression()
+            .map(|e| {
+                Expression::from_expression_node(e.clone(), ctx).maybe_convert_to(
+                    Type::String,
+                    &e,
+                    &mut ctx.diag,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        if let Err(message) =
+            crate::literals::check_translation_placeholders(&text, args.len(), plural.is_some())
+        {
+            ctx.diag.push_error(message, &node);
+        }
+        let (plural, count) = match plural {
+            Some((plural, count, plural_node)) => {
+                if let Err(message) =
+                    crate::literals::check_translation_placeholders(&plural, args.len(), true)
+                {
+                    ctx.diag.push_error(message, &plural_node);
+                }
+                (plural, count)
+            }
+            None => (String::new(), Expression::NumberLiteral(0., Unit::None)),
+        };
+
+        Expression::FunctionCall {
+            function: Box::new(Expression::BuiltinFunctionReference(
+                BuiltinFunction::Translate,
+                Some(node.to_source_location()),
+            )),
+            arguments: vec![
+                Expression::StringLiteral(context),
+                Expression::StringLiteral(text),
+                Expression::StringLiteral(plural),
+                count,
+                Expression::Array { element_ty: Type::String, values: args },
+            ],
+            source_location: Some(node.to_source_location()),
+        }
+    }
+
     fn from_at_linear_gradient(node: syntax_nodes::AtLinearGradient, ctx: &mut LookupCtx) -> Self {
         let mut subs = node
             .children_with_tokens()
@@ -376,6 +460,50 @@ fn from_at_linear_gradient(node: syntax_nodes::AtLinearGradient, ctx: &mut Looku
                 &mut ctx.diag,
             ));
 
+        let stops = Self::gradient_stops(subs, false, ctx);
+        Expression::LinearGradient { angle, stops }
+    }
+
+    fn from_at_radial_gradient(node: syntax_nodes::AtRadialGradient, ctx: &mut LookupCtx) -> Self {
+        let mut subs = node
+            .children_with_tokens()
+            .filter(|n| matches!(n.kind(), SyntaxKind::Comma | SyntaxKind::Expression));
+        let is_circle = subs.next().and_then(|e| e.into_node()).map_or(false, |e| {
+            syntax_nodes::Expression::from(e)
+                .QualifiedName()
+                .map_or(false, |qn| qn.text().to_string().trim() == "circle")
+        });
+        if !is_circle {
+            ctx.diag.push_error(
+                "Expected 'circle': only circular radial gradients are supported".into(),
+                &node,
+            );
+            return Expression::Invalid;
+        }
+        if subs.next().map_or(false, |s| s.kind() != SyntaxKind::Comma) {
+            ctx.diag.push_error("'circle' must be followed by a comma".into(), &node);
+            return Expression::Invalid;
+        }
+        let stops = Self::gradient_stops(subs, false, ctx);
+        Expression::RadialGradient { stops }
+    }
+
+    fn from_at_conic_gradient(node: syntax_nodes::AtConicGradient, ctx: &mut LookupCtx) -> Self {
+        let subs = node
+            .children_with_tokens()
+            .filter(|n| matches!(n.kind(), SyntaxKind::Comma | SyntaxKind::Expression));
+        let stops = Self::gradient_stops(subs, true, ctx);
+        Expression::ConicGradient { stops }
+    }
+
+    /// Resolve the color stops of a gradient from the remaining expressions and commas.
+    /// When `angle_positions` is true, the positions can also be angles which are converted to
+    /// a fraction of a full turn.
+    fn gradient_stops(
+        subs: impl Iterator<Item = NodeOrToken>,
+        angle_positions: bool,
+        ctx: &mut LookupCtx,
+    ) -> Vec<(Expression, Expression)> {
         let mut stops = vec![];
         enum Stop {
             Empty,
@@ -419,7 +547,16 @@ enum Stop {
                         break;
                     }
                     Stop::Color(col) => {
-                        stops.push((col, e.maybe_convert_to(Type::Float32, &n, &mut ctx.diag)))
+                        let position = if angle_positions && e.ty() == Type::Angle {
+                            Expression::BinaryExpression {
+                                lhs: Box::new(e),
+                                rhs: Box::new(Expression::NumberLiteral(360., Unit::Deg)),
+                                op: '/',
+                            }
+                        } else {
+                            e.maybe_convert_to(Type::Float32, &n, &mut ctx.diag)
+                        };
+                        stops.push((col, position))
                     }
                 }
             }
@@ -473,7 +610,7 @@ enum Stop {
             start += pos + 1;
         }
 
-        Expression::LinearGradient { angle, stops }
+        stops
     }
 
     /// Perform the lookup
@@ -537,6 +674,14 @@ fn from_qualified_name_node(node: syntax_nodes::QualifiedName, ctx: &mut LookupC
                 }
                 r
             }
+            LookupResult::Expression {
+                expression: r @ Expression::FunctionReference(..), ..
+            } => {
+                if let Some(x) = it.next() {
+                    ctx.diag.push_error("Cannot access fields of a function".into(), &x)
+                }
+                r
+            }
             LookupResult::Enumeration(enumeration) => {
                 if let Some(next_identifier) = it.next() {
                     match enumeration
@@ -638,6 +783,24 @@ fn from_function_call_node(
         };
         arguments.extend(sub_expr);
 
+        if matches!(ctx.property_type, Type::Function { .. }) {
+            // The functions declared in the .60 file are pure, so that bindings can call them
+            match &*function {
+                Expression::CallbackReference(..) => ctx.diag.push_error(
+                    "Cannot call a callback in a function: functions must be pure".into(),
+                    &node,
+                ),
+                Expression::BuiltinFunctionReference(f, _) if f.has_side_effects() => {
+                    ctx.diag.push_error(
+                        "Cannot call a function with side effects in a function: functions must be pure"
+                            .into(),
+                        &node,
+                    )
+                }
+                _ => {}
+            }
+        }
+
         let arguments = match function.ty() {
             Type::Function { args, .. } | Type::Callback { args, .. } => {
                 if arguments.len() != args.len() {
@@ -684,6 +847,12 @@ fn from_self_assignment_node(
             .or_else(|| node.child_token(SyntaxKind::DivEqual).and(Some('/')))
             .or_else(|| node.child_token(SyntaxKind::Equal).and(Some('=')))
             .unwrap_or('_');
+        if matches!(ctx.property_type, Type::Function { .. }) {
+            ctx.diag.push_error(
+                "Cannot assign a property in a function: functions must be pure".into(),
+                &node,
+            );
+        }
         if !lhs.try_set_rw() && lhs.ty() != Type::Invalid {
             ctx.diag.push_error(
                 format!(
@@ -895,15 +1064,44 @@ fn from_string_template_node(
         node: syntax_nodes::StringTemplate,
         ctx: &mut LookupCtx,
     ) -> Expression {
-        let mut exprs = node.Expression().map(|e| {
-            Expression::from_expression_node(e.clone(), ctx).maybe_convert_to(
-                Type::String,
-                &e,
-                ctx.diag,
-            )
-        });
-        let mut result = exprs.next().unwrap_or_default();
-        for x in exprs {
+        // The children alternate between string literals and embedded expressions.
+        // A literal that follows an expression may start with the format specifier of that expression.
+        let mut parts = vec![];
+        let mut pending_value = None;
+        for (index, e) in node.Expression().enumerate() {
+            if index % 2 == 1 {
+                pending_value = Some((Expression::from_expression_node(e.clone(), ctx), e));
+                continue;
+            }
+            let text = e.child_text(SyntaxKind::StringLiteral).unwrap_or_default();
+            let (spec, literal) = match text.strip_prefix(':') {
+                Some(rest) => {
+                    let end = rest.find('}').unwrap_or(rest.len());
+                    (Some(&rest[..end]), &rest[end..])
+                }
+                None => (None, text.as_str()),
+            };
+            if let Some((value, value_node)) = pending_value.take() {
+                parts.push(match spec {
+                    Some(spec) => Self::format_template_value(value, spec, &e, ctx),
+                    None => value.maybe_convert_to(Type::String, &value_node, ctx.diag),
+                });
+            }
+            parts.push(crate::literals::unescape_string(literal).map_or_else(
+                || {
+                    ctx.diag.push_error("Cannot parse string literal".into(), &e);
+                    Expression::Invalid
+                },
+                Expression::StringLiteral,
+            ));
+        }
+        if let Some((value, value_node)) = pending_value {
+            // Only happens in case of parse error
+            parts.push(value.maybe_convert_to(Type::String, &value_node, ctx.diag));
+        }
+        let mut parts = parts.into_iter();
+        let mut result = parts.next().unwrap_or_default();
+        for x in parts {
             result = Expression::BinaryExpression {
                 lhs: Box::new(std::mem::take(&mut result)),
                 rhs: Box::new(x),
@@ -913,6 +1111,42 @@ fn from_string_template_node(
         result
     }
 
+    /// Convert the value of a string template to a string, using the format specifier `spec`
+    fn format_template_value(
+        value: Expression,
+        spec: &str,
+        node: &syntax_nodes::Expression,
+        ctx: &mut LookupCtx,
+    ) -> Expression {
+        let (function, value) = match value.ty() {
+            Type::Float32 => (BuiltinFunction::FormatNumber, value),
+            Type::Int32 => (BuiltinFunction::FormatInteger, value),
+            Type::String => (BuiltinFunction::FormatString, value),
+            Type::Invalid => return Expression::Invalid,
+            ty => {
+                ctx.diag.push_error(
+                    format!("Format specifiers cannot be used with values of type '{}'", ty),
+                    node,
+                );
+                return Expression::Invalid;
+            }
+        };
+        if let Err(message) = crate::literals::check_format_specifier(
+            spec,
+            !matches!(function, BuiltinFunction::FormatString),
+        ) {
+            ctx.diag.push_error(message, node);
+        }
+        Expression::FunctionCall {
+            function: Box::new(Expression::BuiltinFunctionReference(
+                function,
+                Some(node.to_source_location()),
+            )),
+            arguments: vec![value, Expression::StringLiteral(spec.into())],
+            source_location: Some(node.to_source_location()),
+        }
+    }
+
     /// This function is used to find a type that's suitable for casting each instance of a bunch of expressions
     /// to a type that captures most aspects. For example for an array of object literals the result is a merge of
     /// all seen fields.
@@ -1004,6 +1238,13 @@ fn continue_lookup_within_element(
         Expression::CallbackReference(NamedReference::new(elem, &resolved_name))
     } else if matches!(property_type, Type::Function { .. }) {
         let member = elem.borrow().base_type.lookup_member_function(&resolved_name);
+        if matches!(member, Expression::Invalid) {
+            // Not a builtin member function, so this is a function declared in the .60 file
+            if let Some(x) = it.next() {
+                ctx.diag.push_error("Cannot access fields of a function".into(), &x)
+            }
+            return Expression::FunctionReference(NamedReference::new(elem, &resolved_name));
+        }
         Expression::MemberFunction {
             base: Box::new(Expression::ElementReference(Rc::downgrade(elem))),
             base_node: Some(NodeOrToken::Node(node.into())),
@@ -7,17 +7,21 @@
     This file is also available under commercial licensing terms.
     Please contact info@sixtyfps.io for more information.
 LICENSE END */
-//! Pass that lowers synthetic `opacity`, `visibility`, or `rotate` properties to their Element.
-//! TODO: the rotation is not yet implemented
+//! Pass that lowers synthetic `opacity`, `scale-*`, `skew-*` and `transform-origin-*`
+//! properties to their Element.
 
 use std::cell::RefCell;
 use std::rc::Rc;
 
 use crate::diagnostics::BuildDiagnostics;
-use crate::expression_tree::{BindingExpression, NamedReference};
+use crate::expression_tree::{BindingExpression, Expression, NamedReference};
 use crate::langtype::Type;
 use crate::object_tree::{self, Component, Element, ElementRc};
-use crate::typeregister::TypeRegister;
+use crate::typeregister::{TypeRegister, RESERVED_TRANSFORM_PROPERTIES};
+
+/// The properties that, when set, require a TransformItem. The `transform-origin-*` are only
+/// used when one of these is set.
+const TRANSFORM_PROPERTIES: [&str; 4] = ["scale-x", "scale-y", "skew-x", "skew-y"];
 
 pub(crate) fn handle_transform_and_opacity(
     component: &Rc<Component>,
@@ -31,9 +35,35 @@ pub(crate) fn handle_transform_and_opacity(
             &*b.borrow(),
         );
     }
+    for (prop, _) in RESERVED_TRANSFORM_PROPERTIES {
+        if let Some(b) = component.root_element.borrow().bindings.get(*prop) {
+            diag.push_warning(
+                format!(
+                    "The {} property cannot be used on the root element, it will not be applied",
+                    prop
+                ),
+                &*b.borrow(),
+            );
+        }
+    }
+
+    // The images are processed into textures for the MCU backend, whose renderer can't skew
+    let warn_about_skew = std::env::var("SIXTYFPS_PROCESS_IMAGES").is_ok();
 
     object_tree::recurse_elem_including_sub_components_no_borrow(component, &(), &mut |elem, _| {
-        if elem.borrow().base_type.to_string() == "Opacity" {
+        if warn_about_skew {
+            for prop in &["skew-x", "skew-y"] {
+                if let Some(b) = elem.borrow().bindings.get(*prop) {
+                    diag.push_warning(
+                        format!("The {} property is not supported by the MCU backend, it will not be applied", prop),
+                        &*b.borrow(),
+                    );
+                }
+            }
+        }
+
+        let base_type = elem.borrow().base_type.to_string();
+        if base_type == "Opacity" || base_type == "TransformItem" {
             return;
         }
 
@@ -43,29 +73,43 @@ pub(crate) fn handle_transform_and_opacity(
             std::mem::replace(&mut elem.children, new_children)
         };
 
-        let has_opacity_binding = |e: &ElementRc| {
-            e.borrow().base_type.lookup_property("opacity").property_type != Type::Invalid
-                && (e.borrow().bindings.contains_key("opacity")
-                    || e.borrow()
-                        .property_analysis
-                        .borrow()
-                        .get("opacity")
-                        .map_or(false, |a| a.is_set))
+        let has_binding = |e: &ElementRc, prop: &str| {
+            e.borrow().base_type.lookup_property(prop).property_type != Type::Invalid
+                && (e.borrow().bindings.contains_key(prop)
+                    || e.borrow().property_analysis.borrow().get(prop).map_or(false, |a| a.is_set))
         };
+        let has_opacity_binding = |e: &ElementRc| has_binding(e, "opacity");
+        let has_transform_binding =
+            |e: &ElementRc| TRANSFORM_PROPERTIES.iter().any(|prop| has_binding(e, prop));
 
         for mut child in old_children {
             if child.borrow().repeated.is_some() {
                 let root_elem = child.borrow().base_type.as_component().root_element.clone();
+                if has_transform_binding(&root_elem) {
+                    object_tree::inject_element_as_repeated_element(
+                        &child,
+                        create_transform_element(&root_elem, type_register),
+                    )
+                }
                 if has_opacity_binding(&root_elem) {
                     object_tree::inject_element_as_repeated_element(
                         &child,
                         create_opacity_element(&root_elem, type_register),
                     )
                 }
-            } else if has_opacity_binding(&child) {
-                let new_child = create_opacity_element(&child, type_register);
-                new_child.borrow_mut().children.push(child);
-                child = new_child;
+            } else {
+                // The opacity element wraps the transform element, which wraps the child
+                let original_child = child.clone();
+                if has_transform_binding(&original_child) {
+                    let new_child = create_transform_element(&original_child, type_register);
+                    new_child.borrow_mut().children.push(child);
+                    child = new_child;
+                }
+                if has_opacity_binding(&original_child) {
+                    let new_child = create_opacity_element(&original_child, type_register);
+                    new_child.borrow_mut().children.push(child);
+                    child = new_child;
+                }
             }
 
             elem.borrow_mut().children.push(child);
@@ -87,3 +131,35 @@ fn create_opacity_element(child: &ElementRc, type_register: &TypeRegister) -> El
     };
     Rc::new(RefCell::new(element))
 }
+
+fn create_transform_element(child: &ElementRc, type_register: &TypeRegister) -> ElementRc {
+    // The origin is relative to the child, but the TransformItem has the geometry of the parent
+    let origin = |pos: &str, origin: &str| -> RefCell<BindingExpression> {
+        BindingExpression::from(Expression::BinaryExpression {
+            lhs: Box::new(Expression::PropertyReference(NamedReference::new(child, pos))),
+            rhs: Box::new(Expression::PropertyReference(NamedReference::new(child, origin))),
+            op: '+',
+        })
+        .into()
+    };
+    let element = Element {
+        id: format!("{}-transform", child.borrow().id),
+        base_type: type_register.lookup_element("TransformItem").unwrap(),
+        enclosing_component: child.borrow().enclosing_component.clone(),
+        bindings: TRANSFORM_PROPERTIES
+            .iter()
+            .map(|prop| {
+                (
+                    prop.to_string(),
+                    BindingExpression::new_two_way(NamedReference::new(child, prop)).into(),
+                )
+            })
+            .chain(IntoIterator::into_iter([
+                ("origin-x".to_owned(), origin("x", "transform-origin-x")),
+                ("origin-y".to_owned(), origin("y", "transform-origin-y")),
+            ]))
+            .collect(),
+        ..Default::default()
+    };
+    Rc::new(RefCell::new(element))
+}
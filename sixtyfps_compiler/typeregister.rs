@@ -58,6 +58,31 @@
             ])
             .collect(),
             default_value: 0,
+            node: None,
+        }));
+
+    pub static ACCESSIBLE_ROLE_ENUM: Type =
+        Type::Enumeration(Rc::new(Enumeration {
+            name: "AccessibleRole".into(),
+            values: IntoIterator::into_iter([
+                "none",
+                "button",
+                "checkbox",
+                "combobox",
+                "group",
+                "list",
+                "list-item",
+                "scroll-view",
+                "slider",
+                "spinbox",
+                "tab",
+                "text",
+                "text-input",
+            ])
+            .map(String::from)
+            .collect(),
+            default_value: 0,
+            node: None,
         }));
 }
 
@@ -67,6 +92,13 @@
     ("visible", Type::Bool), // ("enabled", Type::Bool),
 ];
 
+/// The accessible-* properties, except `accessible-role` whose type is an enum
+pub(crate) const RESERVED_ACCESSIBILITY_STRING_PROPERTIES: &[(&str, Type)] = &[
+    ("accessible-label", Type::String),
+    ("accessible-value", Type::String),
+    ("accessible-description", Type::String),
+];
+
 pub(crate) const RESERVED_DROP_SHADOW_PROPERTIES: &[(&str, Type)] = &[
     ("drop-shadow-offset-x", Type::LogicalLength),
     ("drop-shadow-offset-y", Type::LogicalLength),
@@ -74,6 +106,15 @@
     ("drop-shadow-color", Type::Color),
 ];
 
+pub(crate) const RESERVED_TRANSFORM_PROPERTIES: &[(&str, Type)] = &[
+    ("scale-x", Type::Float32),
+    ("scale-y", Type::Float32),
+    ("skew-x", Type::Angle),
+    ("skew-y", Type::Angle),
+    ("transform-origin-x", Type::LogicalLength),
+    ("transform-origin-y", Type::LogicalLength),
+];
+
 /// list of reserved property injected in every item
 pub fn reserved_properties() -> impl Iterator<Item = (&'static str, Type)> {
     RESERVED_GEOMETRY_PROPERTIES
@@ -81,11 +122,14 @@ pub fn reserved_properties() -> impl Iterator<Item = (&'static str, Type)> {
         .chain(RESERVED_LAYOUT_PROPERTIES.iter())
         .chain(RESERVED_OTHER_PROPERTIES.iter())
         .chain(RESERVED_DROP_SHADOW_PROPERTIES.iter())
+        .chain(RESERVED_TRANSFORM_PROPERTIES.iter())
+        .chain(RESERVED_ACCESSIBILITY_STRING_PROPERTIES.iter())
         .map(|(k, v)| (*k, v.clone()))
         .chain(IntoIterator::into_iter([
             ("forward-focus", Type::ElementReference),
             ("focus", BuiltinFunction::SetFocusItem.ty()),
             ("dialog-button-role", DIALOG_BUTTON_ROLE_ENUM.with(|e| e.clone())),
+            ("accessible-role", ACCESSIBLE_ROLE_ENUM.with(|e| e.clone())),
         ]))
 }
 
@@ -176,6 +220,7 @@ pub fn builtin() -> Rc<RefCell<Self>> {
                     name: name.to_owned(),
                     values: values.iter().cloned().map(String::from).collect(),
                     default_value: 0,
+                    node: None,
                 })),
                 name.to_owned(),
             );
@@ -185,6 +230,10 @@ pub fn builtin() -> Rc<RefCell<Self>> {
         declare_enum("TextVerticalAlignment", &["top", "center", "bottom"]);
         declare_enum("TextWrap", &["no-wrap", "word-wrap"]);
         declare_enum("TextOverflow", &["clip", "elide"]);
+        declare_enum("InputType", &["text", "password", "number", "decimal"]);
+        declare_enum("TextDecoration", &["none", "underline", "strikethrough"]);
+        declare_enum("LayoutDirection", &["ltr", "rtl"]);
+        declare_enum("BoundsBehavior", &["stop", "overshoot-and-bounce"]);
         declare_enum(
             "LayoutAlignment",
             &["stretch", "center", "start", "end", "space-between", "space-around"],
@@ -236,6 +285,7 @@ pub fn builtin() -> Rc<RefCell<Self>> {
         declare_enum("PointerEventKind", &["cancel", "down", "up"]);
         declare_enum("PointerEventButton", &["none", "left", "right", "middle"]);
         register.insert_type(DIALOG_BUTTON_ROLE_ENUM.with(|x| x.clone()));
+        register.insert_type(ACCESSIBLE_ROLE_ENUM.with(|x| x.clone()));
 
         register.supported_property_animation_types.insert(Type::Float32.to_string());
         register.supported_property_animation_types.insert(Type::Int32.to_string());
@@ -267,6 +317,40 @@ pub fn builtin() -> Rc<RefCell<Self>> {
             _ => unreachable!(),
         };
 
+        match &mut register.types.get_mut("TextInput").unwrap() {
+            Type::Builtin(ref mut b) => {
+                for (name, function) in [
+                    ("undo", BuiltinFunction::TextInputUndo),
+                    ("redo", BuiltinFunction::TextInputRedo),
+                ] {
+                    Rc::get_mut(b)
+                        .unwrap()
+                        .properties
+                        .insert(name.into(), BuiltinPropertyInfo::new(function.ty()));
+                    Rc::get_mut(b)
+                        .unwrap()
+                        .member_functions
+                        .insert(name.into(), Expression::BuiltinFunctionReference(function, None));
+                }
+            }
+            _ => unreachable!(),
+        };
+
+        match &mut register.types.get_mut("Flickable").unwrap() {
+            Type::Builtin(ref mut b) => {
+                let function = BuiltinFunction::FlickableScrollTo;
+                Rc::get_mut(b)
+                    .unwrap()
+                    .properties
+                    .insert("scroll-to".into(), BuiltinPropertyInfo::new(function.ty()));
+                Rc::get_mut(b).unwrap().member_functions.insert(
+                    "scroll-to".into(),
+                    Expression::BuiltinFunctionReference(function, None),
+                );
+            }
+            _ => unreachable!(),
+        };
+
         Rc::new(RefCell::new(register))
     }
 
@@ -0,0 +1,168 @@
+/* LICENSE BEGIN
+    This file is part of the SixtyFPS Project -- https://sixtyfps.io
+    Copyright (c) 2021 Olivier Goffart <olivier.goffart@sixtyfps.io>
+    Copyright (c) 2021 Simon Hausmann <simon.hausmann@sixtyfps.io>
+
+    SPDX-License-Identifier: GPL-3.0-only
+    This file is also available under commercial licensing terms.
+    Please contact info@sixtyfps.io for more information.
+LICENSE END */
+//! The format specifiers of the `"\{value:spec}"` string templates of the .60 language.
+//! They are checked by the compiler and applied by the runtime.
+
+/// The alignment of a value within the width of a [`FormatSpec`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FormatAlignment {
+    /// `<`
+    Left,
+    /// `>`
+    Right,
+    /// `^`
+    Center,
+}
+
+/// The reason why a format specifier could not be parsed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FormatSpecError {
+    /// The specifier has a `.` that is not followed by the precision
+    MissingPrecision,
+    /// The specifier does not follow the syntax
+    Invalid,
+}
+
+/// A format specifier, as used in the `"\{value:spec}"` string templates of the .60 language.
+///
+/// The syntax is `[[fill]align][+][0][width][,][.precision]`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FormatSpec {
+    /// The character used for padding
+    pub fill: char,
+    /// The alignment, if explicitly specified
+    pub align: Option<FormatAlignment>,
+    /// Always show the sign of numbers
+    pub sign: bool,
+    /// Pad numbers with zeros after the sign
+    pub zero_pad: bool,
+    /// The minimum amount of characters
+    pub width: usize,
+    /// Separate the thousands of numbers with a comma
+    pub grouping: bool,
+    /// The number of decimals for numbers, or the maximum amount of characters for strings
+    pub precision: Option<usize>,
+}
+
+impl Default for FormatSpec {
+    fn default() -> Self {
+        Self {
+            fill: ' ',
+            align: None,
+            sign: false,
+            zero_pad: false,
+            width: 0,
+            grouping: false,
+            precision: None,
+        }
+    }
+}
+
+impl FormatSpec {
+    /// Parse a format specifier.
+    pub fn parse(spec: &str) -> Result<Self, FormatSpecError> {
+        fn alignment(c: char) -> Option<FormatAlignment> {
+            match c {
+                '<' => Some(FormatAlignment::Left),
+                '>' => Some(FormatAlignment::Right),
+                '^' => Some(FormatAlignment::Center),
+                _ => None,
+            }
+        }
+        fn take_number(s: &str) -> Option<(usize, &str)> {
+            let len = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+            Some((s[..len].parse().ok()?, &s[len..]))
+        }
+
+        let mut result = Self::default();
+        let mut rest = spec;
+        let mut chars = rest.chars();
+        match (chars.next(), chars.next()) {
+            (Some(fill), Some(a)) if alignment(a).is_some() => {
+                result.fill = fill;
+                result.align = alignment(a);
+                rest = chars.as_str();
+            }
+            (Some(a), _) if alignment(a).is_some() => {
+                result.align = alignment(a);
+                rest = &rest[1..];
+            }
+            _ => (),
+        }
+        if let Some(r) = rest.strip_prefix('+') {
+            result.sign = true;
+            rest = r;
+        }
+        if let Some(r) = rest.strip_prefix('0') {
+            result.zero_pad = true;
+            rest = r;
+        }
+        if rest.starts_with(|c: char| c.is_ascii_digit()) {
+            let (width, r) = take_number(rest).ok_or(FormatSpecError::Invalid)?;
+            result.width = width;
+            rest = r;
+        }
+        if let Some(r) = rest.strip_prefix(',') {
+            result.grouping = true;
+            rest = r;
+        }
+        if let Some(r) = rest.strip_prefix('.') {
+            let (precision, r) = take_number(r).ok_or(FormatSpecError::MissingPrecision)?;
+            result.precision = Some(precision);
+            rest = r;
+        }
+        if rest.is_empty() {
+            Ok(result)
+        } else {
+            Err(FormatSpecError::Invalid)
+        }
+    }
+
+    /// Returns the first of the `+`, `0` or `,` flags that is set. These flags can only be
+    /// used with numbers.
+    pub fn number_only_flag(&self) -> Option<char> {
+        if self.sign {
+            Some('+')
+        } else if self.zero_pad {
+            Some('0')
+        } else if self.grouping {
+            Some(',')
+        } else {
+            None
+        }
+    }
+}
+
+#[test]
+fn test_format_spec_parse() {
+    assert_eq!(FormatSpec::parse(""), Ok(FormatSpec::default()));
+    assert_eq!(
+        FormatSpec::parse("*^+010,.3"),
+        Ok(FormatSpec {
+            fill: '*',
+            align: Some(FormatAlignment::Center),
+            sign: true,
+            zero_pad: true,
+            width: 10,
+            grouping: true,
+            precision: Some(3),
+        })
+    );
+    assert_eq!(FormatSpec::parse(">>10").map(|s| s.fill), Ok('>'));
+    assert_eq!(FormatSpec::parse("-<8.4").map(|s| s.precision), Ok(Some(4)));
+    assert_eq!(FormatSpec::parse("."), Err(FormatSpecError::MissingPrecision));
+    assert_eq!(FormatSpec::parse("5x"), Err(FormatSpecError::Invalid));
+    assert_eq!(FormatSpec::parse(".2."), Err(FormatSpecError::Invalid));
+    assert_eq!(FormatSpec::parse("<<<"), Err(FormatSpecError::Invalid));
+    assert_eq!(FormatSpec::parse("05").unwrap().number_only_flag(), Some('0'));
+    assert_eq!(FormatSpec::parse("+,").unwrap().number_only_flag(), Some('+'));
+    assert_eq!(FormatSpec::parse(",").unwrap().number_only_flag(), Some(','));
+    assert_eq!(FormatSpec::parse("^10.2").unwrap().number_only_flag(), None);
+}
@@ -0,0 +1,114 @@
+/* LICENSE BEGIN
+    This file is part of the SixtyFPS Project -- https://sixtyfps.io
+    Copyright (c) 2021 Olivier Goffart <olivier.goffart@sixtyfps.io>
+    Copyright (c) 2021 Simon Hausmann <simon.hausmann@sixtyfps.io>
+
+    SPDX-License-Identifier: GPL-3.0-only
+    This file is also available under commercial licensing terms.
+    Please contact info@sixtyfps.io for more information.
+LICENSE END */
+#![warn(missing_docs)]
+//! The accessibility tree, built from the item tree.
+//!
+//! No backend exposes it to the assistive technologies yet: for now it is only used by the
+//! testing API.
+
+use crate::component::ComponentRc;
+use crate::graphics::Rect;
+use crate::item_tree::{ItemVisitorResult, TraversalOrder};
+use crate::items::{AccessibleRole, AccessibleStringProperty, Clip, ItemRef};
+use crate::SharedString;
+use alloc::vec::Vec;
+use core::cell::RefCell;
+
+/// A node of the accessibility tree.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AccessibilityNode {
+    /// The value of the `accessible-role` property
+    pub role: AccessibleRole,
+    /// The value of the `accessible-label` property
+    pub label: SharedString,
+    /// The value of the `accessible-value` property
+    pub value: SharedString,
+    /// The value of the `accessible-description` property
+    pub description: SharedString,
+    /// The geometry of the element, relative to the window, in logical pixels
+    pub geometry: Rect,
+    /// The accessible elements that are descendants of this element in the item tree
+    pub children: Vec<AccessibilityNode>,
+}
+
+impl AccessibilityNode {
+    /// Returns an iterator over this node and all its descendants, depth first.
+    pub fn iter(&self) -> impl Iterator<Item = &AccessibilityNode> {
+        let mut stack = alloc::vec![self];
+        core::iter::from_fn(move || {
+            let node = stack.pop()?;
+            stack.extend(node.children.iter().rev());
+            Some(node)
+        })
+    }
+}
+
+/// Builds the accessibility tree of the component.
+///
+/// The root node stands for the window: its role is `none` and its geometry is the one of the
+/// root item. The items whose role is `none` are not part of the tree: their accessible
+/// descendants become children of their closest accessible ancestor. Elements hidden with
+/// `visible: false` are skipped together with their children.
+pub fn build_accessibility_tree(component: &ComponentRc) -> AccessibilityNode {
+    let root_geometry =
+        crate::items::ItemRc::new(component.clone(), 0).borrow().as_ref().geometry();
+    let stack = RefCell::new(alloc::vec![AccessibilityNode {
+        geometry: root_geometry,
+        ..Default::default()
+    }]);
+
+    #[derive(Default)]
+    struct State {
+        offset: euclid::default::Vector2D<f32>,
+        hidden: bool,
+    }
+
+    crate::item_tree::visit_items_with_post_visit(
+        component,
+        TraversalOrder::BackToFront,
+        |component, item, index, state: &State| {
+            let geometry = item.as_ref().geometry().translate(state.offset);
+            // The `visible` property is lowered to a Clip element that clips everything
+            let hidden = state.hidden
+                || ItemRef::downcast_pin::<Clip>(item)
+                    .map_or(false, |clip| clip.clip() && geometry.is_empty());
+            let component = vtable::VRc::borrow_pin(component);
+            let role = component.as_ref().accessible_role(index);
+            let is_accessible = !hidden && role != AccessibleRole::none;
+            if is_accessible {
+                let string_property = |what| {
+                    let mut result = SharedString::default();
+                    component.as_ref().accessible_string_property(index, what, &mut result);
+                    result
+                };
+                stack.borrow_mut().push(AccessibilityNode {
+                    role,
+                    label: string_property(AccessibleStringProperty::label),
+                    value: string_property(AccessibleStringProperty::value),
+                    description: string_property(AccessibleStringProperty::description),
+                    geometry,
+                    children: Default::default(),
+                });
+            }
+            let state = State { offset: geometry.origin.to_vector(), hidden };
+            (ItemVisitorResult::Continue(state), is_accessible)
+        },
+        |_, _, is_accessible, result| {
+            if is_accessible {
+                let mut stack = stack.borrow_mut();
+                let node = stack.pop().unwrap();
+                stack.last_mut().unwrap().children.push(node);
+            }
+            result
+        },
+        State::default(),
+    );
+    stack.into_inner().pop().unwrap()
+}
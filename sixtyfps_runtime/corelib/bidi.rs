@@ -0,0 +1,108 @@
+/* LICENSE BEGIN
+    This file is part of the SixtyFPS Project -- https://sixtyfps.io
+    Copyright (c) 2021 Olivier Goffart <olivier.goffart@sixtyfps.io>
+    Copyright (c) 2021 Simon Hausmann <simon.hausmann@sixtyfps.io>
+
+    SPDX-License-Identifier: GPL-3.0-only
+    This file is also available under commercial licensing terms.
+    Please contact info@sixtyfps.io for more information.
+LICENSE END */
+/*!
+    Support for bidirectional text, such as Arabic or Hebrew mixed with latin text.
+
+    The backends that shape the text themselves use [`visual_runs`] to draw each run of a line
+    in the right order, and the `TextInput` uses [`is_right_to_left_at`] so that the left and
+    right arrow keys move the cursor visually.
+
+    Without the `std` feature, all the text is considered to be left to right.
+*/
+
+#![warn(missing_docs)]
+use alloc::vec::Vec;
+use core::ops::Range;
+
+/// A part of a line of text that has a single direction
+#[derive(Debug, Clone, PartialEq)]
+pub struct BidiRun {
+    /// The (UTF-8) byte range of the run in the line
+    pub range: Range<usize>,
+    /// The characters of the run are shown from right to left
+    pub rtl: bool,
+}
+
+/// Splits a line of text in runs that have a single direction. The runs are returned in
+/// visual order, from left to right.
+pub fn visual_runs(line: &str) -> Vec<BidiRun> {
+    #[cfg(feature = "std")]
+    {
+        let info = unicode_bidi::BidiInfo::new(line, None);
+        let mut runs = Vec::new();
+        for paragraph in &info.paragraphs {
+            let (levels, ranges) = info.visual_runs(paragraph, paragraph.range.clone());
+            runs.extend(
+                ranges
+                    .into_iter()
+                    .map(|range| BidiRun { rtl: levels[range.start].is_rtl(), range }),
+            );
+        }
+        runs
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        alloc::vec![BidiRun { range: 0..line.len(), rtl: false }]
+    }
+}
+
+/// Returns true if the paragraph (the line ending with a newline) that contains the given byte
+/// offset is written from right to left, based on its first strong character.
+pub fn is_right_to_left_at(text: &str, byte_offset: usize) -> bool {
+    let byte_offset = byte_offset.min(text.len());
+    let start = text[..byte_offset].rfind('\n').map_or(0, |i| i + 1);
+    let end = text[byte_offset..].find('\n').map_or(text.len(), |i| byte_offset + i);
+    is_right_to_left(&text[start..end])
+}
+
+/// Returns true if the text is written from right to left, based on its first strong character.
+pub fn is_right_to_left(text: &str) -> bool {
+    #[cfg(feature = "std")]
+    {
+        unicode_bidi::BidiInfo::new(text, None)
+            .paragraphs
+            .first()
+            .map_or(false, |paragraph| paragraph.level.is_rtl())
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        let _ = text;
+        false
+    }
+}
+
+#[test]
+fn visual_runs_of_mixed_text() {
+    let line = "abc אבג def";
+    let runs = visual_runs(line);
+    assert_eq!(runs.len(), 3);
+    assert_eq!(&line[runs[0].range.clone()], "abc ");
+    assert!(!runs[0].rtl);
+    assert_eq!(&line[runs[1].range.clone()], "אבג");
+    assert!(runs[1].rtl);
+    assert_eq!(&line[runs[2].range.clone()], " def");
+
+    // In a right to left paragraph, the first run is on the right
+    let line = "אבג abc";
+    let runs = visual_runs(line);
+    assert_eq!(runs.len(), 2);
+    assert_eq!(&line[runs[0].range.clone()], "abc");
+    assert!(!runs[0].rtl);
+    assert!(runs[1].rtl);
+}
+
+#[test]
+fn paragraph_direction() {
+    let text = "hello\nשלום world\n123";
+    assert!(!is_right_to_left_at(text, 2));
+    assert!(is_right_to_left_at(text, 8));
+    assert!(is_right_to_left_at(text, text.find("world").unwrap()));
+    assert!(!is_right_to_left_at(text, text.len()));
+}
@@ -53,6 +53,14 @@ pub fn call(&self, a: &Arg) -> Ret {
     pub fn set_handler(&self, f: impl Fn(&Arg) -> Ret + 'static) {
         self.handler.set(Some(Box::new(move |a: &Arg, r: &mut Ret| *r = f(a))));
     }
+
+    /// Returns true if an handler was set with [`Self::set_handler`]
+    pub fn has_handler(&self) -> bool {
+        let handler = self.handler.take();
+        let has_handler = handler.is_some();
+        self.handler.set(handler);
+        has_handler
+    }
 }
 
 #[test]
@@ -12,9 +12,11 @@
 //! This module contains the basic datastructures that are exposed to the C API
 
 use crate::item_tree::{ItemVisitorVTable, TraversalOrder, VisitChildrenResult};
-use crate::items::{ItemVTable, ItemWeak};
+use crate::items::{AccessibleRole, AccessibleStringProperty, ItemVTable, ItemWeak};
 use crate::layout::{LayoutInfo, Orientation};
+use crate::tests::ElementPropertyValue;
 use crate::window::WindowRc;
+use crate::SharedString;
 use vtable::*;
 
 /// A Component is representing an unit that is allocated together
@@ -47,6 +49,38 @@ pub struct ComponentVTable {
     pub layout_info:
         extern "C" fn(core::pin::Pin<VRef<ComponentVTable>>, Orientation) -> LayoutInfo,
 
+    /// Sets `id` and `type_name` to the id and the type name of the element at the given index,
+    /// as they were written in the .60 file. They are empty for items generated by the compiler.
+    pub item_element_info: extern "C" fn(
+        core::pin::Pin<VRef<ComponentVTable>>,
+        index: usize,
+        id: &mut SharedString,
+        type_name: &mut SharedString,
+    ),
+
+    /// Returns the `accessible-role` of the item at the given index.
+    pub accessible_role:
+        extern "C" fn(core::pin::Pin<VRef<ComponentVTable>>, index: usize) -> AccessibleRole,
+
+    /// Sets `result` to the value of the accessible property `what` of the item at the given
+    /// index. `result` is left untouched if that property is not set.
+    pub accessible_string_property: extern "C" fn(
+        core::pin::Pin<VRef<ComponentVTable>>,
+        index: usize,
+        what: AccessibleStringProperty,
+        result: &mut SharedString,
+    ),
+
+    /// Sets `result` to the value of the property `name` of the element at the given index, as
+    /// it is named in the .60 file. `result` is left untouched if the element has no such
+    /// property, or if its type is not supported by `ElementPropertyValue`.
+    pub element_property: extern "C" fn(
+        core::pin::Pin<VRef<ComponentVTable>>,
+        index: usize,
+        name: &SharedString,
+        result: &mut ElementPropertyValue,
+    ),
+
     /// in-place destructor (for VRc)
     pub drop_in_place: unsafe fn(VRefMut<ComponentVTable>) -> vtable::Layout,
     /// dealloc function (for VRc)
@@ -8,23 +8,63 @@
     Please contact info@sixtyfps.io for more information.
 LICENSE END */
 //! The implementation details behind the Flickable
+//!
+//! When the mouse is released, the viewport keeps moving with the velocity of the last moves,
+//! and slows down with the `flick-deceleration`. The motion along each axis is computed
+//! from the time elapsed since the release by [`FlickAxis::position`], in bindings of the
+//! viewport position that depend on the tick of the animation driver.
+//!
+//! The content of the viewport is scaled by the `viewport-scale`, but the viewport position
+//! is not: the point `p` of the content is at the position `viewport-pos + p * viewport-scale`
+//! in the Flickable.
 
 use core::time::Duration;
 
 use crate::animations::EasingCurve;
 use crate::animations::Instant;
 use crate::graphics::Point;
-use crate::input::{InputEventFilterResult, InputEventResult, MouseEvent};
-use crate::items::PointerEventButton;
+use crate::input::{InputEventFilterResult, InputEventResult, MouseEvent, TouchPhase};
+use crate::items::{BoundsBehavior, PointerEventButton};
 use crate::items::{Flickable, PropertyAnimation, Rectangle};
-use core::cell::RefCell;
+use crate::Property;
+use core::cell::{Cell, RefCell};
 use core::pin::Pin;
+use euclid::default::Vector2D;
+#[cfg(not(feature = "std"))]
+use num_traits::float::Float;
 
 /// The distance required before it starts flicking if there is another item intercepting the mouse.
 /// FIXME: this is currently physical pixels, but it should be logical
 const DISTANCE_THRESHOLD: f32 = 4.;
 /// Time required before we stop caring about child event if the mouse hasn't been moved
 const DURATION_THRESHOLD: Duration = Duration::from_millis(500);
+/// If the mouse did not move for that long before it was released, the viewport is not flicked
+const VELOCITY_TIMEOUT: Duration = Duration::from_millis(100);
+/// The maximum distance, in logical pixels, that the viewport can go past its bounds
+const MAX_OVERSHOOT: f32 = 100.;
+/// How many times stronger the deceleration is when the viewport went past its bounds
+const OVERSHOOT_DECELERATION_FACTOR: f32 = 10.;
+/// The duration, in milliseconds, of the bounce back to the bounds
+const BOUNCE_DURATION: f32 = 300.;
+/// The duration, in milliseconds, of the animation of `scroll-to`
+const SCROLL_TO_DURATION: i32 = 250;
+/// The distance, in logical pixels, that the mouse wheel scrolls to double the scale
+const WHEEL_ZOOM_STEP: f32 = 600.;
+
+/// The state of a zoom made with two fingers
+#[derive(Debug, Clone, Copy)]
+struct Pinch {
+    /// The id of the second finger
+    id: u64,
+    /// The current position of the second finger
+    pos: Point,
+    /// The distance between the fingers when the second one touched the screen
+    start_distance: f32,
+    start_scale: f32,
+    /// The point of the content that was between the fingers when the second one touched the
+    /// screen, which stays between them
+    content_anchor: Point,
+}
 
 #[derive(Default, Debug)]
 struct FlickableDataInner {
@@ -34,6 +74,14 @@ struct FlickableDataInner {
     pressed_viewport_pos: Point,
     /// Set to true if the flickable is flicking and capturing all mouse event, not forwarding back to the children
     capture_events: bool,
+    /// The position and the time of the last mouse move, used to compute the velocity
+    last_move_pos: Point,
+    last_move_time: Instant,
+    /// The velocity of the mouse, in logical pixels per millisecond
+    velocity: Vector2D<f32>,
+    /// The current position of the mouse, or of the first finger
+    primary_pos: Point,
+    pinch: Option<Pinch>,
 }
 
 #[derive(Default, Debug)]
@@ -49,17 +97,20 @@ pub fn handle_mouse_filter(
     ) -> InputEventFilterResult {
         let mut inner = self.inner.borrow_mut();
         match event {
-            MouseEvent::MousePressed { pos, button: PointerEventButton::left } => {
+            MouseEvent::MousePressed { pos, button: PointerEventButton::left, .. } => {
+                if flick.is_flicking() {
+                    // A press stops the flick, and is not forwarded to the children
+                    stop_flick(flick);
+                    inner.capture_events = true;
+                }
+                let now = crate::animations::current_tick();
                 inner.pressed_pos = pos;
-                inner.pressed_time = Some(crate::animations::current_tick());
-                inner.pressed_viewport_pos = Point::new(
-                    (Flickable::FIELD_OFFSETS.viewport + Rectangle::FIELD_OFFSETS.x)
-                        .apply_pin(flick)
-                        .get(),
-                    (Flickable::FIELD_OFFSETS.viewport + Rectangle::FIELD_OFFSETS.y)
-                        .apply_pin(flick)
-                        .get(),
-                );
+                inner.pressed_time = Some(now);
+                inner.pressed_viewport_pos = viewport_pos(flick);
+                inner.last_move_pos = pos;
+                inner.last_move_time = now;
+                inner.velocity = Vector2D::zero();
+                inner.primary_pos = pos;
                 if inner.capture_events {
                     InputEventFilterResult::Intercept
                 } else {
@@ -77,6 +128,11 @@ pub fn handle_mouse_filter(
                 }
             }
             MouseEvent::MouseMoved { pos } => {
+                inner.primary_pos = pos;
+                if inner.pinch.is_some() {
+                    Self::update_pinch(&inner, flick);
+                    return InputEventFilterResult::Intercept;
+                }
                 let do_intercept = inner.capture_events
                     || inner.pressed_time.map_or(false, |pressed_time| {
                         crate::animations::current_tick() - pressed_time < DURATION_THRESHOLD
@@ -96,6 +152,17 @@ pub fn handle_mouse_filter(
             MouseEvent::MousePressed { .. } | MouseEvent::MouseReleased { .. } => {
                 InputEventFilterResult::ForwardAndIgnore
             }
+            MouseEvent::TouchPoint { id, pos, phase } => {
+                if Self::touch_point(&mut inner, flick, id, pos, phase) {
+                    // Take the grab from the children when the zoom starts
+                    InputEventFilterResult::Intercept
+                } else {
+                    InputEventFilterResult::ForwardAndIgnore
+                }
+            }
+            MouseEvent::DragMove { .. } | MouseEvent::Drop { .. } => {
+                InputEventFilterResult::ForwardAndIgnore
+            }
         }
     }
 
@@ -110,76 +177,185 @@ pub fn handle_mouse(&self, flick: Pin<&Flickable>, event: MouseEvent) -> InputEv
                 Self::mouse_released(&mut inner, flick, event);
                 InputEventResult::EventAccepted
             }
+            // The filter is not called when the fingers are outside of the Flickable
+            MouseEvent::MouseMoved { pos } if inner.pinch.is_some() => {
+                inner.primary_pos = pos;
+                Self::update_pinch(&inner, flick);
+                InputEventResult::GrabMouse
+            }
             MouseEvent::MouseMoved { pos } => {
                 if inner.pressed_time.is_some() {
                     inner.capture_events = true;
-                    let new_pos = ensure_in_bound(
+                    Self::track_velocity(&mut inner, pos);
+                    let new_pos = drag_position(
                         flick,
                         inner.pressed_viewport_pos + (pos - inner.pressed_pos),
                     );
-                    (Flickable::FIELD_OFFSETS.viewport + Rectangle::FIELD_OFFSETS.x)
-                        .apply_pin(flick)
-                        .set(new_pos.x);
-                    (Flickable::FIELD_OFFSETS.viewport + Rectangle::FIELD_OFFSETS.y)
-                        .apply_pin(flick)
-                        .set(new_pos.y);
+                    viewport_x(flick).set(new_pos.x);
+                    viewport_y(flick).set(new_pos.y);
                     InputEventResult::GrabMouse
                 } else {
                     inner.capture_events = false;
                     InputEventResult::EventIgnored
                 }
             }
+            MouseEvent::MouseWheel { pos, delta, modifiers }
+                if modifiers.control && zoom_enabled(flick) =>
+            {
+                stop_flick(flick);
+                let scale = flick.viewport_scale() * (delta.y / WHEEL_ZOOM_STEP).exp2();
+                zoom(flick, scale, content_position(flick, pos), pos);
+                InputEventResult::EventAccepted
+            }
             MouseEvent::MouseWheel { delta, .. } => {
-                let old_pos = Point::new(
-                    (Flickable::FIELD_OFFSETS.viewport + Rectangle::FIELD_OFFSETS.x)
-                        .apply_pin(flick)
-                        .get(),
-                    (Flickable::FIELD_OFFSETS.viewport + Rectangle::FIELD_OFFSETS.y)
-                        .apply_pin(flick)
-                        .get(),
-                );
-                let new_pos = ensure_in_bound(flick, old_pos + delta.to_vector());
-                (Flickable::FIELD_OFFSETS.viewport + Rectangle::FIELD_OFFSETS.x)
-                    .apply_pin(flick)
-                    .set(new_pos.x);
-                (Flickable::FIELD_OFFSETS.viewport + Rectangle::FIELD_OFFSETS.y)
-                    .apply_pin(flick)
-                    .set(new_pos.y);
+                stop_flick(flick);
+                let new_pos = ensure_in_bound(flick, viewport_pos(flick) + delta.to_vector());
+                viewport_x(flick).set(new_pos.x);
+                viewport_y(flick).set(new_pos.y);
                 InputEventResult::EventAccepted
             }
+            MouseEvent::TouchPoint { id, pos, phase } => {
+                if Self::touch_point(&mut inner, flick, id, pos, phase) {
+                    InputEventResult::GrabMouse
+                } else {
+                    InputEventResult::EventIgnored
+                }
+            }
+            MouseEvent::DragMove { .. } | MouseEvent::Drop { .. } => InputEventResult::EventIgnored,
         }
     }
 
+    /// Handles the other fingers on a touch screen, to zoom with two fingers. Returns true if
+    /// the event is part of a zoom.
+    fn touch_point(
+        inner: &mut FlickableDataInner,
+        flick: Pin<&Flickable>,
+        id: u64,
+        pos: Point,
+        phase: TouchPhase,
+    ) -> bool {
+        match inner.pinch {
+            None if phase == TouchPhase::started
+                && inner.pressed_time.is_some()
+                && zoom_enabled(flick) =>
+            {
+                stop_flick(flick);
+                let center = inner.primary_pos.lerp(pos, 0.5);
+                inner.pinch = Some(Pinch {
+                    id,
+                    pos,
+                    start_distance: (pos - inner.primary_pos).length(),
+                    start_scale: flick.viewport_scale(),
+                    content_anchor: content_position(flick, center),
+                });
+                inner.capture_events = true;
+                true
+            }
+            Some(pinch) if pinch.id == id => {
+                match phase {
+                    TouchPhase::started => {}
+                    TouchPhase::moved => {
+                        inner.pinch = Some(Pinch { pos, ..pinch });
+                        Self::update_pinch(inner, flick);
+                    }
+                    TouchPhase::ended | TouchPhase::cancelled => {
+                        // The drag continues with the first finger
+                        inner.pinch = None;
+                        inner.pressed_pos = inner.primary_pos;
+                        inner.pressed_viewport_pos = viewport_pos(flick);
+                        inner.last_move_pos = inner.primary_pos;
+                        inner.last_move_time = crate::animations::current_tick();
+                        inner.velocity = Vector2D::zero();
+                    }
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Computes the scale from the distance between the two fingers
+    fn update_pinch(inner: &FlickableDataInner, flick: Pin<&Flickable>) {
+        if let Some(pinch) = inner.pinch {
+            let distance = (pinch.pos - inner.primary_pos).length();
+            let scale = if pinch.start_distance > 0. {
+                pinch.start_scale * distance / pinch.start_distance
+            } else {
+                pinch.start_scale
+            };
+            zoom(flick, scale, pinch.content_anchor, inner.primary_pos.lerp(pinch.pos, 0.5));
+        }
+    }
+
+    /// Updates the velocity with a new position of the mouse. The velocity is smoothed to be
+    /// less sensitive to the irregularity of the mouse events.
+    fn track_velocity(inner: &mut FlickableDataInner, pos: Point) {
+        let now = crate::animations::current_tick();
+        let elapsed = now - inner.last_move_time;
+        if elapsed.as_millis() == 0 {
+            return;
+        }
+        let velocity = (pos - inner.last_move_pos) / elapsed.as_millis() as f32;
+        inner.velocity = if elapsed > VELOCITY_TIMEOUT {
+            velocity
+        } else {
+            velocity * 0.8 + inner.velocity * 0.2
+        };
+        inner.last_move_pos = pos;
+        inner.last_move_time = now;
+    }
+
     fn mouse_released(inner: &mut FlickableDataInner, flick: Pin<&Flickable>, event: MouseEvent) {
-        if let (Some(pressed_time), Some(pos)) = (inner.pressed_time, event.pos()) {
-            let dist = pos - inner.pressed_pos;
-            let speed =
-                dist / ((crate::animations::current_tick() - pressed_time).as_millis() as f32);
-
-            let duration = 100;
-            let final_pos = ensure_in_bound(
-                flick,
-                inner.pressed_viewport_pos + dist + speed * (duration as f32),
-            );
+        if inner.pressed_time.is_some() {
+            let moved_recently =
+                crate::animations::current_tick() - inner.last_move_time <= VELOCITY_TIMEOUT;
+            let velocity = if moved_recently && event.pos().is_some() && inner.pinch.is_none() {
+                inner.velocity
+            } else {
+                Vector2D::zero()
+            };
+            start_flick(flick, velocity);
+        }
+        inner.capture_events = false;
+        inner.pressed_time = None;
+        inner.pinch = None;
+    }
+
+    /// Moves the viewport so that the point `pos` of the viewport is at the top left of the
+    /// Flickable, within the bounds.
+    pub fn scroll_to(&self, flick: Pin<&Flickable>, pos: Point, animated: bool) {
+        stop_flick(flick);
+        let new_pos =
+            ensure_in_bound(flick, (pos.to_vector() * -flick.viewport_scale()).to_point());
+        if animated {
             let anim = PropertyAnimation {
-                duration,
+                duration: SCROLL_TO_DURATION,
                 easing: EasingCurve::CubicBezier([0.0, 0.0, 0.58, 1.0]),
                 ..PropertyAnimation::default()
             };
-            (Flickable::FIELD_OFFSETS.viewport + Rectangle::FIELD_OFFSETS.x)
-                .apply_pin(flick)
-                .set_animated_value(final_pos.x, anim.clone());
-            (Flickable::FIELD_OFFSETS.viewport + Rectangle::FIELD_OFFSETS.y)
-                .apply_pin(flick)
-                .set_animated_value(final_pos.y, anim);
-        }
-        inner.capture_events = false; // FIXME: should only be set to false once the flick animation is over
-        inner.pressed_time = None;
+            viewport_x(flick).set_animated_value(new_pos.x, anim.clone());
+            viewport_y(flick).set_animated_value(new_pos.y, anim);
+        } else {
+            viewport_x(flick).set(new_pos.x);
+            viewport_y(flick).set(new_pos.y);
+        }
     }
 }
 
-/// Make sure that the point is within the bounds
-fn ensure_in_bound(flick: Pin<&Flickable>, p: Point) -> Point {
+fn viewport_x(flick: Pin<&Flickable>) -> Pin<&Property<f32>> {
+    (Flickable::FIELD_OFFSETS.viewport + Rectangle::FIELD_OFFSETS.x).apply_pin(flick)
+}
+
+fn viewport_y(flick: Pin<&Flickable>) -> Pin<&Property<f32>> {
+    (Flickable::FIELD_OFFSETS.viewport + Rectangle::FIELD_OFFSETS.y).apply_pin(flick)
+}
+
+fn viewport_pos(flick: Pin<&Flickable>) -> Point {
+    Point::new(viewport_x(flick).get(), viewport_y(flick).get())
+}
+
+/// The minimum and the maximum position of the viewport
+fn bounds(flick: Pin<&Flickable>) -> (Point, Point) {
     let w = flick.width();
     let h = flick.height();
     let vw =
@@ -187,8 +363,235 @@ fn ensure_in_bound(flick: Pin<&Flickable>, p: Point) -> Point {
     let vh = (Flickable::FIELD_OFFSETS.viewport + Rectangle::FIELD_OFFSETS.height)
         .apply_pin(flick)
         .get();
-
-    let min = Point::new(w - vw, h - vh);
+    let scale = flick.viewport_scale();
     let max = Point::new(0., 0.);
+    (Point::new(w - vw * scale, h - vh * scale).min(max), max)
+}
+
+fn zoom_enabled(flick: Pin<&Flickable>) -> bool {
+    flick.min_viewport_scale() < flick.max_viewport_scale()
+}
+
+/// Returns the point of the content of the viewport that is at the position `pos` of the Flickable
+fn content_position(flick: Pin<&Flickable>, pos: Point) -> Point {
+    ((pos - viewport_pos(flick)) / flick.viewport_scale()).to_point()
+}
+
+/// Sets the scale of the content, within the limits, and moves the viewport so that the point
+/// `content_pos` of the content is at the position `pos` of the Flickable, within the bounds.
+fn zoom(flick: Pin<&Flickable>, scale: f32, content_pos: Point, pos: Point) {
+    let scale = scale.max(flick.min_viewport_scale()).min(flick.max_viewport_scale());
+    Flickable::FIELD_OFFSETS.viewport_scale.apply_pin(flick).set(scale);
+    let new_pos = ensure_in_bound(flick, pos - content_pos.to_vector() * scale);
+    viewport_x(flick).set(new_pos.x);
+    viewport_y(flick).set(new_pos.y);
+}
+
+/// Make sure that the point is within the bounds
+fn ensure_in_bound(flick: Pin<&Flickable>, p: Point) -> Point {
+    let (min, max) = bounds(flick);
     p.max(min).min(max)
 }
+
+/// Returns the position of the viewport while it is dragged to `p`: with the `overshoot-and-bounce`
+/// behavior, it follows the mouse past the bounds at half the speed.
+fn drag_position(flick: Pin<&Flickable>, p: Point) -> Point {
+    let bounded = ensure_in_bound(flick, p);
+    if flick.bounds_behavior() != BoundsBehavior::overshoot_and_bounce {
+        return bounded;
+    }
+    let overshoot = |p: f32, bounded: f32| {
+        let d = (p - bounded) / 2.;
+        bounded + d.max(-MAX_OVERSHOOT).min(MAX_OVERSHOOT)
+    };
+    Point::new(overshoot(p.x, bounded.x), overshoot(p.y, bounded.y))
+}
+
+/// Starts moving the viewport with the given velocity, in logical pixels per millisecond, until
+/// the deceleration stops it. If the viewport is past its bounds, it goes back to them.
+fn start_flick(flick: Pin<&Flickable>, velocity: Vector2D<f32>) {
+    let (min, max) = bounds(flick);
+    let start = viewport_pos(flick);
+    let overshoot = flick.bounds_behavior() == BoundsBehavior::overshoot_and_bounce;
+    // in logical pixels per millisecond squared
+    let deceleration = flick.flick_deceleration() / 1_000_000.;
+    let axis = |start, velocity, min, max| FlickAxis {
+        start,
+        velocity,
+        deceleration,
+        min,
+        max,
+        overshoot,
+    };
+    let x = axis(start.x, velocity.x, min.x, max.x);
+    let y = axis(start.y, velocity.y, min.y, max.y);
+    if x.position(0.).1 && y.position(0.).1 {
+        return;
+    }
+
+    let start_time = crate::animations::current_tick();
+    viewport_x(flick).set_binding(flick_binding(x, start_time));
+    viewport_y(flick).set_binding(flick_binding(y, start_time));
+    let finished = Cell::new(false);
+    Flickable::FIELD_OFFSETS.is_flicking.apply_pin(flick).set_binding(move || {
+        if !finished.get() {
+            let t = elapsed_millis(start_time);
+            finished.set(x.position(t).1 && y.position(t).1);
+        }
+        !finished.get()
+    });
+}
+
+/// Stops the flick in progress, leaving the viewport at its current position
+fn stop_flick(flick: Pin<&Flickable>) {
+    if flick.is_flicking() {
+        let pos = viewport_pos(flick);
+        viewport_x(flick).set(pos.x);
+        viewport_y(flick).set(pos.y);
+    }
+    Flickable::FIELD_OFFSETS.is_flicking.apply_pin(flick).set(false);
+}
+
+fn elapsed_millis(start_time: Instant) -> f32 {
+    (crate::animations::current_tick() - start_time).as_millis() as f32
+}
+
+/// Returns a binding for the position of the viewport along an axis that follows the motion
+/// while the animation driver advances, and becomes constant when the motion is over.
+fn flick_binding(axis: FlickAxis, start_time: Instant) -> impl Fn() -> f32 {
+    let final_pos = Cell::new(None);
+    move || {
+        if let Some(pos) = final_pos.get() {
+            return pos;
+        }
+        let (pos, finished) = axis.position(elapsed_millis(start_time));
+        if finished {
+            final_pos.set(Some(pos));
+        } else {
+            crate::animations::CURRENT_ANIMATION_DRIVER
+                .with(|driver| driver.set_has_active_animations());
+        }
+        pos
+    }
+}
+
+/// The motion of the viewport along one axis after it was flicked
+#[derive(Debug, Clone, Copy)]
+struct FlickAxis {
+    /// The position of the viewport when the mouse was released
+    start: f32,
+    /// The velocity in logical pixels per millisecond
+    velocity: f32,
+    /// The deceleration in logical pixels per millisecond squared
+    deceleration: f32,
+    min: f32,
+    max: f32,
+    /// true with the `overshoot-and-bounce` behavior
+    overshoot: bool,
+}
+
+impl FlickAxis {
+    /// Returns the position `t` milliseconds after the release, and whether the motion is over
+    fn position(&self, t: f32) -> (f32, bool) {
+        if self.start < self.min || self.start > self.max {
+            // Dragged past the bounds
+            return bounce(self.start, self.start.max(self.min).min(self.max), t);
+        }
+        let speed = self.velocity.abs();
+        let a = self.deceleration;
+        if speed == 0. || a <= 0. {
+            return (self.start, true);
+        }
+        let direction = self.velocity.signum();
+        let stop_time = speed / a;
+        let free_position = |t: f32| self.start + direction * (speed * t - a * t * t / 2.);
+        let bound = if direction > 0. { self.max } else { self.min };
+        let distance_to_bound = (bound - self.start).abs();
+        if speed * speed / (2. * a) <= distance_to_bound {
+            // Stops before reaching the bound
+            return if t >= stop_time {
+                (free_position(stop_time), true)
+            } else {
+                (free_position(t), false)
+            };
+        }
+        let hit_time = (speed - (speed * speed - 2. * a * distance_to_bound).max(0.).sqrt()) / a;
+        if t < hit_time {
+            return (free_position(t), false);
+        }
+        if !self.overshoot {
+            return (bound, true);
+        }
+        // Goes past the bound with a stronger deceleration, then bounces back
+        let hit_speed = speed - a * hit_time;
+        let overshoot_deceleration =
+            (a * OVERSHOOT_DECELERATION_FACTOR).max(hit_speed * hit_speed / (2. * MAX_OVERSHOOT));
+        let t = t - hit_time;
+        let peak_time = hit_speed / overshoot_deceleration;
+        if t < peak_time {
+            let d = hit_speed * t - overshoot_deceleration * t * t / 2.;
+            return (bound + direction * d, false);
+        }
+        let peak = bound + direction * hit_speed * hit_speed / (2. * overshoot_deceleration);
+        bounce(peak, bound, t - peak_time)
+    }
+}
+
+/// The position `t` milliseconds after the start of the bounce from `from` to `to`, and whether
+/// the bounce is over
+fn bounce(from: f32, to: f32, t: f32) -> (f32, bool) {
+    if t >= BOUNCE_DURATION {
+        return (to, true);
+    }
+    // ease out cubic
+    let progress = 1. - (1. - t / BOUNCE_DURATION).powi(3);
+    (from + (to - from) * progress, false)
+}
+
+#[test]
+fn flick_axis_decelerates() {
+    let axis = FlickAxis {
+        start: -100.,
+        velocity: -1.,
+        deceleration: 0.001,
+        min: -2000.,
+        max: 0.,
+        overshoot: false,
+    };
+    // Stops after 1000ms, 500px further
+    assert_eq!(axis.position(0.), (-100., false));
+    assert_eq!(axis.position(500.), (-100. - 375., false));
+    assert_eq!(axis.position(1000.), (-600., true));
+    assert_eq!(axis.position(2000.), (-600., true));
+
+    let still = FlickAxis { velocity: 0., ..axis };
+    assert_eq!(still.position(0.), (-100., true));
+}
+
+#[test]
+fn flick_axis_bounds() {
+    let axis = FlickAxis {
+        start: -100.,
+        velocity: 1.,
+        deceleration: 0.001,
+        min: -2000.,
+        max: 0.,
+        overshoot: false,
+    };
+    // Reaches the bound at 0 before it would have stopped
+    let (pos, finished) = axis.position(50.);
+    assert!(pos < 0. && !finished);
+    assert_eq!(axis.position(200.), (0., true));
+
+    let axis = FlickAxis { overshoot: true, ..axis };
+    let (pos, finished) = axis.position(200.);
+    assert!(pos > 0. && pos <= MAX_OVERSHOOT && !finished);
+    assert_eq!(axis.position(2000.), (0., true));
+
+    // Dragged past the bound, bounces back
+    let axis = FlickAxis { start: 50., velocity: 0., ..axis };
+    assert_eq!(axis.position(0.), (50., false));
+    let (pos, finished) = axis.position(BOUNCE_DURATION / 2.);
+    assert!(pos > 0. && pos < 50. && !finished);
+    assert_eq!(axis.position(BOUNCE_DURATION), (0., true));
+}
@@ -138,6 +138,14 @@ pub struct FontRequest {
     /// The additional spacing (or shrinking if negative) between glyphs. This is usually not submitted to
     /// the font-subsystem but collected here for API convenience
     pub letter_spacing: Option<f32>,
+    /// Whether an italic font should be used
+    pub italic: bool,
+    /// The distance between the top of two consecutive lines. If None, the height of the font
+    /// is used.
+    pub line_height: Option<f32>,
+    /// The line to draw with the text. Like the letter spacing, this is not submitted to the
+    /// font-subsystem.
+    pub text_decoration: crate::items::TextDecoration,
 }
 
 impl FontRequest {
@@ -149,6 +157,9 @@ pub fn merge(self, other: &FontRequest) -> Self {
             weight: self.weight.or(other.weight),
             pixel_size: self.pixel_size.or(other.pixel_size),
             letter_spacing: self.letter_spacing.or(other.letter_spacing),
+            italic: self.italic,
+            line_height: self.line_height.or(other.line_height),
+            text_decoration: self.text_decoration,
         }
     }
 }
@@ -11,7 +11,7 @@
 This module contains brush related types for the run-time library.
 */
 
-use super::{Color, Point};
+use super::{Color, Point, Size};
 use crate::properties::InterpolatedPropertyValue;
 use crate::SharedVector;
 
@@ -31,6 +31,12 @@ pub enum Brush {
     /// The linear gradient variant of a brush describes the gradient stops for a fill
     /// where all color stops are along a line that's rotated by the specified angle.
     LinearGradient(LinearGradientBrush),
+    /// The radial gradient variant of a brush describes the gradient stops for a fill
+    /// where all color stops are on circles around the center of the shape.
+    RadialGradient(RadialGradientBrush),
+    /// The conic gradient variant of a brush describes the gradient stops for a fill
+    /// where all color stops are rotated around the center of the shape.
+    ConicGradient(ConicGradientBrush),
 }
 
 /// Construct a brush with transparent color
@@ -42,13 +48,19 @@ fn default() -> Self {
 
 impl Brush {
     /// If the brush is SolidColor, the contained color is returned.
-    /// If the brush is a LinearGradient, the color of the first stop is returned.
+    /// If the brush is a gradient, the color of the first stop is returned.
     pub fn color(&self) -> Color {
         match self {
             Brush::SolidColor(col) => *col,
             Brush::LinearGradient(gradient) => {
                 gradient.stops().next().map(|stop| stop.color).unwrap_or_default()
             }
+            Brush::RadialGradient(gradient) => {
+                gradient.stops().next().map(|stop| stop.color).unwrap_or_default()
+            }
+            Brush::ConicGradient(gradient) => {
+                gradient.stops().next().map(|stop| stop.color).unwrap_or_default()
+            }
         }
     }
 
@@ -64,6 +76,57 @@ pub fn is_transparent(&self) -> bool {
         match self {
             Brush::SolidColor(c) => c.alpha() == 0,
             Brush::LinearGradient(_) => false,
+            Brush::RadialGradient(_) => false,
+            Brush::ConicGradient(_) => false,
+        }
+    }
+
+    /// Returns the color of the brush at the given point of a shape of the given size, where
+    /// (0, 0) is the top left corner of the shape's bounding box.
+    ///
+    /// This is meant for the renderers that compute the color of each pixel themselves.
+    pub fn color_at(&self, point: Point, size: Size) -> Color {
+        let center = Point::new(size.width / 2., size.height / 2.);
+        match self {
+            Brush::SolidColor(col) => *col,
+            Brush::LinearGradient(gradient) => {
+                let (start, end) = line_for_angle(gradient.angle());
+                let start = Point::new(start.x * size.width, start.y * size.height);
+                let line = Point::new(end.x * size.width, end.y * size.height) - start;
+                let square_length = line.square_length();
+                if square_length > 0. {
+                    gradient.color_at((point - start).dot(line) / square_length)
+                } else {
+                    gradient.color_at(0.)
+                }
+            }
+            Brush::RadialGradient(gradient) => {
+                // The stop at position 1 is on the circle that goes through the corners
+                let radius = center.to_vector().square_length().sqrt();
+                let distance = (point - center).square_length().sqrt();
+                gradient.color_at(if radius > 0. { distance / radius } else { 0. })
+            }
+            Brush::ConicGradient(gradient) => {
+                let full_turn = 2. * core::f32::consts::PI;
+                let d = point - center;
+                // Angle from the top, going clockwise
+                let mut angle = d.x.atan2(-d.y);
+                if angle < 0. {
+                    angle += full_turn;
+                }
+                gradient.color_at(angle / full_turn)
+            }
+        }
+    }
+
+    /// Returns the color stops of the gradient, or an empty slice for a solid color.
+    fn stops_mut(&mut self) -> &mut [GradientStop] {
+        match self {
+            Brush::SolidColor(_) => &mut [],
+            // skip the first fake stop that just contains the angle
+            Brush::LinearGradient(gradient) => &mut gradient.0.make_mut_slice()[1..],
+            Brush::RadialGradient(gradient) => gradient.0.make_mut_slice(),
+            Brush::ConicGradient(gradient) => gradient.0.make_mut_slice(),
         }
     }
 }
@@ -98,6 +161,89 @@ pub fn stops(&self) -> impl Iterator<Item = &GradientStop> {
         // skip the first fake stop that just contains the angle
         self.0.iter().skip(1)
     }
+    /// Returns the color at the given position on the gradient line, where 0 is the start
+    /// and 1 is the end of the line.
+    pub fn color_at(&self, position: f32) -> Color {
+        color_at_position(self.stops(), position)
+    }
+}
+
+/// The RadialGradientBrush describes a way of filling a shape with different colors, which
+/// are interpolated between different stops. The colors are on circles around the center of
+/// the shape. The stop at position 0 is at the center, and the stop at position 1 is on the
+/// circle that goes through the corners of the shape's bounding box.
+#[derive(Clone, PartialEq, Debug)]
+#[repr(transparent)]
+pub struct RadialGradientBrush(SharedVector<GradientStop>);
+
+impl RadialGradientBrush {
+    /// Creates a new circular radial gradient with the provided color stops.
+    ///
+    /// The stops must be sorted by position.
+    pub fn new_circle(stops: impl IntoIterator<Item = GradientStop>) -> Self {
+        Self(stops.into_iter().collect())
+    }
+    /// Returns the color stops of the radial gradient.
+    pub fn stops(&self) -> impl Iterator<Item = &GradientStop> {
+        self.0.iter()
+    }
+    /// Returns the color at the given distance from the center, where 1 is the distance
+    /// between the center and the corners of the shape.
+    pub fn color_at(&self, position: f32) -> Color {
+        color_at_position(self.stops(), position)
+    }
+}
+
+/// The ConicGradientBrush describes a way of filling a shape with different colors, which
+/// are interpolated between different stops. The colors are rotated around the center of
+/// the shape, starting at the top and going clockwise: the stop at position 0.25 is on the
+/// right of the center and the stop at position 1 is back on the top.
+#[derive(Clone, PartialEq, Debug)]
+#[repr(transparent)]
+pub struct ConicGradientBrush(SharedVector<GradientStop>);
+
+impl ConicGradientBrush {
+    /// Creates a new conic gradient with the provided color stops.
+    ///
+    /// The stops must be sorted by position.
+    pub fn new(stops: impl IntoIterator<Item = GradientStop>) -> Self {
+        Self(stops.into_iter().collect())
+    }
+    /// Returns the color stops of the conic gradient.
+    pub fn stops(&self) -> impl Iterator<Item = &GradientStop> {
+        self.0.iter()
+    }
+    /// Returns the color at the given position around the center, where 0 and 1 are
+    /// at the top of the center.
+    pub fn color_at(&self, position: f32) -> Color {
+        color_at_position(self.stops(), position)
+    }
+}
+
+/// Returns the color at the given position by interpolating between the two surrounding stops.
+/// Positions before the first stop or after the last stop get the color of that stop.
+fn color_at_position<'a>(
+    mut stops: impl Iterator<Item = &'a GradientStop>,
+    position: f32,
+) -> Color {
+    let mut previous = match stops.next() {
+        Some(stop) => stop,
+        None => return Color::default(),
+    };
+    if position <= previous.position {
+        return previous.color;
+    }
+    for stop in stops {
+        if position <= stop.position {
+            let range = stop.position - previous.position;
+            if range <= 0. {
+                return stop.color;
+            }
+            return previous.color.interpolate(&stop.color, (position - previous.position) / range);
+        }
+        previous = stop;
+    }
+    previous.color
 }
 
 /// GradientStop describes a single color stop in a gradient. The colors between multiple
@@ -128,41 +274,58 @@ fn interpolate(&self, target_value: &Self, t: f32) -> Self {
             (Brush::SolidColor(source_col), Brush::SolidColor(target_col)) => {
                 Brush::SolidColor(source_col.interpolate(target_col, t))
             }
-            (Brush::SolidColor(col), Brush::LinearGradient(grad)) => {
-                let mut new_grad = grad.clone();
-                for x in new_grad.0.make_mut_slice().iter_mut().skip(1) {
+            (Brush::SolidColor(col), gradient) => {
+                let mut new_grad = gradient.clone();
+                for x in new_grad.stops_mut() {
                     x.color = col.interpolate(&x.color, t);
                 }
-                Brush::LinearGradient(new_grad)
-            }
-            (a @ Brush::LinearGradient(_), b @ Brush::SolidColor(_)) => {
-                Self::interpolate(b, a, 1. - t)
+                new_grad
             }
+            (a, b @ Brush::SolidColor(_)) => Self::interpolate(b, a, 1. - t),
             (Brush::LinearGradient(lhs), Brush::LinearGradient(rhs)) => {
-                if lhs.0.len() < rhs.0.len() {
-                    Self::interpolate(target_value, self, 1. - t)
+                // The fake first stop is interpolated as well, which interpolates the angle.
+                Brush::LinearGradient(LinearGradientBrush(interpolate_stops(&lhs.0, &rhs.0, t)))
+            }
+            (Brush::RadialGradient(lhs), Brush::RadialGradient(rhs)) => {
+                Brush::RadialGradient(RadialGradientBrush(interpolate_stops(&lhs.0, &rhs.0, t)))
+            }
+            (Brush::ConicGradient(lhs), Brush::ConicGradient(rhs)) => {
+                Brush::ConicGradient(ConicGradientBrush(interpolate_stops(&lhs.0, &rhs.0, t)))
+            }
+            // Gradients of different kinds cannot be interpolated
+            (a, b) => {
+                if t < 0.5 {
+                    a.clone()
                 } else {
-                    let mut new_grad = lhs.clone();
-                    let mut iter = new_grad.0.make_mut_slice().iter_mut();
-                    {
-                        let angle = &mut iter.next().unwrap().position;
-                        *angle = angle.interpolate(&rhs.angle(), t);
-                    }
-                    let mut rhs_stops = rhs.stops();
-                    while let (Some(s1), Some(s2)) = (iter.next(), rhs_stops.next()) {
-                        s1.color = s1.color.interpolate(&s2.color, t);
-                        s1.position = s1.position.interpolate(&s2.position, t);
-                    }
-                    for x in iter {
-                        x.position = x.position.interpolate(&1.0, t);
-                    }
-                    Brush::LinearGradient(new_grad)
+                    b.clone()
                 }
             }
         }
     }
 }
 
+/// Interpolates the stops of two gradients of the same kind. The extra stops of the gradient
+/// that has more stops move to the end.
+fn interpolate_stops(
+    lhs: &SharedVector<GradientStop>,
+    rhs: &SharedVector<GradientStop>,
+    t: f32,
+) -> SharedVector<GradientStop> {
+    if lhs.len() < rhs.len() {
+        return interpolate_stops(rhs, lhs, 1. - t);
+    }
+    let mut result = lhs.clone();
+    let mut iter = result.make_mut_slice().iter_mut();
+    for (s2, s1) in rhs.iter().zip(&mut iter) {
+        s1.color = s1.color.interpolate(&s2.color, t);
+        s1.position = s1.position.interpolate(&s2.position, t);
+    }
+    for x in iter {
+        x.position = x.position.interpolate(&1.0, t);
+    }
+    result
+}
+
 #[test]
 #[allow(clippy::float_cmp)] // We want bit-wise equality here
 fn test_linear_gradient_encoding() {
@@ -176,3 +339,65 @@ fn test_linear_gradient_encoding() {
     assert_eq!(grad.angle(), 256.);
     assert!(grad.stops().eq(stops.iter()));
 }
+
+#[test]
+fn test_gradient_color_at() {
+    let red = Color::from_argb_u8(255, 255, 0, 0);
+    let blue = Color::from_argb_u8(255, 0, 0, 255);
+    let grad = RadialGradientBrush::new_circle([
+        GradientStop { position: 0.25, color: red },
+        GradientStop { position: 0.75, color: blue },
+    ]);
+    assert_eq!(grad.color_at(0.), red);
+    assert_eq!(grad.color_at(0.25), red);
+    assert_eq!(grad.color_at(0.5), red.interpolate(&blue, 0.5));
+    assert_eq!(grad.color_at(0.75), blue);
+    assert_eq!(grad.color_at(1.), blue);
+    assert_eq!(ConicGradientBrush::new([]).color_at(0.5), Color::default());
+}
+
+#[test]
+#[allow(clippy::float_cmp)] // We want bit-wise equality here
+fn test_gradient_interpolation() {
+    let red = Color::from_argb_u8(255, 255, 0, 0);
+    let blue = Color::from_argb_u8(255, 0, 0, 255);
+    let a = Brush::ConicGradient(ConicGradientBrush::new([
+        GradientStop { position: 0., color: red },
+        GradientStop { position: 1., color: red },
+    ]));
+    let b = Brush::ConicGradient(ConicGradientBrush::new([
+        GradientStop { position: 0., color: blue },
+        GradientStop { position: 0.5, color: blue },
+        GradientStop { position: 0.5, color: red },
+    ]));
+    match a.interpolate(&b, 0.5) {
+        Brush::ConicGradient(g) => {
+            let stops = g.stops().collect::<Vec<_>>();
+            assert_eq!(stops.len(), 3);
+            assert_eq!(stops[0].color, blue.interpolate(&red, 0.5));
+            assert_eq!(stops[1].position, 0.75);
+            assert_eq!(stops[2].position, 0.75);
+        }
+        _ => panic!("interpolating two conic gradients should give a conic gradient"),
+    }
+    assert_eq!(Brush::SolidColor(blue).interpolate(&a, 1.), a);
+}
+
+#[test]
+fn test_brush_color_at() {
+    let red = Color::from_argb_u8(255, 255, 0, 0);
+    let blue = Color::from_argb_u8(255, 0, 0, 255);
+    let stops =
+        [GradientStop { position: 0., color: red }, GradientStop { position: 1., color: blue }];
+    let size = Size::new(100., 50.);
+    let linear = Brush::LinearGradient(LinearGradientBrush::new(90., stops.iter().cloned()));
+    assert_eq!(linear.color_at(Point::new(-10., 25.), size), red);
+    assert_eq!(linear.color_at(Point::new(50., 10.), size), red.interpolate(&blue, 0.5));
+    assert_eq!(linear.color_at(Point::new(110., 25.), size), blue);
+    let radial = Brush::RadialGradient(RadialGradientBrush::new_circle(stops.iter().cloned()));
+    assert_eq!(radial.color_at(Point::new(50., 25.), size), red);
+    assert_eq!(radial.color_at(Point::new(100., 50.), size), blue);
+    let conic = Brush::ConicGradient(ConicGradientBrush::new(stops.iter().cloned()));
+    assert_eq!(conic.color_at(Point::new(50., 0.), size), red);
+    assert_eq!(conic.color_at(Point::new(50., 50.), size), red.interpolate(&blue, 0.5));
+}
@@ -11,6 +11,7 @@
 */
 #![warn(missing_docs)]
 
+use crate::animations::Instant;
 use crate::graphics::Point;
 use crate::item_tree::{ItemVisitorResult, VisitChildrenResult};
 use crate::items::{ItemRc, ItemRef, ItemWeak, PointerEventButton};
@@ -22,14 +23,19 @@
 use const_field_offset::FieldOffsets;
 use core::pin::Pin;
 use euclid::default::Vector2D;
+#[cfg(not(feature = "std"))]
+use num_traits::float::Float;
 
 /// A Mouse event
 #[repr(C)]
 #[derive(Debug, Clone, Copy)]
 #[allow(missing_docs)]
 pub enum MouseEvent {
-    /// The mouse was pressed
-    MousePressed { pos: Point, button: PointerEventButton },
+    /// The mouse was pressed.
+    /// `click_count` is the number of presses of the button in quick succession at about the
+    /// same position: 1 for a simple click, 2 for a double click, 3 for a triple click, and so on.
+    /// It is computed by [`crate::window::Window::process_mouse_input`], so the backends set it to 0.
+    MousePressed { pos: Point, button: PointerEventButton, click_count: u8 },
     /// The mouse was released
     MouseReleased { pos: Point, button: PointerEventButton },
     /// The mouse position has changed
@@ -37,9 +43,20 @@ pub enum MouseEvent {
     /// Wheel was operated.
     /// `pos` is the position of the mouse when the event happens.
     /// `delta` is the amount of pixel to scroll.
-    MouseWheel { pos: Point, delta: Point },
+    /// `modifiers` are the keyboard modifiers pressed during the scroll.
+    MouseWheel { pos: Point, delta: Point, modifiers: KeyboardModifiers },
     /// The mouse exited the item or component
     MouseExit,
+    /// The mouse was moved while a drag and drop operation is in progress. The dragged data
+    /// is available with [`crate::window::Window::drag_data`].
+    DragMove { pos: Point },
+    /// The dragged data was dropped at the given position
+    Drop { pos: Point },
+    /// A finger other than the first one touched, moved on, or left a touch screen. The
+    /// first finger is sent as [`MouseEvent::MousePressed`], [`MouseEvent::MouseMoved`] and
+    /// [`MouseEvent::MouseReleased`] events with the left button, and these events are only
+    /// sent to the item that grabbed it.
+    TouchPoint { id: u64, pos: Point, phase: TouchPhase },
 }
 
 impl MouseEvent {
@@ -51,24 +68,89 @@ pub fn pos(&self) -> Option<Point> {
             MouseEvent::MouseMoved { pos } => Some(*pos),
             MouseEvent::MouseWheel { pos, .. } => Some(*pos),
             MouseEvent::MouseExit => None,
+            MouseEvent::DragMove { pos } => Some(*pos),
+            MouseEvent::Drop { pos } => Some(*pos),
+            MouseEvent::TouchPoint { pos, .. } => Some(*pos),
         }
     }
 
     /// Translate the position by the given value
     pub fn translate(&mut self, vec: Vector2D<f32>) {
+        self.map_pos(|pos| pos + vec)
+    }
+
+    /// Replaces the position by the result of `f`
+    pub(crate) fn map_pos(&mut self, f: impl FnOnce(Point) -> Point) {
         let pos = match self {
             MouseEvent::MousePressed { pos, .. } => Some(pos),
             MouseEvent::MouseReleased { pos, .. } => Some(pos),
             MouseEvent::MouseMoved { pos } => Some(pos),
             MouseEvent::MouseWheel { pos, .. } => Some(pos),
             MouseEvent::MouseExit => None,
+            MouseEvent::DragMove { pos } => Some(pos),
+            MouseEvent::Drop { pos } => Some(pos),
+            MouseEvent::TouchPoint { pos, .. } => Some(pos),
         };
         if let Some(pos) = pos {
-            *pos += vec;
+            *pos = f(*pos);
         }
     }
 }
 
+/// The phase of a touch point in a [`MouseEvent::TouchPoint`] event
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(non_camel_case_types)]
+pub enum TouchPhase {
+    /// The finger touched the screen
+    started,
+    /// The finger moved on the screen
+    moved,
+    /// The finger left the screen
+    ended,
+    /// The system cancelled the tracking of the finger
+    cancelled,
+}
+
+/// The maximum time between two mouse presses of a double or triple click
+const MULTI_CLICK_INTERVAL: core::time::Duration = core::time::Duration::from_millis(500);
+/// The maximum distance, in logical pixels, between two mouse presses of a double or triple click
+const MULTI_CLICK_DISTANCE: f32 = 5.;
+
+/// Counts the presses of a mouse button in quick succession, for the `click_count` of
+/// [`MouseEvent::MousePressed`]
+#[derive(Default)]
+pub(crate) struct ClickState {
+    /// The time, the position, the button and the click count of the last press
+    last_press: core::cell::Cell<Option<(Instant, Point, PointerEventButton, u8)>>,
+}
+
+impl ClickState {
+    /// Sets the `click_count` of the event if it is a [`MouseEvent::MousePressed`]
+    pub(crate) fn count_click(&self, event: &mut MouseEvent) {
+        if let MouseEvent::MousePressed { pos, button, click_count } = event {
+            let now = crate::animations::current_tick();
+            *click_count = match self.last_press.get() {
+                Some((time, last_pos, last_button, count))
+                    if last_button == *button
+                        && now >= time
+                        && now - time <= MULTI_CLICK_INTERVAL
+                        && (*pos - last_pos).length() <= MULTI_CLICK_DISTANCE =>
+                {
+                    count.saturating_add(1)
+                }
+                _ => 1,
+            };
+            self.last_press.set(Some((now, *pos, *button, *click_count)));
+        }
+    }
+
+    /// Forgets the previous presses
+    pub(crate) fn reset(&self) {
+        self.last_press.set(None);
+    }
+}
+
 /// This value is returned by the `input_event` function of an Item
 /// to notify the run-time about how the event was handled and
 /// what the next steps are.
@@ -154,6 +236,12 @@ pub enum KeyEventType {
     KeyPressed,
     /// A key on a keyboard was released.
     KeyReleased,
+    /// The input method updated the text being composed, called the preedit text. The `text`
+    /// of the event is the whole preedit text, which replaces the previous one.
+    UpdateComposition,
+    /// The input method finished the composition. The `text` of the event is the text to insert
+    /// in place of the preedit text.
+    CommitComposition,
 }
 
 impl Default for KeyEventType {
@@ -174,6 +262,14 @@ pub struct KeyEvent {
     // note: this field is not exported in the .60 in the KeyEvent builtin struct
     /// Indicates whether the key was pressed or released
     pub event_type: KeyEventType,
+
+    // note: these fields are not exported in the .60 in the KeyEvent builtin struct
+    /// For [`KeyEventType::UpdateComposition`], the byte offset in the preedit text where the
+    /// selection of the input method starts. It is the same as the end when there is only a cursor.
+    pub preedit_selection_start: usize,
+    /// For [`KeyEventType::UpdateComposition`], the byte offset in the preedit text where the
+    /// selection of the input method ends.
+    pub preedit_selection_end: usize,
 }
 
 /// Represents how an item's key_event handler dealt with a key event.
@@ -187,6 +283,17 @@ pub enum KeyEventResult {
     EventIgnored,
 }
 
+/// The data carried by a drag and drop operation, passed to the callbacks of the `DropArea`.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[repr(C)]
+pub struct DropEvent {
+    /// The mime type of the data, for example `text/plain`, or `text/uri-list` for files
+    /// dropped from other applications.
+    pub mime_type: SharedString,
+    /// The data being dragged
+    pub data: SharedString,
+}
+
 /// This event is sent to a component and items when they receive or loose
 /// the keyboard focus.
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -210,6 +317,9 @@ pub struct MouseInputState {
     item_stack: Vec<(ItemWeak, InputEventFilterResult)>,
     /// true if the top item of the stack has the mouse grab
     grabbed: bool,
+    /// The item that started the drag and drop operation in progress. While dragging, the
+    /// item stack contains the drop targets under the mouse instead of the grabber.
+    drag_source: Option<ItemWeak>,
 }
 
 /// Try to handle the mouse grabber. Return true if the event has handled, or false otherwise
@@ -225,6 +335,7 @@ fn handle_mouse_grab(
     let mut event = *mouse_event;
     let mut intercept = false;
     let mut invalid = false;
+    let mut parent: Option<ItemRc> = None;
 
     mouse_input_state.item_stack.retain(|it| {
         if invalid {
@@ -240,6 +351,10 @@ fn handle_mouse_grab(
             item.borrow().as_ref().input_event(MouseEvent::MouseExit, window, &item);
             return false;
         }
+        if let Some(parent) = parent.replace(item.clone()) {
+            // Behind a degenerate transform, the mouse is no longer over the grabber
+            event = map_to_children(parent.borrow(), event).unwrap_or(MouseEvent::MouseExit);
+        }
         let g = item.borrow().as_ref().geometry();
         event.translate(-g.origin.to_vector());
 
@@ -257,7 +372,10 @@ fn handle_mouse_grab(
 
     let grabber = mouse_input_state.item_stack.last().unwrap().0.upgrade().unwrap();
     let input_result = grabber.borrow().as_ref().input_event(event, window, &grabber);
-    if input_result != InputEventResult::GrabMouse {
+    // The other fingers of a touch screen never end the grab of the first one
+    if input_result != InputEventResult::GrabMouse
+        && !matches!(event, MouseEvent::TouchPoint { .. })
+    {
         mouse_input_state.grabbed = false;
         send_exit_events(&mouse_input_state, mouse_event.pos(), window);
     }
@@ -265,24 +383,108 @@ fn handle_mouse_grab(
     true
 }
 
-fn send_exit_events(
-    mouse_input_state: &MouseInputState,
-    mut pos: Option<Point>,
-    window: &WindowRc,
-) {
+fn send_exit_events(mouse_input_state: &MouseInputState, pos: Option<Point>, window: &WindowRc) {
+    // Only the position of the event matters
+    let mut event = pos.map(|pos| MouseEvent::MouseMoved { pos });
     for it in mouse_input_state.item_stack.iter() {
         let item = if let Some(item) = it.0.upgrade() { item } else { break };
         let g = item.borrow().as_ref().geometry();
-        let contains = pos.map_or(false, |p| g.contains(p));
-        if let Some(p) = pos.as_mut() {
-            *p -= g.origin.to_vector();
-        }
+        let contains =
+            event.map_or(false, |e| e.pos().map_or(false, |p| item_contains(item.borrow(), p)));
+        event = event.and_then(|mut event| {
+            event.translate(-g.origin.to_vector());
+            map_to_children(item.borrow(), event)
+        });
         if !contains {
             item.borrow().as_ref().input_event(MouseEvent::MouseExit, window, &item);
         }
     }
 }
 
+/// Maps the position of the event from the coordinates of the item to the coordinates of its
+/// children, for the items that transform their children.
+/// Returns None if the children cannot be reached because the transformation is degenerate.
+fn map_to_children(item: Pin<ItemRef>, mut event: MouseEvent) -> Option<MouseEvent> {
+    if let Some(flickable) = ItemRef::downcast_pin::<crate::items::Flickable>(item) {
+        event.map_pos(|pos| flickable.map_to_children(pos));
+    } else if let Some(transform) = ItemRef::downcast_pin::<crate::items::TransformItem>(item) {
+        if let Some(pos) = event.pos() {
+            let pos = transform.map_to_children(pos)?;
+            event.map_pos(|_| pos);
+        }
+    }
+    Some(event)
+}
+
+/// Returns true if the position, in the coordinates of the parent, is within the item.
+/// The position is first mapped through the transformation of a TransformItem, so that its
+/// children can be reached where they are drawn, even outside of its geometry.
+fn item_contains(item: Pin<ItemRef>, pos: Point) -> bool {
+    let geom = item.as_ref().geometry();
+    match ItemRef::downcast_pin::<crate::items::TransformItem>(item) {
+        Some(transform) => transform
+            .map_to_children(pos - geom.origin.to_vector())
+            .map_or(false, |pos| euclid::Rect::from_size(geom.size).contains(pos)),
+        None => geom.contains(pos),
+    }
+}
+
+/// Called when the grabber started a drag and drop operation with
+/// [`crate::window::Window::start_drag`]: it no longer receives the mouse events until the drop,
+/// they are sent to the drop targets under the mouse instead.
+fn start_drag_from_grabber(mouse_input_state: &mut MouseInputState, window: &WindowRc) {
+    let source = mouse_input_state.item_stack.pop().map(|it| it.0);
+    send_exit_events(mouse_input_state, None, window);
+    mouse_input_state.item_stack.clear();
+    mouse_input_state.grabbed = false;
+    mouse_input_state.drag_source = source;
+}
+
+/// Process a mouse event while a drag and drop operation is in progress: moves and release
+/// become [`MouseEvent::DragMove`] and [`MouseEvent::Drop`] events sent to the items under the
+/// mouse. The drag ends with the drop, or with a [`MouseEvent::MouseExit`], after which the
+/// source receives a [`MouseEvent::MouseExit`].
+fn process_drag_input(
+    component: ComponentRc,
+    mouse_event: MouseEvent,
+    window: &WindowRc,
+    mut mouse_input_state: MouseInputState,
+) -> MouseInputState {
+    let event = match mouse_event {
+        MouseEvent::MouseMoved { pos } | MouseEvent::DragMove { pos } => {
+            MouseEvent::DragMove { pos }
+        }
+        MouseEvent::MouseReleased { pos, .. } | MouseEvent::Drop { pos } => {
+            MouseEvent::Drop { pos }
+        }
+        MouseEvent::MouseExit => MouseEvent::MouseExit,
+        MouseEvent::MousePressed { .. }
+        | MouseEvent::MouseWheel { .. }
+        | MouseEvent::TouchPoint { .. } => return mouse_input_state,
+    };
+
+    let drag_source = mouse_input_state.drag_source.take();
+    send_exit_events(&mouse_input_state, event.pos(), window);
+    match event {
+        MouseEvent::DragMove { .. } => {
+            let mut result = dispatch_mouse_event(component, event, window);
+            result.drag_source = drag_source;
+            return result;
+        }
+        MouseEvent::Drop { .. } => {
+            let result = dispatch_mouse_event(component, event, window);
+            send_exit_events(&result, None, window);
+        }
+        _ => {}
+    }
+
+    window.end_drag();
+    if let Some(source) = drag_source.and_then(|source| source.upgrade()) {
+        source.borrow().as_ref().input_event(MouseEvent::MouseExit, window, &source);
+    }
+    MouseInputState::default()
+}
+
 /// Process the `mouse_event` on the `component`, the `mouse_grabber_stack` is the previous stack
 /// of mouse grabber.
 /// Returns a new mouse grabber stack.
@@ -292,33 +494,62 @@ pub fn process_mouse_input(
     window: &WindowRc,
     mut mouse_input_state: MouseInputState,
 ) -> MouseInputState {
+    if window.drag_data().is_some() {
+        return process_drag_input(component, mouse_event, window, mouse_input_state);
+    }
+
     if handle_mouse_grab(&mouse_event, window, &mut mouse_input_state) {
+        if window.drag_data().is_some() {
+            start_drag_from_grabber(&mut mouse_input_state, window);
+            return process_drag_input(component, mouse_event, window, mouse_input_state);
+        }
+        return mouse_input_state;
+    }
+
+    if let MouseEvent::TouchPoint { .. } = mouse_event {
+        // Only the item that grabbed the first finger receives the other ones
         return mouse_input_state;
     }
 
     send_exit_events(&mouse_input_state, mouse_event.pos(), window);
 
+    dispatch_mouse_event(component, mouse_event, window)
+}
+
+/// Send the `mouse_event` to the items under the mouse, from front to back, until one accepts it.
+/// Returns the new state with the stack of items under the mouse.
+fn dispatch_mouse_event(
+    component: ComponentRc,
+    mouse_event: MouseEvent,
+    window: &WindowRc,
+) -> MouseInputState {
     let mut result = MouseInputState::default();
-    type State = (Vector2D<f32>, Vec<(ItemWeak, InputEventFilterResult)>);
+    // The event in the coordinates of the parent item, or None if the item cannot be reached,
+    // and the stack of the parent items
+    type State = (Option<MouseEvent>, Vec<(ItemWeak, InputEventFilterResult)>);
     crate::item_tree::visit_items_with_post_visit(
         &component,
         crate::item_tree::TraversalOrder::FrontToBack,
         |comp_rc: &ComponentRc,
          item: core::pin::Pin<ItemRef>,
          item_index: usize,
-         (offset, mouse_grabber_stack): &State| {
+         (parent_event, mouse_grabber_stack): &State| {
             let item_rc = ItemRc::new(comp_rc.clone(), item_index);
 
+            let parent_event = match parent_event {
+                Some(parent_event) => *parent_event,
+                None => return (ItemVisitorResult::Continue((None, Vec::new())), None),
+            };
             let geom = item.as_ref().geometry();
-            let geom = geom.translate(*offset);
+            let mut event2 = parent_event;
+            event2.translate(-geom.origin.to_vector());
+            let children_event = map_to_children(item, event2);
 
             let mut mouse_grabber_stack = mouse_grabber_stack.clone();
 
-            let post_visit_state = if mouse_event.pos().map_or(false, |p| geom.contains(p))
+            let post_visit_state = if parent_event.pos().map_or(false, |p| item_contains(item, p))
                 || crate::item_rendering::is_clipping_item(item)
             {
-                let mut event2 = mouse_event;
-                event2.translate(-geom.origin.to_vector());
                 let filter_result =
                     item.as_ref().input_event_filter_before_children(event2, window, &item_rc);
                 mouse_grabber_stack.push((item_rc.downgrade(), filter_result));
@@ -343,10 +574,7 @@ pub fn process_mouse_input(
                 None
             };
 
-            (
-                ItemVisitorResult::Continue((geom.origin.to_vector(), mouse_grabber_stack)),
-                post_visit_state,
-            )
+            (ItemVisitorResult::Continue((children_event, mouse_grabber_stack)), post_visit_state)
         },
         |_, item, post_state, r| {
             if let Some((event2, mouse_grabber_stack, item_rc, intercept)) = post_state {
@@ -373,7 +601,7 @@ pub fn process_mouse_input(
             }
             r
         },
-        (Vector2D::new(0., 0.), Vec::new()),
+        (Some(mouse_event), Vec::new()),
     );
     result
 }
@@ -445,3 +673,30 @@ pub fn stop(&self) {
         self.cursor_blink_timer.stop()
     }
 }
+
+#[test]
+fn click_state_counts_clicks() {
+    let state = ClickState::default();
+    let press = |x: f32, button: PointerEventButton| {
+        let mut event =
+            MouseEvent::MousePressed { pos: Point::new(x, 10.), button, click_count: 0 };
+        state.count_click(&mut event);
+        match event {
+            MouseEvent::MousePressed { click_count, .. } => click_count,
+            _ => unreachable!(),
+        }
+    };
+    assert_eq!(press(10., PointerEventButton::left), 1);
+    assert_eq!(press(12., PointerEventButton::left), 2);
+    assert_eq!(press(12., PointerEventButton::left), 3);
+    // Too far away
+    assert_eq!(press(30., PointerEventButton::left), 1);
+    // Another button
+    assert_eq!(press(30., PointerEventButton::right), 1);
+    // Too late
+    crate::tests::sixtyfps_mock_elapsed_time(MULTI_CLICK_INTERVAL.as_millis() as u64 + 1);
+    assert_eq!(press(30., PointerEventButton::right), 1);
+    assert_eq!(press(30., PointerEventButton::right), 2);
+    state.reset();
+    assert_eq!(press(30., PointerEventButton::right), 1);
+}
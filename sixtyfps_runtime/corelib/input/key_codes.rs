@@ -14,7 +14,7 @@
 // the names comes should match with https://www.w3.org/TR/uievents-key/#named-key-attribute-values,
 for_each_special_keys![
 '\u{0008}'  # Backspace   # Qt_Key_Key_Backspace    # Back          ;
-'\u{0009}'  # Tab         # Qt_Key_Key_Tab          # Tab           ;
+'\u{0009}'  # Tab         # Qt_Key_Key_Tab|Qt_Key_Key_Backtab # Tab    ;
 '\u{000a}'  # Return      # Qt_Key_Key_Enter|Qt_Key_Key_Return # NumpadEnter|Return ;
 '\u{001b}'  # Escape      # Qt_Key_Key_Escape       # Escape       ;
 '\u{007f}'  # Delete      # Qt_Key_Key_Delete       # Delete       ;
@@ -17,6 +17,8 @@
 use crate::item_tree::ItemVisitorResult;
 use core::cell::{Cell, RefCell};
 use core::pin::Pin;
+#[cfg(not(feature = "std"))]
+use num_traits::float::Float;
 
 /// This structure must be present in items that are Rendered and contains information.
 /// Used by the backend.
@@ -86,6 +88,20 @@ pub(crate) fn is_clipping_item(item: Pin<ItemRef>) -> bool {
         || ItemRef::downcast_pin::<Clip>(item).is_some()
 }
 
+/// Returns true if scaling by the given factors and skewing by the given angles cannot be
+/// inverted: everything is flattened to a line or a point, so nothing should be drawn or hit.
+pub fn is_degenerate_transform(
+    x_factor: f32,
+    y_factor: f32,
+    x_angle_in_degrees: f32,
+    y_angle_in_degrees: f32,
+) -> bool {
+    let tan_x = x_angle_in_degrees.to_radians().tan();
+    let tan_y = y_angle_in_degrees.to_radians().tan();
+    let determinant = x_factor * y_factor * (1. - tan_x * tan_y);
+    !determinant.is_finite() || determinant.abs() < f32::EPSILON
+}
+
 /// Renders the tree of items that component holds, using the specified renderer. Rendering is done
 /// relative to the specified origin.
 pub fn render_component_items(
@@ -159,6 +175,12 @@ pub trait ItemRenderer {
 
     fn translate(&mut self, x: f32, y: f32);
     fn rotate(&mut self, angle_in_degrees: f32);
+    /// Scale the coordinate system of all following items by the given factors, relative to
+    /// the current origin.
+    fn scale(&mut self, x_factor: f32, y_factor: f32);
+    /// Skew the coordinate system of all following items by the given angles, relative to the
+    /// current origin: a point `(x, y)` is drawn at `(x + y * tan(x_angle), y + x * tan(y_angle))`.
+    fn skew(&mut self, x_angle_in_degrees: f32, y_angle_in_degrees: f32);
     /// Apply the opacity (between 0 and 1) for all following items until the next call to restore_state.
     fn apply_opacity(&mut self, opacity: f32);
 
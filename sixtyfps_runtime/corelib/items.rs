@@ -27,8 +27,8 @@
 use crate::component::ComponentVTable;
 use crate::graphics::{Brush, Color, Point, Rect};
 use crate::input::{
-    FocusEvent, InputEventFilterResult, InputEventResult, KeyEvent, KeyEventResult, KeyEventType,
-    MouseEvent,
+    DropEvent, FocusEvent, InputEventFilterResult, InputEventResult, KeyEvent, KeyEventResult,
+    KeyEventType, MouseEvent,
 };
 use crate::item_rendering::CachedRenderingData;
 use crate::layout::{LayoutInfo, Orientation};
@@ -40,6 +40,9 @@
 use const_field_offset::FieldOffsets;
 use core::cell::Cell;
 use core::pin::Pin;
+use euclid::default::Vector2D;
+#[cfg(not(feature = "std"))]
+use num_traits::float::Float;
 use sixtyfps_corelib_macros::*;
 use vtable::*;
 
@@ -47,6 +50,10 @@
 pub use text::*;
 mod image;
 pub use self::image::*;
+mod drag_and_drop;
+pub use drag_and_drop::*;
+mod gestures;
+pub use gestures::*;
 #[cfg(feature = "std")]
 mod path;
 #[cfg(feature = "std")]
@@ -61,6 +68,8 @@
 type KeyEventArg = (KeyEvent,);
 type PointerEventArg = (PointerEvent,);
 type PointArg = (Point,);
+type StringArg = (SharedString,);
+type DropEventArg = (DropEvent,);
 
 #[cfg(all(feature = "ffi", windows))]
 #[macro_export]
@@ -431,7 +440,14 @@ fn input_event_filter_before_children(
         window: &WindowRc,
         _self_rc: &ItemRc,
     ) -> InputEventFilterResult {
-        if !self.enabled() {
+        if !self.enabled()
+            || matches!(
+                event,
+                MouseEvent::DragMove { .. }
+                    | MouseEvent::Drop { .. }
+                    | MouseEvent::TouchPoint { .. }
+            )
+        {
             return InputEventFilterResult::ForwardAndIgnore;
         }
         if let Some(pos) = event.pos() {
@@ -471,7 +487,7 @@ fn input_event(
         };
 
         match event {
-            MouseEvent::MousePressed { pos, button } => {
+            MouseEvent::MousePressed { pos, button, .. } => {
                 self.grabbed.set(true);
                 if button == PointerEventButton::left {
                     Self::FIELD_OFFSETS.pressed_x.apply_pin(self).set(pos.x);
@@ -517,6 +533,9 @@ fn input_event(
                     InputEventResult::EventAccepted
                 }
             }
+            MouseEvent::DragMove { .. }
+            | MouseEvent::Drop { .. }
+            | MouseEvent::TouchPoint { .. } => return InputEventResult::EventIgnored,
         };
         result
     }
@@ -566,8 +585,11 @@ pub struct FocusScope {
     pub width: Property<f32>,
     pub height: Property<f32>,
     pub has_focus: Property<bool>,
+    pub tab_order: Property<i32>,
     pub key_pressed: Callback<KeyEventArg, EventResult>,
     pub key_released: Callback<KeyEventArg, EventResult>,
+    pub focus_in: Callback<VoidArg>,
+    pub focus_out: Callback<VoidArg>,
     /// FIXME: remove this
     pub cached_rendering_data: CachedRenderingData,
 }
@@ -615,6 +637,9 @@ fn key_event(self: Pin<&Self>, event: &KeyEvent, _window: &WindowRc) -> KeyEvent
             KeyEventType::KeyReleased => {
                 Self::FIELD_OFFSETS.key_released.apply_pin(self).call(&(event.clone(),))
             }
+            KeyEventType::UpdateComposition | KeyEventType::CommitComposition => {
+                return KeyEventResult::EventIgnored
+            }
         };
         match r {
             EventResult::accept => KeyEventResult::EventAccepted,
@@ -631,6 +656,11 @@ fn focus_event(self: Pin<&Self>, event: &FocusEvent, _window: &WindowRc) {
                 self.has_focus.set(false);
             }
         }
+        match event {
+            FocusEvent::FocusIn => Self::FIELD_OFFSETS.focus_in.apply_pin(self).call(&()),
+            FocusEvent::FocusOut => Self::FIELD_OFFSETS.focus_out.apply_pin(self).call(&()),
+            FocusEvent::WindowReceivedFocus | FocusEvent::WindowLostFocus => {}
+        }
     }
 
     fn render(self: Pin<&Self>, _backend: &mut ItemRendererRef) {}
@@ -856,6 +886,114 @@ impl ItemConsts for Rotate {
     fn sixtyfps_get_RotateVTable() -> RotateVTable for Rotate
 }
 
+#[repr(C)]
+#[derive(FieldOffsets, Default, SixtyFPSElement)]
+#[pin]
+/// The TransformItem is not meant to be used directly by the .60 code, instead, the `scale-x`,
+/// `scale-y`, `skew-x`, `skew-y` and `transform-origin-*` properties should be used
+pub struct TransformItem {
+    // FIXME: this element shouldn't need these geometry property
+    pub x: Property<f32>,
+    pub y: Property<f32>,
+    pub width: Property<f32>,
+    pub height: Property<f32>,
+    pub scale_x: Property<f32>,
+    pub scale_y: Property<f32>,
+    pub skew_x: Property<f32>,
+    pub skew_y: Property<f32>,
+    pub origin_x: Property<f32>,
+    pub origin_y: Property<f32>,
+    pub cached_rendering_data: CachedRenderingData,
+}
+
+impl TransformItem {
+    /// Returns true if the transformation cannot be inverted, in which case the children are
+    /// neither drawn nor reachable by the mouse.
+    fn is_degenerate(self: Pin<&Self>) -> bool {
+        crate::item_rendering::is_degenerate_transform(
+            self.scale_x(),
+            self.scale_y(),
+            self.skew_x(),
+            self.skew_y(),
+        )
+    }
+
+    /// Maps a position in the coordinates of this item to the coordinates of its children,
+    /// by applying the inverse of the transformation used when rendering.
+    /// Returns None if the transformation is degenerate.
+    pub(crate) fn map_to_children(self: Pin<&Self>, pos: Point) -> Option<Point> {
+        if self.is_degenerate() {
+            return None;
+        }
+        let origin = Point::new(self.origin_x(), self.origin_y());
+        let p = pos - origin;
+        let (x, y) = (p.x / self.scale_x(), p.y / self.scale_y());
+        let tan_x = self.skew_x().to_radians().tan();
+        let tan_y = self.skew_y().to_radians().tan();
+        let det = 1. - tan_x * tan_y;
+        Some(origin + Vector2D::new((x - y * tan_x) / det, (y - x * tan_y) / det))
+    }
+}
+
+impl Item for TransformItem {
+    fn init(self: Pin<&Self>, _window: &WindowRc) {}
+
+    fn geometry(self: Pin<&Self>) -> Rect {
+        euclid::rect(self.x(), self.y(), self.width(), self.height())
+    }
+
+    fn layout_info(self: Pin<&Self>, _orientation: Orientation, _window: &WindowRc) -> LayoutInfo {
+        LayoutInfo { stretch: 1., ..LayoutInfo::default() }
+    }
+
+    fn input_event_filter_before_children(
+        self: Pin<&Self>,
+        _: MouseEvent,
+        _window: &WindowRc,
+        _self_rc: &ItemRc,
+    ) -> InputEventFilterResult {
+        InputEventFilterResult::ForwardAndIgnore
+    }
+
+    fn input_event(
+        self: Pin<&Self>,
+        _: MouseEvent,
+        _window: &WindowRc,
+        _self_rc: &ItemRc,
+    ) -> InputEventResult {
+        InputEventResult::EventIgnored
+    }
+
+    fn key_event(self: Pin<&Self>, _: &KeyEvent, _window: &WindowRc) -> KeyEventResult {
+        KeyEventResult::EventIgnored
+    }
+
+    fn focus_event(self: Pin<&Self>, _: &FocusEvent, _window: &WindowRc) {}
+
+    fn render(self: Pin<&Self>, backend: &mut ItemRendererRef) {
+        if self.is_degenerate() {
+            // Everything is flattened, so there is nothing to see
+            (*backend).combine_clip(Rect::default(), 0., 0.);
+            return;
+        }
+        (*backend).translate(self.origin_x(), self.origin_y());
+        (*backend).scale(self.scale_x(), self.scale_y());
+        (*backend).skew(self.skew_x(), self.skew_y());
+        (*backend).translate(-self.origin_x(), -self.origin_y());
+    }
+}
+
+impl ItemConsts for TransformItem {
+    const cached_rendering_data_offset: const_field_offset::FieldOffset<
+        TransformItem,
+        CachedRenderingData,
+    > = TransformItem::FIELD_OFFSETS.cached_rendering_data.as_unpinned_projection();
+}
+
+declare_item_vtable! {
+    fn sixtyfps_get_TransformItemVTable() -> TransformItemVTable for TransformItem
+}
+
 /// The implementation of the `Flickable` element
 #[repr(C)]
 #[derive(FieldOffsets, Default, SixtyFPSElement)]
@@ -867,12 +1005,41 @@ pub struct Flickable {
     pub height: Property<f32>,
     pub viewport: Rectangle,
     pub interactive: Property<bool>,
+    pub flick_deceleration: Property<f32>,
+    pub bounds_behavior: Property<BoundsBehavior>,
+    pub is_flicking: Property<bool>,
+    pub viewport_scale: Property<f32>,
+    pub min_viewport_scale: Property<f32>,
+    pub max_viewport_scale: Property<f32>,
     data: FlickableDataBox,
 
     /// FIXME: remove this
     pub cached_rendering_data: CachedRenderingData,
 }
 
+impl Flickable {
+    /// Moves the viewport so that the position (`x`, `y`) of the viewport is at the top left
+    /// corner of the Flickable, optionally with an animation.
+    pub fn scroll_to(self: Pin<&Self>, x: f32, y: f32, animated: bool) {
+        self.data.scroll_to(self, Point::new(x, y), animated)
+    }
+
+    /// The translation applied before the scale to the coordinate system of the children, so
+    /// that the viewport stays at its position and only its content is scaled.
+    fn scale_offset(self: Pin<&Self>) -> Vector2D<f32> {
+        let viewport_x =
+            (Self::FIELD_OFFSETS.viewport + Rectangle::FIELD_OFFSETS.x).apply_pin(self);
+        let viewport_y =
+            (Self::FIELD_OFFSETS.viewport + Rectangle::FIELD_OFFSETS.y).apply_pin(self);
+        euclid::vec2(viewport_x.get(), viewport_y.get()) * (1. - self.viewport_scale())
+    }
+
+    /// Maps a position in the coordinates of the Flickable to the coordinates of its children
+    pub(crate) fn map_to_children(self: Pin<&Self>, pos: Point) -> Point {
+        ((pos - self.scale_offset()).to_vector() / self.viewport_scale()).to_point()
+    }
+}
+
 impl Item for Flickable {
     fn init(self: Pin<&Self>, _window: &WindowRc) {}
 
@@ -929,7 +1096,13 @@ fn focus_event(self: Pin<&Self>, _: &FocusEvent, _window: &WindowRc) {}
 
     fn render(self: Pin<&Self>, backend: &mut ItemRendererRef) {
         let geometry = self.geometry();
-        (*backend).combine_clip(euclid::rect(0., 0., geometry.width(), geometry.height()), 0., 0.)
+        (*backend).combine_clip(euclid::rect(0., 0., geometry.width(), geometry.height()), 0., 0.);
+        let scale = self.viewport_scale();
+        if scale != 1. {
+            let offset = self.scale_offset();
+            (*backend).translate(offset.x, offset.y);
+            (*backend).scale(scale, scale);
+        }
     }
 }
 
@@ -997,6 +1170,41 @@ pub struct PropertyAnimation {
     pub easing: crate::animations::EasingCurve,
 }
 
+#[derive(Copy, Clone, Debug, PartialEq, strum::EnumString, strum::Display)]
+#[repr(C)]
+#[allow(non_camel_case_types)]
+/// The direction in which the horizontal layouts place their children, and to which side
+/// the texts aligned `left` or `right` go
+pub enum LayoutDirection {
+    /// Left to right
+    ltr,
+    /// Right to left: the horizontal layouts and the text alignments are mirrored
+    rtl,
+}
+
+impl Default for LayoutDirection {
+    fn default() -> Self {
+        Self::ltr
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, strum::EnumString, strum::Display)]
+#[repr(C)]
+#[allow(non_camel_case_types)]
+/// What the Flickable does when its viewport reaches the bounds
+pub enum BoundsBehavior {
+    /// The viewport stops at the bounds
+    stop,
+    /// The viewport can go past the bounds, and then bounces back to them
+    overshoot_and_bounce,
+}
+
+impl Default for BoundsBehavior {
+    fn default() -> Self {
+        Self::stop
+    }
+}
+
 /// The implementation of the `Window` element
 #[repr(C)]
 #[derive(FieldOffsets, Default, SixtyFPSElement)]
@@ -1011,6 +1219,7 @@ pub struct WindowItem {
     pub default_font_family: Property<SharedString>,
     pub default_font_size: Property<f32>,
     pub default_font_weight: Property<i32>,
+    pub layout_direction: Property<LayoutDirection>,
     pub cached_rendering_data: CachedRenderingData,
 }
 
@@ -1178,6 +1387,18 @@ fn sixtyfps_get_ImageItemVTable() -> ImageItemVTable for ImageItem
     fn sixtyfps_get_ClippedImageVTable() -> ClippedImageVTable for ClippedImage
 }
 
+declare_item_vtable! {
+    fn sixtyfps_get_DragAreaVTable() -> DragAreaVTable for DragArea
+}
+
+declare_item_vtable! {
+    fn sixtyfps_get_DropAreaVTable() -> DropAreaVTable for DropArea
+}
+
+declare_item_vtable! {
+    fn sixtyfps_get_GestureAreaVTable() -> GestureAreaVTable for GestureArea
+}
+
 #[cfg(feature = "std")]
 declare_item_vtable! {
     fn sixtyfps_get_PathVTable() -> PathVTable for Path
@@ -1263,3 +1484,39 @@ pub struct PointerEvent {
     pub button: PointerEventButton,
     pub kind: PointerEventKind,
 }
+
+/// The role of an element for the assistive technologies, set with the `accessible-role` property.
+#[derive(Copy, Clone, Debug, PartialEq, strum::EnumString, strum::Display)]
+#[repr(C)]
+#[allow(non_camel_case_types)]
+pub enum AccessibleRole {
+    none,
+    button,
+    checkbox,
+    combobox,
+    group,
+    list,
+    list_item,
+    scroll_view,
+    slider,
+    spinbox,
+    tab,
+    text,
+    text_input,
+}
+
+impl Default for AccessibleRole {
+    fn default() -> Self {
+        Self::none
+    }
+}
+
+/// The accessible properties of type string, passed to `ComponentVTable::accessible_string_property`.
+#[derive(Copy, Clone, Debug, PartialEq, strum::EnumString, strum::Display)]
+#[repr(C)]
+#[allow(non_camel_case_types)]
+pub enum AccessibleStringProperty {
+    label,
+    value,
+    description,
+}
@@ -0,0 +1,268 @@
+/* LICENSE BEGIN
+    This file is part of the SixtyFPS Project -- https://sixtyfps.io
+    Copyright (c) 2021 Olivier Goffart <olivier.goffart@sixtyfps.io>
+    Copyright (c) 2021 Simon Hausmann <simon.hausmann@sixtyfps.io>
+
+    SPDX-License-Identifier: GPL-3.0-only
+    This file is also available under commercial licensing terms.
+    Please contact info@sixtyfps.io for more information.
+LICENSE END */
+/*!
+This module contains the builtin drag and drop related items.
+
+When adding an item or a property, it needs to be kept in sync with different place.
+Lookup the [`crate::items`] module documentation.
+*/
+use super::{DropEventArg, Item, ItemConsts, ItemRc, MouseCursor, PointerEventButton};
+use crate::graphics::Rect;
+use crate::input::{
+    DropEvent, FocusEvent, InputEventFilterResult, InputEventResult, KeyEvent, KeyEventResult,
+    MouseEvent,
+};
+use crate::item_rendering::{CachedRenderingData, ItemRenderer};
+use crate::layout::{LayoutInfo, Orientation};
+#[cfg(feature = "rtti")]
+use crate::rtti::*;
+use crate::window::WindowRc;
+use crate::{Callback, Property, SharedString};
+use const_field_offset::FieldOffsets;
+use core::cell::Cell;
+use core::pin::Pin;
+use sixtyfps_corelib_macros::*;
+
+/// The distance, in logical pixels, that the mouse needs to move while pressed to start a drag
+const DRAG_DISTANCE_THRESHOLD: f32 = 8.;
+
+/// The implementation of the `DragArea` element
+#[repr(C)]
+#[derive(FieldOffsets, Default, SixtyFPSElement)]
+#[pin]
+pub struct DragArea {
+    pub x: Property<f32>,
+    pub y: Property<f32>,
+    pub width: Property<f32>,
+    pub height: Property<f32>,
+    pub enabled: Property<bool>,
+    pub mime_type: Property<SharedString>,
+    pub data: Property<SharedString>,
+    pub dragging: Property<bool>,
+    pub cached_rendering_data: CachedRenderingData,
+    /// true when the left button is pressed, until the drag and drop operation is over
+    pressed: Cell<bool>,
+    pressed_x: Cell<f32>,
+    pressed_y: Cell<f32>,
+}
+
+impl DragArea {
+    fn moved_enough(self: Pin<&Self>, event: &MouseEvent) -> bool {
+        match event {
+            MouseEvent::MouseMoved { pos } if self.pressed.get() => {
+                let (dx, dy) = (pos.x - self.pressed_x.get(), pos.y - self.pressed_y.get());
+                dx * dx + dy * dy > DRAG_DISTANCE_THRESHOLD * DRAG_DISTANCE_THRESHOLD
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Item for DragArea {
+    fn init(self: Pin<&Self>, _window: &WindowRc) {}
+
+    fn geometry(self: Pin<&Self>) -> Rect {
+        euclid::rect(self.x(), self.y(), self.width(), self.height())
+    }
+
+    fn layout_info(self: Pin<&Self>, _orientation: Orientation, _window: &WindowRc) -> LayoutInfo {
+        LayoutInfo::default()
+    }
+
+    fn input_event_filter_before_children(
+        self: Pin<&Self>,
+        event: MouseEvent,
+        _window: &WindowRc,
+        _self_rc: &ItemRc,
+    ) -> InputEventFilterResult {
+        if !self.enabled() {
+            return InputEventFilterResult::ForwardAndIgnore;
+        }
+        match event {
+            MouseEvent::MousePressed { pos, button: PointerEventButton::left, .. } => {
+                self.pressed.set(true);
+                self.pressed_x.set(pos.x);
+                self.pressed_y.set(pos.y);
+                InputEventFilterResult::ForwardAndInterceptGrab
+            }
+            // Take the grab from the children to start the drag
+            MouseEvent::MouseMoved { .. } if self.moved_enough(&event) => {
+                InputEventFilterResult::Intercept
+            }
+            MouseEvent::MouseReleased { button: PointerEventButton::left, .. } => {
+                self.pressed.set(false);
+                InputEventFilterResult::ForwardAndInterceptGrab
+            }
+            MouseEvent::DragMove { .. } | MouseEvent::Drop { .. } => {
+                InputEventFilterResult::ForwardAndIgnore
+            }
+            _ => InputEventFilterResult::ForwardAndInterceptGrab,
+        }
+    }
+
+    fn input_event(
+        self: Pin<&Self>,
+        event: MouseEvent,
+        window: &WindowRc,
+        _self_rc: &ItemRc,
+    ) -> InputEventResult {
+        if matches!(event, MouseEvent::MouseExit) {
+            self.pressed.set(false);
+            Self::FIELD_OFFSETS.dragging.apply_pin(self).set(false);
+            return InputEventResult::EventAccepted;
+        }
+        if !self.enabled() {
+            return InputEventResult::EventIgnored;
+        }
+        match event {
+            MouseEvent::MousePressed { pos, button: PointerEventButton::left, .. } => {
+                self.pressed.set(true);
+                self.pressed_x.set(pos.x);
+                self.pressed_y.set(pos.y);
+                InputEventResult::GrabMouse
+            }
+            MouseEvent::MouseMoved { .. } if self.pressed.get() => {
+                if self.moved_enough(&event) && !self.dragging() {
+                    Self::FIELD_OFFSETS.dragging.apply_pin(self).set(true);
+                    window.start_drag(DropEvent { mime_type: self.mime_type(), data: self.data() });
+                }
+                InputEventResult::GrabMouse
+            }
+            MouseEvent::MouseReleased { button: PointerEventButton::left, .. } => {
+                self.pressed.set(false);
+                InputEventResult::EventAccepted
+            }
+            _ => InputEventResult::EventIgnored,
+        }
+    }
+
+    fn key_event(self: Pin<&Self>, _: &KeyEvent, _window: &WindowRc) -> KeyEventResult {
+        KeyEventResult::EventIgnored
+    }
+
+    fn focus_event(self: Pin<&Self>, _: &FocusEvent, _window: &WindowRc) {}
+
+    fn render(self: Pin<&Self>, _backend: &mut &mut dyn ItemRenderer) {}
+}
+
+impl ItemConsts for DragArea {
+    const cached_rendering_data_offset: const_field_offset::FieldOffset<
+        DragArea,
+        CachedRenderingData,
+    > = DragArea::FIELD_OFFSETS.cached_rendering_data.as_unpinned_projection();
+}
+
+/// The implementation of the `DropArea` element
+#[repr(C)]
+#[derive(FieldOffsets, Default, SixtyFPSElement)]
+#[pin]
+pub struct DropArea {
+    pub x: Property<f32>,
+    pub y: Property<f32>,
+    pub width: Property<f32>,
+    pub height: Property<f32>,
+    pub enabled: Property<bool>,
+    pub contains_drag: Property<bool>,
+    pub mouse_x: Property<f32>,
+    pub mouse_y: Property<f32>,
+    pub can_drop: Callback<DropEventArg, bool>,
+    pub dropped: Callback<DropEventArg>,
+    pub cached_rendering_data: CachedRenderingData,
+}
+
+impl DropArea {
+    /// Returns true if the data can be dropped here. Everything is accepted if there is no
+    /// handler for the `can-drop` callback.
+    fn accepts_drop(self: Pin<&Self>, data: &DropEvent) -> bool {
+        let can_drop = Self::FIELD_OFFSETS.can_drop.apply_pin(self);
+        !can_drop.has_handler() || can_drop.call(&(data.clone(),))
+    }
+}
+
+impl Item for DropArea {
+    fn init(self: Pin<&Self>, _window: &WindowRc) {}
+
+    fn geometry(self: Pin<&Self>) -> Rect {
+        euclid::rect(self.x(), self.y(), self.width(), self.height())
+    }
+
+    fn layout_info(self: Pin<&Self>, _orientation: Orientation, _window: &WindowRc) -> LayoutInfo {
+        LayoutInfo::default()
+    }
+
+    fn input_event_filter_before_children(
+        self: Pin<&Self>,
+        event: MouseEvent,
+        _window: &WindowRc,
+        _self_rc: &ItemRc,
+    ) -> InputEventFilterResult {
+        match event {
+            MouseEvent::DragMove { .. } | MouseEvent::Drop { .. } | MouseEvent::MouseExit
+                if self.enabled() =>
+            {
+                InputEventFilterResult::ForwardEvent
+            }
+            _ => InputEventFilterResult::ForwardAndIgnore,
+        }
+    }
+
+    fn input_event(
+        self: Pin<&Self>,
+        event: MouseEvent,
+        window: &WindowRc,
+        _self_rc: &ItemRc,
+    ) -> InputEventResult {
+        let contains_drag = Self::FIELD_OFFSETS.contains_drag.apply_pin(self);
+        let pos = match event {
+            MouseEvent::DragMove { pos } | MouseEvent::Drop { pos } => pos,
+            MouseEvent::MouseExit => {
+                if contains_drag.get() {
+                    contains_drag.set(false);
+                    window.set_mouse_cursor(MouseCursor::default);
+                }
+                return InputEventResult::EventAccepted;
+            }
+            _ => return InputEventResult::EventIgnored,
+        };
+        let data = match window.drag_data() {
+            Some(data) if self.enabled() && self.accepts_drop(&data) => data,
+            _ => {
+                contains_drag.set(false);
+                return InputEventResult::EventIgnored;
+            }
+        };
+        Self::FIELD_OFFSETS.mouse_x.apply_pin(self).set(pos.x);
+        Self::FIELD_OFFSETS.mouse_y.apply_pin(self).set(pos.y);
+        if let MouseEvent::Drop { .. } = event {
+            contains_drag.set(false);
+            window.set_mouse_cursor(MouseCursor::default);
+            Self::FIELD_OFFSETS.dropped.apply_pin(self).call(&(data,));
+        } else {
+            contains_drag.set(true);
+            window.set_mouse_cursor(MouseCursor::copy);
+        }
+        InputEventResult::EventAccepted
+    }
+
+    fn key_event(self: Pin<&Self>, _: &KeyEvent, _window: &WindowRc) -> KeyEventResult {
+        KeyEventResult::EventIgnored
+    }
+
+    fn focus_event(self: Pin<&Self>, _: &FocusEvent, _window: &WindowRc) {}
+
+    fn render(self: Pin<&Self>, _backend: &mut &mut dyn ItemRenderer) {}
+}
+
+impl ItemConsts for DropArea {
+    const cached_rendering_data_offset: const_field_offset::FieldOffset<
+        DropArea,
+        CachedRenderingData,
+    > = DropArea::FIELD_OFFSETS.cached_rendering_data.as_unpinned_projection();
+}
@@ -0,0 +1,306 @@
+/* LICENSE BEGIN
+    This file is part of the SixtyFPS Project -- https://sixtyfps.io
+    Copyright (c) 2021 Olivier Goffart <olivier.goffart@sixtyfps.io>
+    Copyright (c) 2021 Simon Hausmann <simon.hausmann@sixtyfps.io>
+
+    SPDX-License-Identifier: GPL-3.0-only
+    This file is also available under commercial licensing terms.
+    Please contact info@sixtyfps.io for more information.
+LICENSE END */
+/*!
+This module contains the `GestureArea` item, which recognizes double clicks, long presses and
+the pinch and rotate gestures made with two fingers on a touch screen.
+
+When adding an item or a property, it needs to be kept in sync with different place.
+Lookup the [`crate::items`] module documentation.
+*/
+use super::{Item, ItemConsts, ItemRc, ItemRef, PointerEventButton, VoidArg};
+use crate::graphics::{Point, Rect};
+use crate::input::{
+    FocusEvent, InputEventFilterResult, InputEventResult, KeyEvent, KeyEventResult, MouseEvent,
+    TouchPhase,
+};
+use crate::item_rendering::{CachedRenderingData, ItemRenderer};
+use crate::layout::{LayoutInfo, Orientation};
+#[cfg(feature = "rtti")]
+use crate::rtti::*;
+use crate::timers::{Timer, TimerMode};
+use crate::window::WindowRc;
+use crate::{Callback, Property};
+use alloc::boxed::Box;
+use const_field_offset::FieldOffsets;
+use core::cell::Cell;
+use core::pin::Pin;
+#[cfg(not(feature = "std"))]
+use num_traits::float::Float;
+use sixtyfps_corelib_macros::*;
+
+/// How long the mouse or the finger needs to stay pressed for a long press
+const LONG_PRESS_DURATION: core::time::Duration = core::time::Duration::from_millis(800);
+/// The distance, in logical pixels, that the mouse or the finger can move during a long press
+const LONG_PRESS_DISTANCE: f32 = 8.;
+
+/// The implementation of the `GestureArea` element
+#[repr(C)]
+#[derive(FieldOffsets, Default, SixtyFPSElement)]
+#[pin]
+pub struct GestureArea {
+    pub x: Property<f32>,
+    pub y: Property<f32>,
+    pub width: Property<f32>,
+    pub height: Property<f32>,
+    pub enabled: Property<bool>,
+    pub pressed: Property<bool>,
+    pub pinching: Property<bool>,
+    pub scale: Property<f32>,
+    pub rotation: Property<f32>,
+    pub double_clicked: Callback<VoidArg>,
+    pub long_pressed: Callback<VoidArg>,
+    pub pinch_finished: Callback<VoidArg>,
+    data: GestureAreaDataBox,
+    pub cached_rendering_data: CachedRenderingData,
+}
+
+impl GestureArea {
+    fn press(self: Pin<&Self>, pos: Point, click_count: u8, self_rc: &ItemRc) {
+        Self::FIELD_OFFSETS.pressed.apply_pin(self).set(true);
+        self.data.pressed_pos.set(pos);
+        self.data.primary_pos.set(pos);
+        // The press after a double click starts a new one
+        if click_count > 0 && click_count % 2 == 0 {
+            self.data.long_press_timer.stop();
+            Self::FIELD_OFFSETS.double_clicked.apply_pin(self).call(&());
+            return;
+        }
+        let self_weak = self_rc.downgrade();
+        self.data.long_press_timer.start(TimerMode::SingleShot, LONG_PRESS_DURATION, move || {
+            if let Some(item_rc) = self_weak.upgrade() {
+                if let Some(area) = ItemRef::downcast_pin::<GestureArea>(item_rc.borrow()) {
+                    if area.pressed() && !area.pinching() {
+                        Self::FIELD_OFFSETS.long_pressed.apply_pin(area).call(&());
+                    }
+                }
+            }
+        });
+    }
+
+    fn move_primary(self: Pin<&Self>, pos: Point) {
+        self.data.primary_pos.set(pos);
+        if self.pressed() {
+            if (pos - self.data.pressed_pos.get()).length() > LONG_PRESS_DISTANCE {
+                self.data.long_press_timer.stop();
+            }
+            self.update_pinch();
+        }
+    }
+
+    fn release(self: Pin<&Self>) {
+        Self::FIELD_OFFSETS.pressed.apply_pin(self).set(false);
+        self.data.long_press_timer.stop();
+        self.finish_pinch();
+    }
+
+    fn touch_point(self: Pin<&Self>, id: u64, pos: Point, phase: TouchPhase) {
+        let pinch = self.data.pinch.get();
+        match phase {
+            TouchPhase::started if pinch.is_none() => {
+                self.data.long_press_timer.stop();
+                self.data.pinch.set(Some(PinchState {
+                    id,
+                    pos,
+                    start_primary_pos: self.data.primary_pos.get(),
+                    start_pos: pos,
+                }));
+                Self::FIELD_OFFSETS.scale.apply_pin(self).set(1.);
+                Self::FIELD_OFFSETS.rotation.apply_pin(self).set(0.);
+                Self::FIELD_OFFSETS.pinching.apply_pin(self).set(true);
+            }
+            TouchPhase::moved => {
+                if let Some(pinch) = pinch.filter(|pinch| pinch.id == id) {
+                    self.data.pinch.set(Some(PinchState { pos, ..pinch }));
+                    self.update_pinch();
+                }
+            }
+            TouchPhase::ended | TouchPhase::cancelled => {
+                if pinch.map_or(false, |pinch| pinch.id == id) {
+                    self.finish_pinch();
+                }
+            }
+            TouchPhase::started => {}
+        }
+    }
+
+    /// Computes the scale and the rotation from the positions of the two fingers
+    fn update_pinch(self: Pin<&Self>) {
+        let pinch = if let Some(pinch) = self.data.pinch.get() { pinch } else { return };
+        let start = pinch.start_pos - pinch.start_primary_pos;
+        let current = pinch.pos - self.data.primary_pos.get();
+        let start_length = start.length();
+        if start_length > 0. {
+            Self::FIELD_OFFSETS.scale.apply_pin(self).set(current.length() / start_length);
+        }
+        let mut rotation = (current.y.atan2(current.x) - start.y.atan2(start.x)).to_degrees();
+        if rotation > 180. {
+            rotation -= 360.;
+        } else if rotation <= -180. {
+            rotation += 360.;
+        }
+        Self::FIELD_OFFSETS.rotation.apply_pin(self).set(rotation);
+    }
+
+    fn finish_pinch(self: Pin<&Self>) {
+        if self.data.pinch.take().is_some() {
+            Self::FIELD_OFFSETS.pinching.apply_pin(self).set(false);
+            Self::FIELD_OFFSETS.pinch_finished.apply_pin(self).call(&());
+        }
+    }
+}
+
+impl Item for GestureArea {
+    fn init(self: Pin<&Self>, _window: &WindowRc) {
+        Self::FIELD_OFFSETS.scale.apply_pin(self).set(1.);
+    }
+
+    fn geometry(self: Pin<&Self>) -> Rect {
+        euclid::rect(self.x(), self.y(), self.width(), self.height())
+    }
+
+    fn layout_info(self: Pin<&Self>, _orientation: Orientation, _window: &WindowRc) -> LayoutInfo {
+        LayoutInfo::default()
+    }
+
+    fn input_event_filter_before_children(
+        self: Pin<&Self>,
+        event: MouseEvent,
+        _window: &WindowRc,
+        self_rc: &ItemRc,
+    ) -> InputEventFilterResult {
+        if !self.enabled() {
+            return InputEventFilterResult::ForwardAndIgnore;
+        }
+        // The gestures are recognized here, so that they are also recognized while a child has
+        // the grab. This function is called for the events sent to this item too.
+        match event {
+            MouseEvent::MousePressed { pos, button: PointerEventButton::left, click_count } => {
+                self.press(pos, click_count, self_rc)
+            }
+            MouseEvent::MouseMoved { pos } => self.move_primary(pos),
+            MouseEvent::MouseReleased { button: PointerEventButton::left, .. } => self.release(),
+            MouseEvent::TouchPoint { id, pos, phase } if self.pressed() => {
+                self.touch_point(id, pos, phase);
+                // Take the grab from the children when a second finger touches the screen
+                if phase == TouchPhase::started {
+                    return InputEventFilterResult::Intercept;
+                }
+            }
+            _ => {}
+        }
+        InputEventFilterResult::ForwardAndInterceptGrab
+    }
+
+    fn input_event(
+        self: Pin<&Self>,
+        event: MouseEvent,
+        _window: &WindowRc,
+        _self_rc: &ItemRc,
+    ) -> InputEventResult {
+        if matches!(event, MouseEvent::MouseExit) {
+            self.release();
+            return InputEventResult::EventAccepted;
+        }
+        if !self.enabled() {
+            return InputEventResult::EventIgnored;
+        }
+        match event {
+            MouseEvent::MousePressed { button: PointerEventButton::left, .. } => {
+                InputEventResult::GrabMouse
+            }
+            MouseEvent::MouseMoved { .. } | MouseEvent::TouchPoint { .. } if self.pressed() => {
+                InputEventResult::GrabMouse
+            }
+            MouseEvent::MouseReleased { button: PointerEventButton::left, .. } => {
+                InputEventResult::EventAccepted
+            }
+            _ => InputEventResult::EventIgnored,
+        }
+    }
+
+    fn key_event(self: Pin<&Self>, _: &KeyEvent, _window: &WindowRc) -> KeyEventResult {
+        KeyEventResult::EventIgnored
+    }
+
+    fn focus_event(self: Pin<&Self>, _: &FocusEvent, _window: &WindowRc) {}
+
+    fn render(self: Pin<&Self>, _backend: &mut &mut dyn ItemRenderer) {}
+}
+
+impl ItemConsts for GestureArea {
+    const cached_rendering_data_offset: const_field_offset::FieldOffset<
+        GestureArea,
+        CachedRenderingData,
+    > = GestureArea::FIELD_OFFSETS.cached_rendering_data.as_unpinned_projection();
+}
+
+/// The positions of the second finger during a pinch
+#[derive(Clone, Copy)]
+struct PinchState {
+    /// The id of the second finger
+    id: u64,
+    /// The current position of the second finger
+    pos: Point,
+    /// The position of the first finger when the second one touched the screen
+    start_primary_pos: Point,
+    /// The position of the second finger when it touched the screen
+    start_pos: Point,
+}
+
+/// The internal state of a GestureArea that is not exposed through properties
+#[derive(Default)]
+pub struct GestureAreaData {
+    /// The position of the last press
+    pressed_pos: Cell<Point>,
+    /// The current position of the mouse, or of the first finger
+    primary_pos: Cell<Point>,
+    pinch: Cell<Option<PinchState>>,
+    long_press_timer: Timer,
+}
+
+#[repr(C)]
+/// Wraps the internal data structure for the GestureArea
+pub struct GestureAreaDataBox(core::ptr::NonNull<GestureAreaData>);
+
+impl Default for GestureAreaDataBox {
+    fn default() -> Self {
+        GestureAreaDataBox(Box::leak(Box::new(GestureAreaData::default())).into())
+    }
+}
+impl Drop for GestureAreaDataBox {
+    fn drop(&mut self) {
+        // Safety: the self.0 was constructed from a Box::leak in GestureAreaDataBox::default
+        unsafe {
+            Box::from_raw(self.0.as_ptr());
+        }
+    }
+}
+impl core::ops::Deref for GestureAreaDataBox {
+    type Target = GestureAreaData;
+    fn deref(&self) -> &Self::Target {
+        // Safety: initialized in GestureAreaDataBox::default
+        unsafe { self.0.as_ref() }
+    }
+}
+
+/// # Safety
+/// This must be called using a non-null pointer pointing to a chunk of memory big enough to
+/// hold a GestureAreaDataBox
+#[no_mangle]
+pub unsafe extern "C" fn sixtyfps_gesturearea_data_init(data: *mut GestureAreaDataBox) {
+    core::ptr::write(data, GestureAreaDataBox::default());
+}
+
+/// # Safety
+/// This must be called using a non-null pointer pointing to an initialized GestureAreaDataBox
+#[no_mangle]
+pub unsafe extern "C" fn sixtyfps_gesturearea_data_free(data: *mut GestureAreaDataBox) {
+    core::ptr::drop_in_place(data);
+}
@@ -14,8 +14,8 @@
 Lookup the [`crate::items`] module documentation.
 */
 
-use super::{Item, ItemConsts, ItemRc, PointArg, PointerEventButton, VoidArg};
-use crate::graphics::{Brush, Color, FontRequest, Rect};
+use super::{Item, ItemConsts, ItemRc, PointArg, PointerEventButton, StringArg, VoidArg};
+use crate::graphics::{Brush, Color, FontRequest, Point, Rect, Size};
 use crate::input::{
     key_codes, FocusEvent, InputEventFilterResult, InputEventResult, KeyEvent, KeyEventResult,
     KeyEventType, KeyboardModifiers, MouseEvent,
@@ -24,12 +24,18 @@
 use crate::layout::{LayoutInfo, Orientation};
 #[cfg(feature = "rtti")]
 use crate::rtti::*;
+use crate::styled_text::StyledText;
 use crate::window::WindowRc;
 use crate::{Callback, Property, SharedString};
+use alloc::boxed::Box;
+use alloc::rc::Rc;
 use alloc::string::String;
+use alloc::vec::Vec;
 use const_field_offset::FieldOffsets;
+use core::cell::RefCell;
 use core::pin::Pin;
 use sixtyfps_corelib_macros::*;
+use unicode_segmentation::UnicodeSegmentation;
 
 #[cfg(not(feature = "std"))]
 use num_traits::float::Float;
@@ -49,6 +55,18 @@ fn default() -> Self {
     }
 }
 
+impl TextHorizontalAlignment {
+    /// Returns the alignment to use in a window with the given layout direction: `left` and
+    /// `right` are swapped in right to left windows.
+    pub fn for_layout_direction(self, direction: super::LayoutDirection) -> Self {
+        match (self, direction) {
+            (Self::left, super::LayoutDirection::rtl) => Self::right,
+            (Self::right, super::LayoutDirection::rtl) => Self::left,
+            (alignment, _) => alignment,
+        }
+    }
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, strum::EnumString, strum::Display)]
 #[repr(C)]
 #[allow(non_camel_case_types)]
@@ -92,6 +110,46 @@ fn default() -> Self {
     }
 }
 
+/// The kind of text a `TextInput` accepts, and how it is shown
+#[derive(Copy, Clone, Debug, PartialEq, strum::EnumString, strum::Display)]
+#[repr(C)]
+#[allow(non_camel_case_types)]
+pub enum InputType {
+    /// Any text
+    text,
+    /// Any text, shown masked by the password character
+    password,
+    /// An integer number
+    number,
+    /// A decimal number
+    decimal,
+}
+
+impl Default for InputType {
+    fn default() -> Self {
+        Self::text
+    }
+}
+
+/// The line drawn with the text
+#[derive(Copy, Clone, Debug, PartialEq, strum::EnumString, strum::Display)]
+#[repr(C)]
+#[allow(non_camel_case_types)]
+pub enum TextDecoration {
+    /// No line
+    none,
+    /// A line under the text
+    underline,
+    /// A line through the middle of the text
+    strikethrough,
+}
+
+impl Default for TextDecoration {
+    fn default() -> Self {
+        Self::none
+    }
+}
+
 /// The implementation of the `Text` element
 #[repr(C)]
 #[derive(FieldOffsets, Default, SixtyFPSElement)]
@@ -107,10 +165,20 @@ pub struct Text {
     pub wrap: Property<TextWrap>,
     pub overflow: Property<TextOverflow>,
     pub letter_spacing: Property<f32>,
+    pub font_italic: Property<bool>,
+    pub text_decoration: Property<TextDecoration>,
+    pub line_height: Property<f32>,
+    pub max_lines: Property<i32>,
+    /// The text with markup, shown instead of `text` when it is not empty
+    pub styled_text: Property<SharedString>,
+    pub link_clicked: Callback<StringArg>,
     pub x: Property<f32>,
     pub y: Property<f32>,
     pub width: Property<f32>,
     pub height: Property<f32>,
+    /// Set while the mouse is pressed on a link
+    pub pressed: core::cell::Cell<bool>,
+    data: TextDataBox,
     pub cached_rendering_data: CachedRenderingData,
 }
 
@@ -122,8 +190,14 @@ fn geometry(self: Pin<&Self>) -> Rect {
     }
 
     fn layout_info(self: Pin<&Self>, orientation: Orientation, window: &WindowRc) -> LayoutInfo {
-        let implicit_size = |max_width| {
-            window.text_size(self.unresolved_font_request(), self.text().as_str(), max_width)
+        let styled_text = self.parsed_styled_text();
+        let implicit_size = |max_width| match &styled_text {
+            Some(styled_text) => {
+                styled_text_size(styled_text, self.unresolved_font_request(), window, max_width)
+            }
+            None => {
+                window.text_size(self.unresolved_font_request(), self.text().as_str(), max_width)
+            }
         };
 
         // Stretch uses `round_layout` to explicitly align the top left and bottom right of layout nodes
@@ -148,11 +222,17 @@ fn layout_info(self: Pin<&Self>, orientation: Orientation, window: &WindowRc) ->
                 }
             }
             Orientation::Vertical => {
-                let h = match self.wrap() {
+                let mut h = match self.wrap() {
                     TextWrap::no_wrap => implicit_size(None).height,
                     TextWrap::word_wrap => implicit_size(Some(self.width())).height,
+                };
+                let max_lines = self.max_lines();
+                if max_lines > 0 {
+                    let line_advance =
+                        window.text_size(self.unresolved_font_request(), "*", None).height;
+                    h = h.min(max_lines as f32 * line_advance);
                 }
-                .ceil();
+                let h = h.ceil();
                 LayoutInfo { min: h, preferred: h, ..LayoutInfo::default() }
             }
         }
@@ -164,16 +244,47 @@ fn input_event_filter_before_children(
         _window: &WindowRc,
         _self_rc: &ItemRc,
     ) -> InputEventFilterResult {
-        InputEventFilterResult::ForwardAndIgnore
+        if self.styled_text().is_empty() {
+            InputEventFilterResult::ForwardAndIgnore
+        } else {
+            // The links of the styled text can be clicked
+            InputEventFilterResult::ForwardEvent
+        }
     }
 
     fn input_event(
         self: Pin<&Self>,
-        _: MouseEvent,
-        _window: &WindowRc,
+        event: MouseEvent,
+        window: &WindowRc,
         _self_rc: &ItemRc,
     ) -> InputEventResult {
-        InputEventResult::EventIgnored
+        let link_at = |pos| {
+            let styled_text = self.parsed_styled_text()?;
+            let offset = window.text_byte_offset_for_position(self, pos)?;
+            styled_text.link_at(offset).cloned()
+        };
+        match event {
+            MouseEvent::MousePressed { pos, button: PointerEventButton::left, .. }
+                if link_at(pos).is_some() =>
+            {
+                self.pressed.set(true);
+                InputEventResult::GrabMouse
+            }
+            MouseEvent::MouseExit => {
+                self.pressed.set(false);
+                InputEventResult::EventIgnored
+            }
+            MouseEvent::MouseReleased { pos, button: PointerEventButton::left }
+                if self.pressed.get() =>
+            {
+                self.pressed.set(false);
+                if let Some(link) = link_at(pos) {
+                    Self::FIELD_OFFSETS.link_clicked.apply_pin(self).call(&(link,));
+                }
+                InputEventResult::EventAccepted
+            }
+            _ => InputEventResult::EventIgnored,
+        }
     }
 
     fn key_event(self: Pin<&Self>, _: &KeyEvent, _window: &WindowRc) -> KeyEventResult {
@@ -193,6 +304,15 @@ impl ItemConsts for Text {
 }
 
 impl Text {
+    /// The horizontal alignment of the text, mirrored if the layout direction of the window is
+    /// right to left.
+    pub fn visual_horizontal_alignment(
+        self: Pin<&Self>,
+        window: &WindowRc,
+    ) -> TextHorizontalAlignment {
+        self.horizontal_alignment().for_layout_direction(window.layout_direction())
+    }
+
     pub fn unresolved_font_request(self: Pin<&Self>) -> FontRequest {
         FontRequest {
             family: {
@@ -220,10 +340,112 @@ pub fn unresolved_font_request(self: Pin<&Self>) -> FontRequest {
                 }
             },
             letter_spacing: Some(self.letter_spacing()),
+            italic: self.font_italic(),
+            line_height: {
+                let line_height = self.line_height();
+                if line_height > 0. {
+                    Some(line_height)
+                } else {
+                    None
+                }
+            },
+            text_decoration: self.text_decoration(),
+        }
+    }
+
+    /// Returns the parsed `styled-text`, or None if it is empty and `text` is shown instead.
+    /// The markup is only parsed again when it changes.
+    pub fn parsed_styled_text(self: Pin<&Self>) -> Option<Rc<StyledText>> {
+        let markup = self.styled_text();
+        if markup.is_empty() {
+            return None;
+        }
+        let mut cache = self.data.styled_text.borrow_mut();
+        match cache.as_ref() {
+            Some((cached_markup, styled_text)) if *cached_markup == markup => {
+                Some(styled_text.clone())
+            }
+            _ => {
+                let styled_text = Rc::new(StyledText::parse(&markup));
+                *cache = Some((markup, styled_text.clone()));
+                Some(styled_text)
+            }
         }
     }
 }
 
+/// Returns the size of the styled text, with each word measured with the font of its span and
+/// the lines wrapped the same way as when the text is drawn.
+fn styled_text_size(
+    styled_text: &StyledText,
+    font_request: FontRequest,
+    window: &WindowRc,
+    max_width: Option<f32>,
+) -> Size {
+    let span_fonts = styled_text
+        .spans
+        .iter()
+        .map(|span| span.style.font_request(font_request.clone()))
+        .collect::<Vec<_>>();
+    let lines = styled_text.layout_lines(max_width, |span_index, word| {
+        window.text_size(span_fonts[span_index].clone(), word, None).width
+    });
+    let width =
+        lines.iter().map(|line| line.last().map_or(0., |run| run.x + run.width)).fold(0., f32::max);
+    let line_height = span_fonts
+        .into_iter()
+        .map(|font| window.text_size(font, " ", None).height)
+        .fold(0., f32::max);
+    Size::new(width, lines.len() as f32 * line_height)
+}
+
+/// The internal state of a Text that is not exposed through properties
+#[derive(Default)]
+pub struct TextData {
+    /// The markup of the last parsed `styled-text`, and the result of parsing it
+    styled_text: RefCell<Option<(SharedString, Rc<StyledText>)>>,
+}
+
+#[repr(C)]
+/// Wraps the internal data structure for the Text
+pub struct TextDataBox(core::ptr::NonNull<TextData>);
+
+impl Default for TextDataBox {
+    fn default() -> Self {
+        TextDataBox(Box::leak(Box::new(TextData::default())).into())
+    }
+}
+impl Drop for TextDataBox {
+    fn drop(&mut self) {
+        // Safety: the self.0 was constructed from a Box::leak in TextDataBox::default
+        unsafe {
+            Box::from_raw(self.0.as_ptr());
+        }
+    }
+}
+impl core::ops::Deref for TextDataBox {
+    type Target = TextData;
+    fn deref(&self) -> &Self::Target {
+        // Safety: initialized in TextDataBox::default
+        unsafe { self.0.as_ref() }
+    }
+}
+
+/// # Safety
+/// This must be called using a non-null pointer pointing to a chunk of memory big enough to
+/// hold a TextDataBox
+#[no_mangle]
+pub unsafe extern "C" fn sixtyfps_text_data_init(data: *mut TextDataBox) {
+    core::ptr::write(data, TextDataBox::default());
+}
+
+/// # Safety
+/// This must be called using a non-null pointer pointing to an initialized TextDataBox
+#[no_mangle]
+pub unsafe extern "C" fn sixtyfps_text_data_free(data: *mut TextDataBox) {
+    core::ptr::drop_in_place(data);
+}
+
 /// The implementation of the `TextInput` element
 #[repr(C)]
 #[derive(FieldOffsets, Default, SixtyFPSElement)]
@@ -240,6 +462,9 @@ pub struct TextInput {
     pub vertical_alignment: Property<TextVerticalAlignment>,
     pub wrap: Property<TextWrap>,
     pub letter_spacing: Property<f32>,
+    pub font_italic: Property<bool>,
+    pub text_decoration: Property<TextDecoration>,
+    pub line_height: Property<f32>,
     pub x: Property<f32>,
     pub y: Property<f32>,
     pub width: Property<f32>,
@@ -248,6 +473,10 @@ pub struct TextInput {
     pub anchor_position: Property<i32>, // byte offset
     pub text_cursor_width: Property<f32>,
     pub cursor_visible: Property<bool>,
+    /// The text being composed with the input method, shown at the cursor position
+    pub preedit_text: Property<SharedString>,
+    pub preedit_selection_start: Property<i32>, // byte offset in the preedit text
+    pub preedit_selection_end: Property<i32>,   // byte offset in the preedit text
     pub has_focus: Property<bool>,
     pub enabled: Property<bool>,
     pub accepted: Callback<VoidArg>,
@@ -255,6 +484,15 @@ pub struct TextInput {
     pub edited: Callback<VoidArg>,
     pub pressed: core::cell::Cell<bool>,
     pub single_line: Property<bool>,
+    pub input_type: Property<InputType>,
+    pub password_character: Property<SharedString>,
+    pub validator_regex: Property<SharedString>,
+    pub minimum: Property<f32>,
+    pub maximum: Property<f32>,
+    pub tab_order: Property<i32>,
+    pub can_undo: Property<bool>,
+    pub can_redo: Property<bool>,
+    data: TextInputDataBox,
     pub cached_rendering_data: CachedRenderingData,
 }
 
@@ -267,7 +505,7 @@ fn geometry(self: Pin<&Self>) -> Rect {
     }
 
     fn layout_info(self: Pin<&Self>, orientation: Orientation, window: &WindowRc) -> LayoutInfo {
-        let text = self.text();
+        let text = self.visual_representation().text;
         let implicit_size = |max_width| {
             window.text_size(
                 self.unresolved_font_request(),
@@ -328,11 +566,18 @@ fn input_event(
             return InputEventResult::EventIgnored;
         }
         match event {
-            MouseEvent::MousePressed { pos, button: PointerEventButton::left } => {
-                let clicked_offset = window.text_input_byte_offset_for_position(self, pos) as i32;
-                self.as_ref().pressed.set(true);
-                self.as_ref().anchor_position.set(clicked_offset);
-                self.set_cursor_position(clicked_offset, window);
+            MouseEvent::MousePressed { pos, button: PointerEventButton::left, click_count } => {
+                let clicked_offset = window.text_input_byte_offset_for_position(self, pos);
+                // A fourth click starts again with a simple click
+                match click_count.saturating_sub(1) % 3 + 1 {
+                    2 => self.select_word(clicked_offset, window),
+                    3 => self.select_line(clicked_offset, window),
+                    _ => {
+                        self.as_ref().pressed.set(true);
+                        self.as_ref().anchor_position.set(clicked_offset as i32);
+                        self.set_cursor_position(clicked_offset as i32, window);
+                    }
+                }
                 if !self.has_focus() {
                     window.clone().set_focus_item(self_rc);
                 }
@@ -359,10 +604,79 @@ fn key_event(self: Pin<&Self>, event: &KeyEvent, window: &WindowRc) -> KeyEventR
         }
 
         match event.event_type {
+            KeyEventType::UpdateComposition => {
+                if self.preedit_text().is_empty() && !event.text.is_empty() && self.has_selection()
+                {
+                    // The composed text replaces the selection
+                    self.delete_selection(window);
+                }
+                self.preedit_selection_start.set(event.preedit_selection_start as i32);
+                self.preedit_selection_end.set(event.preedit_selection_end as i32);
+                self.preedit_text.set(event.text.clone());
+                self.show_cursor(window);
+                KeyEventResult::EventAccepted
+            }
+            KeyEventType::CommitComposition => {
+                self.clear_preedit();
+                let selection_before = self.cursor_and_anchor();
+                self.replace_selection(&event.text, EditKind::Typing, selection_before, window);
+                self.show_cursor(window);
+                KeyEventResult::EventAccepted
+            }
+            KeyEventType::KeyPressed if !self.preedit_text().is_empty() => {
+                // The keys belong to the input method while it composes text
+                KeyEventResult::EventAccepted
+            }
             KeyEventType::KeyPressed => {
                 if let Some(keycode) = event.text.chars().next() {
-                    if let Ok(text_cursor_movement) = TextCursorDirection::try_from(keycode.clone())
+                    if let Ok(mut text_cursor_movement) =
+                        TextCursorDirection::try_from(keycode.clone())
                     {
+                        if self.single_line()
+                            && matches!(
+                                text_cursor_movement,
+                                TextCursorDirection::PreviousLine | TextCursorDirection::NextLine
+                            )
+                        {
+                            return KeyEventResult::EventIgnored;
+                        }
+                        // The arrow keys move visually: in a right to left paragraph, the left
+                        // arrow moves to the next character
+                        if crate::bidi::is_right_to_left_at(
+                            &self.text(),
+                            self.cursor_position().max(0) as usize,
+                        ) {
+                            text_cursor_movement = match text_cursor_movement {
+                                TextCursorDirection::Forward => TextCursorDirection::Backward,
+                                TextCursorDirection::Backward => TextCursorDirection::Forward,
+                                other => other,
+                            };
+                        }
+                        if event.modifiers.control {
+                            text_cursor_movement = match text_cursor_movement {
+                                TextCursorDirection::Forward => TextCursorDirection::ForwardByWord,
+                                TextCursorDirection::Backward => {
+                                    TextCursorDirection::BackwardByWord
+                                }
+                                TextCursorDirection::StartOfLine => {
+                                    TextCursorDirection::StartOfText
+                                }
+                                TextCursorDirection::EndOfLine => TextCursorDirection::EndOfText,
+                                other => other,
+                            };
+                        }
+                        if self.input_type() == InputType::password {
+                            // Don't reveal where the words are
+                            text_cursor_movement = match text_cursor_movement {
+                                TextCursorDirection::ForwardByWord => {
+                                    TextCursorDirection::EndOfText
+                                }
+                                TextCursorDirection::BackwardByWord => {
+                                    TextCursorDirection::StartOfText
+                                }
+                                other => other,
+                            };
+                        }
                         TextInput::move_cursor(
                             self,
                             text_cursor_movement,
@@ -392,7 +706,17 @@ fn key_event(self: Pin<&Self>, event: &KeyEvent, window: &WindowRc) -> KeyEventR
                     return KeyEventResult::EventIgnored;
                 }
                 if event.modifiers.control {
-                    if event.text == "a" {
+                    if event.text.eq_ignore_ascii_case("z") {
+                        if event.modifiers.shift {
+                            self.redo(window);
+                        } else {
+                            self.undo(window);
+                        }
+                        return KeyEventResult::EventAccepted;
+                    } else if event.text.eq_ignore_ascii_case("y") {
+                        self.redo(window);
+                        return KeyEventResult::EventAccepted;
+                    } else if event.text == "a" {
                         self.select_all(window);
                         return KeyEventResult::EventAccepted;
                     } else if event.text == "c" {
@@ -401,32 +725,22 @@ fn key_event(self: Pin<&Self>, event: &KeyEvent, window: &WindowRc) -> KeyEventR
                     } else if event.text == "v" {
                         self.paste(window);
                         return KeyEventResult::EventAccepted;
-                    } else if event.text == "x" {
+                    } else if event.text == "x" && self.input_type() != InputType::password {
                         self.copy();
                         self.delete_selection(window);
                         return KeyEventResult::EventAccepted;
                     }
                     return KeyEventResult::EventIgnored;
                 }
-                self.delete_selection(window);
-
-                let mut text: String = self.text().into();
 
                 // FIXME: respect grapheme boundaries
-                let insert_pos = self.selection_anchor_and_cursor().1;
-                text.insert_str(insert_pos, &event.text);
-
-                self.as_ref().text.set(text.into());
-                let new_cursor_pos = (insert_pos + event.text.len()) as i32;
-                self.as_ref().anchor_position.set(new_cursor_pos);
-                self.set_cursor_position(new_cursor_pos, window);
+                let selection_before = self.cursor_and_anchor();
+                self.replace_selection(&event.text, EditKind::Typing, selection_before, window);
 
                 // Keep the cursor visible when inserting text. Blinking should only occur when
                 // nothing is entered or the cursor isn't moved.
                 self.as_ref().show_cursor(window);
 
-                Self::FIELD_OFFSETS.edited.apply_pin(self).call(&());
-
                 KeyEventResult::EventAccepted
             }
             _ => KeyEventResult::EventIgnored,
@@ -438,10 +752,18 @@ fn focus_event(self: Pin<&Self>, event: &FocusEvent, window: &WindowRc) {
             FocusEvent::FocusIn | FocusEvent::WindowReceivedFocus => {
                 self.has_focus.set(true);
                 self.show_cursor(window);
+                window.set_ime_allowed(self.input_type() != InputType::password);
+                let cursor_position = self.cursor_position().max(0) as usize;
+                self.update_ime_cursor_rect(
+                    window.text_input_position_for_byte_offset(self, cursor_position),
+                    window,
+                );
             }
             FocusEvent::FocusOut | FocusEvent::WindowLostFocus => {
                 self.has_focus.set(false);
-                self.hide_cursor()
+                self.hide_cursor();
+                self.clear_preedit();
+                window.set_ime_allowed(false);
             }
         }
     }
@@ -462,6 +784,10 @@ enum TextCursorDirection {
     Forward,
     Backward,
     PreviousCharacter, // breaks grapheme boundaries, so only used by delete-previous-char
+    ForwardByWord,
+    BackwardByWord,
+    PreviousLine,
+    NextLine,
     StartOfLine,
     EndOfLine,
     StartOfText,
@@ -475,6 +801,8 @@ fn try_from(value: char) -> Result<Self, Self::Error> {
         Ok(match value {
             key_codes::LeftArrow => Self::Backward,
             key_codes::RightArrow => Self::Forward,
+            key_codes::UpArrow => Self::PreviousLine,
+            key_codes::DownArrow => Self::NextLine,
             key_codes::Home => Self::StartOfLine,
             key_codes::End => Self::EndOfLine,
             _ => return Err(()),
@@ -482,6 +810,287 @@ fn try_from(value: char) -> Result<Self, Self::Error> {
     }
 }
 
+/// The kind of edit recorded in the undo history. Consecutive edits of the kind
+/// `Typing` or `Deletion` are merged into a single undo step.
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum EditKind {
+    Typing,
+    Deletion,
+    /// Paste, cut, or deletion of the selection
+    Other,
+}
+
+/// An entry of the undo history: the text `removed` at the byte offset `position`
+/// was replaced with the text `inserted`.
+#[derive(Debug)]
+struct UndoStep {
+    position: usize,
+    removed: String,
+    inserted: String,
+    /// The cursor and anchor position before the edit
+    selection_before: (i32, i32),
+    kind: EditKind,
+}
+
+impl UndoStep {
+    /// Merges `next` into this step if it continues the same typing or deletion.
+    fn merge(&mut self, next: &UndoStep) -> bool {
+        match (self.kind, next.kind) {
+            (EditKind::Typing, EditKind::Typing) => {
+                if next.removed.is_empty() && next.position == self.position + self.inserted.len() {
+                    self.inserted.push_str(&next.inserted);
+                    return true;
+                }
+            }
+            (EditKind::Deletion, EditKind::Deletion) => {
+                if next.position + next.removed.len() == self.position {
+                    // Backspace
+                    self.removed.insert_str(0, &next.removed);
+                    self.position = next.position;
+                    return true;
+                } else if next.position == self.position {
+                    // Delete
+                    self.removed.push_str(&next.removed);
+                    return true;
+                }
+            }
+            _ => {}
+        }
+        false
+    }
+}
+
+/// The undo and redo history of a TextInput
+#[derive(Default)]
+struct UndoHistory {
+    undo_stack: RefCell<Vec<UndoStep>>,
+    redo_stack: RefCell<Vec<UndoStep>>,
+    /// The text after the last change recorded in the history. When the text property
+    /// no longer has this value, the text was set programmatically and the history is stale.
+    text: RefCell<SharedString>,
+}
+
+impl UndoHistory {
+    fn record(&self, step: UndoStep, text_before: &SharedString, text_after: SharedString) {
+        if !self.is_up_to_date(text_before) {
+            self.clear();
+        }
+        self.redo_stack.borrow_mut().clear();
+        let mut undo_stack = self.undo_stack.borrow_mut();
+        if !undo_stack.last_mut().map_or(false, |last| last.merge(&step)) {
+            undo_stack.push(step);
+        }
+        *self.text.borrow_mut() = text_after;
+    }
+
+    fn is_up_to_date(&self, text: &SharedString) -> bool {
+        *self.text.borrow() == *text
+    }
+
+    fn clear(&self) {
+        self.undo_stack.borrow_mut().clear();
+        self.redo_stack.borrow_mut().clear();
+    }
+}
+
+/// The internal state of a TextInput that is not exposed through properties
+#[derive(Default)]
+pub struct TextInputData {
+    undo_history: UndoHistory,
+    /// The pattern of the last `validator-regex`, and the regular expression compiled from it
+    #[cfg(feature = "std")]
+    validator_regex: RefCell<Option<(SharedString, Option<regex::Regex>)>>,
+}
+
+impl TextInputData {
+    /// Returns true if the whole text matches the pattern. An empty or invalid pattern
+    /// matches any text. Without the `std` feature, there is no regex engine and any text matches.
+    #[cfg(feature = "std")]
+    fn matches_validator_regex(&self, pattern: &SharedString, text: &str) -> bool {
+        if pattern.is_empty() {
+            return true;
+        }
+        let mut cache = self.validator_regex.borrow_mut();
+        if cache.as_ref().map_or(true, |(cached_pattern, _)| cached_pattern != pattern) {
+            let regex = regex::Regex::new(&alloc::format!("^(?:{})$", pattern)).ok();
+            *cache = Some((pattern.clone(), regex));
+        }
+        cache
+            .as_ref()
+            .and_then(|(_, regex)| regex.as_ref())
+            .map_or(true, |regex| regex.is_match(text))
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn matches_validator_regex(&self, _pattern: &SharedString, _text: &str) -> bool {
+        // The regex crate needs std: the `validator-regex` property is documented as being ignored
+        true
+    }
+}
+
+/// Returns true if the text is a number, or the beginning of a number, that can still be
+/// between `minimum` and `maximum` once more digits are typed. The range is only checked when
+/// `minimum` is smaller than `maximum`.
+fn is_acceptable_number(text: &str, decimal: bool, minimum: f32, maximum: f32) -> bool {
+    let has_range = minimum < maximum;
+    let digits = text.strip_prefix('-').unwrap_or(text);
+    if digits.len() != text.len() && has_range && minimum >= 0. {
+        return false;
+    }
+    let mut has_decimal_point = false;
+    for c in digits.chars() {
+        if c == '.' && decimal && !has_decimal_point {
+            has_decimal_point = true;
+        } else if !c.is_ascii_digit() {
+            return false;
+        }
+    }
+    if !has_range {
+        return true;
+    }
+    // Typing more digits only moves the value away from zero, so only the values that are
+    // already too far from it are rejected.
+    match text.parse::<f32>() {
+        Ok(value) => !(value > maximum && value > 0.) && !(value < minimum && value < 0.),
+        Err(_) => true,
+    }
+}
+
+/// The text of a `TextInput` as the renderers show it. It contains the preedit text of the
+/// input method at the cursor position, and is masked when the input type is password. The
+/// cursor and anchor positions are byte offsets in that text.
+pub struct TextInputVisualRepresentation {
+    pub text: SharedString,
+    /// The range of the preedit text in `text`, which is empty when there is no composition.
+    /// The renderers underline it.
+    pub preedit_range: core::ops::Range<usize>,
+    pub cursor_position: i32,
+    pub anchor_position: i32,
+    /// The text with the preedit text, before masking
+    unmasked_text: SharedString,
+    /// The byte offset in the `text` property where the preedit text is inserted
+    preedit_start: usize,
+    preedit_len: usize,
+    /// The length in bytes of the password character, or 0 when the text is not masked
+    mask_len: usize,
+}
+
+impl TextInputVisualRepresentation {
+    /// Returns the byte offset in the shown text of the byte offset in the `text` property
+    pub fn map_byte_offset_to_visual(&self, byte_offset: usize) -> usize {
+        if self.preedit_len > 0 && byte_offset > self.preedit_start {
+            self.mask_offset(byte_offset + self.preedit_len)
+        } else {
+            self.mask_offset(byte_offset)
+        }
+    }
+
+    /// Returns the byte offset in the `text` property of the byte offset in the shown text.
+    /// The offsets within the preedit text are mapped to the position where it is inserted.
+    pub fn map_byte_offset_from_visual(&self, byte_offset: usize) -> usize {
+        let unmasked_offset = self.unmask_offset(byte_offset);
+        if unmasked_offset <= self.preedit_start {
+            unmasked_offset
+        } else if unmasked_offset < self.preedit_start + self.preedit_len {
+            self.preedit_start
+        } else {
+            unmasked_offset - self.preedit_len
+        }
+    }
+
+    /// Same as [`TextInput::selection_anchor_and_cursor`], in the shown text
+    pub fn selection_anchor_and_cursor(&self) -> (usize, usize) {
+        let max_pos = self.text.len() as i32;
+        let cursor_pos = self.cursor_position.max(0).min(max_pos);
+        let anchor_pos = self.anchor_position.max(0).min(max_pos);
+
+        if anchor_pos > cursor_pos {
+            (cursor_pos as _, anchor_pos as _)
+        } else {
+            (anchor_pos as _, cursor_pos as _)
+        }
+    }
+
+    fn mask_offset(&self, unmasked_offset: usize) -> usize {
+        if self.mask_len == 0 {
+            return unmasked_offset;
+        }
+        self.unmasked_text.char_indices().take_while(|(index, _)| *index < unmasked_offset).count()
+            * self.mask_len
+    }
+
+    fn unmask_offset(&self, byte_offset: usize) -> usize {
+        if self.mask_len == 0 {
+            return byte_offset;
+        }
+        self.unmasked_text
+            .char_indices()
+            .nth(byte_offset / self.mask_len)
+            .map_or(self.unmasked_text.len(), |(index, _)| index)
+    }
+}
+
+#[repr(C)]
+/// Wraps the internal data structure for the TextInput
+pub struct TextInputDataBox(core::ptr::NonNull<TextInputData>);
+
+impl Default for TextInputDataBox {
+    fn default() -> Self {
+        TextInputDataBox(Box::leak(Box::new(TextInputData::default())).into())
+    }
+}
+impl Drop for TextInputDataBox {
+    fn drop(&mut self) {
+        // Safety: the self.0 was constructed from a Box::leak in TextInputDataBox::default
+        unsafe {
+            Box::from_raw(self.0.as_ptr());
+        }
+    }
+}
+impl core::ops::Deref for TextInputDataBox {
+    type Target = TextInputData;
+    fn deref(&self) -> &Self::Target {
+        // Safety: initialized in TextInputDataBox::default
+        unsafe { self.0.as_ref() }
+    }
+}
+
+/// # Safety
+/// This must be called using a non-null pointer pointing to a chunk of memory big enough to
+/// hold a TextInputDataBox
+#[no_mangle]
+pub unsafe extern "C" fn sixtyfps_textinput_data_init(data: *mut TextInputDataBox) {
+    core::ptr::write(data, TextInputDataBox::default());
+}
+
+/// # Safety
+/// This must be called using a non-null pointer pointing to an initialized TextInputDataBox
+#[no_mangle]
+pub unsafe extern "C" fn sixtyfps_textinput_data_free(data: *mut TextInputDataBox) {
+    core::ptr::drop_in_place(data);
+}
+
+/// Returns the byte offset of the end of the first word that ends after the byte offset,
+/// or the end of the text.
+fn next_word_boundary(text: &str, offset: usize) -> usize {
+    text.split_word_bound_indices()
+        .filter(|(_, word)| word.chars().any(char::is_alphanumeric))
+        .map(|(start, word)| start + word.len())
+        .find(|end| *end > offset)
+        .unwrap_or_else(|| text.len())
+}
+
+/// Returns the byte offset of the start of the last word that starts before the byte offset,
+/// or the start of the text.
+fn previous_word_boundary(text: &str, offset: usize) -> usize {
+    text.split_word_bound_indices()
+        .filter(|(_, word)| word.chars().any(char::is_alphanumeric))
+        .map(|(start, _)| start)
+        .take_while(|start| *start < offset)
+        .last()
+        .unwrap_or(0)
+}
+
 enum AnchorMode {
     KeepAnchor,
     MoveAnchor,
@@ -538,9 +1147,16 @@ fn move_cursor(
                     }
                 }
             }
-            // FIXME: StartOfLine and EndOfLine should respect line boundaries
-            TextCursorDirection::StartOfLine => 0,
-            TextCursorDirection::EndOfLine => text.len(),
+            TextCursorDirection::ForwardByWord => next_word_boundary(&text, last_cursor_pos),
+            TextCursorDirection::BackwardByWord => previous_word_boundary(&text, last_cursor_pos),
+            TextCursorDirection::PreviousLine => {
+                self.offset_on_adjacent_line(last_cursor_pos, false, window)
+            }
+            TextCursorDirection::NextLine => {
+                self.offset_on_adjacent_line(last_cursor_pos, true, window)
+            }
+            TextCursorDirection::StartOfLine => self.line_boundary(last_cursor_pos, false, window),
+            TextCursorDirection::EndOfLine => self.line_boundary(last_cursor_pos, true, window),
             TextCursorDirection::StartOfText => 0,
             TextCursorDirection::EndOfText => text.len(),
         };
@@ -560,19 +1176,100 @@ fn move_cursor(
         new_cursor_pos != last_cursor_pos
     }
 
+    /// Returns the start (or the end if `end` is true) of the visual line containing the byte
+    /// offset. The line geometry is found by hit testing in the backend.
+    fn line_boundary(self: Pin<&Self>, offset: usize, end: bool, window: &WindowRc) -> usize {
+        if self.single_line() {
+            return if end { self.text().len() } else { 0 };
+        }
+        let line_pos = window.text_input_position_for_byte_offset(self, offset);
+        let x = if end { f32::MAX } else { f32::MIN };
+        let y = line_pos.y + self.line_advance(window) / 2.;
+        window.text_input_byte_offset_for_position(self, Point::new(x, y))
+    }
+
+    /// Returns the byte offset on the line below (or above) the byte offset which is
+    /// horizontally the closest. When there is no such line, that's the end (or the start) of
+    /// the text.
+    fn offset_on_adjacent_line(
+        self: Pin<&Self>,
+        offset: usize,
+        below: bool,
+        window: &WindowRc,
+    ) -> usize {
+        let pos = window.text_input_position_for_byte_offset(self, offset);
+        let line_height = self.line_advance(window);
+        let y = if below { pos.y + line_height * 1.5 } else { pos.y - line_height / 2. };
+        let new_offset = window.text_input_byte_offset_for_position(self, Point::new(pos.x, y));
+        // Beyond the first or last line, the backends return an offset on another line
+        let new_y = window.text_input_position_for_byte_offset(self, new_offset).y;
+        if below && new_y <= pos.y {
+            self.text().len()
+        } else if !below && new_y >= pos.y {
+            0
+        } else {
+            new_offset
+        }
+    }
+
+    /// The distance between the top of two lines
+    fn line_advance(self: Pin<&Self>, window: &WindowRc) -> f32 {
+        window.text_size(self.unresolved_font_request(), "*", None).height
+    }
+
+    fn select_word(self: Pin<&Self>, offset: usize, window: &WindowRc) {
+        if self.input_type() == InputType::password {
+            self.select_all(window);
+            return;
+        }
+        let text = self.text();
+        let (start, end) = text
+            .split_word_bound_indices()
+            .map(|(start, word)| (start, start + word.len()))
+            .take_while(|(start, _)| *start <= offset)
+            .last()
+            .unwrap_or_default();
+        self.anchor_position.set(start as i32);
+        self.set_cursor_position(end as i32, window);
+    }
+
+    fn select_line(self: Pin<&Self>, offset: usize, window: &WindowRc) {
+        self.anchor_position.set(self.line_boundary(offset, false, window) as i32);
+        self.set_cursor_position(self.line_boundary(offset, true, window) as i32, window);
+    }
+
     fn set_cursor_position(self: Pin<&Self>, new_position: i32, window: &WindowRc) {
         self.cursor_position.set(new_position);
         if new_position >= 0 {
             let pos = window.text_input_position_for_byte_offset(self, new_position as usize);
             Self::FIELD_OFFSETS.cursor_position_changed.apply_pin(self).call(&(pos,));
+            self.update_ime_cursor_rect(pos, window);
+        }
+    }
+
+    /// Tells the input method where the cursor is, given its position, when the text input
+    /// has the focus
+    fn update_ime_cursor_rect(self: Pin<&Self>, pos: Point, window: &WindowRc) {
+        if self.has_focus() {
+            let size = Size::new(self.text_cursor_width(), self.line_advance(window));
+            window.set_text_input_cursor_rect(Rect::new(pos, size));
         }
     }
 
+    fn clear_preedit(self: Pin<&Self>) {
+        self.preedit_text.set(Default::default());
+        self.preedit_selection_start.set(0);
+        self.preedit_selection_end.set(0);
+    }
+
     fn delete_char(self: Pin<&Self>, window: &WindowRc) {
-        if !self.has_selection() {
-            self.move_cursor(TextCursorDirection::Forward, AnchorMode::KeepAnchor, window);
+        if self.has_selection() {
+            self.delete_selection(window);
+            return;
         }
-        self.delete_selection(window);
+        let selection_before = self.cursor_and_anchor();
+        self.move_cursor(TextCursorDirection::Forward, AnchorMode::KeepAnchor, window);
+        self.replace_selection("", EditKind::Deletion, selection_before, window);
     }
 
     fn delete_previous(self: Pin<&Self>, window: &WindowRc) {
@@ -580,30 +1277,197 @@ fn delete_previous(self: Pin<&Self>, window: &WindowRc) {
             self.delete_selection(window);
             return;
         }
+        let selection_before = self.cursor_and_anchor();
         if self.move_cursor(TextCursorDirection::PreviousCharacter, AnchorMode::MoveAnchor, window)
         {
-            self.delete_char(window);
+            self.move_cursor(TextCursorDirection::Forward, AnchorMode::KeepAnchor, window);
+            self.replace_selection("", EditKind::Deletion, selection_before, window);
         }
     }
 
     fn delete_selection(self: Pin<&Self>, window: &WindowRc) {
-        let text: String = self.text().into();
-        if text.is_empty() {
+        let selection_before = self.cursor_and_anchor();
+        self.replace_selection("", EditKind::Other, selection_before, window);
+    }
+
+    /// Replaces the selected text with `text_to_insert`, places the cursor after the inserted text
+    /// and records the change in the undo history. `selection_before` is the cursor and anchor
+    /// position that undoing the change restores.
+    fn replace_selection(
+        self: Pin<&Self>,
+        text_to_insert: &str,
+        kind: EditKind,
+        selection_before: (i32, i32),
+        window: &WindowRc,
+    ) {
+        let (start, end) = self.selection_anchor_and_cursor();
+        if start == end && text_to_insert.is_empty() {
             return;
         }
 
-        let (anchor, cursor) = self.selection_anchor_and_cursor();
-        if anchor == cursor {
+        let text = self.text();
+        let new_text: SharedString =
+            [text.split_at(start).0, text_to_insert, text.split_at(end).1].concat().into();
+        if !self.accepts(&new_text) {
+            return;
+        }
+        self.text.set(new_text.clone());
+
+        let step = UndoStep {
+            position: start,
+            removed: text.split_at(start).1.split_at(end - start).0.into(),
+            inserted: text_to_insert.into(),
+            selection_before,
+            kind,
+        };
+        self.data.undo_history.record(step, &text, new_text);
+        self.update_undo_state();
+
+        let cursor_pos = (start + text_to_insert.len()) as i32;
+        self.anchor_position.set(cursor_pos);
+        self.set_cursor_position(cursor_pos, window);
+        Self::FIELD_OFFSETS.edited.apply_pin(self).call(&());
+    }
+
+    /// Reverts the last edit or group of edits made by the user.
+    pub fn undo(self: Pin<&Self>, window: &WindowRc) {
+        let text = self.text();
+        if !self.data.undo_history.is_up_to_date(&text) {
+            self.data.undo_history.clear();
+            self.update_undo_state();
+            return;
+        }
+        let step = match self.data.undo_history.undo_stack.borrow_mut().pop() {
+            Some(step) => step,
+            None => return,
+        };
+
+        let new_text: SharedString = [
+            text.split_at(step.position).0,
+            step.removed.as_str(),
+            text.split_at(step.position + step.inserted.len()).1,
+        ]
+        .concat()
+        .into();
+        self.text.set(new_text.clone());
+        *self.data.undo_history.text.borrow_mut() = new_text;
+
+        let (cursor, anchor) = step.selection_before;
+        self.data.undo_history.redo_stack.borrow_mut().push(step);
+        self.update_undo_state();
+
+        self.anchor_position.set(anchor);
+        self.set_cursor_position(cursor, window);
+        Self::FIELD_OFFSETS.edited.apply_pin(self).call(&());
+    }
+
+    /// Applies again the last edit reverted with [`Self::undo`].
+    pub fn redo(self: Pin<&Self>, window: &WindowRc) {
+        let text = self.text();
+        if !self.data.undo_history.is_up_to_date(&text) {
+            self.data.undo_history.clear();
+            self.update_undo_state();
             return;
         }
+        let step = match self.data.undo_history.redo_stack.borrow_mut().pop() {
+            Some(step) => step,
+            None => return,
+        };
 
-        let text = [text.split_at(anchor).0, text.split_at(cursor).1].concat();
-        self.text.set(text.into());
-        self.anchor_position.set(anchor as i32);
-        self.set_cursor_position(anchor as i32, window);
+        let new_text: SharedString = [
+            text.split_at(step.position).0,
+            step.inserted.as_str(),
+            text.split_at(step.position + step.removed.len()).1,
+        ]
+        .concat()
+        .into();
+        self.text.set(new_text.clone());
+        *self.data.undo_history.text.borrow_mut() = new_text;
+
+        let cursor_pos = (step.position + step.inserted.len()) as i32;
+        self.data.undo_history.undo_stack.borrow_mut().push(step);
+        self.update_undo_state();
+
+        self.anchor_position.set(cursor_pos);
+        self.set_cursor_position(cursor_pos, window);
         Self::FIELD_OFFSETS.edited.apply_pin(self).call(&());
     }
 
+    /// Returns true if the input type, the range and the validator regex accept the text
+    fn accepts(self: Pin<&Self>, text: &str) -> bool {
+        let accepted_by_type = match self.input_type() {
+            InputType::text | InputType::password => true,
+            InputType::number => is_acceptable_number(text, false, self.minimum(), self.maximum()),
+            InputType::decimal => is_acceptable_number(text, true, self.minimum(), self.maximum()),
+        };
+        accepted_by_type && self.data.matches_validator_regex(&self.validator_regex(), text)
+    }
+
+    /// Returns the text as it is shown, see [`TextInputVisualRepresentation`]
+    pub fn visual_representation(self: Pin<&Self>) -> TextInputVisualRepresentation {
+        let text = self.text();
+        let mut visual = TextInputVisualRepresentation {
+            text: text.clone(),
+            preedit_range: 0..0,
+            cursor_position: self.cursor_position(),
+            anchor_position: self.anchor_position(),
+            unmasked_text: text.clone(),
+            preedit_start: 0,
+            preedit_len: 0,
+            mask_len: 0,
+        };
+        let preedit_text = self.preedit_text();
+        if !preedit_text.is_empty() {
+            let start = (visual.cursor_position.max(0) as usize).min(text.len());
+            visual.unmasked_text =
+                [text.split_at(start).0, preedit_text.as_str(), text.split_at(start).1]
+                    .concat()
+                    .into();
+            visual.text = visual.unmasked_text.clone();
+            visual.preedit_start = start;
+            visual.preedit_len = preedit_text.len();
+            visual.preedit_range = start..start + preedit_text.len();
+            // The cursor and the selection are the ones of the input method, within the preedit text
+            let preedit_offset =
+                |offset: i32| (start + (offset.max(0) as usize).min(preedit_text.len())) as i32;
+            visual.cursor_position = preedit_offset(self.preedit_selection_end());
+            visual.anchor_position = preedit_offset(self.preedit_selection_start());
+        }
+        if self.input_type() == InputType::password {
+            let password_character = self.password_character();
+            let mask = password_character.chars().next().unwrap_or('●');
+            visual.mask_len = mask.len_utf8();
+            visual.text = core::iter::repeat(mask)
+                .take(visual.unmasked_text.chars().count())
+                .collect::<String>()
+                .into();
+            let map_position = |position: i32| {
+                if position >= 0 {
+                    visual.mask_offset(position as usize) as i32
+                } else {
+                    position
+                }
+            };
+            let cursor_position = map_position(visual.cursor_position);
+            let anchor_position = map_position(visual.anchor_position);
+            let preedit_range = visual.mask_offset(visual.preedit_range.start)
+                ..visual.mask_offset(visual.preedit_range.end);
+            visual.cursor_position = cursor_position;
+            visual.anchor_position = anchor_position;
+            visual.preedit_range = preedit_range;
+        }
+        visual
+    }
+
+    fn update_undo_state(self: Pin<&Self>) {
+        self.can_undo.set(!self.data.undo_history.undo_stack.borrow().is_empty());
+        self.can_redo.set(!self.data.undo_history.redo_stack.borrow().is_empty());
+    }
+
+    fn cursor_and_anchor(self: Pin<&Self>) -> (i32, i32) {
+        (self.cursor_position(), self.anchor_position())
+    }
+
     // Avoid accessing self.cursor_position()/self.anchor_position() directly, always
     // use this bounds-checking function.
     pub fn selection_anchor_and_cursor(self: Pin<&Self>) -> (usize, usize) {
@@ -630,19 +1494,13 @@ fn selected_text(self: Pin<&Self>) -> String {
     }
 
     fn insert(self: Pin<&Self>, text_to_insert: &str, window: &WindowRc) {
-        self.delete_selection(window);
-        let mut text: String = self.text().into();
-        let cursor_pos = self.selection_anchor_and_cursor().1;
+        let selection_before = self.cursor_and_anchor();
         if text_to_insert.contains('\n') && self.single_line() {
-            text.insert_str(cursor_pos, &text_to_insert.replace('\n', " "));
+            let text_to_insert = text_to_insert.replace('\n', " ");
+            self.replace_selection(&text_to_insert, EditKind::Other, selection_before, window);
         } else {
-            text.insert_str(cursor_pos, text_to_insert);
+            self.replace_selection(text_to_insert, EditKind::Other, selection_before, window);
         }
-        let cursor_pos = cursor_pos + text_to_insert.len();
-        self.text.set(text.into());
-        self.anchor_position.set(cursor_pos as i32);
-        self.set_cursor_position(cursor_pos as i32, window);
-        Self::FIELD_OFFSETS.edited.apply_pin(self).call(&());
     }
 
     fn select_all(self: Pin<&Self>, window: &WindowRc) {
@@ -651,6 +1509,9 @@ fn select_all(self: Pin<&Self>, window: &WindowRc) {
     }
 
     fn copy(self: Pin<&Self>) {
+        if self.input_type() == InputType::password {
+            return;
+        }
         if let Some(backend) = crate::backend::instance() {
             backend.set_clipboard_text(self.selected_text());
         }
@@ -663,6 +1524,15 @@ fn paste(self: Pin<&Self>, window: &WindowRc) {
         }
     }
 
+    /// The horizontal alignment of the text, mirrored if the layout direction of the window is
+    /// right to left.
+    pub fn visual_horizontal_alignment(
+        self: Pin<&Self>,
+        window: &WindowRc,
+    ) -> TextHorizontalAlignment {
+        self.horizontal_alignment().for_layout_direction(window.layout_direction())
+    }
+
     pub fn unresolved_font_request(self: Pin<&Self>) -> FontRequest {
         FontRequest {
             family: {
@@ -690,6 +1560,16 @@ pub fn unresolved_font_request(self: Pin<&Self>) -> FontRequest {
                 }
             },
             letter_spacing: Some(self.letter_spacing()),
+            italic: self.font_italic(),
+            line_height: {
+                let line_height = self.line_height();
+                if line_height > 0. {
+                    Some(line_height)
+                } else {
+                    None
+                }
+            },
+            text_decoration: self.text_decoration(),
         }
     }
 }
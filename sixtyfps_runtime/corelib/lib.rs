@@ -76,8 +76,10 @@ unsafe impl<T> Send for OnceCell<T> {}
     unsafe impl<T> Sync for OnceCell<T> {}
 }
 
+pub mod accessibility;
 pub mod animations;
 pub mod backend;
+pub mod bidi;
 pub mod callbacks;
 pub mod component;
 pub(crate) mod flickable;
@@ -92,8 +94,10 @@ unsafe impl<T> Sync for OnceCell<T> {}
 pub mod sharedvector;
 pub mod slice;
 pub mod string;
+pub mod styled_text;
 pub mod tests;
 pub mod timers;
+pub mod translations;
 pub mod window;
 
 #[cfg(feature = "rtti")]
@@ -144,6 +148,7 @@ pub fn use_modules() -> usize {
             + graphics::ffi::sixtyfps_new_path_elements as usize
             + properties::ffi::sixtyfps_property_init as usize
             + string::ffi::sixtyfps_shared_string_bytes as usize
+            + translations::ffi::sixtyfps_translate as usize
             + window::ffi::sixtyfps_windowrc_drop as usize
             + component::ffi::sixtyfps_component_init_items as usize
             + timers::ffi::sixtyfps_timer_start as usize
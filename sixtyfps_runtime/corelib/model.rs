@@ -20,8 +20,11 @@
 use crate::properties::dependency_tracker::DependencyNode;
 use crate::Property;
 use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
 use alloc::vec::Vec;
 use core::cell::{Cell, RefCell};
+use core::cmp::Ordering;
+use core::ops::Range;
 use core::pin::Pin;
 use once_cell::unsync::OnceCell;
 use pin_project::pin_project;
@@ -105,6 +108,25 @@ pub fn row_removed(&self, index: usize, count: usize) {
                 .for_each(|p| unsafe { &**p }.row_removed(index, count))
         }
     }
+    /// Notify the peers that `count` rows starting at `from` were moved so that they now
+    /// start at the row `to` (as an index in the model after the move)
+    pub fn row_moved(&self, from: usize, to: usize, count: usize) {
+        if let Some(inner) = self.inner.get() {
+            inner
+                .as_ref()
+                .project_ref()
+                .peers
+                .for_each(|p| unsafe { &**p }.row_moved(from, to, count))
+        }
+    }
+    /// Notify the peers that the model has changed completely, so that all the rows
+    /// need to be queried again
+    pub fn reset(&self) {
+        if let Some(inner) = self.inner.get() {
+            inner.model_dirty_property.mark_dirty();
+            inner.as_ref().project_ref().peers.for_each(|p| unsafe { &**p }.reset())
+        }
+    }
     /// Attach one peer. The peer will be notified when the model changes
     #[deprecated(
         note = "In your model, re-implement `model_tracker` of the `Model` trait instead of calling this function from our `attach_peer` implementation"
@@ -213,6 +235,12 @@ fn set_row_data(&self, _row: usize, _data: Self::Data) {
             core::any::type_name::<Self>(),
         );
     }
+    /// Called by the views that only instantiate the rows that are visible, such as the
+    /// `ListView`, with the range of rows that they are about to show.
+    ///
+    /// Models that load their data lazily can use this to fetch these rows, and the ones
+    /// around them, ahead of time. The default implementation does nothing.
+    fn set_visible_rows(&self, _rows: Range<usize>) {}
     /// The implementation should forward to [`ModelNotify::attach`]
     #[deprecated(note = "Re-implement model_tracker instead of this function")]
     fn attach_peer(&self, peer: ModelPeer) {
@@ -443,6 +471,12 @@ fn set_row_data(&self, row: usize, data: Self::Data) {
         self.0.as_ref().unwrap().set_row_data(row, data)
     }
 
+    fn set_visible_rows(&self, rows: Range<usize>) {
+        if let Some(model) = self.0.as_ref() {
+            model.set_visible_rows(rows)
+        }
+    }
+
     fn attach_peer(&self, peer: ModelPeer) {
         // Forward, in case the model doesn't provide `model_tracker` yet.
         if let Some(model) = self.0.as_ref() {
@@ -460,6 +494,663 @@ fn as_any(&self) -> &dyn core::any::Any {
     }
 }
 
+/// Listens to the notifications of the model wrapped by one of the model adapters
+/// ([`FilterModel`], [`SortModel`]), and forwards them to the adapter.
+struct ModelAdapterPeer(Pin<Rc<DependencyNode<*const dyn ErasedRepeater>>>);
+
+impl ModelAdapterPeer {
+    /// Safety: `listener` must stay valid for as long as the returned peer is alive
+    unsafe fn new<T>(listener: *const dyn ErasedRepeater, source: &ModelHandle<T>) -> Self {
+        let node = Rc::pin(DependencyNode::new(listener));
+        #[allow(deprecated)]
+        source.attach_peer(ModelPeer { inner: PinWeak::downgrade(node.clone()) });
+        Self(node)
+    }
+}
+
+struct FilterModelInner<T> {
+    source: ModelHandle<T>,
+    filter: Box<dyn Fn(&T) -> bool>,
+    /// The rows of the source model that are accepted by the filter, in increasing order
+    mapping: RefCell<Vec<usize>>,
+    notify: ModelNotify,
+}
+
+impl<T> FilterModelInner<T> {
+    fn accepts(&self, row: usize) -> bool {
+        (self.filter)(&self.source.row_data(row))
+    }
+
+    fn build_mapping(&self) -> Vec<usize> {
+        (0..self.source.row_count()).filter(|row| self.accepts(*row)).collect()
+    }
+}
+
+impl<T> ErasedRepeater for FilterModelInner<T> {
+    fn row_changed(&self, row: usize) {
+        let accepted = self.accepts(row);
+        let position = self.mapping.borrow().binary_search(&row);
+        match (position, accepted) {
+            (Ok(position), true) => self.notify.row_changed(position),
+            (Ok(position), false) => {
+                self.mapping.borrow_mut().remove(position);
+                self.notify.row_removed(position, 1)
+            }
+            (Err(position), true) => {
+                self.mapping.borrow_mut().insert(position, row);
+                self.notify.row_added(position, 1)
+            }
+            (Err(_), false) => {}
+        }
+    }
+
+    fn row_added(&self, index: usize, count: usize) {
+        let added = (index..index + count).filter(|row| self.accepts(*row)).collect::<Vec<_>>();
+        let added_count = added.len();
+        let position = {
+            let mut mapping = self.mapping.borrow_mut();
+            let position = mapping.binary_search(&index).unwrap_or_else(|p| p);
+            mapping[position..].iter_mut().for_each(|row| *row += count);
+            mapping.splice(position..position, added);
+            position
+        };
+        if added_count > 0 {
+            self.notify.row_added(position, added_count);
+        }
+    }
+
+    fn row_removed(&self, index: usize, count: usize) {
+        let (start, end) = {
+            let mut mapping = self.mapping.borrow_mut();
+            let start = mapping.binary_search(&index).unwrap_or_else(|p| p);
+            let end = mapping.binary_search(&(index + count)).unwrap_or_else(|p| p);
+            mapping.drain(start..end);
+            mapping[start..].iter_mut().for_each(|row| *row -= count);
+            (start, end)
+        };
+        if end > start {
+            self.notify.row_removed(start, end - start);
+        }
+    }
+
+    fn row_moved(&self, from: usize, to: usize, count: usize) {
+        let (start, moved, position) = {
+            let mut mapping = self.mapping.borrow_mut();
+            let start = mapping.binary_search(&from).unwrap_or_else(|p| p);
+            let end = mapping.binary_search(&(from + count)).unwrap_or_else(|p| p);
+            mapping.iter_mut().for_each(|row| *row = moved_row(*row, from, to, count));
+            mapping.sort_unstable();
+            (start, end - start, mapping.binary_search(&to).unwrap_or_else(|p| p))
+        };
+        if moved > 0 && start != position {
+            self.notify.row_moved(start, position, moved);
+        }
+    }
+
+    fn reset(&self) {
+        let mapping = self.build_mapping();
+        *self.mapping.borrow_mut() = mapping;
+        self.notify.reset();
+    }
+}
+
+/// A model that only exposes the rows of another model which are accepted by a filter function.
+///
+/// The filtered model listens to the changes of the source model and updates itself accordingly.
+/// Call [`FilterModel::reset`] when the filter function itself changes its behavior.
+///
+/// ## Example
+///
+/// ```
+/// # use sixtyfps_corelib::model::{FilterModel, Model, ModelHandle, VecModel};
+/// # use std::rc::Rc;
+/// let model = Rc::new(VecModel::from(vec![1, 2, 3, 4, 5, 6]));
+/// let even = FilterModel::new(ModelHandle::new(model.clone()), |x| x % 2 == 0);
+/// assert_eq!(even.iter().collect::<Vec<_>>(), vec![2, 4, 6]);
+/// model.push(8);
+/// assert_eq!(even.iter().collect::<Vec<_>>(), vec![2, 4, 6, 8]);
+/// ```
+pub struct FilterModel<T> {
+    // Must be dropped before `inner`, as it points to it
+    _peer: ModelAdapterPeer,
+    inner: Box<FilterModelInner<T>>,
+}
+
+impl<T: 'static> FilterModel<T> {
+    /// Creates a new model containing the rows of `source` for which `filter` returns true.
+    pub fn new(source: ModelHandle<T>, filter: impl Fn(&T) -> bool + 'static) -> Self {
+        let inner = Box::new(FilterModelInner {
+            source,
+            filter: Box::new(filter),
+            mapping: Default::default(),
+            notify: Default::default(),
+        });
+        *inner.mapping.borrow_mut() = inner.build_mapping();
+        // Safety: the peer is dropped before the inner, whose address is stable as it is boxed
+        let peer = unsafe {
+            ModelAdapterPeer::new(&*inner as &dyn ErasedRepeater as *const _, &inner.source)
+        };
+        Self { _peer: peer, inner }
+    }
+
+    /// Applies the filter again on every row of the source model.
+    ///
+    /// This must be called when the filter function changes its behavior.
+    pub fn reset(&self) {
+        self.inner.reset();
+    }
+
+    /// Returns the row in the source model that corresponds to the given row of this model.
+    pub fn unfiltered_row(&self, filtered_row: usize) -> usize {
+        self.inner.mapping.borrow()[filtered_row]
+    }
+
+    /// Returns the source model
+    pub fn source_model(&self) -> &ModelHandle<T> {
+        &self.inner.source
+    }
+}
+
+impl<T: 'static> Model for FilterModel<T> {
+    type Data = T;
+
+    fn row_count(&self) -> usize {
+        self.inner.mapping.borrow().len()
+    }
+
+    fn row_data(&self, row: usize) -> Self::Data {
+        self.inner.source.row_data(self.unfiltered_row(row))
+    }
+
+    fn set_row_data(&self, row: usize, data: Self::Data) {
+        self.inner.source.set_row_data(self.unfiltered_row(row), data)
+    }
+
+    fn model_tracker(&self) -> &dyn ModelTracker {
+        &self.inner.notify
+    }
+
+    fn as_any(&self) -> &dyn core::any::Any {
+        self
+    }
+}
+
+struct SortModelInner<T> {
+    source: ModelHandle<T>,
+    compare: Box<dyn Fn(&T, &T) -> Ordering>,
+    /// The rows of the source model, in sorted order
+    mapping: RefCell<Vec<usize>>,
+    notify: ModelNotify,
+}
+
+impl<T> SortModelInner<T> {
+    fn build_mapping(&self) -> Vec<usize> {
+        let mut rows = (0..self.source.row_count())
+            .map(|row| (row, self.source.row_data(row)))
+            .collect::<Vec<_>>();
+        rows.sort_by(|a, b| (self.compare)(&a.1, &b.1));
+        rows.into_iter().map(|(row, _)| row).collect()
+    }
+
+    /// Returns the position at which the row with the given data needs to be inserted,
+    /// after all the rows that compare equal to it.
+    fn sorted_position(&self, data: &T) -> usize {
+        let mapping = self.mapping.borrow();
+        let (mut low, mut high) = (0, mapping.len());
+        while low < high {
+            let middle = (low + high) / 2;
+            if (self.compare)(&self.source.row_data(mapping[middle]), data) == Ordering::Greater {
+                high = middle;
+            } else {
+                low = middle + 1;
+            }
+        }
+        low
+    }
+}
+
+impl<T> ErasedRepeater for SortModelInner<T> {
+    fn row_changed(&self, row: usize) {
+        let old_position = match self.mapping.borrow().iter().position(|r| *r == row) {
+            Some(position) => position,
+            None => return,
+        };
+        self.mapping.borrow_mut().remove(old_position);
+        let new_position = self.sorted_position(&self.source.row_data(row));
+        self.mapping.borrow_mut().insert(new_position, row);
+        if new_position == old_position {
+            self.notify.row_changed(new_position);
+        } else {
+            self.notify.row_removed(old_position, 1);
+            self.notify.row_added(new_position, 1);
+        }
+    }
+
+    fn row_added(&self, index: usize, count: usize) {
+        self.mapping.borrow_mut().iter_mut().filter(|r| **r >= index).for_each(|r| *r += count);
+        for row in index..index + count {
+            let position = self.sorted_position(&self.source.row_data(row));
+            self.mapping.borrow_mut().insert(position, row);
+            self.notify.row_added(position, 1);
+        }
+    }
+
+    fn row_removed(&self, index: usize, count: usize) {
+        let removed = {
+            let mut mapping = self.mapping.borrow_mut();
+            let removed = mapping
+                .iter()
+                .enumerate()
+                .filter(|(_, r)| (index..index + count).contains(*r))
+                .map(|(position, _)| position)
+                .collect::<Vec<_>>();
+            mapping.iter_mut().filter(|r| **r >= index + count).for_each(|r| *r -= count);
+            removed
+        };
+        for position in removed.into_iter().rev() {
+            self.mapping.borrow_mut().remove(position);
+            self.notify.row_removed(position, 1);
+        }
+    }
+
+    fn row_moved(&self, from: usize, to: usize, count: usize) {
+        // The data didn't change, so the sorted order stays the same
+        self.mapping
+            .borrow_mut()
+            .iter_mut()
+            .for_each(|row| *row = moved_row(*row, from, to, count));
+    }
+
+    fn reset(&self) {
+        let mapping = self.build_mapping();
+        *self.mapping.borrow_mut() = mapping;
+        self.notify.reset();
+    }
+}
+
+/// A model that exposes the rows of another model sorted with a comparison function.
+///
+/// The sorted model listens to the changes of the source model and updates itself accordingly.
+/// Call [`SortModel::reset`] when the comparison function itself changes its behavior.
+///
+/// ## Example
+///
+/// ```
+/// # use sixtyfps_corelib::model::{Model, ModelHandle, SortModel, VecModel};
+/// # use std::rc::Rc;
+/// let model = Rc::new(VecModel::from(vec![3, 1, 2]));
+/// let sorted = SortModel::new(ModelHandle::new(model.clone()), |a, b| a.cmp(b));
+/// assert_eq!(sorted.iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+/// model.push(0);
+/// assert_eq!(sorted.iter().collect::<Vec<_>>(), vec![0, 1, 2, 3]);
+/// ```
+pub struct SortModel<T> {
+    // Must be dropped before `inner`, as it points to it
+    _peer: ModelAdapterPeer,
+    inner: Box<SortModelInner<T>>,
+}
+
+impl<T: 'static> SortModel<T> {
+    /// Creates a new model containing the rows of `source` sorted according to `compare`.
+    ///
+    /// The sort is stable: rows that compare equal keep their relative order.
+    pub fn new(source: ModelHandle<T>, compare: impl Fn(&T, &T) -> Ordering + 'static) -> Self {
+        let inner = Box::new(SortModelInner {
+            source,
+            compare: Box::new(compare),
+            mapping: Default::default(),
+            notify: Default::default(),
+        });
+        *inner.mapping.borrow_mut() = inner.build_mapping();
+        // Safety: the peer is dropped before the inner, whose address is stable as it is boxed
+        let peer = unsafe {
+            ModelAdapterPeer::new(&*inner as &dyn ErasedRepeater as *const _, &inner.source)
+        };
+        Self { _peer: peer, inner }
+    }
+
+    /// Sorts all the rows of the source model again.
+    ///
+    /// This must be called when the comparison function changes its behavior.
+    pub fn reset(&self) {
+        self.inner.reset();
+    }
+
+    /// Returns the row in the source model that corresponds to the given row of this model.
+    pub fn unsorted_row(&self, sorted_row: usize) -> usize {
+        self.inner.mapping.borrow()[sorted_row]
+    }
+
+    /// Returns the source model
+    pub fn source_model(&self) -> &ModelHandle<T> {
+        &self.inner.source
+    }
+}
+
+impl<T: 'static> Model for SortModel<T> {
+    type Data = T;
+
+    fn row_count(&self) -> usize {
+        self.inner.mapping.borrow().len()
+    }
+
+    fn row_data(&self, row: usize) -> Self::Data {
+        self.inner.source.row_data(self.unsorted_row(row))
+    }
+
+    fn set_row_data(&self, row: usize, data: Self::Data) {
+        self.inner.source.set_row_data(self.unsorted_row(row), data)
+    }
+
+    fn model_tracker(&self) -> &dyn ModelTracker {
+        &self.inner.notify
+    }
+
+    fn as_any(&self) -> &dyn core::any::Any {
+        self
+    }
+}
+
+/// A model that exposes the rows of another model transformed by a mapping function.
+///
+/// The rows of the mapped model are the same as the rows of the source model, so the
+/// notifications of the source model are used as is.
+///
+/// ## Example
+///
+/// ```
+/// # use sixtyfps_corelib::model::{MapModel, Model, ModelHandle, VecModel};
+/// # use std::rc::Rc;
+/// let model = Rc::new(VecModel::from(vec![1, 2, 3]));
+/// let strings = MapModel::new(ModelHandle::new(model.clone()), |x| x.to_string());
+/// assert_eq!(strings.row_data(1), "2");
+/// ```
+pub struct MapModel<T, U> {
+    source: ModelHandle<T>,
+    map: Box<dyn Fn(T) -> U>,
+}
+
+impl<T, U> MapModel<T, U> {
+    /// Creates a new model whose rows are the rows of `source` passed through `map`.
+    pub fn new(source: ModelHandle<T>, map: impl Fn(T) -> U + 'static) -> Self {
+        Self { source, map: Box::new(map) }
+    }
+
+    /// Returns the source model
+    pub fn source_model(&self) -> &ModelHandle<T> {
+        &self.source
+    }
+}
+
+impl<T: 'static, U: 'static> Model for MapModel<T, U> {
+    type Data = U;
+
+    fn row_count(&self) -> usize {
+        self.source.row_count()
+    }
+
+    fn row_data(&self, row: usize) -> Self::Data {
+        (self.map)(self.source.row_data(row))
+    }
+
+    fn set_visible_rows(&self, rows: Range<usize>) {
+        self.source.set_visible_rows(rows)
+    }
+
+    fn attach_peer(&self, peer: ModelPeer) {
+        // Forward, in case the source model doesn't provide `model_tracker` yet.
+        #[allow(deprecated)]
+        self.source.attach_peer(peer)
+    }
+
+    fn model_tracker(&self) -> &dyn ModelTracker {
+        self.source.model_tracker()
+    }
+
+    fn as_any(&self) -> &dyn core::any::Any {
+        self
+    }
+}
+
+struct LazyModelInner<T> {
+    row_count: usize,
+    page_size: usize,
+    max_cached_pages: usize,
+    /// The number of pages fetched ahead of time on each side of the visible rows
+    prefetch_pages: usize,
+    /// The pages that were requested, by page index. `None` while the data has not arrived yet.
+    pages: BTreeMap<usize, Option<Vec<T>>>,
+    /// The page of the row that was last accessed, used to find the pages that are far off-screen
+    last_page: usize,
+}
+
+impl<T> LazyModelInner<T> {
+    fn page_rows(&self, page: usize) -> Range<usize> {
+        page * self.page_size..((page + 1) * self.page_size).min(self.row_count)
+    }
+
+    /// Drop the pages that are the farthest away from the last accessed page
+    fn evict_pages(&mut self) {
+        while self.pages.len() > self.max_cached_pages.max(1) {
+            let last_page = self.last_page;
+            let farthest = self
+                .pages
+                .keys()
+                .copied()
+                .max_by_key(
+                    |page| if *page > last_page { *page - last_page } else { last_page - *page },
+                )
+                .unwrap();
+            self.pages.remove(&farthest);
+        }
+    }
+}
+
+/// A model for large data sets that loads its rows lazily, one page at a time.
+///
+/// When a `ListView` shows some rows, the fetcher function is called with the range of rows of
+/// each page that contains them, and of the pages around them (see
+/// [`LazyModel::set_prefetch_pages`]), unless these pages were already requested. A row that is
+/// accessed otherwise, with [`Model::row_data`], has its page fetched the same way. The fetcher can then load the data, possibly asynchronously, and pass it
+/// to [`LazyModel::set_rows`]. Until then, the rows of the page have the placeholder value.
+/// Only a limited number of pages are kept in memory: the pages that are the farthest away from
+/// the last visible or accessed row are evicted, and will be fetched again if they are needed.
+///
+/// This is meant to be used with a `ListView`, which only accesses the rows that are visible.
+/// Other views, such as a `for` loop in a layout, access all the rows and fetch all the pages.
+///
+/// ## Example
+///
+/// ```
+/// # use sixtyfps_corelib::model::{LazyModel, Model};
+/// # use std::rc::Rc;
+/// let model = Rc::new(LazyModel::new(1_000_000, 100, String::new(), |_rows| {}));
+/// let weak = Rc::downgrade(&model);
+/// model.set_fetcher(move |rows| {
+///     // This could also be done later, when the data arrives from the network
+///     let data = rows.clone().map(|row| format!("row {}", row)).collect();
+///     weak.upgrade().unwrap().set_rows(rows.start, data);
+/// });
+/// assert_eq!(model.row_data(4242), "row 4242");
+/// ```
+pub struct LazyModel<T> {
+    inner: RefCell<LazyModelInner<T>>,
+    placeholder: T,
+    fetcher: RefCell<Rc<dyn Fn(Range<usize>)>>,
+    /// Set while the fetcher is called from `row_data` or `set_visible_rows`, as the views must not be notified then
+    fetching: Cell<bool>,
+    notify: ModelNotify,
+}
+
+impl<T: Clone + 'static> LazyModel<T> {
+    /// Creates a new model with `row_count` rows, fetched by pages of `page_size` rows.
+    pub fn new(
+        row_count: usize,
+        page_size: usize,
+        placeholder: T,
+        fetcher: impl Fn(Range<usize>) + 'static,
+    ) -> Self {
+        Self {
+            inner: RefCell::new(LazyModelInner {
+                row_count,
+                page_size: page_size.max(1),
+                max_cached_pages: 16,
+                prefetch_pages: 1,
+                pages: Default::default(),
+                last_page: 0,
+            }),
+            placeholder,
+            fetcher: RefCell::new(Rc::new(fetcher)),
+            fetching: Cell::new(false),
+            notify: Default::default(),
+        }
+    }
+
+    /// Replaces the function that is called to fetch the rows of a page
+    pub fn set_fetcher(&self, fetcher: impl Fn(Range<usize>) + 'static) {
+        *self.fetcher.borrow_mut() = Rc::new(fetcher);
+    }
+
+    /// Sets how many pages are kept in memory (16 by default)
+    pub fn set_max_cached_pages(&self, max_cached_pages: usize) {
+        let mut inner = self.inner.borrow_mut();
+        inner.max_cached_pages = max_cached_pages;
+        inner.evict_pages();
+    }
+
+    /// Sets how many pages are fetched ahead of time before and after the pages of the rows
+    /// that are visible in a `ListView` (1 by default)
+    pub fn set_prefetch_pages(&self, prefetch_pages: usize) {
+        self.inner.borrow_mut().prefetch_pages = prefetch_pages;
+    }
+
+    /// Provides the data of the rows starting at `start`, usually in response to a call
+    /// to the fetcher.
+    pub fn set_rows(&self, start: usize, rows: Vec<T>) {
+        let count = {
+            let mut inner = self.inner.borrow_mut();
+            let count = rows.len().min(inner.row_count.saturating_sub(start));
+            for (row, data) in (start..start + count).zip(rows) {
+                let page = row / inner.page_size;
+                let page_rows = inner.page_rows(page);
+                let placeholder = &self.placeholder;
+                let page_data = inner
+                    .pages
+                    .entry(page)
+                    .or_insert(None)
+                    .get_or_insert_with(|| alloc::vec![placeholder.clone(); page_rows.len()]);
+                page_data[row - page_rows.start] = data;
+            }
+            inner.evict_pages();
+            count
+        };
+        if !self.fetching.get() {
+            for row in start..start + count {
+                self.notify.row_changed(row);
+            }
+        }
+    }
+
+    /// Changes the number of rows in the model. All the cached pages are dropped, and the
+    /// rows will be fetched again.
+    pub fn set_row_count(&self, row_count: usize) {
+        {
+            let mut inner = self.inner.borrow_mut();
+            inner.row_count = row_count;
+            inner.pages.clear();
+        }
+        self.notify.reset();
+    }
+
+    /// Calls the fetcher for each page in `pages` that was not requested yet
+    fn fetch_pages(&self, pages: Range<usize>) {
+        let missing = {
+            let mut inner = self.inner.borrow_mut();
+            let missing: Vec<usize> =
+                pages.filter(|page| !inner.pages.contains_key(page)).collect();
+            for page in &missing {
+                inner.pages.insert(*page, None);
+            }
+            inner.evict_pages();
+            missing.into_iter().map(|page| inner.page_rows(page)).collect::<Vec<_>>()
+        };
+        if missing.is_empty() {
+            return;
+        }
+        let fetcher = self.fetcher.borrow().clone();
+        self.fetching.set(true);
+        for page_rows in missing {
+            fetcher(page_rows);
+        }
+        self.fetching.set(false);
+    }
+}
+
+impl<T: Clone + 'static> Model for LazyModel<T> {
+    type Data = T;
+
+    fn row_count(&self) -> usize {
+        self.inner.borrow().row_count
+    }
+
+    fn row_data(&self, row: usize) -> Self::Data {
+        let (page, page_size) = {
+            let mut inner = self.inner.borrow_mut();
+            let page = row / inner.page_size;
+            inner.last_page = page;
+            match inner.pages.get(&page) {
+                Some(Some(data)) => return data[row - page * inner.page_size].clone(),
+                Some(None) => return self.placeholder.clone(),
+                None => {}
+            }
+            (page, inner.page_size)
+        };
+        self.fetch_pages(page..page + 1);
+        // The fetcher might have provided the data right away
+        match self.inner.borrow().pages.get(&page) {
+            Some(Some(data)) => data[row - page * page_size].clone(),
+            _ => self.placeholder.clone(),
+        }
+    }
+
+    fn set_visible_rows(&self, rows: Range<usize>) {
+        let pages = {
+            let mut inner = self.inner.borrow_mut();
+            let rows = rows.start..rows.end.min(inner.row_count);
+            if rows.is_empty() {
+                return;
+            }
+            let first_page = rows.start / inner.page_size;
+            let last_page = (rows.end - 1) / inner.page_size;
+            let page_count = (inner.row_count + inner.page_size - 1) / inner.page_size;
+            inner.last_page = (first_page + last_page) / 2;
+            first_page.saturating_sub(inner.prefetch_pages)
+                ..(last_page + inner.prefetch_pages + 1).min(page_count)
+        };
+        self.fetch_pages(pages);
+    }
+
+    fn set_row_data(&self, row: usize, data: Self::Data) {
+        {
+            let mut inner = self.inner.borrow_mut();
+            let page_size = inner.page_size;
+            match inner.pages.get_mut(&(row / page_size)) {
+                Some(Some(page_data)) => page_data[row % page_size] = data,
+                _ => return,
+            }
+        }
+        self.notify.row_changed(row);
+    }
+
+    fn model_tracker(&self) -> &dyn ModelTracker {
+        &self.notify
+    }
+
+    fn as_any(&self) -> &dyn core::any::Any {
+        self
+    }
+}
+
 /// Component that can be instantiated by a repeater.
 pub trait RepeatedComponent: crate::component::Component {
     /// The data corresponding to the model
@@ -509,10 +1200,26 @@ fn default() -> Self {
         RepeaterInner { components: Default::default(), offset: 0, cached_item_height: 0. }
     }
 }
+/// Receives the notifications of a model: implemented by the [`Repeater`] and by the model adapters
 trait ErasedRepeater {
     fn row_changed(&self, row: usize);
     fn row_added(&self, index: usize, count: usize);
     fn row_removed(&self, index: usize, count: usize);
+    fn row_moved(&self, from: usize, to: usize, count: usize);
+    fn reset(&self);
+}
+
+/// Returns the new index of `row` after the `count` rows starting at `from` were moved to `to`
+fn moved_row(row: usize, from: usize, to: usize, count: usize) -> usize {
+    if (from..from + count).contains(&row) {
+        row - from + to
+    } else if from < to && (from + count..to + count).contains(&row) {
+        row - count
+    } else if to < from && (to..from).contains(&row) {
+        row + count
+    } else {
+        row
+    }
 }
 
 impl<C: RepeatedComponent> ErasedRepeater for Repeater<C> {
@@ -571,6 +1278,41 @@ fn row_removed(&self, mut index: usize, mut count: usize) {
             c.0 = RepeatedComponentState::Dirty;
         }
     }
+    /// Notify the peers that rows were moved
+    fn row_moved(&self, from: usize, to: usize, count: usize) {
+        if count == 0 || from == to {
+            return;
+        }
+        let mut inner = self.inner.borrow_mut();
+        let inner = &mut *inner;
+        // The rows between `start` and `end` all change their index
+        let start = from.min(to).max(inner.offset);
+        let end = (from.max(to) + count).min(inner.offset + inner.components.len());
+        if start >= end {
+            return;
+        }
+        self.is_dirty.set(true);
+        let components = &mut inner.components[start - inner.offset..end - inner.offset];
+        if from.min(to) >= inner.offset && from.max(to) + count == end {
+            // All the moved rows have a component: keep these components with their rows
+            if from < to {
+                components.rotate_left(count);
+            } else {
+                components.rotate_right(count);
+            }
+        }
+        for c in components.iter_mut() {
+            c.0 = RepeatedComponentState::Dirty;
+        }
+    }
+    /// Notify the peers that the model was reset
+    fn reset(&self) {
+        self.is_dirty.set(true);
+        for c in self.inner.borrow_mut().components.iter_mut() {
+            // The components are kept to be re-used with the new data
+            c.0 = RepeatedComponentState::Dirty;
+        }
+    }
 }
 
 /// This field is put in a component when using the `for` syntax
@@ -770,6 +1512,7 @@ pub fn ensure_updated_listview(
                 loop {
                     self.inner.borrow_mut().cached_item_height = element_height;
                     self.set_offset(offset, count);
+                    model.set_visible_rows(offset..offset + count);
                     self.ensure_updated_impl(init, &model, count);
                     let end = self.compute_layout_listview(viewport_width, listview_width);
                     let adjusted_element_height =
@@ -947,3 +1690,241 @@ fn test_tracking_model_handle() {
         1
     );
 }
+
+#[cfg(test)]
+#[derive(Default)]
+struct TestView {
+    changes: RefCell<Vec<alloc::string::String>>,
+}
+
+#[cfg(test)]
+impl ErasedRepeater for TestView {
+    fn row_changed(&self, row: usize) {
+        self.changes.borrow_mut().push(alloc::format!("changed {}", row));
+    }
+    fn row_added(&self, index: usize, count: usize) {
+        self.changes.borrow_mut().push(alloc::format!("added {} {}", index, count));
+    }
+    fn row_removed(&self, index: usize, count: usize) {
+        self.changes.borrow_mut().push(alloc::format!("removed {} {}", index, count));
+    }
+    fn row_moved(&self, from: usize, to: usize, count: usize) {
+        self.changes.borrow_mut().push(alloc::format!("moved {} {} {}", from, to, count));
+    }
+    fn reset(&self) {
+        self.changes.borrow_mut().push("reset".into());
+    }
+}
+
+#[test]
+fn test_filter_model() {
+    let model = Rc::new(VecModel::from(vec![1, 2, 3, 4, 5, 6]));
+    let filtered = Rc::new(FilterModel::new(ModelHandle::new(model.clone()), |x| x % 2 == 0));
+    let view = Box::new(TestView::default());
+    let _peer = unsafe {
+        ModelAdapterPeer::new(
+            &*view as &dyn ErasedRepeater as *const _,
+            &ModelHandle::new(filtered.clone()),
+        )
+    };
+    assert_eq!(filtered.iter().collect::<Vec<_>>(), vec![2, 4, 6]);
+
+    model.push(8);
+    model.push(9);
+    assert_eq!(filtered.iter().collect::<Vec<_>>(), vec![2, 4, 6, 8]);
+    assert_eq!(filtered.unfiltered_row(3), 6);
+    model.remove(1);
+    assert_eq!(filtered.iter().collect::<Vec<_>>(), vec![4, 6, 8]);
+    model.remove(0);
+    model.set_row_data(0, 10);
+    model.set_row_data(1, 7);
+    model.set_row_data(0, 3);
+    assert_eq!(filtered.iter().collect::<Vec<_>>(), vec![6, 8]);
+    filtered.set_row_data(0, 12);
+    assert_eq!(model.iter().collect::<Vec<_>>(), vec![3, 7, 5, 12, 8, 9]);
+    assert_eq!(
+        *view.changes.borrow(),
+        vec!["added 3 1", "removed 0 1", "added 0 1", "removed 1 1", "removed 0 1", "changed 0"]
+    );
+}
+
+#[test]
+fn test_sort_model() {
+    let model = Rc::new(VecModel::from(vec![(3, 'a'), (1, 'b'), (2, 'c'), (1, 'd')]));
+    let sorted = Rc::new(SortModel::new(ModelHandle::new(model.clone()), |a, b| a.0.cmp(&b.0)));
+    let view = Box::new(TestView::default());
+    let _peer = unsafe {
+        ModelAdapterPeer::new(
+            &*view as &dyn ErasedRepeater as *const _,
+            &ModelHandle::new(sorted.clone()),
+        )
+    };
+    let letters = || sorted.iter().map(|x| x.1).collect::<String>();
+    assert_eq!(letters(), "bdca");
+
+    model.push((2, 'e'));
+    assert_eq!(letters(), "bdcea");
+    model.remove(0);
+    assert_eq!(letters(), "bdce");
+    assert_eq!(sorted.unsorted_row(1), 2);
+    model.set_row_data(0, (5, 'b'));
+    assert_eq!(letters(), "dceb");
+    model.set_row_data(1, (1, 'f'));
+    assert_eq!(letters(), "dfeb");
+    assert_eq!(
+        *view.changes.borrow(),
+        vec!["added 3 1", "removed 4 1", "removed 0 1", "added 3 1", "changed 1"]
+    );
+}
+
+#[test]
+fn test_map_model() {
+    let model = Rc::new(VecModel::from(vec![1, 2, 3]));
+    let mapped = MapModel::new(ModelHandle::new(model.clone()), |x| x * 10);
+    assert_eq!(mapped.iter().collect::<Vec<_>>(), vec![10, 20, 30]);
+    model.push(4);
+    assert_eq!(mapped.row_count(), 4);
+    assert_eq!(mapped.row_data(3), 40);
+}
+
+#[test]
+fn test_moved_rows_in_adapters() {
+    struct MovableModel {
+        array: RefCell<Vec<i32>>,
+        notify: ModelNotify,
+    }
+    impl Model for MovableModel {
+        type Data = i32;
+        fn row_count(&self) -> usize {
+            self.array.borrow().len()
+        }
+        fn row_data(&self, row: usize) -> i32 {
+            self.array.borrow()[row]
+        }
+        fn model_tracker(&self) -> &dyn ModelTracker {
+            &self.notify
+        }
+        fn as_any(&self) -> &dyn core::any::Any {
+            self
+        }
+    }
+    let model = Rc::new(MovableModel {
+        array: RefCell::new(vec![1, 2, 3, 4, 5, 6]),
+        notify: Default::default(),
+    });
+    let filtered = Rc::new(FilterModel::new(ModelHandle::new(model.clone()), |x| x % 2 == 0));
+    let sorted = Rc::new(SortModel::new(ModelHandle::new(model.clone()), |a, b| b.cmp(a)));
+    let filtered_view = Box::new(TestView::default());
+    let sorted_view = Box::new(TestView::default());
+    let _peers = unsafe {
+        [
+            ModelAdapterPeer::new(
+                &*filtered_view as &dyn ErasedRepeater as *const _,
+                &ModelHandle::new(filtered.clone()),
+            ),
+            ModelAdapterPeer::new(
+                &*sorted_view as &dyn ErasedRepeater as *const _,
+                &ModelHandle::new(sorted.clone()),
+            ),
+        ]
+    };
+
+    // Move [5, 6] to the front
+    model.array.borrow_mut().rotate_right(2);
+    model.notify.row_moved(4, 0, 2);
+    assert_eq!(filtered.iter().collect::<Vec<_>>(), vec![6, 2, 4]);
+    assert_eq!(sorted.iter().collect::<Vec<_>>(), vec![6, 5, 4, 3, 2, 1]);
+    assert_eq!(sorted.unsorted_row(0), 1);
+
+    // Move [6] after [2]
+    model.array.borrow_mut()[1..4].rotate_left(1);
+    model.notify.row_moved(1, 3, 1);
+    assert_eq!(model.iter().collect::<Vec<_>>(), vec![5, 1, 2, 6, 3, 4]);
+    assert_eq!(filtered.iter().collect::<Vec<_>>(), vec![2, 6, 4]);
+    assert_eq!(filtered.unfiltered_row(1), 3);
+
+    *model.array.borrow_mut() = vec![8, 7];
+    model.notify.reset();
+    assert_eq!(filtered.iter().collect::<Vec<_>>(), vec![8]);
+    assert_eq!(sorted.iter().collect::<Vec<_>>(), vec![8, 7]);
+
+    assert_eq!(*filtered_view.changes.borrow(), vec!["moved 2 0 1", "moved 0 1 1", "reset"]);
+    assert_eq!(*sorted_view.changes.borrow(), vec!["reset"]);
+}
+
+#[test]
+fn test_moved_row() {
+    // [a, b, c, d, e] -> [a, d, e, b, c]
+    assert_eq!((0..5).map(|row| moved_row(row, 3, 1, 2)).collect::<Vec<_>>(), vec![0, 3, 4, 1, 2]);
+    // [a, b, c, d, e] -> [b, c, a, d, e]
+    assert_eq!((0..5).map(|row| moved_row(row, 0, 2, 1)).collect::<Vec<_>>(), vec![2, 0, 1, 3, 4]);
+}
+
+#[test]
+fn test_lazy_model() {
+    let requests = Rc::new(RefCell::new(Vec::new()));
+    let model = {
+        let requests = requests.clone();
+        Rc::new(LazyModel::new(95, 10, -1, move |rows| requests.borrow_mut().push(rows)))
+    };
+    let view = Box::new(TestView::default());
+    let _peer = unsafe {
+        ModelAdapterPeer::new(
+            &*view as &dyn ErasedRepeater as *const _,
+            &ModelHandle::new(model.clone()),
+        )
+    };
+    model.set_max_cached_pages(2);
+
+    assert_eq!(model.row_data(12), -1);
+    assert_eq!(model.row_data(13), -1);
+    assert_eq!(*requests.borrow(), vec![10..20]);
+    model.set_rows(10, (10..20).collect());
+    assert_eq!(model.row_data(13), 13);
+    assert_eq!(view.changes.borrow().len(), 10);
+    assert_eq!(view.changes.borrow()[0], "changed 10");
+
+    // The last page is shorter
+    assert_eq!(model.row_data(94), -1);
+    assert_eq!(requests.borrow()[1], 90..95);
+    model.set_rows(90, (90..95).collect());
+    assert_eq!(model.row_data(94), 94);
+
+    // Page 1 is the farthest from page 7, so it is evicted
+    assert_eq!(model.row_data(70), -1);
+    assert_eq!(model.row_data(94), 94);
+    assert_eq!(model.row_data(15), -1);
+    assert_eq!(requests.borrow()[2..], [70..80, 10..20]);
+
+    model.set_row_count(20);
+    assert_eq!(*view.changes.borrow().last().unwrap(), "reset");
+    assert_eq!(model.row_data(15), -1);
+    assert_eq!(requests.borrow().last(), Some(&(10..20)));
+}
+
+#[test]
+fn test_lazy_model_visible_rows() {
+    let requests = Rc::new(RefCell::new(Vec::new()));
+    let model = {
+        let requests = requests.clone();
+        Rc::new(LazyModel::new(95, 10, -1, move |rows| requests.borrow_mut().push(rows)))
+    };
+    let handle = ModelHandle::new(model.clone() as Rc<dyn Model<Data = i32>>);
+
+    // The visible pages are fetched, with one page ahead on each side
+    handle.set_visible_rows(12..25);
+    assert_eq!(*requests.borrow(), vec![0..10, 10..20, 20..30, 30..40]);
+    assert_eq!(model.row_data(22), -1);
+    assert_eq!(requests.borrow().len(), 4);
+
+    // Only the pages that were not requested yet are fetched
+    model.set_prefetch_pages(2);
+    handle.set_visible_rows(41..45);
+    assert_eq!(requests.borrow()[4..], [40..50, 50..60, 60..70]);
+
+    // The pages past the end are not fetched
+    handle.set_visible_rows(90..100);
+    assert_eq!(requests.borrow()[7..], [70..80, 80..90, 90..95]);
+    handle.set_visible_rows(95..100);
+    assert_eq!(requests.borrow().len(), 10);
+}
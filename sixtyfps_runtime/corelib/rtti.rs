@@ -41,6 +41,10 @@ pub trait ValueType: 'static + Default + Clone $(+ TryInto<$ty> + TryFrom<$ty>)*
     crate::items::TextVerticalAlignment,
     crate::items::TextOverflow,
     crate::items::TextWrap,
+    crate::items::InputType,
+    crate::items::TextDecoration,
+    crate::items::LayoutDirection,
+    crate::items::BoundsBehavior,
     crate::model::StandardListViewItem,
     crate::items::ImageFit,
     crate::items::ImageRendering,
@@ -55,6 +59,8 @@ pub trait ValueType: 'static + Default + Clone $(+ TryInto<$ty> + TryFrom<$ty>)*
     crate::items::PointerEvent,
     crate::items::PointerEventButton,
     crate::items::PointerEventKind,
+    crate::input::DropEvent,
+    crate::items::AccessibleRole,
 ];
 
 /// What kind of animation is on a binding
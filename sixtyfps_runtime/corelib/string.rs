@@ -205,6 +205,200 @@ fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
     }
 }
 
+/// Returns the number of characters (unicode scalar values) in the string.
+pub fn string_length(s: &str) -> i32 {
+    s.chars().count() as i32
+}
+
+/// Returns the part of `s` that starts at the character index `start` and that contains
+/// at most `length` characters. Negative values are treated as 0.
+pub fn string_substring(s: &str, start: i32, length: i32) -> SharedString {
+    s.chars().skip(start.max(0) as usize).take(length.max(0) as usize).collect::<String>().into()
+}
+
+/// Splits `s` at each occurrence of `separator`. An empty separator splits the string
+/// into its individual characters.
+pub fn string_split(s: &str, separator: &str) -> alloc::vec::Vec<SharedString> {
+    if separator.is_empty() {
+        let mut buffer = [0; 4];
+        s.chars().map(|c| SharedString::from(&*c.encode_utf8(&mut buffer))).collect()
+    } else {
+        s.split(separator).map(SharedString::from).collect()
+    }
+}
+
+pub use sixtyfps_common::format::{FormatAlignment, FormatSpec};
+
+fn pad(
+    spec: &FormatSpec,
+    prefix: &str,
+    value: &str,
+    default_align: FormatAlignment,
+) -> SharedString {
+    let len = prefix.chars().count() + value.chars().count();
+    let padding = spec.width.saturating_sub(len);
+    let mut result = String::with_capacity(prefix.len() + value.len() + padding);
+    let (before, after) = match spec.align {
+        None if spec.zero_pad => {
+            result.push_str(prefix);
+            result.extend(core::iter::repeat('0').take(padding));
+            result.push_str(value);
+            return result.into();
+        }
+        Some(FormatAlignment::Left) => (0, padding),
+        Some(FormatAlignment::Center) => (padding / 2, padding - padding / 2),
+        Some(FormatAlignment::Right) => (padding, 0),
+        None if default_align == FormatAlignment::Left => (0, padding),
+        None => (padding, 0),
+    };
+    result.extend(core::iter::repeat(spec.fill).take(before));
+    result.push_str(prefix);
+    result.push_str(value);
+    result.extend(core::iter::repeat(spec.fill).take(after));
+    result.into()
+}
+
+/// Rounds the decimal representation `digits` (without sign) to `precision` decimals,
+/// rounding half away from zero.
+fn round_decimal(digits: &str, precision: usize) -> String {
+    let (int_part, frac_part) = match digits.find('.') {
+        Some(dot) => (&digits[..dot], &digits[dot + 1..]),
+        None => (digits, ""),
+    };
+    let mut result = int_part
+        .bytes()
+        .chain(frac_part.bytes().chain(core::iter::repeat(b'0')).take(precision))
+        .collect::<alloc::vec::Vec<u8>>();
+    if frac_part.as_bytes().get(precision).map_or(false, |d| *d >= b'5') {
+        let mut carry = true;
+        for d in result.iter_mut().rev() {
+            if *d == b'9' {
+                *d = b'0';
+            } else {
+                *d += 1;
+                carry = false;
+                break;
+            }
+        }
+        if carry {
+            result.insert(0, b'1');
+        }
+    }
+    if precision > 0 {
+        let int_len = result.len() - precision;
+        result.insert(int_len, b'.');
+    }
+    // Only ASCII digits and a dot
+    String::from_utf8(result).unwrap()
+}
+
+/// Format a number with the given format specifier. Numbers are right-aligned by default.
+/// An invalid specifier is treated like an empty one.
+///
+/// The `float` type of the .60 language is a f32. The number is formatted from the shortest
+/// decimal representation of its f32 value, so that the result does not depend on whether the
+/// backend stores it as f32 or as f64. Use [`format_integer`] for the `int` type.
+pub fn format_number(value: f64, spec: &str) -> SharedString {
+    let spec = FormatSpec::parse(spec).unwrap_or_default();
+    let value = value as f32;
+    let negative = value < 0.;
+    let magnitude = if negative { -value } else { value };
+    let mut digits = alloc::format!("{}", magnitude);
+    if let (Some(precision), true) = (spec.precision, magnitude.is_finite()) {
+        digits = round_decimal(&digits, precision);
+    }
+    format_digits(&spec, negative, digits)
+}
+
+/// Format an integer with the given format specifier, like [`format_number`], but without
+/// going through a floating point value, so that all the digits are exact.
+pub fn format_integer(value: i64, spec: &str) -> SharedString {
+    let spec = FormatSpec::parse(spec).unwrap_or_default();
+    let mut digits = alloc::format!("{}", value.unsigned_abs());
+    if let Some(precision) = spec.precision {
+        digits = round_decimal(&digits, precision);
+    }
+    format_digits(&spec, value < 0, digits)
+}
+
+/// Adds the thousands separators, the sign and the padding to the decimal representation
+/// `digits` of the magnitude of a number.
+fn format_digits(spec: &FormatSpec, negative: bool, mut digits: String) -> SharedString {
+    if spec.grouping {
+        let int_len = digits.find(|c: char| !c.is_ascii_digit()).unwrap_or(digits.len());
+        let mut grouped = String::with_capacity(digits.len() + int_len / 3);
+        for (i, c) in digits[..int_len].chars().enumerate() {
+            if i > 0 && (int_len - i) % 3 == 0 {
+                grouped.push(',');
+            }
+            grouped.push(c);
+        }
+        grouped.push_str(&digits[int_len..]);
+        digits = grouped;
+    }
+    let sign = if negative {
+        "-"
+    } else if spec.sign {
+        "+"
+    } else {
+        ""
+    };
+    pad(spec, sign, &digits, FormatAlignment::Right)
+}
+
+/// Format a string with the given format specifier. Strings are left-aligned by default and
+/// the precision is the maximum amount of characters that are kept.
+/// An invalid specifier is treated like an empty one.
+pub fn format_string(value: &str, spec: &str) -> SharedString {
+    let spec = FormatSpec::parse(spec).unwrap_or_default();
+    match spec.precision {
+        Some(precision) => {
+            let truncated = value.chars().take(precision).collect::<String>();
+            pad(&spec, "", &truncated, FormatAlignment::Left)
+        }
+        None => pad(&spec, "", value, FormatAlignment::Left),
+    }
+}
+
+#[test]
+fn test_string_functions() {
+    assert_eq!(string_length("héllo"), 5);
+    assert_eq!(string_substring("héllo world", 1, 4), "éllo");
+    assert_eq!(string_substring("héllo", 3, 100), "lo");
+    assert_eq!(string_substring("héllo", -1, 2), "hé");
+    assert_eq!(string_substring("héllo", 10, 2), "");
+    assert_eq!(string_split("a,b,,c", ","), ["a", "b", "", "c"]);
+    assert_eq!(string_split("a--b", "--"), ["a", "b"]);
+    assert_eq!(string_split("hé", ""), ["h", "é"]);
+}
+
+#[test]
+fn test_format_spec() {
+    assert_eq!(format_number(3.14159, ".2"), "3.14");
+    assert_eq!(format_number(42., "05"), "00042");
+    assert_eq!(format_number(-42., "05"), "-0042");
+    assert_eq!(format_number(42., "+"), "+42");
+    assert_eq!(format_number(42., "5"), "   42");
+    assert_eq!(format_number(42., "<5"), "42   ");
+    assert_eq!(format_number(42., "*^6"), "**42**");
+    assert_eq!(format_number(1234567.5, ",.2"), "1,234,567.50");
+    // 1234567.891 is 1234567.875 as f32, whose shortest representation is 1234567.9
+    assert_eq!(format_number(1234567.891, ",.2"), "1,234,567.90");
+    assert_eq!(format_number(0.125, ".2"), "0.13");
+    assert_eq!(format_number(9.999, ".2"), "10.00");
+    assert_eq!(format_number(2.5, ".0"), "3");
+    assert_eq!(format_number(-123456., ","), "-123,456");
+    assert_eq!(format_number(999., ","), "999");
+    // Integers above 2^24 cannot be represented exactly as f32
+    assert_eq!(format_integer(123456789, ","), "123,456,789");
+    assert_eq!(format_integer(-2147483648, ",.1"), "-2,147,483,648.0");
+    assert_eq!(format_integer(16777217, "+012"), "+00016777217");
+    assert_eq!(format_string("abc", "5"), "abc  ");
+    assert_eq!(format_string("abc", ">5"), "  abc");
+    assert_eq!(format_string("abcdef", ".3"), "abc");
+    assert_eq!(format_string("abcdef", "-^7.4"), "-abcd--");
+}
+
 #[test]
 fn simple_test() {
     let x = SharedString::from("hello world!");
@@ -327,4 +521,86 @@ fn test_sixtyfps_shared_string_append() {
         append("!");
         assert_eq!(s.as_str(), "Hello, world!");
     }
+
+    /// Returns the number of characters in the string
+    #[no_mangle]
+    pub extern "C" fn sixtyfps_shared_string_length(ss: &SharedString) -> i32 {
+        string_length(ss)
+    }
+
+    /// Replace `out` with the upper case version of `ss`
+    #[no_mangle]
+    pub extern "C" fn sixtyfps_shared_string_to_uppercase(
+        out: &mut SharedString,
+        ss: &SharedString,
+    ) {
+        *out = ss.to_uppercase().into();
+    }
+
+    /// Replace `out` with the lower case version of `ss`
+    #[no_mangle]
+    pub extern "C" fn sixtyfps_shared_string_to_lowercase(
+        out: &mut SharedString,
+        ss: &SharedString,
+    ) {
+        *out = ss.to_lowercase().into();
+    }
+
+    /// Replace `out` with `ss` without leading and trailing whitespace
+    #[no_mangle]
+    pub extern "C" fn sixtyfps_shared_string_trim(out: &mut SharedString, ss: &SharedString) {
+        *out = ss.trim().into();
+    }
+
+    /// Replace `out` with at most `length` characters of `ss`, starting at the character `start`
+    #[no_mangle]
+    pub extern "C" fn sixtyfps_shared_string_substring(
+        out: &mut SharedString,
+        ss: &SharedString,
+        start: i32,
+        length: i32,
+    ) {
+        *out = string_substring(ss, start, length);
+    }
+
+    /// Replace `out` with `ss` where all occurrences of `from` are replaced by `to`
+    #[no_mangle]
+    pub extern "C" fn sixtyfps_shared_string_replace(
+        out: &mut SharedString,
+        ss: &SharedString,
+        from: &SharedString,
+        to: &SharedString,
+    ) {
+        *out = ss.replace(from.as_str(), to).into();
+    }
+
+    /// Replace `out` with the number `n` formatted according to the format specifier `spec`
+    #[no_mangle]
+    pub extern "C" fn sixtyfps_shared_string_format_number(
+        out: &mut SharedString,
+        n: f64,
+        spec: &SharedString,
+    ) {
+        *out = format_number(n, spec);
+    }
+
+    /// Replace `out` with the integer `n` formatted according to the format specifier `spec`
+    #[no_mangle]
+    pub extern "C" fn sixtyfps_shared_string_format_integer(
+        out: &mut SharedString,
+        n: i64,
+        spec: &SharedString,
+    ) {
+        *out = format_integer(n, spec);
+    }
+
+    /// Replace `out` with `ss` formatted according to the format specifier `spec`
+    #[no_mangle]
+    pub extern "C" fn sixtyfps_shared_string_format_string(
+        out: &mut SharedString,
+        ss: &SharedString,
+        spec: &SharedString,
+    ) {
+        *out = format_string(ss, spec);
+    }
 }
@@ -0,0 +1,335 @@
+/* LICENSE BEGIN
+    This file is part of the SixtyFPS Project -- https://sixtyfps.io
+    Copyright (c) 2021 Olivier Goffart <olivier.goffart@sixtyfps.io>
+    Copyright (c) 2021 Simon Hausmann <simon.hausmann@sixtyfps.io>
+
+    SPDX-License-Identifier: GPL-3.0-only
+    This file is also available under commercial licensing terms.
+    Please contact info@sixtyfps.io for more information.
+LICENSE END */
+/*!
+    Support for the `styled-text` property of the `Text` element.
+
+    The markup is a small subset of HTML:
+     * `<b>` and `<strong>` make the text bold, `<i>` and `<em>` make it italic and `<u>` underlines it.
+     * `<font color="#rrggbb">` changes the color of the text. The color is given as `#rgb`,
+       `#rgba`, `#rrggbb` or `#rrggbbaa`.
+     * `<a href="url">` makes the text a link: it is underlined, and clicking on it calls the
+       `link-clicked` callback with the url.
+     * `<br>` starts a new line.
+     * `&lt;`, `&gt;`, `&amp;`, `&quot;` and `&apos;` are replaced by the character they stand for.
+
+    A `<` that doesn't start one of these tags is shown as is, and a closing tag without its
+    opening tag is ignored.
+*/
+
+#![warn(missing_docs)]
+use crate::graphics::{Color, FontRequest};
+use crate::SharedString;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+/// The style of a run of styled text
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TextStyle {
+    /// The text is bold
+    pub bold: bool,
+    /// The text is italic
+    pub italic: bool,
+    /// The text is underlined. This is also the case for links.
+    pub underline: bool,
+    /// The color of the text, if it is not the color of the `Text` element
+    pub color: Option<Color>,
+    /// The url of the link
+    pub link: Option<SharedString>,
+}
+
+impl TextStyle {
+    /// Returns the font request of the `Text` element, adapted for this style
+    pub fn font_request(&self, mut request: FontRequest) -> FontRequest {
+        if self.bold {
+            request.weight = Some(700);
+        }
+        request.italic |= self.italic;
+        request
+    }
+}
+
+/// A run of text that has the same style
+#[derive(Debug, Clone, PartialEq)]
+pub struct StyledTextSpan {
+    /// The (UTF-8) byte range of the run in [`StyledText::text`]
+    pub range: Range<usize>,
+    /// The style of the run
+    pub style: TextStyle,
+}
+
+/// A part of a [`StyledTextSpan`] that is laid out on a single line, see [`StyledText::layout_lines`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct StyledTextRun {
+    /// The index of the span in [`StyledText::spans`]
+    pub span_index: usize,
+    /// The (UTF-8) byte range of the run in [`StyledText::text`]
+    pub range: Range<usize>,
+    /// The position of the run from the start of the line
+    pub x: f32,
+    /// The width of the run
+    pub width: f32,
+}
+
+/// The result of parsing the markup of a `styled-text` property
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct StyledText {
+    /// The text without the markup
+    pub text: String,
+    /// The runs of text, covering all of `text` in order
+    pub spans: Vec<StyledTextSpan>,
+}
+
+impl StyledText {
+    /// Parses the markup of a `styled-text` property.
+    pub fn parse(markup: &str) -> Self {
+        let mut result = Self::default();
+        // The open tags, with the style of the text they contain
+        let mut open_tags: Vec<(&str, TextStyle)> = Vec::new();
+        let mut rest = markup;
+        while !rest.is_empty() {
+            let style = open_tags.last().map(|(_, style)| style.clone()).unwrap_or_default();
+            if rest.starts_with('<') {
+                if let Some((tag, len)) = parse_tag(rest) {
+                    rest = &rest[len..];
+                    match tag {
+                        Tag::Open(name, attribute) => {
+                            let mut style = style;
+                            match name {
+                                "b" | "strong" => style.bold = true,
+                                "i" | "em" => style.italic = true,
+                                "u" => style.underline = true,
+                                "font" => style.color = attribute.and_then(parse_color),
+                                "a" => {
+                                    style.underline = true;
+                                    style.link = Some(attribute.unwrap_or_default().into());
+                                }
+                                _ => unreachable!(),
+                            }
+                            open_tags.push((name, style));
+                        }
+                        Tag::Close(name) => {
+                            if let Some(index) = open_tags.iter().rposition(|(n, _)| *n == name) {
+                                open_tags.truncate(index);
+                            }
+                        }
+                        Tag::LineBreak => result.push("\n", style),
+                    }
+                    continue;
+                }
+            } else if rest.starts_with('&') {
+                if let Some((replacement, len)) = ENTITIES
+                    .iter()
+                    .find(|(entity, _)| rest.starts_with(entity))
+                    .map(|(entity, replacement)| (*replacement, entity.len()))
+                {
+                    result.push(replacement, style);
+                    rest = &rest[len..];
+                    continue;
+                }
+            }
+            // Copy the text until the next tag or entity, but at least one character
+            let first_len = rest.chars().next().map_or(0, char::len_utf8);
+            let len = rest[first_len..]
+                .find(|c: char| c == '<' || c == '&')
+                .map_or(rest.len(), |i| i + first_len);
+            result.push(&rest[..len], style);
+            rest = &rest[len..];
+        }
+        result
+    }
+
+    /// Returns the url of the link at the given byte offset in the text, if any
+    pub fn link_at(&self, byte_offset: usize) -> Option<&SharedString> {
+        self.spans
+            .iter()
+            .find(|span| span.range.contains(&byte_offset))
+            .and_then(|span| span.style.link.as_ref())
+    }
+
+    /// Returns true if some of the text is a link
+    pub fn has_links(&self) -> bool {
+        self.spans.iter().any(|span| span.style.link.is_some())
+    }
+
+    /// Splits the text in lines of runs. `measure` returns the width of a text in the font of
+    /// the span with the given index. When `max_width` is set, the lines are wrapped between
+    /// words so that they are not wider, unless a single word is.
+    pub fn layout_lines(
+        &self,
+        max_width: Option<f32>,
+        mut measure: impl FnMut(usize, &str) -> f32,
+    ) -> Vec<Vec<StyledTextRun>> {
+        let mut lines: Vec<Vec<StyledTextRun>> = alloc::vec![Vec::new()];
+        let mut x = 0.;
+        for (span_index, span) in self.spans.iter().enumerate() {
+            // The words keep their trailing whitespace, and the newlines are words of their own
+            let mut words = Vec::new();
+            let mut word_start = span.range.start;
+            let mut previous_is_whitespace = false;
+            for (i, c) in self.text[span.range.clone()].char_indices() {
+                let i = span.range.start + i;
+                if c == '\n' || (previous_is_whitespace && !c.is_whitespace()) {
+                    words.push(word_start..i);
+                    word_start = i;
+                }
+                if c == '\n' {
+                    words.push(i..i + 1);
+                    word_start = i + 1;
+                }
+                previous_is_whitespace = c.is_whitespace();
+            }
+            words.push(word_start..span.range.end);
+
+            for word in words.into_iter().filter(|word| !word.is_empty()) {
+                let word_text = &self.text[word.clone()];
+                if word_text == "\n" {
+                    lines.push(Vec::new());
+                    x = 0.;
+                    continue;
+                }
+                let width = measure(span_index, word_text);
+                if max_width.map_or(false, |max_width| x + width > max_width)
+                    && !lines.last().unwrap().is_empty()
+                {
+                    lines.push(Vec::new());
+                    x = 0.;
+                }
+                let line = lines.last_mut().unwrap();
+                match line.last_mut() {
+                    Some(run) if run.span_index == span_index && run.range.end == word.start => {
+                        run.range.end = word.end;
+                        run.width += width;
+                    }
+                    _ => line.push(StyledTextRun { span_index, range: word, x, width }),
+                }
+                x += width;
+            }
+        }
+        lines
+    }
+
+    fn push(&mut self, text: &str, style: TextStyle) {
+        let start = self.text.len();
+        self.text.push_str(text);
+        match self.spans.last_mut() {
+            Some(last) if last.style == style => last.range.end = self.text.len(),
+            _ => self.spans.push(StyledTextSpan { range: start..self.text.len(), style }),
+        }
+    }
+}
+
+const ENTITIES: [(&str, &str); 5] =
+    [("&lt;", "<"), ("&gt;", ">"), ("&amp;", "&"), ("&quot;", "\""), ("&apos;", "'")];
+
+enum Tag<'a> {
+    /// The name of the tag, and the value of its `color` or `href` attribute
+    Open(&'a str, Option<&'a str>),
+    Close(&'a str),
+    LineBreak,
+}
+
+/// Parses the tag at the start of `text`, and returns it with its length in bytes.
+fn parse_tag(text: &str) -> Option<(Tag, usize)> {
+    let end = text.find('>')?;
+    let content = text[1..end].trim();
+    let tag = if let Some(name) = content.strip_prefix('/') {
+        let name = name.trim();
+        if !["b", "strong", "i", "em", "u", "font", "a"].contains(&name) {
+            return None;
+        }
+        Tag::Close(name)
+    } else {
+        let name_end = content.find(char::is_whitespace).unwrap_or(content.len());
+        let (name, attributes) = content.split_at(name_end);
+        match name {
+            "br" | "br/" => Tag::LineBreak,
+            "b" | "strong" | "i" | "em" | "u" if attributes.trim().is_empty() => {
+                Tag::Open(name, None)
+            }
+            "font" => Tag::Open(name, Some(parse_attribute(attributes, "color")?)),
+            "a" => Tag::Open(name, Some(parse_attribute(attributes, "href")?)),
+            _ => return None,
+        }
+    };
+    Some((tag, end + 1))
+}
+
+/// Parses `name="value"` (or with single quotes, or without quotes) in the attributes of a tag.
+fn parse_attribute<'a>(attributes: &'a str, name: &str) -> Option<&'a str> {
+    let value = attributes.trim().strip_prefix(name)?.trim_start().strip_prefix('=')?.trim();
+    for quote in &['"', '\''] {
+        if let Some(quoted) = value.strip_prefix(*quote) {
+            return quoted.strip_suffix(*quote);
+        }
+    }
+    Some(value)
+}
+
+fn parse_color(color: &str) -> Option<Color> {
+    let hex = color.strip_prefix('#')?;
+    let digits =
+        hex.chars().map(|c| c.to_digit(16).map(|d| d as u8)).collect::<Option<Vec<_>>>()?;
+    let (r, g, b, a) = match digits.as_slice() {
+        [r, g, b] => (r * 17, g * 17, b * 17, 255),
+        [r, g, b, a] => (r * 17, g * 17, b * 17, a * 17),
+        [r1, r2, g1, g2, b1, b2] => (r1 * 16 + r2, g1 * 16 + g2, b1 * 16 + b2, 255),
+        [r1, r2, g1, g2, b1, b2, a1, a2] => {
+            (r1 * 16 + r2, g1 * 16 + g2, b1 * 16 + b2, a1 * 16 + a2)
+        }
+        _ => return None,
+    };
+    Some(Color::from_argb_u8(a, r, g, b))
+}
+
+#[test]
+fn parse_styled_text() {
+    let styled = StyledText::parse("Read the <b>terms</b> &amp; <a href=\"https://x.y\">more</a>");
+    assert_eq!(styled.text, "Read the terms & more");
+    assert_eq!(styled.spans.len(), 4);
+    assert_eq!(styled.spans[0].range, 0..9);
+    assert_eq!(styled.spans[0].style, TextStyle::default());
+    assert_eq!(styled.spans[1].range, 9..14);
+    assert!(styled.spans[1].style.bold);
+    assert_eq!(styled.spans[2].range, 14..17);
+    assert_eq!(styled.link_at(18).map(|s| s.as_str()), Some("https://x.y"));
+    assert_eq!(styled.link_at(3), None);
+    assert!(styled.spans[3].style.underline);
+}
+
+#[test]
+fn parse_nested_and_invalid_markup() {
+    let styled = StyledText::parse("<i>a<font color='#f00'>b</i>c</font>1 < 2<br>x</b>");
+    assert_eq!(styled.text, "abc1 < 2\nx");
+    assert_eq!(styled.spans.len(), 3);
+    assert!(styled.spans[0].style.italic);
+    assert_eq!(styled.spans[0].range, 0..1);
+    assert_eq!(styled.spans[1].style.color, Some(Color::from_rgb_u8(255, 0, 0)));
+    assert!(styled.spans[1].style.italic);
+    assert_eq!(styled.spans[2].style, TextStyle::default());
+    assert_eq!(styled.spans[2].range, 2..10);
+}
+
+#[test]
+fn layout_styled_text_lines() {
+    let styled = StyledText::parse("ab <b>cd ef</b><br>g");
+    let measure = |span_index: usize, text: &str| (span_index + 1) as f32 * text.len() as f32;
+    let lines = styled.layout_lines(None, measure);
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0].len(), 2);
+    assert_eq!(lines[0][0], StyledTextRun { span_index: 0, range: 0..3, x: 0., width: 3. });
+    assert_eq!(lines[0][1], StyledTextRun { span_index: 1, range: 3..8, x: 3., width: 10. });
+    assert_eq!(lines[1][0].range, 9..10);
+    // The bold words are twice as wide, so "ef" doesn't fit anymore
+    let lines = styled.layout_lines(Some(10.), measure);
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[0][1].range, 3..6);
+    assert_eq!(lines[1][0], StyledTextRun { span_index: 1, range: 6..8, x: 0., width: 4. });
+}
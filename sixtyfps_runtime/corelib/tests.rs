@@ -11,7 +11,7 @@
 #![warn(missing_docs)]
 #![allow(unsafe_code)]
 
-use crate::input::{KeyEvent, KeyEventType, KeyboardModifiers, MouseEvent};
+use crate::input::{KeyEvent, KeyEventType, KeyboardModifiers, MouseEvent, TouchPhase};
 use crate::window::WindowRc;
 use crate::SharedString;
 
@@ -19,12 +19,18 @@
 /// Normally, the event loop update the time of the animation using
 /// real time, but in tests, it is more convenient to use the fake time.
 /// This function will add some milliseconds to the fake time
+///
+/// With the `std` feature, this is the same clock as the one of the virtual event loop, see
+/// [`virtual_time`]. Unlike [`advance_time`], timers and posted events are not processed.
 #[no_mangle]
 pub extern "C" fn sixtyfps_mock_elapsed_time(time_in_ms: u64) {
+    let duration = core::time::Duration::from_millis(time_in_ms);
+    #[cfg(feature = "std")]
+    set_virtual_time(virtual_time() + duration);
+    #[cfg(not(feature = "std"))]
     crate::animations::CURRENT_ANIMATION_DRIVER.with(|driver| {
-        let mut tick = driver.current_tick();
-        tick += core::time::Duration::from_millis(time_in_ms);
-        driver.update_animations(tick)
+        let tick = driver.current_tick();
+        driver.update_animations(tick + duration)
     })
 }
 
@@ -45,21 +51,82 @@ pub extern "C" fn sixtyfps_send_mouse_click(
         window,
         state,
     );
+    let mut press = MouseEvent::MousePressed {
+        pos,
+        button: crate::items::PointerEventButton::left,
+        click_count: 0,
+    };
+    window.count_click(&mut press);
+    state = crate::input::process_mouse_input(component.clone(), press, window, state);
+    sixtyfps_mock_elapsed_time(50);
+    crate::input::process_mouse_input(
+        component.clone(),
+        MouseEvent::MouseReleased { pos, button: crate::items::PointerEventButton::left },
+        window,
+        state,
+    );
+}
+
+/// Simulate a drag with the left mouse button, from a position to another one within the component.
+/// The mouse moves in a few steps, and `duration_ms` milliseconds of mocked time elapse in total
+/// between the press and the release. Use 0 for a drag where the time does not matter.
+#[no_mangle]
+pub extern "C" fn sixtyfps_send_mouse_drag(
+    component: &crate::component::ComponentRc,
+    from_x: f32,
+    from_y: f32,
+    to_x: f32,
+    to_y: f32,
+    duration_ms: u64,
+    window: &WindowRc,
+) {
+    const STEPS: u32 = 10;
+    let mut state = crate::input::MouseInputState::default();
+    let from = euclid::point2(from_x, from_y);
+    let to = euclid::point2(to_x, to_y);
+    let button = crate::items::PointerEventButton::left;
+
     state = crate::input::process_mouse_input(
         component.clone(),
-        MouseEvent::MousePressed { pos, button: crate::items::PointerEventButton::left },
+        MouseEvent::MouseMoved { pos: from },
         window,
         state,
     );
-    sixtyfps_mock_elapsed_time(50);
+    let mut press = MouseEvent::MousePressed { pos: from, button, click_count: 0 };
+    window.count_click(&mut press);
+    state = crate::input::process_mouse_input(component.clone(), press, window, state);
+    let elapsed_at_step = |step: u32| duration_ms * step as u64 / STEPS as u64;
+    for step in 1..=STEPS {
+        sixtyfps_mock_elapsed_time(elapsed_at_step(step) - elapsed_at_step(step - 1));
+        let pos = from.lerp(to, step as f32 / STEPS as f32);
+        state = crate::input::process_mouse_input(
+            component.clone(),
+            MouseEvent::MouseMoved { pos },
+            window,
+            state,
+        );
+    }
     crate::input::process_mouse_input(
         component.clone(),
-        MouseEvent::MouseReleased { pos, button: crate::items::PointerEventButton::left },
+        MouseEvent::MouseReleased { pos: to, button },
         window,
         state,
     );
 }
 
+/// Simulate a finger that touches, moves on, or leaves a touch screen, at a position within the
+/// component. `id` identifies the finger while it touches the screen.
+#[no_mangle]
+pub extern "C" fn sixtyfps_send_touch_event(
+    id: u64,
+    x: f32,
+    y: f32,
+    phase: TouchPhase,
+    window: &WindowRc,
+) {
+    window.clone().process_touch_input(id, euclid::point2(x, y), phase);
+}
+
 /// Simulate a character input event.
 #[no_mangle]
 pub extern "C" fn send_keyboard_string_sequence(
@@ -79,15 +146,516 @@ pub extern "C" fn send_keyboard_string_sequence(
             event_type: KeyEventType::KeyPressed,
             text: text.clone(),
             modifiers,
+            ..Default::default()
         });
         window.clone().process_key_input(&KeyEvent {
             event_type: KeyEventType::KeyReleased,
             text,
             modifiers,
+            ..Default::default()
         });
     }
 }
 
+/// Simulate the input method composing text. The preedit text is shown at the cursor position,
+/// and `cursor` is the byte offset of the cursor of the input method in the preedit text.
+#[no_mangle]
+pub extern "C" fn sixtyfps_send_composition_update(
+    preedit_text: &crate::SharedString,
+    cursor: usize,
+    window: &WindowRc,
+) {
+    window.clone().process_key_input(&KeyEvent {
+        event_type: KeyEventType::UpdateComposition,
+        text: preedit_text.clone(),
+        preedit_selection_start: cursor,
+        preedit_selection_end: cursor,
+        ..Default::default()
+    });
+}
+
+/// Simulate the input method committing the composed text, which replaces the preedit text.
+#[no_mangle]
+pub extern "C" fn sixtyfps_send_composition_commit(text: &crate::SharedString, window: &WindowRc) {
+    window.clone().process_key_input(&KeyEvent {
+        event_type: KeyEventType::CommitComposition,
+        text: text.clone(),
+        ..Default::default()
+    });
+}
+
+#[cfg(feature = "std")]
+std::thread_local!(
+    /// The time of the virtual event loop, which only advances with [`advance_time`]
+    static VIRTUAL_TIME: core::cell::Cell<core::time::Duration> = Default::default()
+);
+
+#[cfg(feature = "std")]
+std::thread_local!(
+    /// The events posted to the virtual event loop of this thread
+    static POSTED_EVENTS: core::cell::RefCell<std::collections::VecDeque<Box<dyn FnOnce()>>> =
+        Default::default()
+);
+
+#[cfg(feature = "std")]
+std::thread_local!(static QUIT_REQUESTED: core::cell::Cell<bool> = Default::default());
+
+/// Returns the time elapsed in the virtual event loop used by the testing backend.
+/// It only changes when calling [`advance_time`] or [`run_virtual_event_loop`].
+#[cfg(feature = "std")]
+pub fn virtual_time() -> core::time::Duration {
+    VIRTUAL_TIME.with(|time| time.get())
+}
+
+/// Moves the virtual time forward and advances the animations by the same amount.
+#[cfg(feature = "std")]
+fn set_virtual_time(new_time: core::time::Duration) {
+    let elapsed = new_time - VIRTUAL_TIME.with(|time| time.replace(new_time));
+    if elapsed > core::time::Duration::default() {
+        crate::animations::CURRENT_ANIMATION_DRIVER.with(|driver| {
+            let tick = driver.current_tick();
+            driver.update_animations(tick + elapsed)
+        })
+    }
+}
+
+/// Queues an event for the virtual event loop. It is run by the next call to
+/// [`process_posted_events`], [`advance_time`] or [`run_virtual_event_loop`].
+///
+/// Like the virtual time, the queue is per thread: the event must be posted from the thread
+/// that runs the virtual event loop, or it is never run.
+#[cfg(feature = "std")]
+pub fn post_virtual_event(event: Box<dyn FnOnce() + Send>) {
+    POSTED_EVENTS.with(|events| events.borrow_mut().push_back(event));
+}
+
+/// Runs the events that were posted to the virtual event loop, in order, including the ones
+/// posted while processing them.
+#[cfg(feature = "std")]
+pub fn process_posted_events() {
+    loop {
+        let event = POSTED_EVENTS.with(|events| events.borrow_mut().pop_front());
+        match event {
+            Some(event) => event(),
+            None => break,
+        }
+    }
+}
+
+/// Advances the virtual time of the testing backend.
+///
+/// The timers that expire within `duration` are fired in the order of their timeout, after the
+/// animations have been advanced up to that timeout. The posted events are processed before
+/// each timer, and at the end.
+#[cfg(feature = "std")]
+pub fn advance_time(duration: core::time::Duration) {
+    let target = virtual_time() + duration;
+    loop {
+        process_posted_events();
+        let now = virtual_time();
+        let next = crate::timers::TimerList::next_timeout()
+            .map(|timeout| core::time::Duration::from_millis(timeout.0))
+            .filter(|timeout| *timeout < target)
+            .map_or(target, |timeout| timeout.max(now));
+        set_virtual_time(next);
+        crate::timers::TimerList::maybe_activate_timers();
+        if next >= target {
+            break;
+        }
+    }
+    process_posted_events();
+}
+
+/// Same as [`advance_time`], with the duration in milliseconds.
+#[cfg(feature = "std")]
+#[no_mangle]
+pub extern "C" fn sixtyfps_testing_advance_time(time_in_ms: u64) {
+    advance_time(core::time::Duration::from_millis(time_in_ms))
+}
+
+/// Runs the virtual event loop of the testing backend: processes the posted events and jumps the
+/// virtual time to the next timer, as long as there are timers or animations running.
+/// Returns when [`quit_virtual_event_loop`] is called, or when there is nothing left to do,
+/// since no external event can ever wake it up.
+#[cfg(feature = "std")]
+pub fn run_virtual_event_loop() {
+    loop {
+        process_posted_events();
+        if QUIT_REQUESTED.with(|quit| quit.replace(false)) {
+            break;
+        }
+        if let Some(timeout) = crate::timers::TimerList::next_timeout() {
+            let timeout = core::time::Duration::from_millis(timeout.0);
+            advance_time(timeout.checked_sub(virtual_time()).unwrap_or_default());
+        } else if crate::animations::CURRENT_ANIMATION_DRIVER
+            .with(|driver| driver.has_active_animations())
+        {
+            // One frame
+            advance_time(core::time::Duration::from_millis(16));
+        } else {
+            break;
+        }
+    }
+}
+
+/// Makes [`run_virtual_event_loop`] return.
+#[cfg(feature = "std")]
+pub fn quit_virtual_event_loop() {
+    QUIT_REQUESTED.with(|quit| quit.set(true));
+}
+
+/// The kind of value held by an [`ElementPropertyValue`].
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ElementPropertyKind {
+    /// There is no value: the element has no such property, or its type is not supported
+    None,
+    /// The value of a property of type `int`, `float`, `length`, `duration`, `angle`, ... is
+    /// in the `number` field
+    Number,
+    /// The value of a property of type `bool` is in the `boolean` field
+    Bool,
+    /// The value of a property of type `string` is in the `string` field
+    String,
+    /// The value of a property of type `brush` or `color` is in the `brush` field
+    Brush,
+}
+
+impl Default for ElementPropertyKind {
+    fn default() -> Self {
+        Self::None
+    }
+}
+
+/// The value of a property of an element, as returned by [`ElementHandle::property`].
+/// Only the field that corresponds to the `kind` is set.
+#[repr(C)]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ElementPropertyValue {
+    /// The kind of value
+    pub kind: ElementPropertyKind,
+    /// The value if the kind is [`ElementPropertyKind::Number`]
+    pub number: f64,
+    /// The value if the kind is [`ElementPropertyKind::Bool`]
+    pub boolean: bool,
+    /// The value if the kind is [`ElementPropertyKind::String`]
+    pub string: SharedString,
+    /// The value if the kind is [`ElementPropertyKind::Brush`]
+    pub brush: crate::Brush,
+}
+
+impl ElementPropertyValue {
+    /// Returns the value if it is a number
+    pub fn as_number(&self) -> Option<f64> {
+        (self.kind == ElementPropertyKind::Number).then(|| self.number)
+    }
+
+    /// Returns the value if it is a boolean
+    pub fn as_bool(&self) -> Option<bool> {
+        (self.kind == ElementPropertyKind::Bool).then(|| self.boolean)
+    }
+
+    /// Returns the value if it is a string
+    pub fn as_string(&self) -> Option<&str> {
+        (self.kind == ElementPropertyKind::String).then(|| self.string.as_str())
+    }
+
+    /// Returns the value if it is a brush or a color
+    pub fn as_brush(&self) -> Option<&crate::Brush> {
+        (self.kind == ElementPropertyKind::Brush).then(|| &self.brush)
+    }
+}
+
+macro_rules! element_property_value_from_number {
+    ($($ty:ty)*) => {$(
+        impl From<$ty> for ElementPropertyValue {
+            fn from(value: $ty) -> Self {
+                Self { kind: ElementPropertyKind::Number, number: value as f64, ..Default::default() }
+            }
+        }
+    )*};
+}
+element_property_value_from_number!(f32 f64 i32 i64);
+
+impl From<bool> for ElementPropertyValue {
+    fn from(value: bool) -> Self {
+        Self { kind: ElementPropertyKind::Bool, boolean: value, ..Default::default() }
+    }
+}
+
+impl From<SharedString> for ElementPropertyValue {
+    fn from(value: SharedString) -> Self {
+        Self { kind: ElementPropertyKind::String, string: value, ..Default::default() }
+    }
+}
+
+impl From<crate::Brush> for ElementPropertyValue {
+    fn from(value: crate::Brush) -> Self {
+        Self { kind: ElementPropertyKind::Brush, brush: value, ..Default::default() }
+    }
+}
+
+impl From<crate::Color> for ElementPropertyValue {
+    fn from(value: crate::Color) -> Self {
+        crate::Brush::SolidColor(value).into()
+    }
+}
+
+/// A handle to an element of a component, as returned by [`find_elements`].
+///
+/// It can be used to query the geometry and state of the element, or to interact with it.
+#[derive(Clone)]
+pub struct ElementHandle {
+    item: crate::items::ItemRc,
+    id: SharedString,
+    type_name: SharedString,
+    root_component: crate::component::ComponentRc,
+    window: WindowRc,
+}
+
+impl ElementHandle {
+    /// The id of the element, as written in the .60 file. It is empty if the element has no id.
+    pub fn id(&self) -> &str {
+        self.id.as_str()
+    }
+
+    /// The type of the element, as written in the .60 file. For example `Text` or `Button`.
+    pub fn type_name(&self) -> &str {
+        self.type_name.as_str()
+    }
+
+    /// The item in the item tree.
+    pub fn item(&self) -> &crate::items::ItemRc {
+        &self.item
+    }
+
+    /// Returns the geometry of the element relative to the window, in logical pixels.
+    pub fn absolute_geometry(&self) -> crate::graphics::Rect {
+        element_absolute_geometry(&self.item)
+    }
+
+    /// Returns true if a part of the element is on screen: it has a non-empty area that is not
+    /// clipped away by the window or by one of its ancestors, and none of its ancestors is fully
+    /// transparent.
+    pub fn is_visible(&self) -> bool {
+        element_is_visible(&self.item)
+    }
+
+    /// Returns the text of the element if it is a `Text` or a `TextInput`.
+    /// For a `Text` with a `styled-text`, this is the text without the markup.
+    pub fn text(&self) -> Option<SharedString> {
+        element_text(&self.item)
+    }
+
+    /// Returns the value of the property `name` of the element, as it is named in the .60 file,
+    /// or None if the element has no such property or if its type is not supported by
+    /// [`ElementPropertyValue`].
+    ///
+    /// With the generated code, the properties of the elements that are inside of a component
+    /// used by the component, such as the `Text` inside of a `Button`, are not available.
+    pub fn property(&self, name: &str) -> Option<ElementPropertyValue> {
+        element_property(&self.item, name)
+    }
+
+    /// Simulates a click in the middle of the element.
+    pub fn click(&self) {
+        let center = self.absolute_geometry().center();
+        sixtyfps_send_mouse_click(&self.root_component, center.x, center.y, &self.window);
+    }
+}
+
+/// Returns the chain of items from the root of the window to this item, included.
+fn ancestors_and_self(item: &crate::items::ItemRc) -> alloc::vec::Vec<crate::items::ItemRc> {
+    let mut chain = alloc::vec![item.clone()];
+    while let Some(parent) = chain.last().unwrap().parent_item().upgrade() {
+        chain.push(parent);
+    }
+    chain.reverse();
+    chain
+}
+
+fn element_absolute_geometry(item: &crate::items::ItemRc) -> crate::graphics::Rect {
+    let mut geometry = crate::graphics::Rect::default();
+    for item in ancestors_and_self(item) {
+        let item_geometry = item.borrow().as_ref().geometry();
+        geometry = item_geometry.translate(geometry.origin.to_vector());
+    }
+    geometry
+}
+
+fn element_is_visible(item: &crate::items::ItemRc) -> bool {
+    use crate::items::{Clip, Flickable, ItemRef, Opacity};
+    let chain = ancestors_and_self(item);
+    let mut offset = euclid::Vector2D::zero();
+    let mut clip: Option<crate::graphics::Rect> = None;
+    for (index, item) in chain.iter().enumerate() {
+        let item = item.borrow();
+        let geometry = item.as_ref().geometry().translate(offset);
+        if index + 1 == chain.len() {
+            return clip
+                .map_or(Some(geometry), |clip| clip.intersection(&geometry))
+                .map_or(false, |visible| !visible.is_empty());
+        }
+        let clips_children = index == 0
+            || ItemRef::downcast_pin::<Clip>(item).map_or(false, |clip| clip.clip())
+            || ItemRef::downcast_pin::<Flickable>(item).is_some();
+        if clips_children {
+            clip = match clip {
+                Some(clip) => match clip.intersection(&geometry) {
+                    Some(clip) => Some(clip),
+                    None => return false,
+                },
+                None => Some(geometry),
+            };
+        }
+        if ItemRef::downcast_pin::<Opacity>(item).map_or(false, |o| o.opacity() <= 0.) {
+            return false;
+        }
+        offset = geometry.origin.to_vector();
+    }
+    false
+}
+
+fn element_text(item: &crate::items::ItemRc) -> Option<SharedString> {
+    use crate::items::{ItemRef, Text, TextInput};
+    let item = item.borrow();
+    if let Some(text) = ItemRef::downcast_pin::<Text>(item) {
+        Some(
+            text.parsed_styled_text()
+                .map_or_else(|| text.text(), |styled| styled.text.as_str().into()),
+        )
+    } else {
+        ItemRef::downcast_pin::<TextInput>(item).map(|text_input| text_input.text())
+    }
+}
+
+fn element_property(item: &crate::items::ItemRc, name: &str) -> Option<ElementPropertyValue> {
+    let mut result = ElementPropertyValue::default();
+    vtable::VRc::borrow_pin(&item.component()).as_ref().element_property(
+        item.index(),
+        &name.into(),
+        &mut result,
+    );
+    (result.kind != ElementPropertyKind::None).then(|| result)
+}
+
+/// Returns all the elements of the component for which `predicate` returns true, in the
+/// order of the item tree. The elements created by repeaters and conditional elements are
+/// included.
+pub fn find_elements(
+    component: &crate::component::ComponentRc,
+    window: &WindowRc,
+    mut predicate: impl FnMut(&ElementHandle) -> bool,
+) -> alloc::vec::Vec<ElementHandle> {
+    let mut result = alloc::vec::Vec::new();
+    crate::item_tree::visit_items(
+        component,
+        crate::item_tree::TraversalOrder::BackToFront,
+        |item_component, _, index, _| {
+            let mut element = ElementHandle {
+                item: crate::items::ItemRc::new(item_component.clone(), index),
+                id: Default::default(),
+                type_name: Default::default(),
+                root_component: component.clone(),
+                window: window.clone(),
+            };
+            vtable::VRc::borrow_pin(item_component).as_ref().item_element_info(
+                index,
+                &mut element.id,
+                &mut element.type_name,
+            );
+            if !element.type_name.is_empty() && predicate(&element) {
+                result.push(element);
+            }
+            crate::item_tree::ItemVisitorResult::Continue(())
+        },
+        (),
+    );
+    result
+}
+
+/// What [`sixtyfps_testing_find_elements`] compares to the given string.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ElementQuery {
+    /// The id of the element, as written in the .60 file
+    Id,
+    /// The type of the element, as written in the .60 file
+    TypeName,
+    /// The text of a `Text` or a `TextInput`
+    Text,
+}
+
+/// Sets `result` to the items of the elements of the component that match the query, in the
+/// order of the item tree.
+#[no_mangle]
+pub extern "C" fn sixtyfps_testing_find_elements(
+    component: &crate::component::ComponentRc,
+    window: &WindowRc,
+    query: ElementQuery,
+    value: &SharedString,
+    result: &mut crate::SharedVector<crate::items::ItemRc>,
+) {
+    let elements = find_elements(component, window, |element| match query {
+        ElementQuery::Id => element.id() == value.as_str(),
+        ElementQuery::TypeName => element.type_name() == value.as_str(),
+        ElementQuery::Text => element.text().as_ref() == Some(value),
+    });
+    *result = elements.into_iter().map(|element| element.item).collect();
+}
+
+/// Sets `id` and `type_name` to the id and the type of the element of the item, as written
+/// in the .60 file.
+#[no_mangle]
+pub extern "C" fn sixtyfps_testing_element_info(
+    item: &crate::items::ItemRc,
+    id: &mut SharedString,
+    type_name: &mut SharedString,
+) {
+    vtable::VRc::borrow_pin(&item.component()).as_ref().item_element_info(
+        item.index(),
+        id,
+        type_name,
+    );
+}
+
+/// See [`ElementHandle::absolute_geometry`]
+#[no_mangle]
+pub extern "C" fn sixtyfps_testing_element_absolute_geometry(
+    item: &crate::items::ItemRc,
+) -> crate::graphics::Rect {
+    element_absolute_geometry(item)
+}
+
+/// See [`ElementHandle::is_visible`]
+#[no_mangle]
+pub extern "C" fn sixtyfps_testing_element_is_visible(item: &crate::items::ItemRc) -> bool {
+    element_is_visible(item)
+}
+
+/// See [`ElementHandle::text`]. Returns false if the element has no text.
+#[no_mangle]
+pub extern "C" fn sixtyfps_testing_element_text(
+    item: &crate::items::ItemRc,
+    result: &mut SharedString,
+) -> bool {
+    element_text(item).map(|text| *result = text).is_some()
+}
+
+/// See [`ElementHandle::property`]. `result` is left untouched if there is no such property.
+#[no_mangle]
+pub extern "C" fn sixtyfps_testing_element_property(
+    item: &crate::items::ItemRc,
+    name: &SharedString,
+    result: &mut ElementPropertyValue,
+) {
+    vtable::VRc::borrow_pin(&item.component()).as_ref().element_property(
+        item.index(),
+        name,
+        result,
+    );
+}
+
 cfg_if::cfg_if! {
     if #[cfg(target_arch = "wasm32")] {
         use wasm_bindgen::prelude::*;
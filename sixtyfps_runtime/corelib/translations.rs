@@ -0,0 +1,615 @@
+/* LICENSE BEGIN
+    This file is part of the SixtyFPS Project -- https://sixtyfps.io
+    Copyright (c) 2021 Olivier Goffart <olivier.goffart@sixtyfps.io>
+    Copyright (c) 2021 Simon Hausmann <simon.hausmann@sixtyfps.io>
+
+    SPDX-License-Identifier: GPL-3.0-only
+    This file is also available under commercial licensing terms.
+    Please contact info@sixtyfps.io for more information.
+LICENSE END */
+/*!
+    Support for the translations of the `@tr(...)` macro.
+
+    The messages are looked up in a catalog of [`Translations`], usually loaded from a gettext `.po`
+    file. Changing the current translations with [`set_translations`] re-evaluates all the bindings
+    that use `@tr`.
+*/
+
+#![warn(missing_docs)]
+use crate::{Property, SharedString};
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::cell::RefCell;
+use core::fmt::Write;
+use core::pin::Pin;
+
+/// Substitute the placeholders of a (translated) message.
+///
+/// `{}` is replaced by the next argument, `{0}`, `{1}`, ... by the argument with that index, and
+/// `{n}` by the count `n` of the plural form. `{{` and `}}` are replaced by a single brace.
+pub fn format_translation(text: &str, n: i32, args: &[SharedString]) -> SharedString {
+    let mut result = String::with_capacity(text.len());
+    let mut next_arg = 0;
+    let mut rest = text;
+    while let Some(pos) = rest.find(|c| c == '{' || c == '}') {
+        result.push_str(&rest[..pos]);
+        let brace = rest[pos..].chars().next().unwrap();
+        rest = &rest[pos + 1..];
+        if let Some(r) = rest.strip_prefix(brace) {
+            result.push(brace);
+            rest = r;
+            continue;
+        }
+        let end = match rest.find('}') {
+            Some(end) if brace == '{' => end,
+            _ => {
+                result.push(brace);
+                continue;
+            }
+        };
+        let placeholder = &rest[..end];
+        rest = &rest[end + 1..];
+        if placeholder.is_empty() {
+            next_arg += 1;
+            result.push_str(args.get(next_arg - 1).map_or("", |a| a.as_str()));
+        } else if placeholder == "n" {
+            write!(result, "{}", n).unwrap();
+        } else if let Some(arg) = placeholder.parse::<usize>().ok().and_then(|i| args.get(i)) {
+            result.push_str(arg);
+        } else {
+            result.push('{');
+            result.push_str(placeholder);
+            result.push('}');
+        }
+    }
+    result.push_str(rest);
+    result.into()
+}
+
+/// The expression of the `plural=` of a gettext `Plural-Forms` header. It computes the index of
+/// the plural form to use for a count `n`
+#[derive(Debug, Clone, PartialEq)]
+enum PluralExpression {
+    N,
+    Constant(u64),
+    Not(Box<PluralExpression>),
+    Binary(Box<PluralExpression>, &'static str, Box<PluralExpression>),
+    Conditional(Box<PluralExpression>, Box<PluralExpression>, Box<PluralExpression>),
+}
+
+impl Default for PluralExpression {
+    /// The rule of the languages which have a singular and a plural form, like English: `n != 1`
+    fn default() -> Self {
+        Self::Binary(Box::new(Self::N), "!=", Box::new(Self::Constant(1)))
+    }
+}
+
+impl PluralExpression {
+    fn parse(source: &str) -> Option<Self> {
+        let mut parser = PluralExpressionParser { rest: source };
+        let result = parser.conditional()?;
+        parser.rest.trim().is_empty().then(|| result)
+    }
+
+    fn evaluate(&self, n: u64) -> u64 {
+        match self {
+            Self::N => n,
+            Self::Constant(c) => *c,
+            Self::Not(e) => (e.evaluate(n) == 0) as u64,
+            Self::Binary(lhs, op, rhs) => {
+                let (lhs, rhs) = (lhs.evaluate(n), rhs.evaluate(n));
+                match *op {
+                    "||" => (lhs != 0 || rhs != 0) as u64,
+                    "&&" => (lhs != 0 && rhs != 0) as u64,
+                    "==" => (lhs == rhs) as u64,
+                    "!=" => (lhs != rhs) as u64,
+                    "<" => (lhs < rhs) as u64,
+                    ">" => (lhs > rhs) as u64,
+                    "<=" => (lhs <= rhs) as u64,
+                    ">=" => (lhs >= rhs) as u64,
+                    "+" => lhs.wrapping_add(rhs),
+                    "-" => lhs.wrapping_sub(rhs),
+                    "*" => lhs.wrapping_mul(rhs),
+                    "/" => lhs.checked_div(rhs).unwrap_or(0),
+                    "%" => lhs.checked_rem(rhs).unwrap_or(0),
+                    _ => unreachable!(),
+                }
+            }
+            Self::Conditional(cond, a, b) => {
+                if cond.evaluate(n) != 0 {
+                    a.evaluate(n)
+                } else {
+                    b.evaluate(n)
+                }
+            }
+        }
+    }
+}
+
+/// Recursive descent parser for the C-like expression of the plural forms
+struct PluralExpressionParser<'a> {
+    rest: &'a str,
+}
+
+impl<'a> PluralExpressionParser<'a> {
+    /// The binary operators, from the lowest to the highest precedence
+    const BINARY_OPERATORS: &'static [&'static [&'static str]] =
+        &[&["||"], &["&&"], &["==", "!="], &["<=", ">=", "<", ">"], &["+", "-"], &["*", "/", "%"]];
+
+    fn eat(&mut self, token: &str) -> bool {
+        self.rest = self.rest.trim_start();
+        match self.rest.strip_prefix(token) {
+            Some(rest) => {
+                self.rest = rest;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn conditional(&mut self) -> Option<PluralExpression> {
+        let condition = self.binary(0)?;
+        if !self.eat("?") {
+            return Some(condition);
+        }
+        let a = self.conditional()?;
+        if !self.eat(":") {
+            return None;
+        }
+        let b = self.conditional()?;
+        Some(PluralExpression::Conditional(Box::new(condition), Box::new(a), Box::new(b)))
+    }
+
+    fn binary(&mut self, level: usize) -> Option<PluralExpression> {
+        let operators = match Self::BINARY_OPERATORS.get(level) {
+            Some(operators) => *operators,
+            None => return self.unary(),
+        };
+        let mut lhs = self.binary(level + 1)?;
+        'next: loop {
+            for op in operators {
+                if self.eat(op) {
+                    let rhs = self.binary(level + 1)?;
+                    lhs = PluralExpression::Binary(Box::new(lhs), *op, Box::new(rhs));
+                    continue 'next;
+                }
+            }
+            return Some(lhs);
+        }
+    }
+
+    fn unary(&mut self) -> Option<PluralExpression> {
+        if self.eat("!") {
+            Some(PluralExpression::Not(Box::new(self.unary()?)))
+        } else if self.eat("(") {
+            let e = self.conditional()?;
+            self.eat(")").then(|| e)
+        } else if self.eat("n") {
+            Some(PluralExpression::N)
+        } else {
+            let len = self.rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(self.rest.len());
+            let value = self.rest[..len].parse().ok()?;
+            self.rest = &self.rest[len..];
+            Some(PluralExpression::Constant(value))
+        }
+    }
+}
+
+/// The error returned when the translations cannot be loaded
+#[derive(Debug, Clone, PartialEq)]
+pub struct TranslationsError {
+    /// The line of the `.po` file with the error, starting at 1
+    pub line: usize,
+    /// The description of the error
+    pub message: String,
+}
+
+impl core::fmt::Display for TranslationsError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TranslationsError {}
+
+/// A catalog of translated messages.
+///
+/// ```
+/// # use sixtyfps_corelib::translations::Translations;
+/// let translations = Translations::from_po(r#"
+/// msgid "Hello"
+/// msgstr "Bonjour"
+/// "#).unwrap();
+/// assert_eq!(translations.translate("", "Hello", "", 0), Some("Bonjour"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Translations {
+    /// The translations of each (context, message), one for each plural form
+    messages: BTreeMap<(String, String), Vec<String>>,
+    plural_rule: PluralExpression,
+}
+
+impl Translations {
+    /// Creates an empty catalog, for a language with one singular and one plural form
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the translation of the message `text` in the given context. `translations` contains
+    /// the translation for each plural form, or a single translation if the message has no plural.
+    pub fn insert(&mut self, context: &str, text: &str, translations: Vec<String>) {
+        self.messages.insert((context.into(), text.into()), translations);
+    }
+
+    /// Set the rule that selects the plural form, in the syntax of the `plural=` expression of the
+    /// gettext `Plural-Forms` header. For example `n == 1 ? 0 : n >= 2 && n <= 4 ? 1 : 2`.
+    /// Returns false if the rule cannot be parsed.
+    pub fn set_plural_rule(&mut self, rule: &str) -> bool {
+        match PluralExpression::parse(rule) {
+            Some(rule) => {
+                self.plural_rule = rule;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the translation of the message `text`, or None if there is none.
+    /// If `plural` is not empty, the translation of the plural form for the count `n` is returned.
+    pub fn translate(&self, context: &str, text: &str, plural: &str, n: i32) -> Option<&str> {
+        let forms = self.messages.get(&(context.into(), text.into()))?;
+        let index = if plural.is_empty() {
+            0
+        } else {
+            self.plural_rule.evaluate(n.unsigned_abs() as u64) as usize
+        };
+        forms.get(index).map(|s| s.as_str()).filter(|s| !s.is_empty())
+    }
+
+    /// Parse the content of a gettext `.po` file
+    pub fn from_po(source: &str) -> Result<Self, TranslationsError> {
+        #[derive(Default)]
+        struct Entry {
+            context: Option<String>,
+            id: Option<String>,
+            strings: Vec<String>,
+            fuzzy: bool,
+        }
+        // The field that gets the continuation lines
+        enum Field {
+            None,
+            Context,
+            Id,
+            IdPlural,
+            Str(usize),
+        }
+
+        let mut result = Self::default();
+        let mut add_entry = |entry: Entry, line: usize| -> Result<(), TranslationsError> {
+            match entry.id {
+                Some(id) if id.is_empty() && entry.context.is_none() => {
+                    let header = entry.strings.first().map_or("", |s| s.as_str());
+                    if let Some(plural_forms) =
+                        header.lines().find_map(|l| l.strip_prefix("Plural-Forms:"))
+                    {
+                        let rule =
+                            plural_forms.split(';').find_map(|s| s.trim().strip_prefix("plural="));
+                        if !rule.map_or(false, |rule| result.set_plural_rule(rule)) {
+                            return Err(TranslationsError {
+                                line,
+                                message: "Invalid Plural-Forms header".into(),
+                            });
+                        }
+                    }
+                }
+                Some(id) if !entry.fuzzy => {
+                    result.insert(&entry.context.unwrap_or_default(), &id, entry.strings)
+                }
+                _ => (),
+            }
+            Ok(())
+        };
+
+        let mut entry = Entry::default();
+        let mut entry_line = 0;
+        let mut field = Field::None;
+        for (index, line) in source.lines().enumerate() {
+            let line_number = index + 1;
+            let error =
+                |message: &str| TranslationsError { line: line_number, message: message.into() };
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let starts_new_entry = line.starts_with('#')
+                || line.starts_with("msgctxt")
+                || (line.starts_with("msgid") && !line.starts_with("msgid_plural"));
+            if starts_new_entry && entry.id.is_some() {
+                add_entry(core::mem::take(&mut entry), entry_line)?;
+                field = Field::None;
+            }
+            if let Some(comment) = line.strip_prefix('#') {
+                if comment.starts_with(',') && comment.contains("fuzzy") {
+                    entry.fuzzy = true;
+                }
+                continue;
+            }
+            let (keyword, value) = match line.find('"') {
+                Some(pos) => (
+                    line[..pos].trim(),
+                    parse_po_string(&line[pos..]).ok_or_else(|| error("Invalid string"))?,
+                ),
+                None => return Err(error("Expected a string")),
+            };
+            match keyword {
+                "" => {
+                    let target = match field {
+                        Field::None => return Err(error("Unexpected string")),
+                        Field::Context => entry.context.as_mut(),
+                        Field::Id => entry.id.as_mut(),
+                        // The plural form of the message is not needed to translate it
+                        Field::IdPlural => None,
+                        Field::Str(index) => entry.strings.get_mut(index),
+                    };
+                    if let Some(target) = target {
+                        target.push_str(&value);
+                    }
+                }
+                "msgctxt" => {
+                    entry.context = Some(value);
+                    entry_line = line_number;
+                    field = Field::Context;
+                }
+                "msgid" => {
+                    entry.id = Some(value);
+                    entry_line = line_number;
+                    field = Field::Id;
+                }
+                "msgid_plural" => field = Field::IdPlural,
+                "msgstr" => {
+                    entry.strings = vec![value];
+                    field = Field::Str(0);
+                }
+                _ => {
+                    let index = keyword
+                        .strip_prefix("msgstr[")
+                        .and_then(|k| k.strip_suffix(']'))
+                        .and_then(|k| k.parse::<usize>().ok())
+                        .ok_or_else(|| error("Unknown keyword"))?;
+                    if entry.strings.len() <= index {
+                        entry.strings.resize(index + 1, String::new());
+                    }
+                    entry.strings[index] = value;
+                    field = Field::Str(index);
+                }
+            }
+        }
+        add_entry(entry, entry_line)?;
+        Ok(result)
+    }
+}
+
+/// Unescape a quoted string of a `.po` file
+fn parse_po_string(s: &str) -> Option<String> {
+    let s = s.strip_prefix('"')?.strip_suffix('"')?;
+    let mut result = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c == '"' {
+            return None;
+        }
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        result.push(match chars.next()? {
+            'n' => '\n',
+            't' => '\t',
+            'r' => '\r',
+            c @ ('"' | '\\') => c,
+            _ => return None,
+        });
+    }
+    Some(result)
+}
+
+struct TranslationsState {
+    translations: RefCell<Option<Translations>>,
+    /// Changed each time the translations change, so that the bindings depending on it
+    /// are re-evaluated
+    generation: Pin<Box<Property<u32>>>,
+}
+
+impl Default for TranslationsState {
+    fn default() -> Self {
+        Self { translations: Default::default(), generation: Box::pin(Property::new(0)) }
+    }
+}
+
+#[cfg(all(not(feature = "std"), feature = "unsafe_single_core"))]
+use crate::unsafe_single_core::thread_local;
+
+thread_local!(static CURRENT_TRANSLATIONS : TranslationsState = TranslationsState::default());
+
+/// Set the translations used by the `@tr` macro, or go back to the original messages with None.
+/// All the bindings which use `@tr` are re-evaluated.
+pub fn set_translations(translations: Option<Translations>) {
+    CURRENT_TRANSLATIONS.with(|state| {
+        *state.translations.borrow_mut() = translations;
+        let generation = state.generation.as_ref();
+        generation.set(generation.get_untracked().wrapping_add(1));
+    })
+}
+
+/// Translate the message `text` with the current translations, and substitute the placeholders
+/// with `args` (see [`format_translation`]). If `plural` is not empty, it is the plural form of
+/// the message for the count `n`.
+///
+/// This registers the current binding as a dependency, so that it is re-evaluated when
+/// [`set_translations`] is called.
+pub fn translate(
+    context: &str,
+    text: &str,
+    plural: &str,
+    n: i32,
+    args: &[SharedString],
+) -> SharedString {
+    CURRENT_TRANSLATIONS.with(|state| {
+        state.generation.as_ref().get();
+        let translations = state.translations.borrow();
+        let message = translations
+            .as_ref()
+            .and_then(|t| t.translate(context, text, plural, n))
+            .unwrap_or(if plural.is_empty() || n == 1 { text } else { plural });
+        format_translation(message, n, args)
+    })
+}
+
+#[test]
+fn test_format_translation() {
+    let args = [SharedString::from("a"), SharedString::from("b")];
+    assert_eq!(format_translation("x{}y{}z", 0, &args), "xaybz");
+    assert_eq!(format_translation("{1} {0}", 0, &args), "b a");
+    assert_eq!(format_translation("{n} files in {}", 42, &args), "42 files in a");
+    assert_eq!(format_translation("{{}} {{n}} }", 1, &args), "{} {n} }");
+    assert_eq!(format_translation("{x} {3} {", 1, &args), "{x} {3} {");
+    assert_eq!(format_translation("é{}é", 1, &args), "éaé");
+}
+
+#[test]
+fn test_plural_expression() {
+    // Polish
+    let rule = PluralExpression::parse(
+        "(n==1 ? 0 : n%10>=2 && n%10<=4 && (n%100<10 || n%100>=20) ? 1 : 2)",
+    )
+    .unwrap();
+    let forms: Vec<u64> = [1, 2, 4, 5, 12, 22, 25, 0].iter().map(|n| rule.evaluate(*n)).collect();
+    assert_eq!(forms, [0, 1, 1, 2, 2, 1, 2, 2]);
+    // Japanese
+    assert_eq!(PluralExpression::parse("0").unwrap().evaluate(5), 0);
+    assert_eq!(PluralExpression::parse(" n > 1").unwrap().evaluate(1), 0);
+    assert_eq!(PluralExpression::parse("!(n % 0)").unwrap().evaluate(3), 1);
+    assert_eq!(PluralExpression::parse("n +"), None);
+    assert_eq!(PluralExpression::parse("n ? 1"), None);
+    assert_eq!(PluralExpression::parse("(n"), None);
+    assert_eq!(PluralExpression::parse("x"), None);
+}
+
+#[test]
+fn test_from_po() {
+    let translations = Translations::from_po(
+        r#"
+# A comment
+msgid ""
+msgstr ""
+"Content-Type: text/plain; charset=UTF-8\n"
+"Plural-Forms: nplurals=3; plural=n==1 ? 0 : n%10>=2 && n%10<=4 && (n%100<10 || n%100>=20) ? 1 : 2;\n"
+
+#: main.60:3
+msgid "Hello"
+msgstr "Cześć"
+
+msgctxt "Menu"
+msgid "Open"
+msgstr ""
+"Otwórz"
+
+msgid "Close"
+msgstr ""
+
+#, fuzzy
+msgid "Quit"
+msgstr "Wyjście"
+
+msgid "{n} file"
+msgid_plural "{n} files"
+msgstr[0] "{n} plik"
+msgstr[1] "{n} pliki"
+msgstr[2] "{n} plików"
+
+#~ msgid "Obsolete"
+#~ msgstr "Przestarzały"
+"#,
+    )
+    .unwrap();
+    assert_eq!(translations.translate("", "Hello", "", 0), Some("Cześć"));
+    assert_eq!(translations.translate("Menu", "Open", "", 0), Some("Otwórz"));
+    assert_eq!(translations.translate("", "Open", "", 0), None);
+    assert_eq!(translations.translate("", "Close", "", 0), None);
+    assert_eq!(translations.translate("", "Quit", "", 0), None);
+    assert_eq!(translations.translate("", "Obsolete", "", 0), None);
+    assert_eq!(translations.translate("", "{n} file", "{n} files", 1), Some("{n} plik"));
+    assert_eq!(translations.translate("", "{n} file", "{n} files", 3), Some("{n} pliki"));
+    assert_eq!(translations.translate("", "{n} file", "{n} files", 5), Some("{n} plików"));
+
+    let error = Translations::from_po("msgid \"a\"\nmsgstr \"b\"\nfoo \"c\"").unwrap_err();
+    assert_eq!(error.line, 3);
+    assert!(Translations::from_po("msgid \"a\nmsgstr \"b\"").is_err());
+    assert!(Translations::from_po("\"a\"").is_err());
+    assert!(Translations::from_po("msgid \"\"\nmsgstr \"Plural-Forms: plural=n ?;\\n\"").is_err());
+}
+
+#[test]
+fn test_translate() {
+    assert_eq!(translate("", "Hello {}", "", 0, &["World".into()]), "Hello World");
+    assert_eq!(translate("", "{n} file", "{n} files", 1, &[]), "1 file");
+    assert_eq!(translate("", "{n} file", "{n} files", 2, &[]), "2 files");
+
+    let mut translations = Translations::new();
+    translations.insert("", "Hello {}", vec!["Bonjour {}".into()]);
+    translations.insert("", "{n} file", vec!["{n} fichier".into(), "{n} fichiers".into()]);
+    assert!(translations.set_plural_rule("n > 1"));
+
+    let property = Box::pin(Property::new(SharedString::default()));
+    property.as_ref().set_binding(|| translate("", "Hello {}", "", 0, &["World".into()]));
+    assert_eq!(property.as_ref().get(), "Hello World");
+    set_translations(Some(translations));
+    assert_eq!(property.as_ref().get(), "Bonjour World");
+    assert_eq!(translate("", "{n} file", "{n} files", 0, &[]), "0 fichier");
+    assert_eq!(translate("", "{n} file", "{n} files", 2, &[]), "2 fichiers");
+    set_translations(None);
+    assert_eq!(property.as_ref().get(), "Hello World");
+}
+
+#[cfg(feature = "ffi")]
+pub(crate) mod ffi {
+    #![allow(unsafe_code)]
+
+    use super::*;
+    use crate::slice::Slice;
+
+    /// Translate a message, see [`translate`]
+    #[no_mangle]
+    pub extern "C" fn sixtyfps_translate(
+        out: &mut SharedString,
+        context: &SharedString,
+        text: &SharedString,
+        plural: &SharedString,
+        n: i32,
+        args: Slice<SharedString>,
+    ) {
+        *out = translate(context, text, plural, n, args.as_slice());
+    }
+
+    /// Load the translations from the content of a gettext `.po` file.
+    /// Returns false if the content cannot be parsed.
+    #[no_mangle]
+    pub extern "C" fn sixtyfps_translations_load_po(source: Slice<u8>) -> bool {
+        match core::str::from_utf8(source.as_slice()).ok().map(Translations::from_po) {
+            Some(Ok(translations)) => {
+                set_translations(Some(translations));
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Remove the translations, so that the original messages are shown
+    #[no_mangle]
+    pub extern "C" fn sixtyfps_translations_clear() {
+        set_translations(None)
+    }
+}
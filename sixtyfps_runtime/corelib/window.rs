@@ -11,12 +11,15 @@
 //! Exposed Window API
 
 use crate::component::{ComponentRc, ComponentWeak};
-use crate::graphics::{Point, Size};
-use crate::input::{KeyEvent, MouseEvent, MouseInputState, TextCursorBlinker};
-use crate::items::{ItemRc, ItemRef, ItemWeak, MouseCursor};
+use crate::graphics::{Point, Rect, Size};
+use crate::input::{
+    DropEvent, KeyEvent, MouseEvent, MouseInputState, TextCursorBlinker, TouchPhase,
+};
+use crate::items::{ItemRc, ItemRef, ItemWeak, MouseCursor, PointerEventButton};
 use crate::properties::{Property, PropertyTracker};
 use alloc::boxed::Box;
 use alloc::rc::{Rc, Weak};
+use alloc::vec::Vec;
 use core::cell::{Cell, RefCell};
 use core::pin::Pin;
 
@@ -75,13 +78,43 @@ fn text_input_byte_offset_for_position(
     ) -> usize;
 
     /// That's the opposite of [`Self::text_input_byte_offset_for_position`]
-    /// It takes a (UTF-8) byte offset in the text property, and returns its position
+    /// It takes a (UTF-8) byte offset in the text property, and returns its position, at the top
+    /// of its line.
     fn text_input_position_for_byte_offset(
         &self,
         text_input: Pin<&crate::items::TextInput>,
         byte_offset: usize,
     ) -> Point;
 
+    /// Returns the (UTF-8) byte offset, in the text of the parsed `styled-text` of the `Text` item,
+    /// of the character under the given position. Returns None if there is no character there, or
+    /// if the backend doesn't support it.
+    /// This is used to find the link that is clicked.
+    fn text_byte_offset_for_position(
+        &self,
+        _text: Pin<&crate::items::Text>,
+        _pos: Point,
+    ) -> Option<usize> {
+        None
+    }
+
+    /// Enables or disables the input method of the platform. It is enabled while a text input
+    /// that accepts composed text has the focus.
+    fn set_ime_allowed(&self, _allowed: bool) {}
+
+    /// Tells the input method where the text cursor is, in logical window coordinates, so
+    /// that it can show its candidate window next to it.
+    fn set_ime_cursor_rect(&self, _rect: Rect) {}
+
+    /// Renders the content of the window into a buffer of RGBA pixels, with the size of the
+    /// window in physical pixels.
+    /// Returns None if the backend does not support it.
+    fn take_snapshot(
+        &self,
+    ) -> Option<crate::graphics::SharedPixelBuffer<crate::graphics::Rgba8Pixel>> {
+        None
+    }
+
     /// Return self as any so the backend can upcast
     fn as_any(&self) -> &dyn core::any::Any;
 }
@@ -149,6 +182,11 @@ pub struct Window {
     scale_factor: Pin<Box<Property<f32>>>,
     active: Pin<Box<Property<bool>>>,
     active_popup: RefCell<Option<PopupWindow>>,
+    /// The data of the drag and drop operation in progress, if any
+    drag_data: RefCell<Option<DropEvent>>,
+    /// The id of the finger that is sent as mouse events, while it touches the screen
+    primary_touch_id: Cell<Option<u64>>,
+    click_state: crate::input::ClickState,
 }
 
 impl Drop for Window {
@@ -177,6 +215,9 @@ pub fn new(
             scale_factor: Box::pin(Property::new(1.)),
             active: Box::pin(Property::new(false)),
             active_popup: Default::default(),
+            drag_data: Default::default(),
+            primary_touch_id: Default::default(),
+            click_state: Default::default(),
         });
         let window_weak = Rc::downgrade(&window);
         window.platform_window.set(platform_window_fn(&window_weak)).ok().unwrap();
@@ -211,6 +252,9 @@ pub fn set_component(&self, component: &ComponentRc) {
         self.close_popup();
         self.focus_item.replace(Default::default());
         self.mouse_input_state.replace(Default::default());
+        self.drag_data.replace(None);
+        self.primary_touch_id.set(None);
+        self.click_state.reset();
         self.component.replace(ComponentRc::downgrade(component));
         self.meta_properties_tracker.set_dirty(); // component changed, layout constraints for sure must be re-calculated
         self.request_window_properties_update();
@@ -237,6 +281,7 @@ pub fn try_component(&self) -> Option<ComponentRc> {
     /// * `component`: The SixtyFPS compiled component that provides the tree of items.
     pub fn process_mouse_input(self: Rc<Self>, mut event: MouseEvent) {
         crate::animations::update_animations();
+        self.click_state.count_click(&mut event);
 
         let embedded_popup_component =
             self.active_popup.borrow().as_ref().and_then(|popup| match popup.location {
@@ -288,6 +333,67 @@ pub fn process_mouse_input(self: Rc<Self>, mut event: MouseEvent) {
             }
         }
     }
+
+    /// Receive a touch event and pass it to the items of the component. The first finger that
+    /// touches the screen is sent as left button mouse events, and the other fingers are sent
+    /// as [`MouseEvent::TouchPoint`] events to the item that grabbed the first one.
+    ///
+    /// Arguments:
+    /// * `id`: The identifier of the finger, which stays the same while it touches the screen.
+    /// * `pos`: The position of the finger in window coordinates.
+    /// * `phase`: Whether the finger started touching the screen, moved, or was lifted.
+    pub fn process_touch_input(self: Rc<Self>, id: u64, pos: Point, phase: TouchPhase) {
+        let is_primary = match self.primary_touch_id.get() {
+            Some(primary_id) => primary_id == id,
+            None => phase == TouchPhase::started,
+        };
+        let event = if is_primary {
+            match phase {
+                TouchPhase::started => {
+                    self.primary_touch_id.set(Some(id));
+                    MouseEvent::MousePressed {
+                        pos,
+                        button: PointerEventButton::left,
+                        click_count: 0,
+                    }
+                }
+                TouchPhase::moved => MouseEvent::MouseMoved { pos },
+                TouchPhase::ended | TouchPhase::cancelled => {
+                    self.primary_touch_id.set(None);
+                    MouseEvent::MouseReleased { pos, button: PointerEventButton::left }
+                }
+            }
+        } else {
+            MouseEvent::TouchPoint { id, pos, phase }
+        };
+        self.process_mouse_input(event);
+    }
+
+    /// Sets the `click_count` of a [`MouseEvent::MousePressed`] event that is not sent through
+    /// [`Self::process_mouse_input`], from the previous presses
+    pub(crate) fn count_click(&self, event: &mut MouseEvent) {
+        self.click_state.count_click(event)
+    }
+
+    /// Starts a drag and drop operation with the given data. Until the mouse is released, the
+    /// mouse events are sent as [`MouseEvent::DragMove`] and [`MouseEvent::Drop`] to the items
+    /// under the mouse.
+    ///
+    /// This is called by the `DragArea` when the mouse moved far enough, or by the backend when
+    /// data from another application is dragged over the window.
+    pub fn start_drag(&self, data: DropEvent) {
+        self.drag_data.replace(Some(data));
+    }
+
+    /// Returns the data of the drag and drop operation in progress, if any.
+    pub fn drag_data(&self) -> Option<DropEvent> {
+        self.drag_data.borrow().clone()
+    }
+
+    /// Ends the drag and drop operation in progress, after the drop or when it was cancelled.
+    pub(crate) fn end_drag(&self) {
+        self.drag_data.replace(None);
+    }
     /// Receive a key event and pass it to the items of the component to
     /// change their state.
     ///
@@ -304,6 +410,98 @@ pub fn process_key_input(self: Rc<Self>, event: &KeyEvent) {
             }
             item = focus_item.parent_item();
         }
+
+        // Tab and Shift+Tab move the focus along the focus chain when no item used them
+        if event.event_type == crate::input::KeyEventType::KeyPressed
+            && event.text.chars().next() == Some(crate::input::key_codes::Tab)
+        {
+            if event.modifiers.shift {
+                self.focus_previous_item();
+            } else {
+                self.focus_next_item();
+            }
+        }
+    }
+
+    /// Returns the items that can receive the keyboard focus with Tab, in the order in which
+    /// they receive it.
+    ///
+    /// These are the `FocusScope` and the enabled `TextInput` items which are not hidden with
+    /// `visible: false`. The items with a positive `tab-order` come first, by increasing
+    /// `tab-order`, followed by the items with a `tab-order` of 0 in the order of the item tree.
+    /// The items with a negative `tab-order` are not part of the chain.
+    pub fn focus_chain(&self) -> Vec<ItemRc> {
+        use crate::item_tree::{ItemVisitorResult, TraversalOrder};
+        use crate::items::{Clip, FocusScope, TextInput};
+
+        let component = match self.try_component() {
+            Some(component) => component,
+            None => return Vec::new(),
+        };
+        let mut chain = Vec::new();
+        crate::item_tree::visit_items(
+            &component,
+            TraversalOrder::BackToFront,
+            |component, item, index, hidden: &bool| {
+                // The `visible` property is lowered to a Clip element that clips everything
+                let hidden = *hidden
+                    || ItemRef::downcast_pin::<Clip>(item)
+                        .map_or(false, |clip| clip.clip() && item.as_ref().geometry().is_empty());
+                let tab_order = if let Some(focus_scope) = ItemRef::downcast_pin::<FocusScope>(item)
+                {
+                    Some(focus_scope.tab_order())
+                } else {
+                    ItemRef::downcast_pin::<TextInput>(item)
+                        .filter(|text_input| text_input.enabled())
+                        .map(|text_input| text_input.tab_order())
+                };
+                match tab_order {
+                    Some(tab_order) if !hidden && tab_order >= 0 => {
+                        chain.push((tab_order, ItemRc::new(component.clone(), index)))
+                    }
+                    _ => {}
+                }
+                ItemVisitorResult::Continue(hidden)
+            },
+            false,
+        );
+        // The sort is stable, so the items with the same tab-order stay in the item tree order
+        chain.sort_by_key(|(tab_order, _)| if *tab_order == 0 { i32::MAX } else { *tab_order });
+        chain.into_iter().map(|(_, item)| item).collect()
+    }
+
+    /// Moves the keyboard focus to the next item of the [focus chain](Self::focus_chain),
+    /// or to the first one if no item of the chain has the focus.
+    pub fn focus_next_item(self: Rc<Self>) {
+        let chain = self.focus_chain();
+        let next = match self.focus_chain_position(&chain) {
+            Some(position) => chain.get((position + 1) % chain.len()),
+            None => chain.first(),
+        };
+        if let Some(next) = next {
+            self.set_focus_item(next);
+        }
+    }
+
+    /// Moves the keyboard focus to the previous item of the [focus chain](Self::focus_chain),
+    /// or to the last one if no item of the chain has the focus.
+    pub fn focus_previous_item(self: Rc<Self>) {
+        let chain = self.focus_chain();
+        let previous = match self.focus_chain_position(&chain) {
+            Some(position) => chain.get((position + chain.len() - 1) % chain.len()),
+            None => chain.last(),
+        };
+        if let Some(previous) = previous {
+            self.set_focus_item(previous);
+        }
+    }
+
+    fn focus_chain_position(&self, chain: &[ItemRc]) -> Option<usize> {
+        let focus_item = self.focus_item.borrow().upgrade()?;
+        chain.iter().position(|item| {
+            item.index() == focus_item.index()
+                && vtable::VRc::ptr_eq(&item.component(), &focus_item.component())
+        })
     }
 
     /// Installs a binding on the specified property that's toggled whenever the text cursor is supposed to be visible or not.
@@ -323,18 +521,33 @@ pub fn set_cursor_blink_binding(&self, prop: &crate::Property<bool>) {
     /// Sets the focus to the item pointed to by item_ptr. This will remove the focus from any
     /// currently focused item.
     pub fn set_focus_item(self: Rc<Self>, focus_item: &ItemRc) {
-        if let Some(old_focus_item) = self.as_ref().focus_item.borrow().upgrade() {
+        // Don't keep the focus_item borrowed while sending the events, as the focus-in and
+        // focus-out callbacks may change the focus again
+        let old_focus_item = self.as_ref().focus_item.replace(focus_item.downgrade()).upgrade();
+        if let Some(old_focus_item) = old_focus_item {
             old_focus_item
                 .borrow()
                 .as_ref()
                 .focus_event(&crate::input::FocusEvent::FocusOut, &self);
         }
 
-        *self.as_ref().focus_item.borrow_mut() = focus_item.downgrade();
-
         focus_item.borrow().as_ref().focus_event(&crate::input::FocusEvent::FocusIn, &self);
     }
 
+    /// Tells the input method where the text cursor of the focus item is. The rectangle is
+    /// relative to the focus item, and is mapped to window coordinates.
+    pub fn set_text_input_cursor_rect(&self, rect: Rect) {
+        let mut rect = rect;
+        let mut item = self.focus_item.borrow().upgrade();
+        while let Some(current) = item {
+            rect = rect.translate(current.borrow().as_ref().geometry().origin.to_vector());
+            item = current.parent_item().upgrade();
+        }
+        if let Some(platform_window) = self.platform_window.get() {
+            platform_window.set_ime_cursor_rect(rect);
+        }
+    }
+
     /// Sets the focus on the window to true or false, depending on the have_focus argument.
     /// This results in WindowFocusReceived and WindowFocusLost events.
     pub fn set_focus(self: Rc<Self>, have_focus: bool) {
@@ -536,6 +749,24 @@ pub fn default_font_properties(&self) -> crate::graphics::FontRequest {
             .unwrap_or_default()
     }
 
+    /// Returns the layout direction that is set on the root item if it's a Window item.
+    pub fn layout_direction(&self) -> crate::items::LayoutDirection {
+        self.try_component()
+            .and_then(|component_rc| {
+                let component = ComponentRc::borrow_pin(&component_rc);
+                let root_item = component.as_ref().get_item_ref(0);
+                ItemRef::downcast_pin(root_item).map(
+                    |window_item: Pin<&crate::items::WindowItem>| window_item.layout_direction(),
+                )
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns true if the layouts and the text alignments of this window are mirrored.
+    pub fn is_right_to_left(&self) -> bool {
+        self.layout_direction() == crate::items::LayoutDirection::rtl
+    }
+
     /// Sets the size of the window item. This method is typically called in response to receiving a
     /// window resize event from the windowing system.
     /// Size is in logical pixels.
@@ -673,6 +904,15 @@ pub mod ffi {
         window.set_scale_factor(value)
     }
 
+    /// Returns true if the layout direction of the window is right to left.
+    #[no_mangle]
+    pub unsafe extern "C" fn sixtyfps_windowrc_is_right_to_left(
+        handle: *const WindowRcOpaque,
+    ) -> bool {
+        let window = &*(handle as *const WindowRc);
+        window.is_right_to_left()
+    }
+
     /// Sets the window scale factor, merely for testing purposes.
     #[no_mangle]
     pub unsafe extern "C" fn sixtyfps_windowrc_free_graphics_resources<'a>(
@@ -693,6 +933,50 @@ pub mod ffi {
         window.clone().set_focus_item(focus_item)
     }
 
+    /// Reverts the last edit of the given TextInput item
+    #[no_mangle]
+    pub unsafe extern "C" fn sixtyfps_windowrc_text_input_undo(
+        handle: *const WindowRcOpaque,
+        text_input: &ItemRc,
+    ) {
+        let window = &*(handle as *const WindowRc);
+        if let Some(text_input) =
+            ItemRef::downcast_pin::<crate::items::TextInput>(text_input.borrow())
+        {
+            text_input.undo(window);
+        }
+    }
+
+    /// Applies again the last edit of the given TextInput item that was reverted
+    #[no_mangle]
+    pub unsafe extern "C" fn sixtyfps_windowrc_text_input_redo(
+        handle: *const WindowRcOpaque,
+        text_input: &ItemRc,
+    ) {
+        let window = &*(handle as *const WindowRc);
+        if let Some(text_input) =
+            ItemRef::downcast_pin::<crate::items::TextInput>(text_input.borrow())
+        {
+            text_input.redo(window);
+        }
+    }
+
+    /// Moves the viewport of the given Flickable item to the given position
+    #[no_mangle]
+    pub unsafe extern "C" fn sixtyfps_windowrc_flickable_scroll_to(
+        _handle: *const WindowRcOpaque,
+        flickable: &ItemRc,
+        x: f32,
+        y: f32,
+        animated: bool,
+    ) {
+        if let Some(flickable) =
+            ItemRef::downcast_pin::<crate::items::Flickable>(flickable.borrow())
+        {
+            flickable.scroll_to(x, y, animated);
+        }
+    }
+
     /// Associates the window with the given component.
     #[no_mangle]
     pub unsafe extern "C" fn sixtyfps_windowrc_set_component(
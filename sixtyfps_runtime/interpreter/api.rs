@@ -48,6 +48,8 @@ pub enum ValueType {
     Brush,
     /// Correspond to `image` type in .60.
     Image,
+    /// Correspond to an enum type in .60.
+    Enumeration,
     /// The type is not a public type but something internal.
     Other = -1,
 }
@@ -70,6 +72,7 @@ fn from(ty: LangType) -> Self {
             LangType::Struct { .. } => Self::Struct,
             LangType::Void => Self::Void,
             LangType::Image => Self::Image,
+            LangType::Enumeration(_) => Self::Enumeration,
             _ => Self::Other,
         }
     }
@@ -115,9 +118,8 @@ pub enum Value {
     #[doc(hidden)]
     /// An easing curve
     EasingCurve(sixtyfps_corelib::animations::EasingCurve),
-    #[doc(hidden)]
-    /// An enumeration, like `TextHorizontalAlignment::align_center`, represented by `("TextHorizontalAlignment", "align_center")`.
-    /// FIXME: consider representing that with a number?
+    /// A value of an enum, like `TextHorizontalAlignment.center` or the value of an enum declared
+    /// in a .60 file, represented by the name of the enum and of the value: `("TextHorizontalAlignment", "center")`.
     EnumerationValue(String, String),
     #[doc(hidden)]
     LayoutCache(SharedVector<f32>),
@@ -136,6 +138,7 @@ pub fn value_type(&self) -> ValueType {
             Value::Struct(_) => ValueType::Struct,
             Value::Brush(_) => ValueType::Brush,
             Value::Image(_) => ValueType::Image,
+            Value::EnumerationValue(..) => ValueType::Enumeration,
             _ => ValueType::Other,
         }
     }
@@ -259,10 +262,11 @@ fn try_into(self) -> Result<$name, ()> {
 declare_value_struct_conversion!(struct sixtyfps_corelib::model::StandardListViewItem { text });
 declare_value_struct_conversion!(struct sixtyfps_corelib::properties::StateInfo { current_state, previous_state, change_time });
 declare_value_struct_conversion!(struct sixtyfps_corelib::input::KeyboardModifiers { control, alt, shift, meta });
-declare_value_struct_conversion!(struct sixtyfps_corelib::input::KeyEvent { event_type, text, modifiers });
+declare_value_struct_conversion!(struct sixtyfps_corelib::input::KeyEvent { event_type, text, modifiers, ..Default::default() });
 declare_value_struct_conversion!(struct sixtyfps_corelib::layout::LayoutInfo { min, max, min_percent, max_percent, preferred, stretch });
 declare_value_struct_conversion!(struct sixtyfps_corelib::graphics::Point { x, y, ..Default::default()});
 declare_value_struct_conversion!(struct sixtyfps_corelib::items::PointerEvent { kind, button });
+declare_value_struct_conversion!(struct sixtyfps_corelib::input::DropEvent { mime_type, data });
 
 /// Implement From / TryInto for Value that convert an `enum` to/from `Value::EnumerationValue`
 ///
@@ -306,6 +310,10 @@ fn try_into(self) -> Result<$ty, ()> {
 );
 declare_value_enum_conversion!(sixtyfps_corelib::items::TextOverflow, TextOverflow);
 declare_value_enum_conversion!(sixtyfps_corelib::items::TextWrap, TextWrap);
+declare_value_enum_conversion!(sixtyfps_corelib::items::InputType, InputType);
+declare_value_enum_conversion!(sixtyfps_corelib::items::TextDecoration, TextDecoration);
+declare_value_enum_conversion!(sixtyfps_corelib::items::LayoutDirection, LayoutDirection);
+declare_value_enum_conversion!(sixtyfps_corelib::items::BoundsBehavior, BoundsBehavior);
 declare_value_enum_conversion!(sixtyfps_corelib::layout::LayoutAlignment, LayoutAlignment);
 declare_value_enum_conversion!(sixtyfps_corelib::items::ImageFit, ImageFit);
 declare_value_enum_conversion!(sixtyfps_corelib::items::ImageRendering, ImageRendering);
@@ -317,6 +325,7 @@ fn try_into(self) -> Result<$ty, ()> {
 declare_value_enum_conversion!(sixtyfps_corelib::items::PointerEventKind, PointerEventKind);
 declare_value_enum_conversion!(sixtyfps_corelib::items::PointerEventButton, PointerEventButton);
 declare_value_enum_conversion!(sixtyfps_corelib::items::DialogButtonRole, DialogButtonRole);
+declare_value_enum_conversion!(sixtyfps_corelib::items::AccessibleRole, AccessibleRole);
 
 impl From<sixtyfps_corelib::animations::Instant> for Value {
     fn from(value: sixtyfps_corelib::animations::Instant) -> Self {
@@ -1089,6 +1098,9 @@ pub fn run_event_loop() {
 pub mod testing {
     use sixtyfps_corelib::window::WindowHandleAccess;
 
+    pub use sixtyfps_corelib::input::TouchPhase;
+    pub use sixtyfps_corelib::tests::{advance_time, process_posted_events};
+
     /// Wrapper around [`sixtyfps_corelib::tests::sixtyfps_send_mouse_click`]
     pub fn send_mouse_click(comp: &super::ComponentInstance, x: f32, y: f32) {
         sixtyfps_corelib::tests::sixtyfps_send_mouse_click(
@@ -1098,6 +1110,41 @@ pub fn send_mouse_click(comp: &super::ComponentInstance, x: f32, y: f32) {
             comp.window().window_handle(),
         );
     }
+    /// Wrapper around [`sixtyfps_corelib::tests::sixtyfps_send_mouse_drag`]
+    pub fn send_mouse_drag(
+        comp: &super::ComponentInstance,
+        from_x: f32,
+        from_y: f32,
+        to_x: f32,
+        to_y: f32,
+        duration: core::time::Duration,
+    ) {
+        sixtyfps_corelib::tests::sixtyfps_send_mouse_drag(
+            &vtable::VRc::into_dyn(comp.inner.clone()),
+            from_x,
+            from_y,
+            to_x,
+            to_y,
+            duration.as_millis() as u64,
+            comp.window().window_handle(),
+        );
+    }
+    /// Wrapper around [`sixtyfps_corelib::tests::sixtyfps_send_touch_event`]
+    pub fn send_touch_event(
+        comp: &super::ComponentInstance,
+        id: u64,
+        x: f32,
+        y: f32,
+        phase: TouchPhase,
+    ) {
+        sixtyfps_corelib::tests::sixtyfps_send_touch_event(
+            id,
+            x,
+            y,
+            phase,
+            comp.window().window_handle(),
+        );
+    }
     /// Wrapper around [`sixtyfps_corelib::tests::send_keyboard_string_sequence`]
     pub fn send_keyboard_string_sequence(
         comp: &super::ComponentInstance,
@@ -1109,6 +1156,92 @@ pub fn send_keyboard_string_sequence(
             comp.window().window_handle(),
         );
     }
+    /// Wrapper around [`sixtyfps_corelib::tests::sixtyfps_send_composition_update`]
+    pub fn send_composition_update(
+        comp: &super::ComponentInstance,
+        preedit_text: sixtyfps_corelib::SharedString,
+        cursor: usize,
+    ) {
+        sixtyfps_corelib::tests::sixtyfps_send_composition_update(
+            &preedit_text,
+            cursor,
+            comp.window().window_handle(),
+        );
+    }
+    /// Wrapper around [`sixtyfps_corelib::tests::sixtyfps_send_composition_commit`]
+    pub fn send_composition_commit(
+        comp: &super::ComponentInstance,
+        text: sixtyfps_corelib::SharedString,
+    ) {
+        sixtyfps_corelib::tests::sixtyfps_send_composition_commit(
+            &text,
+            comp.window().window_handle(),
+        );
+    }
+    /// Renders the window of the component into a buffer of RGBA pixels.
+    /// Returns None if the backend does not support it, only the testing backend does.
+    pub fn take_snapshot(
+        comp: &super::ComponentInstance,
+    ) -> Option<sixtyfps_corelib::graphics::SharedPixelBuffer<sixtyfps_corelib::graphics::Rgba8Pixel>>
+    {
+        comp.window().window_handle().take_snapshot()
+    }
+
+    #[cfg(feature = "testing")]
+    pub use sixtyfps_rendering_backend_testing::{
+        compare_with_reference, UPDATE_REFERENCE_ENV_VAR,
+    };
+
+    pub use sixtyfps_corelib::tests::ElementHandle;
+
+    /// Returns all the elements of the component for which `predicate` returns true, in the order
+    /// of the item tree. This includes the elements created by `for` and `if`.
+    pub fn find_elements(
+        comp: &super::ComponentInstance,
+        predicate: impl FnMut(&ElementHandle) -> bool,
+    ) -> Vec<ElementHandle> {
+        sixtyfps_corelib::tests::find_elements(
+            &vtable::VRc::into_dyn(comp.inner.clone()),
+            comp.window().window_handle(),
+            predicate,
+        )
+    }
+
+    /// Returns the elements of the component that have the given id in the .60 file.
+    pub fn find_elements_by_id(comp: &super::ComponentInstance, id: &str) -> Vec<ElementHandle> {
+        find_elements(comp, |element| element.id() == id)
+    }
+
+    /// Returns the elements of the component of the given type, for example `Text` or `Button`.
+    pub fn find_elements_by_type(
+        comp: &super::ComponentInstance,
+        type_name: &str,
+    ) -> Vec<ElementHandle> {
+        find_elements(comp, |element| element.type_name() == type_name)
+    }
+
+    /// Returns the `Text` and `TextInput` elements of the component that display the given text.
+    pub fn find_elements_by_text(
+        comp: &super::ComponentInstance,
+        text: &str,
+    ) -> Vec<ElementHandle> {
+        find_elements(comp, |element| element.text().map_or(false, |t| t.as_str() == text))
+    }
+
+    /// Returns the value of the property `name` of the element, or None if the element has no
+    /// such property.
+    pub fn element_property(element: &ElementHandle, name: &str) -> Option<super::Value> {
+        crate::dynamic_component::get_item_property(element.item(), name)
+    }
+
+    pub use sixtyfps_corelib::accessibility::AccessibilityNode;
+
+    /// Builds the accessibility tree of the component, from its `accessible-*` properties.
+    pub fn accessibility_tree(comp: &super::ComponentInstance) -> AccessibilityNode {
+        sixtyfps_corelib::accessibility::build_accessibility_tree(&vtable::VRc::into_dyn(
+            comp.inner.clone(),
+        ))
+    }
 }
 
 #[test]
@@ -1357,6 +1490,202 @@ fn component_definition_struct_properties() {
     );
 }
 
+#[test]
+fn take_snapshot() {
+    use sixtyfps_corelib::window::WindowHandleAccess;
+    sixtyfps_rendering_backend_testing::init();
+    let mut compiler = ComponentCompiler::default();
+    let comp_def = spin_on::spin_on(
+        compiler.build_from_source(
+            r#"
+    export Dummy := Window {
+        width: 10px;
+        height: 10px;
+        background: #ff0000;
+        Rectangle { x: 5px; width: 5px; background: #0000ff; }
+    }"#
+            .into(),
+            "".into(),
+        ),
+    )
+    .unwrap();
+    let instance = comp_def.create();
+    instance.window().window_handle().set_scale_factor(2.);
+
+    let snapshot = testing::take_snapshot(&instance).unwrap();
+    assert_eq!((snapshot.width(), snapshot.height()), (20, 20));
+    let pixel = |x: usize, y: usize| {
+        let p = snapshot.as_slice()[y * snapshot.width() + x];
+        (p.r, p.g, p.b, p.a)
+    };
+    assert_eq!(pixel(2, 10), (0xff, 0, 0, 0xff));
+    assert_eq!(pixel(9, 19), (0xff, 0, 0, 0xff));
+    assert_eq!(pixel(10, 0), (0, 0, 0xff, 0xff));
+    assert_eq!(pixel(19, 19), (0, 0, 0xff, 0xff));
+
+    // A missing reference is an error, unless the references are being updated
+    use sixtyfps_rendering_backend_testing::{compare_with_reference, UPDATE_REFERENCE_ENV_VAR};
+    if std::env::var_os(UPDATE_REFERENCE_ENV_VAR).is_none() {
+        let missing = std::env::temp_dir().join("sixtyfps-no-such-directory/reference.png");
+        assert!(compare_with_reference(&snapshot, &missing, 0).is_err());
+    }
+}
+
+#[test]
+fn virtual_event_loop() {
+    use sixtyfps_corelib::timers::{Timer, TimerMode};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    sixtyfps_rendering_backend_testing::init();
+
+    let start = sixtyfps_corelib::tests::virtual_time();
+    let log = Arc::new(Mutex::new(Vec::<String>::new()));
+    let push = |log: &Arc<Mutex<Vec<String>>>, what: &str| {
+        let elapsed = (sixtyfps_corelib::tests::virtual_time() - start).as_millis();
+        log.lock().unwrap().push(format!("{} {}", what, elapsed));
+    };
+
+    let timer = Timer::default();
+    timer.start(TimerMode::Repeated, Duration::from_millis(100), {
+        let log = log.clone();
+        move || push(&log, "repeated")
+    });
+    Timer::single_shot(Duration::from_millis(150), {
+        let log = log.clone();
+        move || {
+            push(&log, "single");
+            let log = log.clone();
+            sixtyfps_corelib::backend::instance()
+                .unwrap()
+                .post_event(Box::new(move || log.lock().unwrap().push("posted".into())));
+        }
+    });
+
+    testing::advance_time(Duration::from_millis(99));
+    assert!(log.lock().unwrap().is_empty());
+    testing::advance_time(Duration::from_millis(151));
+    assert_eq!(*log.lock().unwrap(), ["repeated 100", "single 150", "posted", "repeated 200"]);
+}
+
+#[test]
+fn find_elements() {
+    use sixtyfps_corelib::graphics::euclid;
+    sixtyfps_rendering_backend_testing::init();
+    let mut compiler = ComponentCompiler::default();
+    let comp_def = spin_on::spin_on(
+        compiler.build_from_source(
+            r#"
+    MyButton := Rectangle {
+        property <string> label;
+        callback clicked;
+        width: 40px;
+        height: 20px;
+        Text { text: label; }
+        TouchArea { clicked => { root.clicked(); } }
+    }
+    export Dummy := Window {
+        width: 100px;
+        height: 100px;
+        property <string> save-label: "Save";
+        property <int> saved;
+        save := MyButton { x: 10px; y: 20px; label: save-label; clicked => { root.saved += 1; } }
+        MyButton { x: 10px; y: 50px; label: "Hidden"; visible: false; }
+        for name in ["A", "B"]: Text { x: 200px; text: name; }
+    }"#
+            .into(),
+            "".into(),
+        ),
+    )
+    .unwrap();
+    let instance = comp_def.create();
+
+    let save = testing::find_elements_by_id(&instance, "save");
+    assert_eq!(save.len(), 1);
+    assert_eq!(save[0].type_name(), "MyButton");
+    assert_eq!(save[0].absolute_geometry(), euclid::rect(10., 20., 40., 20.));
+    assert!(save[0].is_visible());
+    assert_eq!(testing::element_property(&save[0], "label"), Some(Value::String("Save".into())));
+    assert_eq!(testing::element_property(&save[0], "x"), Some(Value::Number(10.)));
+    assert_eq!(testing::element_property(&save[0], "not-a-property"), None);
+
+    let label = testing::find_elements_by_text(&instance, "Save");
+    assert_eq!(label.len(), 1);
+    assert_eq!(label[0].type_name(), "Text");
+    assert_eq!(label[0].absolute_geometry().origin, euclid::point2(10., 20.));
+    label[0].click();
+    assert_eq!(instance.get_property("saved").unwrap(), Value::Number(1.));
+
+    let texts = testing::find_elements_by_type(&instance, "Text");
+    let texts_content = texts.iter().map(|t| t.text().unwrap()).collect::<Vec<_>>();
+    assert_eq!(texts_content, ["Save", "Hidden", "A", "B"]);
+    assert!(texts[0].is_visible());
+    // Hidden with `visible: false`
+    assert!(!texts[1].is_visible());
+    // Outside of the window
+    assert!(!texts[2].is_visible());
+}
+
+#[test]
+fn accessibility_tree() {
+    use sixtyfps_corelib::graphics::euclid;
+    use sixtyfps_corelib::items::AccessibleRole;
+    sixtyfps_rendering_backend_testing::init();
+    let mut compiler = ComponentCompiler::default();
+    let comp_def = spin_on::spin_on(
+        compiler.build_from_source(
+            r#"
+    MyCheckBox := Rectangle {
+        property <string> text;
+        property <bool> checked;
+        accessible-role: checkbox;
+        accessible-label: text;
+        accessible-value: checked ? "checked" : "unchecked";
+        width: 40px;
+        height: 20px;
+        TouchArea { clicked => { root.checked = !root.checked; } }
+    }
+    export Dummy := Window {
+        width: 100px;
+        height: 100px;
+        Rectangle {
+            x: 5px;
+            y: 5px;
+            accessible-role: group;
+            accessible-label: "Options";
+            MyCheckBox { x: 10px; y: 20px; text: "Dark mode"; accessible-description: "Use a dark theme"; }
+            MyCheckBox { text: "Hidden"; visible: false; }
+        }
+        for name in ["A", "B"]: Text { text: name; accessible-role: text; accessible-label: text; }
+    }"#
+            .into(),
+            "".into(),
+        ),
+    )
+    .unwrap();
+    let instance = comp_def.create();
+
+    let tree = testing::accessibility_tree(&instance);
+    assert_eq!(tree.role, AccessibleRole::none);
+    assert_eq!(tree.children.len(), 3);
+    let group = &tree.children[0];
+    assert_eq!(group.role, AccessibleRole::group);
+    assert_eq!(group.label, "Options");
+    assert_eq!(group.children.len(), 1);
+    let checkbox = &group.children[0];
+    assert_eq!(checkbox.role, AccessibleRole::checkbox);
+    assert_eq!(checkbox.label, "Dark mode");
+    assert_eq!(checkbox.value, "unchecked");
+    assert_eq!(checkbox.description, "Use a dark theme");
+    assert_eq!(checkbox.geometry, euclid::rect(15., 25., 40., 20.));
+    let labels = tree.children[1..].iter().map(|n| n.label.as_str()).collect::<Vec<_>>();
+    assert_eq!(labels, ["A", "B"]);
+
+    testing::find_elements_by_type(&instance, "MyCheckBox")[0].click();
+    let tree = testing::accessibility_tree(&instance);
+    assert_eq!(tree.children[0].children[0].value, "checked");
+    assert_eq!(tree.iter().count(), 5);
+}
+
 #[cfg(feature = "ffi")]
 #[allow(missing_docs)]
 #[path = "ffi.rs"]
@@ -22,13 +22,15 @@
     ItemTreeNode, ItemVisitorRefMut, ItemVisitorVTable, TraversalOrder, VisitChildrenResult,
 };
 use sixtyfps_corelib::items::{
-    Flickable, ItemRc, ItemRef, ItemVTable, ItemWeak, PropertyAnimation,
+    AccessibleRole, AccessibleStringProperty, Flickable, ItemRc, ItemRef, ItemVTable, ItemWeak,
+    PropertyAnimation,
 };
 use sixtyfps_corelib::layout::{BoxLayoutCellData, LayoutInfo, Orientation};
 use sixtyfps_corelib::model::RepeatedComponent;
 use sixtyfps_corelib::model::Repeater;
 use sixtyfps_corelib::properties::InterpolatedPropertyValue;
 use sixtyfps_corelib::rtti::{self, AnimatedBindingKind, FieldOffset, PropertyInfo};
+use sixtyfps_corelib::tests::ElementPropertyValue;
 use sixtyfps_corelib::window::{api::Window, WindowHandleAccess, WindowRc};
 use sixtyfps_corelib::{Brush, Color, Property, SharedString, SharedVector};
 use std::collections::BTreeMap;
@@ -180,6 +182,33 @@ fn get_item_ref(self: Pin<&Self>, index: usize) -> Pin<ItemRef> {
     fn parent_item(self: Pin<&Self>, index: usize, result: &mut ItemWeak) {
         self.borrow().as_ref().parent_item(index, result)
     }
+    fn item_element_info(
+        self: Pin<&Self>,
+        index: usize,
+        id: &mut SharedString,
+        type_name: &mut SharedString,
+    ) {
+        self.borrow().as_ref().item_element_info(index, id, type_name)
+    }
+    fn accessible_role(self: Pin<&Self>, index: usize) -> AccessibleRole {
+        self.borrow().as_ref().accessible_role(index)
+    }
+    fn accessible_string_property(
+        self: Pin<&Self>,
+        index: usize,
+        what: AccessibleStringProperty,
+        result: &mut SharedString,
+    ) {
+        self.borrow().as_ref().accessible_string_property(index, what, result)
+    }
+    fn element_property(
+        self: Pin<&Self>,
+        index: usize,
+        name: &SharedString,
+        result: &mut ElementPropertyValue,
+    ) {
+        self.borrow().as_ref().element_property(index, name, result)
+    }
 }
 
 sixtyfps_corelib::ComponentVTable_static!(static COMPONENT_BOX_VT for ErasedComponentBox);
@@ -280,6 +309,10 @@ pub struct ComponentDescription<'id> {
     /// INVARIANT: both dynamic_type and item_tree have the same lifetime id. Here it is erased to 'static
     dynamic_type: Rc<dynamic_type::TypeInfo<'id>>,
     item_tree: Vec<ItemTreeNode<crate::dynamic_type::Instance<'id>>>,
+    /// The id and type name of the elements in the item tree, as written in the .60 file
+    element_infos: Vec<(SharedString, SharedString)>,
+    /// The elements with accessible properties, indexed by their index in the item tree
+    accessible_elements: HashMap<usize, ElementRc>,
     pub(crate) items: HashMap<String, ItemWithinComponent>,
     pub(crate) custom_properties: HashMap<String, PropertiesWithinComponent>,
     pub(crate) custom_callbacks: HashMap<String, FieldOffset<Instance<'id>, Callback>>,
@@ -741,6 +774,9 @@ pub(crate) fn generate_component<'id>(
                 rtti_for::<Rectangle>(),
                 rtti_for::<BorderRectangle>(),
                 rtti_for::<TouchArea>(),
+                rtti_for::<DragArea>(),
+                rtti_for::<DropArea>(),
+                rtti_for::<GestureArea>(),
                 rtti_for::<FocusScope>(),
                 rtti_for::<Path>(),
                 rtti_for::<Flickable>(),
@@ -750,6 +786,7 @@ pub(crate) fn generate_component<'id>(
                 rtti_for::<BoxShadow>(),
                 rtti_for::<Rotate>(),
                 rtti_for::<Opacity>(),
+                rtti_for::<TransformItem>(),
             ]
             .iter()
             .cloned(),
@@ -777,6 +814,8 @@ fn push(rtti: &mut HashMap<&str, Rc<ItemRTTI>>) {
 
     struct TreeBuilder<'id> {
         tree_array: Vec<ItemTreeNode<Instance<'id>>>,
+        element_infos: Vec<(SharedString, SharedString)>,
+        accessible_elements: HashMap<usize, ElementRc>,
         items_types: HashMap<String, ItemWithinComponent>,
         type_builder: dynamic_type::TypeBuilder<'id>,
         repeater: Vec<ErasedRepeaterWithinComponent<'id>>,
@@ -796,6 +835,8 @@ fn push_repeated_item(
             self.tree_array
                 .push(ItemTreeNode::DynamicTree { index: repeater_count as usize, parent_index });
             let item = item_rc.borrow();
+            let (id, type_name) = item.source_id_and_type_name();
+            self.element_infos.push((id.into(), type_name.into()));
             let base_component = item.base_type.as_component();
             self.repeater_names.insert(item.id.clone(), self.repeater.len());
             generativity::make_guard!(guard);
@@ -812,9 +853,11 @@ fn push_repeated_item(
         fn push_native_item(
             &mut self,
             rc_item: &ElementRc,
+            _source_element: &ElementRc,
             child_offset: u32,
             parent_index: u32,
             _component_state: &Self::SubComponentState,
+            _source_component_state: &Self::SubComponentState,
         ) {
             let item = rc_item.borrow();
             let rt = self.rtti.get(&*item.base_type.as_native().class_name).unwrap_or_else(|| {
@@ -838,6 +881,11 @@ fn push_native_item(
                 children_count: item.children.len() as u32,
                 parent_index,
             });
+            let (id, type_name) = item.source_id_and_type_name();
+            self.element_infos.push((id.into(), type_name.into()));
+            if !item.accessibility_props.is_empty() {
+                self.accessible_elements.insert(self.tree_array.len() - 1, rc_item.clone());
+            }
             self.items_types.insert(
                 item.id.clone(),
                 ItemWithinComponent { offset, rtti: rt.clone(), elem: rc_item.clone() },
@@ -867,6 +915,8 @@ fn enter_component_children(
 
     let mut builder = TreeBuilder {
         tree_array: vec![],
+        element_infos: vec![],
+        accessible_elements: HashMap::new(),
         items_types: HashMap::new(),
         type_builder: dynamic_type::TypeBuilder::new(guard),
         repeater: vec![],
@@ -932,12 +982,16 @@ fn animated_property_info<T: Clone + Default + InterpolatedPropertyValue + 'stat
                     .insert(name.clone(), builder.type_builder.add_field_type::<Callback>());
                 continue;
             }
+            // functions do not need storage, their body is evaluated when called
+            Type::Function { .. } => continue,
             Type::Struct { name: Some(name), .. } if name.ends_with("::StateInfo") => {
                 property_info::<sixtyfps_corelib::properties::StateInfo>()
             }
             Type::Struct { .. } => property_info::<Value>(),
             Type::Array(_) => property_info::<Value>(),
             Type::Percent => property_info::<f32>(),
+            // enums declared in .60 files have no native type
+            Type::Enumeration(e) if e.node.is_some() => property_info::<Value>(),
             Type::Enumeration(e) => match e.name.as_ref() {
                 "LayoutAlignment" => property_info::<sixtyfps_corelib::layout::LayoutAlignment>(),
                 "TextHorizontalAlignment" => {
@@ -948,6 +1002,10 @@ fn animated_property_info<T: Clone + Default + InterpolatedPropertyValue + 'stat
                 }
                 "TextWrap" => property_info::<sixtyfps_corelib::items::TextWrap>(),
                 "TextOverflow" => property_info::<sixtyfps_corelib::items::TextOverflow>(),
+                "InputType" => property_info::<sixtyfps_corelib::items::InputType>(),
+                "TextDecoration" => property_info::<sixtyfps_corelib::items::TextDecoration>(),
+                "LayoutDirection" => property_info::<sixtyfps_corelib::items::LayoutDirection>(),
+                "BoundsBehavior" => property_info::<sixtyfps_corelib::items::BoundsBehavior>(),
                 "ImageFit" => property_info::<sixtyfps_corelib::items::ImageFit>(),
                 "FillRule" => property_info::<sixtyfps_corelib::items::FillRule>(),
                 "MouseCursor" => property_info::<sixtyfps_corelib::items::MouseCursor>(),
@@ -1035,6 +1093,10 @@ fn animated_property_info<T: Clone + Default + InterpolatedPropertyValue + 'stat
         layout_info,
         get_item_ref,
         parent_item,
+        item_element_info,
+        accessible_role,
+        accessible_string_property,
+        element_property,
         drop_in_place,
         dealloc,
     };
@@ -1042,6 +1104,8 @@ fn animated_property_info<T: Clone + Default + InterpolatedPropertyValue + 'stat
         ct: t,
         dynamic_type: builder.type_builder.build(),
         item_tree: builder.tree_array,
+        element_infos: builder.element_infos,
+        accessible_elements: builder.accessible_elements,
         items: builder.items_types,
         custom_properties,
         custom_callbacks,
@@ -1165,8 +1229,10 @@ pub fn instantiate(
 
     // Some properties are generated as Value, but for which the default constructed Value must be initialized
     for (prop_name, decl) in &component_type.original.root_element.borrow().property_declarations {
-        if !matches!(decl.property_type, Type::Struct { .. } | Type::Array(_))
-            || decl.is_alias.is_some()
+        if !matches!(
+            decl.property_type,
+            Type::Struct { .. } | Type::Array(_) | Type::Enumeration(_)
+        ) || decl.is_alias.is_some()
         {
             continue;
         }
@@ -1558,6 +1624,95 @@ extern "C" fn layout_info(component: ComponentRefPin, orientation: Orientation)
     *result = ItemRc::new(self_rc, *parent_index as _).downgrade();
 }
 
+/// Returns the value of the property `name` of the element of the given item, or None if the
+/// element has no such property or if the item does not belong to a component of the interpreter.
+pub(crate) fn get_item_property(item: &ItemRc, name: &str) -> Option<Value> {
+    let component = item.component();
+    let erased =
+        vtable::VRef::downcast_pin::<ErasedComponentBox>(vtable::VRc::borrow_pin(&component))?;
+    generativity::make_guard!(guard);
+    let component_box = erased.get_ref().unerase(guard);
+    let instance_ref = component_box.borrow_instance();
+    load_item_property(instance_ref, item.index(), name)
+}
+
+/// Evaluate the property `name` of the element of the item at `index`, by its name in the .60 file.
+fn load_item_property(instance_ref: InstanceRef, index: usize, name: &str) -> Option<Value> {
+    let element = instance_ref
+        .component_type
+        .items
+        .values()
+        .find(|item_within_component| item_within_component.item_index() == index)?
+        .elem
+        .clone();
+    let nr = object_tree::source_property_reference(&element, name)?;
+    eval::load_property(instance_ref, &nr.element(), nr.name()).ok()
+}
+
+extern "C" fn item_element_info(
+    component: ComponentRefPin,
+    index: usize,
+    id: &mut SharedString,
+    type_name: &mut SharedString,
+) {
+    generativity::make_guard!(guard);
+    // This is fine since we can only be called with a component that with our vtable which is a ComponentDescription
+    let instance_ref = unsafe { InstanceRef::from_pin_ref(component, guard) };
+    let (element_id, element_type_name) = &instance_ref.component_type.element_infos[index];
+    *id = element_id.clone();
+    *type_name = element_type_name.clone();
+}
+
+/// Evaluate the `accessible-{what}` property of the item at `index`, if it is set.
+fn load_accessible_property(instance_ref: InstanceRef, index: usize, what: &str) -> Option<Value> {
+    let element = instance_ref.component_type.accessible_elements.get(&index)?;
+    let nr = element.borrow().accessibility_props.get(what)?.clone();
+    eval::load_property(instance_ref, &nr.element(), nr.name()).ok()
+}
+
+extern "C" fn accessible_role(component: ComponentRefPin, index: usize) -> AccessibleRole {
+    generativity::make_guard!(guard);
+    // This is fine since we can only be called with a component that with our vtable which is a ComponentDescription
+    let instance_ref = unsafe { InstanceRef::from_pin_ref(component, guard) };
+    load_accessible_property(instance_ref, index, "role")
+        .and_then(|v| v.try_into().ok())
+        .unwrap_or_default()
+}
+
+extern "C" fn accessible_string_property(
+    component: ComponentRefPin,
+    index: usize,
+    what: AccessibleStringProperty,
+    result: &mut SharedString,
+) {
+    generativity::make_guard!(guard);
+    // This is fine since we can only be called with a component that with our vtable which is a ComponentDescription
+    let instance_ref = unsafe { InstanceRef::from_pin_ref(component, guard) };
+    if let Some(value) = load_accessible_property(instance_ref, index, &what.to_string()) {
+        if let Ok(value) = value.try_into() {
+            *result = value;
+        }
+    }
+}
+
+extern "C" fn element_property(
+    component: ComponentRefPin,
+    index: usize,
+    name: &SharedString,
+    result: &mut ElementPropertyValue,
+) {
+    generativity::make_guard!(guard);
+    // This is fine since we can only be called with a component that with our vtable which is a ComponentDescription
+    let instance_ref = unsafe { InstanceRef::from_pin_ref(component, guard) };
+    match load_item_property(instance_ref, index, name) {
+        Some(Value::Number(number)) => *result = number.into(),
+        Some(Value::Bool(boolean)) => *result = boolean.into(),
+        Some(Value::String(string)) => *result = string.into(),
+        Some(Value::Brush(brush)) => *result = brush.into(),
+        _ => {}
+    }
+}
+
 unsafe extern "C" fn drop_in_place(component: vtable::VRefMut<ComponentVTable>) -> vtable::Layout {
     let instance_ptr = component.as_ptr() as *mut Instance<'static>;
     let layout = (*instance_ptr).type_info().layout();
@@ -11,7 +11,9 @@
 use crate::dynamic_component::InstanceRef;
 use core::convert::TryInto;
 use core::pin::Pin;
-use corelib::graphics::{GradientStop, LinearGradientBrush, PathElement};
+use corelib::graphics::{
+    ConicGradientBrush, GradientStop, LinearGradientBrush, PathElement, RadialGradientBrush,
+};
 use corelib::items::{ItemRef, PropertyAnimation};
 use corelib::rtti::AnimatedBindingKind;
 use corelib::window::{WindowHandleAccess, WindowRc};
@@ -147,6 +149,7 @@ pub fn eval_expression(expression: &Expression, local_context: &mut EvalLocalCon
         Expression::NumberLiteral(n, unit) => Value::Number(unit.normalize(*n)),
         Expression::BoolLiteral(b) => Value::Bool(*b),
         Expression::CallbackReference { .. } => panic!("callback in expression"),
+        Expression::FunctionReference { .. } => panic!("function in expression"),
         Expression::BuiltinFunctionReference(..) => panic!(
             "naked builtin function reference not allowed, should be handled by function call"
         ),
@@ -203,12 +206,22 @@ pub fn eval_expression(expression: &Expression, local_context: &mut EvalLocalCon
                 let args = arguments.iter().map(|e| eval_expression(e, local_context)).collect::<Vec<_>>();
                 invoke_callback(local_context.component_instance, &nr.element(), nr.name(), &args).unwrap()
             }
+            Expression::FunctionReference(nr) => {
+                let args = arguments.iter().map(|e| eval_expression(e, local_context)).collect::<Vec<_>>();
+                call_function(local_context.component_instance, &nr.element(), nr.name(), args).unwrap()
+            }
             Expression::BuiltinFunctionReference(BuiltinFunction::GetWindowScaleFactor, _) => {
                 match local_context.component_instance {
                     ComponentInstance::InstanceRef(component) => Value::Number(window_ref(component).unwrap().scale_factor() as _),
                     ComponentInstance::GlobalComponent(_) => panic!("Cannot get the window from a global component"),
                 }
             }
+            Expression::BuiltinFunctionReference(BuiltinFunction::WindowIsRightToLeft, _) => {
+                match local_context.component_instance {
+                    ComponentInstance::InstanceRef(component) => Value::Bool(window_ref(component).unwrap().is_right_to_left()),
+                    ComponentInstance::GlobalComponent(_) => panic!("Cannot get the window from a global component"),
+                }
+            }
             Expression::BuiltinFunctionReference(BuiltinFunction::Debug, _) => {
                 let to_print: SharedString = eval_expression(&arguments[0], local_context).try_into().unwrap();
                 corelib::debug_log!("{}", to_print);
@@ -288,6 +301,87 @@ pub fn eval_expression(expression: &Expression, local_context: &mut EvalLocalCon
                     panic!("internal error: argument to SetFocusItem must be an element")
                 }
             }
+            Expression::BuiltinFunctionReference(f, _)
+                if matches!(f, BuiltinFunction::TextInputUndo | BuiltinFunction::TextInputRedo) =>
+            {
+                if arguments.len() != 1 {
+                    panic!("internal error: incorrect argument count to {:?}", f)
+                }
+                let component = match local_context.component_instance {
+                    ComponentInstance::InstanceRef(c) => c,
+                    ComponentInstance::GlobalComponent(_) => {
+                        panic!("Cannot access a TextInput from a global component")
+                    }
+                };
+                if let Expression::ElementReference(text_input) = &arguments[0] {
+                    generativity::make_guard!(guard);
+
+                    let text_input = text_input.upgrade().unwrap();
+                    let enclosing_component =
+                        enclosing_component_for_element(&text_input, component, guard);
+                    let item_info = &enclosing_component.component_type.items
+                        [text_input.borrow().id.as_str()];
+                    let text_input_comp =
+                        enclosing_component.self_weak().get().unwrap().upgrade().unwrap();
+                    let item_rc = corelib::items::ItemRc::new(
+                        vtable::VRc::into_dyn(text_input_comp),
+                        item_info.item_index(),
+                    );
+
+                    if let Some(text_input) =
+                        ItemRef::downcast_pin::<corelib::items::TextInput>(item_rc.borrow())
+                    {
+                        let window = window_ref(component).unwrap();
+                        if matches!(f, BuiltinFunction::TextInputUndo) {
+                            text_input.undo(window);
+                        } else {
+                            text_input.redo(window);
+                        }
+                    }
+                    Value::Void
+                } else {
+                    panic!("internal error: argument to {:?} must be an element", f)
+                }
+            }
+            Expression::BuiltinFunctionReference(BuiltinFunction::FlickableScrollTo, _) => {
+                if arguments.len() != 4 {
+                    panic!("internal error: incorrect argument count to FlickableScrollTo")
+                }
+                let component = match local_context.component_instance {
+                    ComponentInstance::InstanceRef(c) => c,
+                    ComponentInstance::GlobalComponent(_) => {
+                        panic!("Cannot access a Flickable from a global component")
+                    }
+                };
+                if let Expression::ElementReference(flickable) = &arguments[0] {
+                    generativity::make_guard!(guard);
+
+                    let flickable = flickable.upgrade().unwrap();
+                    let enclosing_component =
+                        enclosing_component_for_element(&flickable, component, guard);
+                    let item_info =
+                        &enclosing_component.component_type.items[flickable.borrow().id.as_str()];
+                    let flickable_comp =
+                        enclosing_component.self_weak().get().unwrap().upgrade().unwrap();
+                    let item_rc = corelib::items::ItemRc::new(
+                        vtable::VRc::into_dyn(flickable_comp),
+                        item_info.item_index(),
+                    );
+
+                    let x: f64 = eval_expression(&arguments[1], local_context).try_into().unwrap();
+                    let y: f64 = eval_expression(&arguments[2], local_context).try_into().unwrap();
+                    let animated: bool =
+                        eval_expression(&arguments[3], local_context).try_into().unwrap();
+                    if let Some(flickable) =
+                        ItemRef::downcast_pin::<corelib::items::Flickable>(item_rc.borrow())
+                    {
+                        flickable.scroll_to(x as f32, y as f32, animated);
+                    }
+                    Value::Void
+                } else {
+                    panic!("internal error: argument to FlickableScrollTo must be an element")
+                }
+            }
             Expression::BuiltinFunctionReference(BuiltinFunction::ShowPopupWindow, _) => {
                 if arguments.len() != 1 {
                     panic!("internal error: incorrect argument count to ShowPopupWindow")
@@ -343,6 +437,81 @@ pub fn eval_expression(expression: &Expression, local_context: &mut EvalLocalCon
                     panic!("Argument not a string");
                 }
             }
+            Expression::BuiltinFunctionReference(BuiltinFunction::StringLength, _) => {
+                let s: SharedString = eval_expression(&arguments[0], local_context).try_into().unwrap();
+                Value::Number(corelib::string::string_length(&s) as f64)
+            }
+            Expression::BuiltinFunctionReference(BuiltinFunction::StringToUppercase, _) => {
+                let s: SharedString = eval_expression(&arguments[0], local_context).try_into().unwrap();
+                Value::String(s.to_uppercase().into())
+            }
+            Expression::BuiltinFunctionReference(BuiltinFunction::StringToLowercase, _) => {
+                let s: SharedString = eval_expression(&arguments[0], local_context).try_into().unwrap();
+                Value::String(s.to_lowercase().into())
+            }
+            Expression::BuiltinFunctionReference(BuiltinFunction::StringContains, _) => {
+                let s: SharedString = eval_expression(&arguments[0], local_context).try_into().unwrap();
+                let needle: SharedString = eval_expression(&arguments[1], local_context).try_into().unwrap();
+                Value::Bool(s.contains(needle.as_str()))
+            }
+            Expression::BuiltinFunctionReference(BuiltinFunction::StringStartsWith, _) => {
+                let s: SharedString = eval_expression(&arguments[0], local_context).try_into().unwrap();
+                let prefix: SharedString = eval_expression(&arguments[1], local_context).try_into().unwrap();
+                Value::Bool(s.starts_with(prefix.as_str()))
+            }
+            Expression::BuiltinFunctionReference(BuiltinFunction::StringSubstring, _) => {
+                let s: SharedString = eval_expression(&arguments[0], local_context).try_into().unwrap();
+                let start: i32 = eval_expression(&arguments[1], local_context).try_into().unwrap();
+                let length: i32 = eval_expression(&arguments[2], local_context).try_into().unwrap();
+                Value::String(corelib::string::string_substring(&s, start, length))
+            }
+            Expression::BuiltinFunctionReference(BuiltinFunction::StringTrim, _) => {
+                let s: SharedString = eval_expression(&arguments[0], local_context).try_into().unwrap();
+                Value::String(s.trim().into())
+            }
+            Expression::BuiltinFunctionReference(BuiltinFunction::StringReplace, _) => {
+                let s: SharedString = eval_expression(&arguments[0], local_context).try_into().unwrap();
+                let from: SharedString = eval_expression(&arguments[1], local_context).try_into().unwrap();
+                let to: SharedString = eval_expression(&arguments[2], local_context).try_into().unwrap();
+                Value::String(s.replace(from.as_str(), &to).into())
+            }
+            Expression::BuiltinFunctionReference(BuiltinFunction::StringSplit, _) => {
+                let s: SharedString = eval_expression(&arguments[0], local_context).try_into().unwrap();
+                let separator: SharedString = eval_expression(&arguments[1], local_context).try_into().unwrap();
+                Value::Array(
+                    corelib::string::string_split(&s, &separator).into_iter().map(Value::String).collect(),
+                )
+            }
+            Expression::BuiltinFunctionReference(BuiltinFunction::FormatNumber, _) => {
+                let n: f64 = eval_expression(&arguments[0], local_context).try_into().unwrap();
+                let spec: SharedString = eval_expression(&arguments[1], local_context).try_into().unwrap();
+                Value::String(corelib::string::format_number(n, &spec))
+            }
+            Expression::BuiltinFunctionReference(BuiltinFunction::FormatInteger, _) => {
+                let n: f64 = eval_expression(&arguments[0], local_context).try_into().unwrap();
+                let spec: SharedString = eval_expression(&arguments[1], local_context).try_into().unwrap();
+                Value::String(corelib::string::format_integer(n as i64, &spec))
+            }
+            Expression::BuiltinFunctionReference(BuiltinFunction::FormatString, _) => {
+                let s: SharedString = eval_expression(&arguments[0], local_context).try_into().unwrap();
+                let spec: SharedString = eval_expression(&arguments[1], local_context).try_into().unwrap();
+                Value::String(corelib::string::format_string(&s, &spec))
+            }
+            Expression::BuiltinFunctionReference(BuiltinFunction::Translate, _) => {
+                if arguments.len() != 5 {
+                    panic!("internal error: incorrect argument count to Translate")
+                }
+                let mut to_string = |e| -> SharedString { eval_expression(e, local_context).try_into().unwrap() };
+                let context = to_string(&arguments[0]);
+                let text = to_string(&arguments[1]);
+                let plural = to_string(&arguments[2]);
+                let n: i32 = eval_expression(&arguments[3], local_context).try_into().unwrap();
+                let args = match eval_expression(&arguments[4], local_context) {
+                    Value::Array(args) => args.iter().map(|a| a.clone().try_into().unwrap()).collect::<Vec<SharedString>>(),
+                    _ => panic!("internal error: the arguments of Translate must be an array"),
+                };
+                Value::String(corelib::translations::translate(&context, &text, &plural, n, &args))
+            }
             Expression::BuiltinFunctionReference(BuiltinFunction::ColorBrighter, _) => {
                 if arguments.len() != 2 {
                     panic!("internal error: incorrect argument count to ColorBrighter")
@@ -582,6 +751,20 @@ pub fn eval_expression(expression: &Expression, local_context: &mut EvalLocalCon
                 GradientStop{ color, position }
             }))))
         }
+        Expression::RadialGradient{stops} => {
+            Value::Brush(Brush::RadialGradient(RadialGradientBrush::new_circle(stops.iter().map(|(color, stop)| {
+                let color = eval_expression(color, local_context).try_into().unwrap();
+                let position = eval_expression(stop, local_context).try_into().unwrap();
+                GradientStop{ color, position }
+            }))))
+        }
+        Expression::ConicGradient{stops} => {
+            Value::Brush(Brush::ConicGradient(ConicGradientBrush::new(stops.iter().map(|(color, stop)| {
+                let color = eval_expression(color, local_context).try_into().unwrap();
+                let position = eval_expression(stop, local_context).try_into().unwrap();
+                GradientStop{ color, position }
+            }))))
+        }
         Expression::EnumerationValue(value) => {
             Value::EnumerationValue(value.enumeration.name.clone(), value.to_string())
         }
@@ -947,6 +1130,28 @@ pub fn window_ref<'a>(component: InstanceRef<'a, '_>) -> Option<&'a WindowRc> {
         .map(|window| window.window_handle())
 }
 
+/// Evaluate the body of the function `function_name` declared in the .60 file on the given element
+pub(crate) fn call_function(
+    component_instance: ComponentInstance,
+    element: &ElementRc,
+    function_name: &str,
+    args: Vec<Value>,
+) -> Option<Value> {
+    generativity::make_guard!(guard);
+    match enclosing_component_instance_for_element(element, component_instance, guard) {
+        ComponentInstance::InstanceRef(enclosing_component) => {
+            let element = element.borrow();
+            let body = element.bindings.get(function_name)?.borrow();
+            let mut local_context =
+                EvalLocalContext::from_function_arguments(enclosing_component, args);
+            Some(eval_expression(&body.expression, &mut local_context))
+        }
+        ComponentInstance::GlobalComponent(global) => {
+            global.as_ref().call_function(function_name, args).ok()
+        }
+    }
+}
+
 /// Return the component instance which hold the given element.
 /// Does not take in account the global component.
 pub fn enclosing_component_for_element<'a, 'old_id, 'new_id>(
@@ -94,6 +94,8 @@ fn set_property(
     fn get_property(self: Pin<&Self>, prop_name: &str) -> Result<Value, ()>;
 
     fn get_property_ptr(self: Pin<&Self>, prop_name: &str) -> *const ();
+
+    fn call_function(self: Pin<&Self>, function_name: &str, args: Vec<Value>) -> Result<Value, ()>;
 }
 
 pub fn instantiate(description: &CompiledGlobal) -> (String, Pin<Rc<dyn GlobalComponent>>) {
@@ -183,6 +185,18 @@ fn set_callback_handler(
         let comp = self.0.unerase(guard);
         comp.description().set_callback_handler(comp.borrow(), callback_name, handler)
     }
+
+    fn call_function(self: Pin<&Self>, function_name: &str, args: Vec<Value>) -> Result<Value, ()> {
+        generativity::make_guard!(guard);
+        let comp = self.0.unerase(guard);
+        crate::eval::call_function(
+            crate::eval::ComponentInstance::InstanceRef(comp.borrow_instance()),
+            &comp.description().original.root_element,
+            function_name,
+            args,
+        )
+        .ok_or(())
+    }
 }
 
 impl<T: rtti::BuiltinItem + 'static> GlobalComponent for T {
@@ -223,6 +237,14 @@ fn set_callback_handler(
         let cb = Self::callbacks().into_iter().find(|(k, _)| *k == callback_name).ok_or(())?.1;
         cb.set_handler(self, handler)
     }
+
+    fn call_function(
+        self: Pin<&Self>,
+        _function_name: &str,
+        _args: Vec<Value>,
+    ) -> Result<Value, ()> {
+        Err(())
+    }
 }
 
 pub(crate) fn generate(component: &Rc<Component>) -> CompiledGlobal {
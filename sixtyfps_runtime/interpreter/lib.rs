@@ -98,6 +98,10 @@ pub(crate) fn register_font_from_path<P: AsRef<std::path::Path>>(
 #[doc(inline)]
 pub use sixtyfps_corelib::{Brush, Color, SharedString, SharedVector};
 
+/// (Re-export from corelib.)
+#[doc(inline)]
+pub use sixtyfps_corelib::translations::{set_translations, Translations, TranslationsError};
+
 /// One need to use at least one function in each module in order to get them
 /// exported in the final binary.
 /// This only use functions from modules which are not otherwise used.
@@ -19,7 +19,9 @@
 use sixtyfps_corelib as corelib;
 
 use corelib::graphics::Point;
-use corelib::input::{KeyEvent, KeyEventType, KeyboardModifiers, MouseEvent};
+use corelib::input::{
+    DropEvent, KeyEvent, KeyEventType, KeyboardModifiers, MouseEvent, TouchPhase,
+};
 use corelib::SharedString;
 use corelib::{window::*, Color};
 use std::cell::{Cell, RefCell};
@@ -183,7 +185,7 @@ struct NotRunningEventLoop {
 
 impl NotRunningEventLoop {
     fn new() -> Self {
-        let instance = winit::event_loop::EventLoop::with_user_event();
+        let instance = winit::event_loop::EventLoopBuilder::with_user_event().build();
         let event_loop_proxy = instance.create_proxy();
         Self { instance, event_loop_proxy }
     }
@@ -347,6 +349,35 @@ pub fn winit_key_to_string(virtual_keycode: winit::event::VirtualKeyCode) -> Opt
 #[path = "key_codes.rs"]
 mod key_codes;
 
+/// The URL of a file dragged from another application, for a `text/uri-list`
+fn file_url(path: &std::path::Path) -> String {
+    let path = path.to_string_lossy().replace('\\', "/");
+    let mut url = String::from(if path.starts_with('/') { "file://" } else { "file:///" });
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' | b':' => {
+                url.push(byte as char)
+            }
+            _ => url.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    url
+}
+
+/// The data of the files dragged from another application: a `text/uri-list` with one URL per line
+fn file_drop_event(urls: &[String]) -> DropEvent {
+    DropEvent { mime_type: "text/uri-list".into(), data: urls.join("\r\n").into() }
+}
+
+/// The files dragged over a window from another application. winit sends one event per file, so
+/// they are collected here to be dragged and dropped all at once.
+#[derive(Default)]
+struct FileDrag {
+    hovered: Vec<String>,
+    /// The files dropped on a window since the last `MainEventsCleared`, when the drop happens
+    dropped: Option<(Rc<dyn WinitWindow>, Vec<String>)>,
+}
+
 fn process_window_event(
     window: Rc<dyn WinitWindow>,
     event: WindowEvent,
@@ -354,6 +385,7 @@ fn process_window_event(
     control_flow: &mut winit::event_loop::ControlFlow,
     cursor_pos: &mut Point,
     pressed: &mut bool,
+    file_drag: &mut FileDrag,
 ) {
     let runtime_window = window.runtime_window();
     match event {
@@ -390,7 +422,12 @@ fn process_window_event(
 
             let modifiers = window.current_keyboard_modifiers().get();
 
-            let mut event = KeyEvent { event_type: KeyEventType::KeyPressed, text, modifiers };
+            let mut event = KeyEvent {
+                event_type: KeyEventType::KeyPressed,
+                text,
+                modifiers,
+                ..Default::default()
+            };
             runtime_window.clone().process_key_input(&event);
             event.event_type = KeyEventType::KeyReleased;
             runtime_window.process_key_input(&event);
@@ -415,10 +452,34 @@ fn process_window_event(
                     },
                     text,
                     modifiers: window.current_keyboard_modifiers().get(),
+                    ..Default::default()
                 };
                 runtime_window.process_key_input(&event);
             };
         }
+        WindowEvent::Ime(winit::event::Ime::Preedit(text, cursor)) => {
+            corelib::animations::update_animations();
+            let (preedit_selection_start, preedit_selection_end) =
+                cursor.unwrap_or((text.len(), text.len()));
+            let event = KeyEvent {
+                event_type: KeyEventType::UpdateComposition,
+                text: text.into(),
+                modifiers: window.current_keyboard_modifiers().get(),
+                preedit_selection_start,
+                preedit_selection_end,
+            };
+            runtime_window.process_key_input(&event);
+        }
+        WindowEvent::Ime(winit::event::Ime::Commit(text)) => {
+            corelib::animations::update_animations();
+            let event = KeyEvent {
+                event_type: KeyEventType::CommitComposition,
+                text: text.into(),
+                modifiers: window.current_keyboard_modifiers().get(),
+                ..Default::default()
+            };
+            runtime_window.process_key_input(&event);
+        }
         WindowEvent::ModifiersChanged(state) => {
             // To provide an easier cross-platform behavior, we map the command key to control
             // on macOS, and control to meta.
@@ -456,7 +517,12 @@ fn process_window_event(
                     euclid::point2(d.x, d.y)
                 }
             };
-            runtime_window.process_mouse_input(MouseEvent::MouseWheel { pos: *cursor_pos, delta });
+            let modifiers = window.current_keyboard_modifiers().get();
+            runtime_window.process_mouse_input(MouseEvent::MouseWheel {
+                pos: *cursor_pos,
+                delta,
+                modifiers,
+            });
         }
         WindowEvent::MouseInput { state, button, .. } => {
             corelib::animations::update_animations();
@@ -469,7 +535,7 @@ fn process_window_event(
             let ev = match state {
                 winit::event::ElementState::Pressed => {
                     *pressed = true;
-                    MouseEvent::MousePressed { pos: *cursor_pos, button }
+                    MouseEvent::MousePressed { pos: *cursor_pos, button, click_count: 0 }
                 }
                 winit::event::ElementState::Released => {
                     *pressed = false;
@@ -482,18 +548,46 @@ fn process_window_event(
             corelib::animations::update_animations();
             let location = touch.location.to_logical(runtime_window.scale_factor() as f64);
             let pos = euclid::point2(location.x, location.y);
-            let ev = match touch.phase {
+            let phase = match touch.phase {
                 winit::event::TouchPhase::Started => {
                     *pressed = true;
-                    MouseEvent::MousePressed { pos, button: PointerEventButton::left }
+                    TouchPhase::started
                 }
-                winit::event::TouchPhase::Ended | winit::event::TouchPhase::Cancelled => {
+                winit::event::TouchPhase::Ended => {
                     *pressed = false;
-                    MouseEvent::MouseReleased { pos, button: PointerEventButton::left }
+                    TouchPhase::ended
                 }
-                winit::event::TouchPhase::Moved => MouseEvent::MouseMoved { pos },
+                winit::event::TouchPhase::Cancelled => {
+                    *pressed = false;
+                    TouchPhase::cancelled
+                }
+                winit::event::TouchPhase::Moved => TouchPhase::moved,
             };
-            runtime_window.process_mouse_input(ev);
+            runtime_window.process_touch_input(touch.id, pos, phase);
+        }
+        // winit does not report the position of the mouse with the file events, and does not send
+        // CursorMoved while files are dragged over the window. So the position used is the last one
+        // seen before the drag, usually where the mouse entered the window, and the files can only
+        // be dropped on the DropArea at that position.
+        WindowEvent::HoveredFile(ref path) => {
+            corelib::animations::update_animations();
+            file_drag.hovered.push(file_url(path));
+            runtime_window.start_drag(file_drop_event(&file_drag.hovered));
+            runtime_window.process_mouse_input(MouseEvent::DragMove { pos: *cursor_pos });
+        }
+        WindowEvent::DroppedFile(ref path) => {
+            // One event is received for each file: drop them together once all were received
+            file_drag.hovered.clear();
+            file_drag
+                .dropped
+                .get_or_insert_with(|| (window.clone(), Vec::new()))
+                .1
+                .push(file_url(path));
+        }
+        WindowEvent::HoveredFileCancelled => {
+            corelib::animations::update_animations();
+            file_drag.hovered.clear();
+            runtime_window.process_mouse_input(MouseEvent::MouseExit);
         }
         WindowEvent::ScaleFactorChanged { scale_factor, new_inner_size: size } => {
             if !std::env::var("SIXTYFPS_SCALE_FACTOR").is_ok() {
@@ -532,6 +626,7 @@ pub fn run(quit_behavior: sixtyfps_corelib::backend::EventLoopQuitBehavior) {
     // last seen cursor position, (physical coordinate)
     let mut cursor_pos = Point::default();
     let mut pressed = false;
+    let mut file_drag = FileDrag::default();
     let mut run_fn = move |event: Event<CustomEvent>,
                            event_loop_target: &EventLoopWindowTarget<CustomEvent>,
                            control_flow: &mut ControlFlow| {
@@ -550,10 +645,20 @@ pub fn run(quit_behavior: sixtyfps_corelib::backend::EventLoopQuitBehavior) {
                             control_flow,
                             &mut cursor_pos,
                             &mut pressed,
+                            &mut file_drag,
                         );
                     };
                 }
 
+                winit::event::Event::MainEventsCleared => {
+                    if let Some((window, urls)) = file_drag.dropped.take() {
+                        corelib::animations::update_animations();
+                        let runtime_window = window.runtime_window();
+                        runtime_window.start_drag(file_drop_event(&urls));
+                        runtime_window.process_mouse_input(MouseEvent::Drop { pos: cursor_pos });
+                    }
+                }
+
                 winit::event::Event::RedrawRequested(id) => {
                     corelib::animations::update_animations();
                     if let Some(window) = window_by_id(id) {
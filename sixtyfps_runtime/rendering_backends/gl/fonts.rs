@@ -14,11 +14,14 @@
 use font_kit::loader::Loader;
 use sixtyfps_corelib::graphics::{FontRequest, Point, Size};
 use sixtyfps_corelib::items::{
-    TextHorizontalAlignment, TextOverflow, TextVerticalAlignment, TextWrap,
+    Text, TextHorizontalAlignment, TextOverflow, TextVerticalAlignment, TextWrap,
 };
+use sixtyfps_corelib::styled_text::StyledText;
 use sixtyfps_corelib::{SharedString, SharedVector};
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+use std::pin::Pin;
 
 pub const DEFAULT_FONT_SIZE: f32 = 12.;
 pub const DEFAULT_FONT_WEIGHT: i32 = 400; // CSS normal
@@ -76,6 +79,7 @@ pub fn register_font_from_path(_path: &std::path::Path) -> Result<(), Box<dyn st
 struct FontCacheKey {
     family: SharedString,
     weight: i32,
+    italic: bool,
 }
 
 #[derive(Clone)]
@@ -94,7 +98,14 @@ pub fn init_paint(&self, letter_spacing: f32, mut paint: femtovg::Paint) -> femt
         paint
     }
 
-    pub fn text_size(&self, letter_spacing: f32, text: &str, max_width: Option<f32>) -> Size {
+    /// Returns the size of the text. If `line_height` is None, the lines are as high as the font.
+    pub fn text_size(
+        &self,
+        letter_spacing: f32,
+        line_height: Option<f32>,
+        text: &str,
+        max_width: Option<f32>,
+    ) -> Size {
         let paint = self.init_paint(letter_spacing, femtovg::Paint::default());
         let font_metrics = self.text_context.measure_font(paint).unwrap();
         let mut lines = 0;
@@ -120,7 +131,7 @@ pub fn text_size(&self, letter_spacing: f32, text: &str, max_width: Option<f32>)
                 width = measure.width().max(width);
             }
         }
-        euclid::size2(width, lines as f32 * font_metrics.height())
+        euclid::size2(width, lines as f32 * line_height.unwrap_or_else(|| font_metrics.height()))
     }
 }
 
@@ -133,7 +144,9 @@ pub(crate) fn text_size(
     let font =
         FONT_CACHE.with(|cache| cache.borrow_mut().font(font_request.clone(), scale_factor, text));
     let letter_spacing = font_request.letter_spacing.unwrap_or_default();
-    font.text_size(letter_spacing, text, max_width.map(|x| x * scale_factor)) / scale_factor
+    let line_height = font_request.line_height.map(|h| h * scale_factor);
+    font.text_size(letter_spacing, line_height, text, max_width.map(|x| x * scale_factor))
+        / scale_factor
 }
 
 #[derive(Copy, Clone)]
@@ -253,6 +266,7 @@ fn load_single_font(&mut self, request: &FontRequest) -> LoadedFont {
         let cache_key = FontCacheKey {
             family: request.family.clone().unwrap_or_default(),
             weight: request.weight.unwrap(),
+            italic: request.italic,
         };
 
         if let Some(loaded_font) = self.loaded_fonts.get(&cache_key) {
@@ -268,6 +282,7 @@ fn load_single_font(&mut self, request: &FontRequest) -> LoadedFont {
         let query = fontdb::Query {
             families: &[family],
             weight: fontdb::Weight(request.weight.unwrap() as u16),
+            style: if request.italic { fontdb::Style::Italic } else { fontdb::Style::Normal },
             ..Default::default()
         };
 
@@ -416,6 +431,8 @@ fn font_fallbacks_for_request(
             weight: _request.weight,
             pixel_size: _request.pixel_size,
             letter_spacing: _request.letter_spacing,
+            italic: _request.italic,
+            ..Default::default()
         })
         .filter(|request| self.is_known_family(request))
         .collect::<Vec<_>>()
@@ -466,6 +483,8 @@ fn font_fallbacks_for_request(
                 weight: _request.weight,
                 pixel_size: _request.pixel_size,
                 letter_spacing: _request.letter_spacing,
+                italic: _request.italic,
+                ..Default::default()
             })
             .filter(|request| self.is_known_family(request))
             .collect()
@@ -485,6 +504,8 @@ fn font_fallbacks_for_request(
                 weight: _request.weight,
                 pixel_size: _request.pixel_size,
                 letter_spacing: _request.letter_spacing,
+                italic: _request.italic,
+                ..Default::default()
             })
             .filter(|request| self.is_known_family(request))
             .collect()
@@ -502,6 +523,8 @@ fn font_fallbacks_for_request(
             weight: _request.weight,
             pixel_size: _request.pixel_size,
             letter_spacing: _request.letter_spacing,
+            italic: _request.italic,
+            ..Default::default()
         }]
         .iter()
         .filter(|request| self.is_known_family(request))
@@ -597,7 +620,10 @@ fn check_and_update_script_coverage(
 /// Layout the given string in lines, and call the `layout_line` callback with the line to draw at position y.
 /// The signature of the `layout_line` function is: `(canvas, text, pos, start_index, line_metrics)`.
 /// start index is the starting byte of the text in the string.
+/// The lines are `line_height` apart, or the height of the font if it is None, and the glyphs are
+/// centered vertically in their line. When `max_lines` is not 0, at most that many lines are shown.
 /// Returns the baseline y coordinate.
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn layout_text_lines(
     string: &str,
     font: &Font,
@@ -606,6 +632,7 @@ pub(crate) fn layout_text_lines(
     wrap: TextWrap,
     overflow: TextOverflow,
     single_line: bool,
+    (line_height, max_lines): (Option<f32>, usize),
     paint: femtovg::Paint,
     mut layout_line: impl FnMut(&str, Point, usize, &femtovg::TextMetrics),
 ) -> f32 {
@@ -615,18 +642,26 @@ pub(crate) fn layout_text_lines(
     let text_context = FONT_CACHE.with(|cache| cache.borrow().text_context.clone());
     let font_metrics = text_context.measure_font(paint).unwrap();
     let font_height = font_metrics.height();
+    let line_height = line_height.unwrap_or(font_height);
+    let glyph_offset = (line_height - font_height) / 2.;
 
     let text_height = || {
-        if single_line {
-            font_height
+        let height = if single_line {
+            line_height
         } else {
             // Note: this is kind of doing twice the layout because text_size also does it
             font.text_size(
                 paint.letter_spacing(),
+                Some(line_height),
                 string,
                 if wrap { Some(max_width) } else { None },
             )
             .height
+        };
+        if max_lines > 0 {
+            height.min(max_lines as f32 * line_height)
+        } else {
+            height
         }
     };
 
@@ -641,7 +676,7 @@ pub(crate) fn layout_text_lines(
             }
             TextHorizontalAlignment::right => max_width - f32::min(max_width, line_metrics.width()),
         };
-        layout_line(text, Point::new(x, y), start, line_metrics);
+        layout_line(text, Point::new(x, y + glyph_offset), start, line_metrics);
     };
 
     let baseline_y = match vertical_alignment {
@@ -651,8 +686,14 @@ pub(crate) fn layout_text_lines(
     };
     let mut y = baseline_y;
     let mut start = 0;
-    'lines: while start < string.len() && y + font_height <= max_height {
-        if wrap && (!elide || y + 2. * font_height <= max_height) {
+    let mut line_count = 0;
+    'lines: while start < string.len()
+        && y + line_height <= max_height
+        && (max_lines == 0 || line_count < max_lines)
+    {
+        line_count += 1;
+        let last_allowed_line = line_count == max_lines;
+        if wrap && (!elide || (y + 2. * line_height <= max_height && !last_allowed_line)) {
             let index = text_context.break_text(max_width, &string[start..], paint).unwrap();
             if index == 0 {
                 // FIXME the word is too big to be shown, but we should still break, ideally
@@ -662,7 +703,7 @@ pub(crate) fn layout_text_lines(
             let line = &string[start..index];
             let text_metrics = text_context.measure_text(0., 0., line, paint).unwrap();
             process_line(line, y, start, &text_metrics);
-            y += font_height;
+            y += line_height;
             start = index;
         } else {
             let index = if single_line {
@@ -672,8 +713,9 @@ pub(crate) fn layout_text_lines(
             };
             let line = &string[start..index];
             let text_metrics = text_context.measure_text(0., 0., line, paint).unwrap();
-            let elide_last_line =
-                elide && index < string.len() && y + 2. * font_height > max_height;
+            let elide_last_line = elide
+                && index < string.len()
+                && (y + 2. * line_height > max_height || last_allowed_line);
             if text_metrics.width() > max_width || elide_last_line {
                 let w = max_width
                     - if elide {
@@ -692,23 +734,261 @@ pub(crate) fn layout_text_lines(
                         } else {
                             process_line(txt, y, start, &text_metrics);
                         }
-                        y += font_height;
+                        y += line_height;
                         start = index;
                         continue 'lines;
                     }
                 }
                 if elide_last_line {
-                    let elided = format!("{}…", line);
+                    let elided = format!("{}…", line.trim_end());
                     process_line(&elided, y, start, &text_metrics);
-                    y += font_height;
+                    y += line_height;
                     start = index;
                     continue 'lines;
                 }
             }
             process_line(line, y, start, &text_metrics);
-            y += font_height;
+            y += line_height;
             start = index;
         }
     }
     baseline_y
 }
+
+/// A glyph of a line of text, see [`ShapedLine`]
+pub(crate) struct LineGlyph {
+    /// The byte offset of the first character of the glyph in the line
+    pub byte_index: usize,
+    /// The position of the glyph from the left of the line
+    pub x: f32,
+    pub advance: f32,
+    /// The glyph is part of a right to left run
+    pub rtl: bool,
+}
+
+/// A line of text shaped one bidi run at a time, so that the right to left parts of the line are
+/// at the right place, also when the paragraph itself is right to left. Shape a line once with
+/// [`shape_line`] and use the result for drawing, hit testing and placing the cursor.
+pub(crate) struct ShapedLine {
+    /// The glyphs in visual order, from left to right
+    pub glyphs: Vec<LineGlyph>,
+    /// The byte range of each bidi run in the line and its position from the left of the line
+    runs: Vec<(Range<usize>, Range<f32>)>,
+}
+
+pub(crate) fn shape_line(line: &str, paint: femtovg::Paint) -> ShapedLine {
+    let text_context = FONT_CACHE.with(|cache| cache.borrow().text_context.clone());
+    let mut x = 0.;
+    let mut glyphs = Vec::new();
+    let mut runs = Vec::new();
+    for run in sixtyfps_corelib::bidi::visual_runs(line) {
+        let metrics = text_context.measure_text(0., 0., &line[run.range.clone()], paint).unwrap();
+        glyphs.extend(metrics.glyphs.iter().map(|glyph| LineGlyph {
+            byte_index: run.range.start + glyph.byte_index,
+            x: x + glyph.x - glyph.bearing_x,
+            advance: glyph.advance_x,
+            rtl: run.rtl,
+        }));
+        runs.push((run.range, x..x + metrics.width()));
+        x += metrics.width();
+    }
+    ShapedLine { glyphs, runs }
+}
+
+impl ShapedLine {
+    /// Returns the width of the glyphs of the line up to `byte_len`, ignoring the glyphs after it.
+    pub fn width_up_to(&self, byte_len: usize) -> f32 {
+        self.glyphs
+            .iter()
+            .filter(|glyph| glyph.byte_index < byte_len)
+            .map(|glyph| glyph.x + glyph.advance)
+            .fold(0., f32::max)
+    }
+
+    /// Draws the runs of the line that intersect `x_range`, at the position they were shaped at.
+    /// `line` must be the text the line was shaped from.
+    pub fn fill(
+        &self,
+        canvas: &mut crate::Canvas,
+        pos: Point,
+        line: &str,
+        x_range: Range<f32>,
+        paint: femtovg::Paint,
+    ) {
+        for (byte_range, run_x) in &self.runs {
+            if run_x.start < x_range.end && run_x.end > x_range.start {
+                canvas
+                    .fill_text(pos.x + run_x.start, pos.y, &line[byte_range.clone()], paint)
+                    .unwrap();
+            }
+        }
+    }
+}
+
+/// Draws a line of text one bidi run at a time, like [`shape_line`] positions them.
+pub(crate) fn fill_line(canvas: &mut crate::Canvas, pos: Point, line: &str, paint: femtovg::Paint) {
+    let mut x = pos.x;
+    for run in sixtyfps_corelib::bidi::visual_runs(line) {
+        let metrics = canvas.fill_text(x, pos.y, &line[run.range], paint).unwrap();
+        x += metrics.width();
+    }
+}
+
+/// Returns the x coordinate of the cursor placed before the character at `byte_offset` in the
+/// line. In a right to left run, that is the right side of the glyph.
+pub(crate) fn cursor_x_in_line(line: &str, glyphs: &[LineGlyph], byte_offset: usize) -> f32 {
+    if let Some(glyph) = glyphs.iter().find(|glyph| glyph.byte_index == byte_offset) {
+        return if glyph.rtl { glyph.x + glyph.advance } else { glyph.x };
+    }
+    // At the end of the line: the side where the paragraph ends
+    if sixtyfps_corelib::bidi::is_right_to_left(line) {
+        0.
+    } else {
+        glyphs.iter().map(|glyph| glyph.x + glyph.advance).fold(0., f32::max)
+    }
+}
+
+/// Returns the byte offset in the line where the cursor goes for a click at `x`.
+pub(crate) fn byte_offset_in_line_for_x(line: &str, glyphs: &[LineGlyph], x: f32) -> usize {
+    for glyph in glyphs {
+        if x < glyph.x + glyph.advance / 2. {
+            return if glyph.rtl {
+                next_char_offset(line, glyph.byte_index)
+            } else {
+                glyph.byte_index
+            };
+        } else if x < glyph.x + glyph.advance {
+            return if glyph.rtl {
+                glyph.byte_index
+            } else {
+                next_char_offset(line, glyph.byte_index)
+            };
+        }
+    }
+    if sixtyfps_corelib::bidi::is_right_to_left(line) {
+        0
+    } else {
+        line.trim_end().len()
+    }
+}
+
+fn next_char_offset(line: &str, byte_offset: usize) -> usize {
+    byte_offset + line[byte_offset..].chars().next().map_or(0, char::len_utf8)
+}
+
+/// Returns the horizontal extents of the glyphs whose first character is in `byte_range`, merged
+/// when they are next to each other. There can be more than one because of the bidi runs.
+pub(crate) fn x_ranges_in_line(glyphs: &[LineGlyph], byte_range: Range<usize>) -> Vec<Range<f32>> {
+    let mut ranges: Vec<Range<f32>> = Vec::new();
+    for glyph in glyphs.iter().filter(|glyph| byte_range.contains(&glyph.byte_index)) {
+        match ranges.last_mut() {
+            Some(last) if (last.end - glyph.x).abs() < 0.5 => last.end = glyph.x + glyph.advance,
+            _ => ranges.push(glyph.x..glyph.x + glyph.advance),
+        }
+    }
+    ranges
+}
+
+/// A part of a run of styled text that is on a single line
+pub(crate) struct StyledTextRun {
+    /// The (UTF-8) byte range of the part in the text
+    pub range: Range<usize>,
+    /// The index of the run in the spans of the styled text
+    pub span_index: usize,
+    /// The position of the top left corner of the part
+    pub pos: Point,
+    pub width: f32,
+}
+
+/// The result of [`layout_styled_text`]. All the coordinates are in physical pixels.
+pub(crate) struct StyledTextLayout {
+    /// The font of each span of the styled text
+    pub fonts: Vec<Font>,
+    pub runs: Vec<StyledTextRun>,
+    pub line_height: f32,
+}
+
+/// Layout the `styled-text` of the `Text` element in lines, each span with its own font. The words
+/// are wrapped when `wrap` is set, and the lines that don't fit in the height are left out.
+pub(crate) fn layout_styled_text(
+    text: Pin<&Text>,
+    styled_text: &StyledText,
+    default_font_request: &FontRequest,
+    horizontal_alignment: TextHorizontalAlignment,
+    scale_factor: f32,
+) -> StyledTextLayout {
+    let max_width = text.width() * scale_factor;
+    let max_height = text.height() * scale_factor;
+    let letter_spacing = text.letter_spacing() * scale_factor;
+    let font_request = text.unresolved_font_request().merge(default_font_request);
+    let fonts = FONT_CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        styled_text
+            .spans
+            .iter()
+            .map(|span| {
+                cache.font(
+                    span.style.font_request(font_request.clone()),
+                    scale_factor,
+                    &styled_text.text[span.range.clone()],
+                )
+            })
+            .collect::<Vec<_>>()
+    });
+    let text_context = FONT_CACHE.with(|cache| cache.borrow().text_context.clone());
+    let line_height = font_request.line_height.map(|h| h * scale_factor).unwrap_or_else(|| {
+        fonts
+            .iter()
+            .map(|font| {
+                let paint = font.init_paint(letter_spacing, femtovg::Paint::default());
+                text_context.measure_font(paint).unwrap().height()
+            })
+            .fold(0., f32::max)
+    });
+
+    // Split the spans into lines, each word being measured with the font of its span
+    let lines = styled_text.layout_lines(
+        (text.wrap() == TextWrap::word_wrap).then(|| max_width),
+        |span_index, word| {
+            let paint = fonts[span_index].init_paint(letter_spacing, femtovg::Paint::default());
+            text_context.measure_text(0., 0., word, paint).unwrap().width()
+        },
+    );
+
+    let text_height = lines.len() as f32 * line_height;
+    let mut y = match text.vertical_alignment() {
+        TextVerticalAlignment::top => 0.,
+        TextVerticalAlignment::center => max_height / 2. - text_height / 2.,
+        TextVerticalAlignment::bottom => max_height - text_height,
+    };
+    let mut runs = Vec::new();
+    for line in lines {
+        if y + line_height > max_height {
+            break;
+        }
+        let line_width = line.iter().map(|run| run.width).sum::<f32>();
+        // The runs of a right to left paragraph go from the right to the left
+        let rtl = line.first().map_or(false, |first| {
+            let line_range = first.range.start..line.last().unwrap().range.end;
+            sixtyfps_corelib::bidi::is_right_to_left(&styled_text.text[line_range])
+        });
+        let x = match horizontal_alignment {
+            TextHorizontalAlignment::left => 0.,
+            TextHorizontalAlignment::center => {
+                max_width / 2. - f32::min(max_width, line_width) / 2.
+            }
+            TextHorizontalAlignment::right => max_width - f32::min(max_width, line_width),
+        };
+        runs.extend(line.into_iter().map(|run| {
+            let run_x = if rtl { line_width - run.x - run.width } else { run.x };
+            StyledTextRun {
+                range: run.range,
+                span_index: run.span_index,
+                pos: Point::new(x + run_x, y),
+                width: run.width,
+            }
+        }));
+        y += line_height;
+    }
+    StyledTextLayout { fonts, runs, line_height }
+}
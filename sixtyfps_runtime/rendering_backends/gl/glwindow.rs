@@ -15,7 +15,7 @@
 use core::pin::Pin;
 use std::rc::{Rc, Weak};
 
-use super::{ItemGraphicsCache, TextureCache};
+use super::{GradientImageCache, ItemGraphicsCache, TextureCache};
 use crate::event_loop::WinitWindow;
 use const_field_offset::FieldOffsets;
 use corelib::component::ComponentRc;
@@ -41,6 +41,7 @@ pub struct GLWindow {
     pub(crate) graphics_cache: RefCell<ItemGraphicsCache>,
     // This cache only contains textures. The cache for decoded CPU side images is in crate::IMAGE_CACHE.
     pub(crate) texture_cache: RefCell<TextureCache>,
+    pub(crate) gradient_image_cache: RefCell<GradientImageCache>,
 
     #[cfg(target_arch = "wasm32")]
     canvas_id: String,
@@ -64,6 +65,7 @@ pub(crate) fn new(
             currently_pressed_key_code: Default::default(),
             graphics_cache: Default::default(),
             texture_cache: Default::default(),
+            gradient_image_cache: Default::default(),
             #[cfg(target_arch = "wasm32")]
             canvas_id,
         })
@@ -184,6 +186,7 @@ fn draw(self: Rc<Self>) {
             // Delete any images and layer images (and their FBOs) before making the context not current anymore, to
             // avoid GPU memory leaks.
             renderer.graphics_window.texture_cache.borrow_mut().drain();
+            renderer.graphics_window.gradient_image_cache.borrow_mut().drain();
 
             drop(renderer);
 
@@ -413,6 +416,7 @@ fn hide(self: Rc<Self>) {
         self.with_current_context(|| {
             self.graphics_cache.borrow_mut().clear();
             self.texture_cache.borrow_mut().clear();
+            self.gradient_image_cache.borrow_mut().clear();
         });
 
         self.map_state.replace(GraphicsWindowBackendState::Unmapped);
@@ -459,6 +463,18 @@ fn set_mouse_cursor(&self, cursor: MouseCursor) {
         });
     }
 
+    fn set_ime_allowed(&self, allowed: bool) {
+        self.with_window_handle(&mut |winit_window| winit_window.set_ime_allowed(allowed));
+    }
+
+    fn set_ime_cursor_rect(&self, rect: Rect) {
+        // winit only takes the position of the candidate window: put it below the cursor
+        self.with_window_handle(&mut |winit_window| {
+            winit_window
+                .set_ime_position(winit::dpi::LogicalPosition::new(rect.min_x(), rect.max_y()))
+        });
+    }
+
     fn text_size(
         &self,
         font_request: corelib::graphics::FontRequest,
@@ -482,7 +498,8 @@ fn text_input_byte_offset_for_position(
     ) -> usize {
         let scale_factor = self.self_weak.upgrade().unwrap().scale_factor();
         let pos = pos * scale_factor;
-        let text = text_input.text();
+        let visual_representation = text_input.visual_representation();
+        let text = &visual_representation.text;
 
         let mut result = text.len();
 
@@ -492,43 +509,76 @@ fn text_input_byte_offset_for_position(
             return 0;
         }
 
-        let font = crate::fonts::FONT_CACHE.with(|cache| {
-            cache.borrow_mut().font(
-                text_input.unresolved_font_request().merge(&self.default_font_properties()),
-                scale_factor,
-                &text_input.text(),
-            )
-        });
+        let font_request =
+            text_input.unresolved_font_request().merge(&self.default_font_properties());
+        let line_height = font_request.line_height.map(|h| h * scale_factor);
+        let font = crate::fonts::FONT_CACHE
+            .with(|cache| cache.borrow_mut().font(font_request, scale_factor, text));
 
         let paint = font.init_paint(text_input.letter_spacing() * scale_factor, Default::default());
         let text_context =
             crate::fonts::FONT_CACHE.with(|cache| cache.borrow().text_context.clone());
         let font_height = text_context.measure_font(paint).unwrap().height();
+        let line_height = line_height.unwrap_or(font_height);
+        // The lines passed to the callback start at the top of the glyphs
+        let glyph_offset = (line_height - font_height) / 2.;
         crate::fonts::layout_text_lines(
             text.as_str(),
             &font,
             Size::new(width, height),
-            (text_input.horizontal_alignment(), text_input.vertical_alignment()),
+            (
+                text_input.visual_horizontal_alignment(&self.self_weak.upgrade().unwrap()),
+                text_input.vertical_alignment(),
+            ),
             text_input.wrap(),
             sixtyfps_corelib::items::TextOverflow::clip,
             text_input.single_line(),
+            (Some(line_height), 0),
             paint,
-            |line_text, line_pos, start, metrics| {
-                if (line_pos.y..(line_pos.y + font_height)).contains(&pos.y) {
-                    let mut current_x = 0.;
-                    for glyph in &metrics.glyphs {
-                        if line_pos.x + current_x + glyph.advance_x / 2. >= pos.x {
-                            result = start + glyph.byte_index;
-                            return;
-                        }
-                        current_x += glyph.advance_x;
-                    }
-                    result = start + line_text.trim_end().len();
+            |line_text, line_pos, start, _| {
+                let line_top = line_pos.y - glyph_offset;
+                if (line_top..(line_top + line_height)).contains(&pos.y) {
+                    let glyphs = crate::fonts::shape_line(line_text, paint).glyphs;
+                    result = start
+                        + crate::fonts::byte_offset_in_line_for_x(
+                            line_text,
+                            &glyphs,
+                            pos.x - line_pos.x,
+                        );
                 }
             },
         );
 
-        result
+        visual_representation.map_byte_offset_from_visual(result)
+    }
+
+    fn text_byte_offset_for_position(
+        &self,
+        text: Pin<&corelib::items::Text>,
+        pos: Point,
+    ) -> Option<usize> {
+        let styled_text = text.parsed_styled_text()?;
+        let scale_factor = self.self_weak.upgrade().unwrap().scale_factor();
+        let pos = pos * scale_factor;
+        let layout = crate::fonts::layout_styled_text(
+            text,
+            &styled_text,
+            &self.default_font_properties(),
+            text.visual_horizontal_alignment(&self.self_weak.upgrade().unwrap()),
+            scale_factor,
+        );
+        let run = layout.runs.iter().find(|run| {
+            (run.pos.y..run.pos.y + layout.line_height).contains(&pos.y)
+                && (run.pos.x..run.pos.x + run.width).contains(&pos.x)
+        })?;
+        let paint = layout.fonts[run.span_index]
+            .init_paint(text.letter_spacing() * scale_factor, Default::default());
+        let text_context =
+            crate::fonts::FONT_CACHE.with(|cache| cache.borrow().text_context.clone());
+        let metrics =
+            text_context.measure_text(0., 0., &styled_text.text[run.range.clone()], paint).unwrap();
+        let glyph = metrics.glyphs.iter().rev().find(|glyph| run.pos.x + glyph.x <= pos.x);
+        Some(run.range.start + glyph.map_or(0, |glyph| glyph.byte_index))
     }
 
     fn text_input_position_for_byte_offset(
@@ -537,7 +587,9 @@ fn text_input_position_for_byte_offset(
         byte_offset: usize,
     ) -> Point {
         let scale_factor = self.self_weak.upgrade().unwrap().scale_factor();
-        let text = text_input.text();
+        let visual_representation = text_input.visual_representation();
+        let text = &visual_representation.text;
+        let byte_offset = visual_representation.map_byte_offset_to_visual(byte_offset);
 
         let mut result = Point::default();
 
@@ -547,35 +599,38 @@ fn text_input_position_for_byte_offset(
             return result;
         }
 
-        let font = crate::fonts::FONT_CACHE.with(|cache| {
-            cache.borrow_mut().font(
-                text_input.unresolved_font_request().merge(&self.default_font_properties()),
-                scale_factor,
-                &text_input.text(),
-            )
-        });
+        let font_request =
+            text_input.unresolved_font_request().merge(&self.default_font_properties());
+        let line_height = font_request.line_height.map(|h| h * scale_factor);
+        let font = crate::fonts::FONT_CACHE
+            .with(|cache| cache.borrow_mut().font(font_request, scale_factor, text));
 
         let paint = font.init_paint(text_input.letter_spacing() * scale_factor, Default::default());
+        let text_context =
+            crate::fonts::FONT_CACHE.with(|cache| cache.borrow().text_context.clone());
+        let font_height = text_context.measure_font(paint).unwrap().height();
+        let glyph_offset = (line_height.unwrap_or(font_height) - font_height) / 2.;
         crate::fonts::layout_text_lines(
             text.as_str(),
             &font,
             Size::new(width, height),
-            (text_input.horizontal_alignment(), text_input.vertical_alignment()),
+            (
+                text_input.visual_horizontal_alignment(&self.self_weak.upgrade().unwrap()),
+                text_input.vertical_alignment(),
+            ),
             text_input.wrap(),
             sixtyfps_corelib::items::TextOverflow::clip,
             text_input.single_line(),
+            (line_height, 0),
             paint,
-            |line_text, line_pos, start, metrics| {
+            |line_text, line_pos, start, _| {
+                // The position is the one of the top of the line, so that hit testing that
+                // position with `text_input_byte_offset_for_position` finds the same line
+                let line_pos = line_pos - euclid::vec2(0., glyph_offset);
                 if (start..=(start + line_text.len())).contains(&byte_offset) {
-                    for glyph in &metrics.glyphs {
-                        if glyph.byte_index == (byte_offset - start) {
-                            result = line_pos + euclid::vec2(glyph.x, glyph.y);
-                            return;
-                        }
-                    }
-                    if let Some(last) = metrics.glyphs.last() {
-                        result = line_pos + euclid::vec2(last.x + last.advance_x, last.y);
-                    }
+                    let glyphs = crate::fonts::shape_line(line_text, paint).glyphs;
+                    let x = crate::fonts::cursor_x_in_line(line_text, &glyphs, byte_offset - start);
+                    result = line_pos + euclid::vec2(x, 0.);
                 }
             },
         );
@@ -8,11 +8,11 @@
     Please contact info@sixtyfps.io for more information.
 LICENSE END */
 
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::collections::HashMap;
 use std::rc::Rc;
 
-use sixtyfps_corelib::graphics::{SharedImageBuffer, Size};
+use sixtyfps_corelib::graphics::{GradientStop, SharedImageBuffer, Size};
 #[cfg(target_arch = "wasm32")]
 use sixtyfps_corelib::Property;
 use sixtyfps_corelib::{items::ImageRendering, slice::Slice, ImageInner, SharedString};
@@ -408,12 +408,17 @@ pub(crate) fn filter(&self, canvas: &CanvasRc, filter: femtovg::ImageFilter) ->
     }
 
     pub(crate) fn as_paint(&self) -> femtovg::Paint {
+        self.as_paint_at(0., 0.)
+    }
+
+    /// Same as [`Self::as_paint`], but with the top left corner of the image at the given position
+    pub(crate) fn as_paint_at(&self, x: f32, y: f32) -> femtovg::Paint {
         match &*self.0.borrow() {
             ImageData::Texture(tex) => {
                 let size = tex
                     .size()
                     .expect("internal error: CachedImage::as_paint() called on zero-sized texture");
-                femtovg::Paint::image(tex.id, 0., 0., size.width, size.height, 0., 1.)
+                femtovg::Paint::image(tex.id, x, y, size.width, size.height, 0., 1.)
             }
             _ => panic!("internal error: CachedImage::as_paint() called on non-texture image"),
         }
@@ -560,6 +565,62 @@ pub(crate) fn clear(&mut self) {
     }
 }
 
+#[derive(PartialEq, Eq, Hash, Debug)]
+pub struct GradientImageCacheKey {
+    // The position (as bits) and the argb encoded color of each stop
+    stops: Vec<(u32, u32)>,
+    size: euclid::default::Size2D<u32>,
+}
+
+impl GradientImageCacheKey {
+    pub fn new<'a>(
+        stops: impl Iterator<Item = &'a GradientStop>,
+        size: euclid::default::Size2D<u32>,
+    ) -> Self {
+        Self {
+            stops: stops
+                .map(|stop| (stop.position.to_bits(), stop.color.as_argb_encoded()))
+                .collect(),
+            size,
+        }
+    }
+}
+
+// Cache of the images of the gradients that femtovg can't draw itself, like the conic gradients.
+// Entries that were not used while rendering a frame are drained after flushing the renderer
+// commands to the screen.
+#[derive(Default)]
+pub struct GradientImageCache(HashMap<GradientImageCacheKey, (Rc<CachedImage>, Cell<bool>)>);
+
+impl GradientImageCache {
+    pub(crate) fn lookup_image_in_cache_or_create(
+        &mut self,
+        cache_key: GradientImageCacheKey,
+        image_create_fn: impl Fn() -> Option<CachedImage>,
+    ) -> Option<Rc<CachedImage>> {
+        Some(match self.0.entry(cache_key) {
+            std::collections::hash_map::Entry::Occupied(existing_entry) => {
+                let (image, used) = existing_entry.get();
+                used.set(true);
+                image.clone()
+            }
+            std::collections::hash_map::Entry::Vacant(vacant_entry) => {
+                let new_image = Rc::new(image_create_fn()?);
+                vacant_entry.insert((new_image.clone(), Cell::new(true)));
+                new_image
+            }
+        })
+    }
+
+    pub(crate) fn drain(&mut self) {
+        self.0.retain(|_, (_, used)| used.replace(false));
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.0.clear();
+    }
+}
+
 fn image_buffer_to_image_source(
     buffer: &SharedImageBuffer,
 ) -> (femtovg::ImageSource<'_>, femtovg::ImageFlags) {
@@ -28,8 +28,10 @@
 use sixtyfps_corelib::graphics::{
     Brush, Color, Image, ImageInner, IntRect, Point, Rect, RenderingCache, Size,
 };
-use sixtyfps_corelib::item_rendering::{CachedRenderingData, ItemRenderer};
-use sixtyfps_corelib::items::{FillRule, ImageFit, ImageRendering};
+use sixtyfps_corelib::item_rendering::{
+    is_degenerate_transform, CachedRenderingData, ItemRenderer,
+};
+use sixtyfps_corelib::items::{FillRule, ImageFit, ImageRendering, TextDecoration};
 use sixtyfps_corelib::properties::Property;
 use sixtyfps_corelib::window::{Window, WindowRc};
 
@@ -93,7 +95,7 @@ pub struct GLItemRenderer {
     // Layers that were scheduled for rendering where we can't delete the femtovg::ImageId yet
     // because that can only happen after calling `flush`. Otherwise femtovg ends up processing
     // `set_render_target` commands with image ids that have been deleted.
-    layer_images_to_delete_after_flush: Vec<CachedImage>,
+    layer_images_to_delete_after_flush: RefCell<Vec<CachedImage>>,
     graphics_window: Rc<GLWindow>,
     scale_factor: f32,
     /// track the state manually since femtovg don't have accessor for its state
@@ -231,16 +233,17 @@ fn draw_text(&mut self, text: std::pin::Pin<&sixtyfps_corelib::items::Text>) {
             return;
         }
 
+        if let Some(styled_text) = text.parsed_styled_text() {
+            return self.draw_styled_text(text, &styled_text);
+        }
+
         let string = text.text();
         let string = string.as_str();
-        let font = fonts::FONT_CACHE.with(|cache| {
-            cache.borrow_mut().font(
-                text.unresolved_font_request()
-                    .merge(&self.graphics_window.default_font_properties()),
-                self.scale_factor,
-                &text.text(),
-            )
-        });
+        let font_request =
+            text.unresolved_font_request().merge(&self.graphics_window.default_font_properties());
+        let line_height = font_request.line_height.map(|h| h * self.scale_factor);
+        let font = fonts::FONT_CACHE
+            .with(|cache| cache.borrow_mut().font(font_request, self.scale_factor, string));
 
         let paint = match self
             .brush_to_paint(text.color(), &mut rect_to_path(item_rect(text, self.scale_factor)))
@@ -249,18 +252,34 @@ fn draw_text(&mut self, text: std::pin::Pin<&sixtyfps_corelib::items::Text>) {
             None => return,
         };
 
+        let text_decoration = text.text_decoration();
+        let scale_factor = self.scale_factor;
+        let window = self.graphics_window.runtime_window();
         let mut canvas = self.canvas.borrow_mut();
+        let font_metrics = canvas.measure_font(paint).unwrap();
         fonts::layout_text_lines(
             string,
             &font,
             Size::new(max_width, max_height),
-            (text.horizontal_alignment(), text.vertical_alignment()),
+            (text.visual_horizontal_alignment(&window), text.vertical_alignment()),
             text.wrap(),
             text.overflow(),
             false,
+            (line_height, text.max_lines().max(0) as usize),
             paint,
             |to_draw, pos, _, _| {
-                canvas.fill_text(pos.x, pos.y, to_draw.trim_end(), paint).unwrap();
+                let to_draw = to_draw.trim_end();
+                fonts::fill_line(&mut canvas, pos, to_draw, paint);
+                if text_decoration != TextDecoration::none {
+                    let width = canvas.measure_text(0., 0., to_draw, paint).unwrap().width();
+                    draw_text_decoration(
+                        &mut canvas,
+                        text_decoration,
+                        Rect::new(pos, Size::new(width, font_metrics.ascender())),
+                        scale_factor,
+                        paint,
+                    );
+                }
             },
         );
     }
@@ -272,15 +291,15 @@ fn draw_text_input(&mut self, text_input: std::pin::Pin<&sixtyfps_corelib::items
             return;
         }
 
-        let font = fonts::FONT_CACHE.with(|cache| {
-            cache.borrow_mut().font(
-                text_input
-                    .unresolved_font_request()
-                    .merge(&self.graphics_window.default_font_properties()),
-                self.scale_factor,
-                &text_input.text(),
-            )
-        });
+        let visual_representation = text_input.visual_representation();
+        let text = &visual_representation.text;
+
+        let font_request = text_input
+            .unresolved_font_request()
+            .merge(&self.graphics_window.default_font_properties());
+        let line_height = font_request.line_height.map(|h| h * self.scale_factor);
+        let font = fonts::FONT_CACHE
+            .with(|cache| cache.borrow_mut().font(font_request, self.scale_factor, text));
 
         let paint = match self.brush_to_paint(
             text_input.color(),
@@ -290,12 +309,16 @@ fn draw_text_input(&mut self, text_input: std::pin::Pin<&sixtyfps_corelib::items
             None => return,
         };
 
-        let (min_select, max_select) = text_input.selection_anchor_and_cursor();
-        let cursor_pos = text_input.cursor_position();
+        let (min_select, max_select) = visual_representation.selection_anchor_and_cursor();
+        let preedit_range = visual_representation.preedit_range.clone();
+        let scale_factor = self.scale_factor;
+        let cursor_pos = visual_representation.cursor_position;
         let cursor_visible = cursor_pos >= 0 && text_input.cursor_visible() && text_input.enabled();
+        let text_decoration = text_input.text_decoration();
+        let window = self.graphics_window.runtime_window();
         let mut canvas = self.canvas.borrow_mut();
-        let font_height = canvas.measure_font(paint).unwrap().height();
-        let text = text_input.text();
+        let font_metrics = canvas.measure_font(paint).unwrap();
+        let font_height = font_metrics.height();
 
         let mut cursor_point: Option<Point> = None;
 
@@ -303,101 +326,78 @@ fn draw_text_input(&mut self, text_input: std::pin::Pin<&sixtyfps_corelib::items
             text.as_str(),
             &font,
             Size::new(width, height),
-            (text_input.horizontal_alignment(), text_input.vertical_alignment()),
+            (text_input.visual_horizontal_alignment(&window), text_input.vertical_alignment()),
             text_input.wrap(),
             sixtyfps_corelib::items::TextOverflow::clip,
             text_input.single_line(),
+            (line_height, 0),
             paint,
-            |to_draw, pos, start, metrics| {
+            |to_draw, pos, start, _| {
                 let range = start..(start + to_draw.len());
-                if min_select != max_select
-                    && (range.contains(&min_select)
-                        || range.contains(&max_select)
-                        || (min_select..max_select).contains(&start))
-                {
-                    let mut selection_start_x = 0.;
-                    let mut selection_end_x = 0.;
-                    let mut after_selection_x = 0.;
-                    // Determine the first and last (inclusive) glyph of the selection. The anchor
-                    // will always be at the start of a grapheme boundary, so there's at ShapedGlyph
-                    // that has a matching byte index. For the selection end we have to look for the
-                    // visual end of glyph before the cursor, because due to for example ligatures
-                    // (or generally glyph substitution) there may not be a dedicated glyph.
-                    // FIXME: in the case of ligature, there is currently no way to know the exact
-                    // position of the split. When we know it, we might need to draw in two
-                    // steps with clip to draw each part of the ligature in a different color
-                    for glyph in &metrics.glyphs {
-                        if glyph.byte_index == min_select.saturating_sub(start) {
-                            selection_start_x = glyph.x - glyph.bearing_x;
-                        }
-                        if glyph.byte_index == max_select - start
-                            || glyph.byte_index >= to_draw.len()
-                        {
-                            after_selection_x = glyph.x - glyph.bearing_x;
-                            break;
-                        }
-                        selection_end_x = glyph.x + glyph.advance_x;
-                    }
-
-                    let selection_rect = Rect::new(
-                        pos + euclid::vec2(selection_start_x, 0.),
-                        Size::new(selection_end_x - selection_start_x, font_height),
-                    );
-                    canvas.fill_path(
-                        &mut rect_to_path(selection_rect),
-                        femtovg::Paint::color(to_femtovg_color(
-                            &text_input.selection_background_color(),
-                        )),
+                // Shape the line once, with the glyphs in visual order, so that the selection and
+                // the cursor are at the right place in the right to left parts of the line
+                let shaped = fonts::shape_line(to_draw, paint);
+                let glyphs = &shaped.glyphs;
+                if text_decoration != TextDecoration::none {
+                    let width = shaped.width_up_to(to_draw.trim_end().len());
+                    draw_text_decoration(
+                        &mut canvas,
+                        text_decoration,
+                        Rect::new(pos, Size::new(width, font_metrics.ascender())),
+                        scale_factor,
+                        paint,
                     );
+                }
+                shaped.fill(&mut canvas, pos, to_draw, f32::MIN..f32::MAX, paint);
+                if min_select != max_select && min_select < range.end && max_select > range.start {
+                    // Draw the selection background, and the selected glyphs again on top of it
+                    // with the selection color, clipped to the selection.
+                    // FIXME: in the case of ligature, the whole ligature is selected as soon as its
+                    // first character is
                     let mut selected_paint = paint;
                     selected_paint
                         .set_color(to_femtovg_color(&text_input.selection_foreground_color()));
-                    canvas
-                        .fill_text(
-                            pos.x,
-                            pos.y,
-                            &to_draw[..min_select.saturating_sub(start)].trim_end(),
-                            paint,
-                        )
-                        .unwrap();
-                    canvas
-                        .fill_text(
-                            pos.x + selection_start_x,
-                            pos.y,
-                            &to_draw[min_select.saturating_sub(start)
-                                ..(max_select - start).min(to_draw.len())]
-                                .trim_end(),
-                            selected_paint,
-                        )
-                        .unwrap();
-                    canvas
-                        .fill_text(
-                            pos.x + after_selection_x,
-                            pos.y,
-                            &to_draw[(max_select - start).min(to_draw.len())..].trim_end(),
-                            paint,
-                        )
-                        .unwrap();
-                } else {
-                    // no selection on this line
-                    canvas.fill_text(pos.x, pos.y, to_draw.trim_end(), paint).unwrap();
-                };
+                    let selection = min_select.saturating_sub(start)..(max_select - start);
+                    for x_range in fonts::x_ranges_in_line(glyphs, selection) {
+                        let selection_rect = Rect::new(
+                            pos + euclid::vec2(x_range.start, 0.),
+                            Size::new(x_range.end - x_range.start, font_height),
+                        );
+                        canvas.fill_path(
+                            &mut rect_to_path(selection_rect),
+                            femtovg::Paint::color(to_femtovg_color(
+                                &text_input.selection_background_color(),
+                            )),
+                        );
+                        canvas.save();
+                        canvas.intersect_scissor(
+                            selection_rect.min_x(),
+                            selection_rect.min_y(),
+                            selection_rect.width(),
+                            selection_rect.height(),
+                        );
+                        shaped.fill(&mut canvas, pos, to_draw, x_range, selected_paint);
+                        canvas.restore();
+                    }
+                }
+                if preedit_range.start < range.end && preedit_range.end > range.start {
+                    // Underline the part of the text being composed with the input method
+                    let preedit = preedit_range.start.max(start) - start
+                        ..preedit_range.end.min(range.end) - start;
+                    for x_range in fonts::x_ranges_in_line(glyphs, preedit) {
+                        let underline_rect = Rect::new(
+                            pos + euclid::vec2(x_range.start, font_height - scale_factor),
+                            Size::new(x_range.end - x_range.start, scale_factor),
+                        );
+                        canvas.fill_path(&mut rect_to_path(underline_rect), paint);
+                    }
+                }
                 let cursor_pos = cursor_pos as usize;
                 if cursor_visible
                     && (range.contains(&cursor_pos)
                         || (cursor_pos == range.end && cursor_pos == text.len()))
                 {
-                    let cursor_x = metrics
-                        .glyphs
-                        .iter()
-                        .find_map(|glyph| {
-                            if glyph.byte_index == (cursor_pos as usize - start) {
-                                Some(glyph.x)
-                            } else {
-                                None
-                            }
-                        })
-                        .unwrap_or_else(|| metrics.width());
+                    let cursor_x = fonts::cursor_x_in_line(to_draw, glyphs, cursor_pos - start);
                     cursor_point = Some([pos.x + cursor_x, pos.y].into());
                 }
             },
@@ -595,7 +595,7 @@ fn draw_box_shadow(&mut self, box_shadow: std::pin::Pin<&sixtyfps_corelib::items
                             .borrow_mut()
                             .set_render_target(blurred_image.as_render_target());
 
-                        self.layer_images_to_delete_after_flush.push(shadow_image);
+                        self.layer_images_to_delete_after_flush.borrow_mut().push(shadow_image);
 
                         blurred_image
                     } else {
@@ -706,7 +706,7 @@ fn restore_state(&mut self) {
         {
             let paint = layer_to_restore.image.as_paint();
 
-            self.layer_images_to_delete_after_flush.push(layer_to_restore.image);
+            self.layer_images_to_delete_after_flush.borrow_mut().push(layer_to_restore.image);
 
             let mut canvas = self.canvas.borrow_mut();
 
@@ -800,6 +800,45 @@ fn rotate(&mut self, angle_in_degrees: f32) {
         *clip = Rect::new(origin, (end - origin).into());
     }
 
+    fn scale(&mut self, x_factor: f32, y_factor: f32) {
+        if is_degenerate_transform(x_factor, y_factor, 0., 0.) {
+            // Nothing is visible, and the clip can't be mapped back
+            self.combine_clip(Rect::default(), 0., 0.);
+            return;
+        }
+        self.canvas.borrow_mut().scale(x_factor, y_factor);
+        let clip = &mut self.state.last_mut().unwrap().scissor;
+        *clip = Rect::from_points(
+            [clip.min(), clip.max()].iter().map(|p| Point::new(p.x / x_factor, p.y / y_factor)),
+        );
+    }
+
+    fn skew(&mut self, x_angle_in_degrees: f32, y_angle_in_degrees: f32) {
+        if is_degenerate_transform(1., 1., x_angle_in_degrees, y_angle_in_degrees) {
+            // Nothing is visible, and the clip can't be mapped back
+            self.combine_clip(Rect::default(), 0., 0.);
+            return;
+        }
+        let tan_x = x_angle_in_degrees.to_radians().tan();
+        let tan_y = y_angle_in_degrees.to_radians().tan();
+        self.canvas.borrow_mut().set_transform(1., tan_y, tan_x, 1., 0., 0.);
+        // The clip becomes the bounding box of the previous clip in the skewed coordinates
+        let det = 1. - tan_x * tan_y;
+        let unskew_point =
+            |p: Point| Point::new((p.x - p.y * tan_x) / det, (p.y - p.x * tan_y) / det);
+        let clip = &mut self.state.last_mut().unwrap().scissor;
+        *clip = Rect::from_points(
+            [
+                clip.origin,
+                clip.origin + euclid::vec2(clip.width(), 0.),
+                clip.origin + euclid::vec2(0., clip.height()),
+                clip.origin + clip.size,
+            ]
+            .iter()
+            .map(|p| unskew_point(*p)),
+        );
+    }
+
     fn apply_opacity(&mut self, opacity: f32) {
         let state = &mut self.state.last_mut().unwrap().global_alpha;
         *state *= opacity;
@@ -808,6 +847,47 @@ fn apply_opacity(&mut self, opacity: f32) {
 }
 
 impl GLItemRenderer {
+    /// Draws the `styled-text` of the `Text` element, each run with its own font and color
+    fn draw_styled_text(
+        &mut self,
+        text: Pin<&sixtyfps_corelib::items::Text>,
+        styled_text: &sixtyfps_corelib::styled_text::StyledText,
+    ) {
+        let base_paint = match self
+            .brush_to_paint(text.color(), &mut rect_to_path(item_rect(text, self.scale_factor)))
+        {
+            Some(paint) => paint,
+            None => return,
+        };
+        let layout = fonts::layout_styled_text(
+            text,
+            styled_text,
+            &self.graphics_window.default_font_properties(),
+            text.visual_horizontal_alignment(&self.graphics_window.runtime_window()),
+            self.scale_factor,
+        );
+        let letter_spacing = text.letter_spacing() * self.scale_factor;
+        let mut canvas = self.canvas.borrow_mut();
+        for run in &layout.runs {
+            let style = &styled_text.spans[run.span_index].style;
+            let paint = layout.fonts[run.span_index].init_paint(
+                letter_spacing,
+                style
+                    .color
+                    .map_or(base_paint, |color| femtovg::Paint::color(to_femtovg_color(&color))),
+            );
+            let run_text = &styled_text.text[run.range.clone()];
+            canvas.fill_text(run.pos.x, run.pos.y, run_text.trim_end(), paint).unwrap();
+            if style.underline {
+                let underline_rect = Rect::new(
+                    run.pos + euclid::vec2(0., layout.line_height - self.scale_factor),
+                    Size::new(run.width, self.scale_factor),
+                );
+                canvas.fill_path(&mut rect_to_path(underline_rect), paint);
+            }
+        }
+    }
+
     fn colorize_image(
         &self,
         original_cache_entry: ItemGraphicsCacheEntry,
@@ -1042,23 +1122,11 @@ fn brush_to_paint(&self, brush: Brush, path: &mut femtovg::Path) -> Option<femto
         Some(match brush {
             Brush::SolidColor(color) => femtovg::Paint::color(to_femtovg_color(&color)),
             Brush::LinearGradient(gradient) => {
-                // `canvas.path_bbox()` applies the current transform. However we're not interested in that, since
-                // we operate in item local coordinates with the `path` parameter as well as the resulting
-                // paint.
-                let path_bounds = {
-                    let mut canvas = self.canvas.borrow_mut();
-                    canvas.save();
-                    canvas.reset_transform();
-                    let bounding_box = canvas.path_bbox(path);
-                    canvas.restore();
-                    bounding_box
-                };
-
-                let path_width = path_bounds.maxx - path_bounds.minx;
-                let path_height = path_bounds.maxy - path_bounds.miny;
+                let path_bounds = self.path_bounds(path);
 
-                let transform = euclid::Transform2D::scale(path_width, path_height)
-                    .then_translate(euclid::Vector2D::new(path_bounds.minx, path_bounds.miny));
+                let transform =
+                    euclid::Transform2D::scale(path_bounds.width(), path_bounds.height())
+                        .then_translate(path_bounds.origin.to_vector());
 
                 let (start, end) = sixtyfps_corelib::graphics::line_for_angle(gradient.angle());
 
@@ -1071,10 +1139,91 @@ fn brush_to_paint(&self, brush: Brush, path: &mut femtovg::Path) -> Option<femto
                     .collect::<Vec<_>>();
                 femtovg::Paint::linear_gradient_stops(start.x, start.y, end.x, end.y, &stops)
             }
+            Brush::RadialGradient(gradient) => {
+                let path_bounds = self.path_bounds(path);
+
+                // The stop at position 1 is on the circle that goes through the corners
+                let radius = path_bounds.size.to_vector().length() / 2.;
+
+                let stops = gradient
+                    .stops()
+                    .map(|stop| (stop.position, to_femtovg_color(&stop.color)))
+                    .collect::<Vec<_>>();
+                let center = path_bounds.center();
+                femtovg::Paint::radial_gradient_stops(center.x, center.y, 0., radius, &stops)
+            }
+            Brush::ConicGradient(gradient) => {
+                // femtovg has no conic gradient, so render the gradient into a texture that
+                // covers the path's bounding box.
+                let path_bounds = self.path_bounds(path);
+
+                let width = path_bounds.width().ceil() as usize;
+                let height = path_bounds.height().ceil() as usize;
+                if width == 0 || height == 0 {
+                    return None;
+                }
+
+                let cache_key = GradientImageCacheKey::new(
+                    gradient.stops(),
+                    euclid::size2(width as u32, height as u32),
+                );
+                let image = self
+                    .graphics_window
+                    .gradient_image_cache
+                    .borrow_mut()
+                    .lookup_image_in_cache_or_create(cache_key, || {
+                        let (center_x, center_y) = (width as f32 / 2., height as f32 / 2.);
+                        let pixels = (0..height)
+                            .flat_map(|y| (0..width).map(move |x| (x, y)))
+                            .map(|(x, y)| {
+                                let dx = x as f32 + 0.5 - center_x;
+                                let dy = y as f32 + 0.5 - center_y;
+                                // Angle from the top, going clockwise, as a fraction of a full turn
+                                let position = dx.atan2(-dy).rem_euclid(2. * std::f32::consts::PI)
+                                    / (2. * std::f32::consts::PI);
+                                let color = gradient.color_at(position);
+                                rgb::RGBA8::new(
+                                    color.red(),
+                                    color.green(),
+                                    color.blue(),
+                                    color.alpha(),
+                                )
+                            })
+                            .collect::<Vec<_>>();
+
+                        let image_id = self
+                            .canvas
+                            .borrow_mut()
+                            .create_image(
+                                imgref::Img::new(pixels.as_slice(), width, height),
+                                femtovg::ImageFlags::empty(),
+                            )
+                            .ok()?;
+                        Some(CachedImage::new_on_gpu(&self.canvas, image_id))
+                    })?;
+
+                image.as_paint_at(path_bounds.origin.x, path_bounds.origin.y)
+            }
             _ => return None,
         })
     }
 
+    /// Returns the bounding box of the path, in item local coordinates
+    fn path_bounds(&self, path: &mut femtovg::Path) -> Rect {
+        // `canvas.path_bbox()` applies the current transform. However we're not interested in that, since
+        // we operate in item local coordinates with the `path` parameter as well as the resulting
+        // paint.
+        let mut canvas = self.canvas.borrow_mut();
+        canvas.save();
+        canvas.reset_transform();
+        let bounding_box = canvas.path_bbox(path);
+        canvas.restore();
+        Rect::new(
+            Point::new(bounding_box.minx, bounding_box.miny),
+            Size::new(bounding_box.maxx - bounding_box.minx, bounding_box.maxy - bounding_box.miny),
+        )
+    }
+
     // Set the specified path for clipping. This is done by redirecting rendering into
     // an intermediate image and using that to fill the clip path on the next restore_state()
     // call. Therefore this can only be called once per save_state()!
@@ -1134,6 +1283,25 @@ fn current_render_target(&self) -> femtovg::RenderTarget {
     }
 }
 
+/// Draws the underline or the strikethrough line of a line of text. The width of the rect is the
+/// width of the text, and its height is the ascender of the font, from the top of the glyphs.
+fn draw_text_decoration(
+    canvas: &mut Canvas,
+    text_decoration: TextDecoration,
+    rect: Rect,
+    scale_factor: f32,
+    paint: femtovg::Paint,
+) {
+    let y = match text_decoration {
+        TextDecoration::none => return,
+        TextDecoration::underline => rect.max_y() + scale_factor,
+        TextDecoration::strikethrough => rect.min_y() + rect.height() * 2. / 3.,
+    };
+    let mut path = femtovg::Path::new();
+    path.rect(rect.min_x(), y, rect.width(), scale_factor);
+    canvas.fill_path(&mut path, paint);
+}
+
 fn to_femtovg_color(col: &Color) -> femtovg::Color {
     femtovg::Color::rgba(col.red(), col.green(), col.blue(), col.alpha())
 }
@@ -19,7 +19,7 @@
     IntRect, PixelFormat, Point as PointF, Rect as RectF, Size as SizeF,
 };
 use sixtyfps_corelib::items::Item;
-use sixtyfps_corelib::{Color, ImageInner};
+use sixtyfps_corelib::{Brush, Color, ImageInner};
 
 pub fn render_window_frame<T: DrawTarget<Color = Rgb888>>(
     runtime_window: Rc<sixtyfps_corelib::window::Window>,
@@ -96,6 +96,23 @@ pub fn render_window_frame<T: DrawTarget<Color = Rgb888>>(
                         }
                     }
                 }
+                SceneCommand::Gradient { ref brush, geometry } => {
+                    let y = line.line as f32 + 0.5 - geometry.origin.y;
+                    for (x, pix) in line_buffer[(span.x) as usize..(span.x + span.width) as usize]
+                        .iter_mut()
+                        .enumerate()
+                    {
+                        let x = (span.x as usize + x) as f32 + 0.5 - geometry.origin.x;
+                        let color = brush.color_at(PointF::new(x, y), geometry.size);
+                        let a = (u8::MAX - color.alpha()) as u16;
+                        let b = color.alpha() as u16;
+                        *pix = Rgb888::new(
+                            ((pix.r() as u16 * a + color.red() as u16 * b) >> 8) as u8,
+                            ((pix.g() as u16 * a + color.green() as u16 * b) >> 8) as u8,
+                            ((pix.b() as u16 * a + color.blue() as u16 * b) >> 8) as u8,
+                        );
+                    }
+                }
                 SceneCommand::Texture {
                     data,
                     format,
@@ -103,18 +120,22 @@ pub fn render_window_frame<T: DrawTarget<Color = Rgb888>>(
                     source_width,
                     source_height,
                     color,
+                    mirror,
                 } => {
                     let sx = span.width as f32 / source_width as f32;
                     let sy = span.height as f32 / source_height as f32;
                     let bpp = bpp(format) as usize;
                     let y = line.line - span.y;
+                    let y = if mirror.1 { span.height - 1 - y } else { y };
+                    let source_y = ((y as f32 / sy) as usize).min(source_height as usize - 1);
 
                     for (x, pix) in line_buffer[(span.x) as usize..(span.x + span.width) as usize]
                         .iter_mut()
                         .enumerate()
                     {
-                        let pos = ((y as f32 / sy) as usize * stride as usize)
-                            + (x as f32 / sx) as usize * bpp;
+                        let x = if mirror.0 { span.width as usize - 1 - x } else { x };
+                        let source_x = ((x as f32 / sx) as usize).min(source_width as usize - 1);
+                        let pos = source_y * stride as usize + source_x * bpp;
                         *pix = match format {
                             PixelFormat::Rgb => {
                                 Rgb888::new(data[pos + 0], data[pos + 1], data[pos + 2])
@@ -228,7 +249,7 @@ fn process_line(&mut self) -> LineCommand {
     }
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone)]
 struct SceneItem {
     x: u16,
     y: u16,
@@ -261,11 +282,19 @@ fn compare_scene_item(a: &SceneItem, b: &SceneItem) -> core::cmp::Ordering {
     core::cmp::Ordering::Equal
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone)]
 enum SceneCommand {
     Rectangle {
         color: Color,
     },
+    /// A gradient brush that fills a rectangle
+    Gradient {
+        brush: Brush,
+        /// The geometry of the whole rectangle in the window, which can be bigger than
+        /// the scene item if it is clipped.
+        geometry: RectF,
+    },
+    /// A part of a texture, which is scaled to fill the scene item
     Texture {
         data: &'static [u8],
         format: PixelFormat,
@@ -273,6 +302,8 @@ enum SceneCommand {
         source_width: u16,
         source_height: u16,
         color: Color,
+        /// Whether the texture is mirrored horizontally and vertically, by a negative scale
+        mirror: (bool, bool),
     },
 }
 
@@ -304,6 +335,7 @@ fn new(size: SizeF) -> Self {
             current_state: RenderState {
                 alpha: 1.,
                 offset: PointF::default(),
+                scale: (1., 1.),
                 clip: RectF::new(PointF::default(), size),
             },
         }
@@ -317,9 +349,14 @@ fn should_draw(&self, rect: &RectF) -> bool {
 
     fn new_scene_item(&mut self, geometry: RectF, command: SceneCommand) {
         let z = self.items.len() as u16;
+        // Round the edges rather than the size, so that adjacent items stay adjacent once scaled
+        let geometry = self.map_to_scene(geometry).round();
+        if geometry.is_empty() {
+            return;
+        }
         self.items.push(SceneItem {
-            x: (self.current_state.offset.x + geometry.origin.x) as _,
-            y: (self.current_state.offset.y + geometry.origin.y) as _,
+            x: geometry.origin.x as _,
+            y: geometry.origin.y as _,
             width: geometry.size.width as _,
             height: geometry.size.height as _,
             z,
@@ -327,6 +364,15 @@ fn new_scene_item(&mut self, geometry: RectF, command: SceneCommand) {
         });
     }
 
+    /// Maps a rectangle in the coordinates of the current item to the coordinates of the scene
+    fn map_to_scene(&self, rect: RectF) -> RectF {
+        let (scale_x, scale_y) = self.current_state.scale;
+        // A negative scale mirrors the rect, normalize it so that it has a positive size
+        let scaled = rect.scale(scale_x, scale_y);
+        RectF::from_points([scaled.origin, scaled.origin + scaled.size])
+            .translate(self.current_state.offset.to_vector())
+    }
+
     fn draw_image_impl(
         &mut self,
         geom: RectF,
@@ -342,16 +388,29 @@ fn draw_image_impl(
             }
             ImageInner::EmbeddedImage(_) => todo!(),
             ImageInner::StaticTextures { size, data, textures } => {
+                // The scale from the image pixels to the item coordinates. The scene item is then
+                // scaled by the current scale, and the renderer fills it with the source pixels.
                 let sx = geom.width() / (size.width as f32);
                 let sy = geom.height() / (size.height as f32);
+                let (scale_x, scale_y) = self.current_state.scale;
                 for t in textures.as_slice() {
                     if let Some(dest_rect) = t
                         .rect
                         .intersection(&source_clip)
                         .and_then(|r| r.cast().scale(sx, sy).intersection(&self.current_state.clip))
                     {
-                        let actual_x = (dest_rect.origin.x / sx) as i32 - t.rect.origin.x;
-                        let actual_y = (dest_rect.origin.y / sy) as i32 - t.rect.origin.y;
+                        // The image pixels covered by the visible part of the texture
+                        let source_x = (dest_rect.min_x() / sx).floor() as i32;
+                        let source_y = (dest_rect.min_y() / sy).floor() as i32;
+                        let source_width =
+                            ((dest_rect.max_x() / sx).ceil() as i32).min(t.rect.max_x()) - source_x;
+                        let source_height =
+                            ((dest_rect.max_y() / sy).ceil() as i32).min(t.rect.max_y()) - source_y;
+                        if source_width <= 0 || source_height <= 0 {
+                            continue;
+                        }
+                        let actual_x = source_x - t.rect.origin.x;
+                        let actual_y = source_y - t.rect.origin.y;
                         let stride = t.rect.width() as u16 * bpp(t.format);
 
                         self.new_scene_item(
@@ -361,10 +420,11 @@ fn draw_image_impl(
                                     + (stride as usize) * (actual_y as usize)
                                     + (bpp(t.format) as usize) * (actual_x as usize))..],
                                 stride,
-                                source_height: (dest_rect.height() / sy) as u16,
-                                source_width: (dest_rect.width() / sx) as u16,
+                                source_height: source_height as u16,
+                                source_width: source_width as u16,
                                 format: t.format,
                                 color: if colorize.alpha() > 0 { colorize } else { t.color },
+                                mirror: (scale_x < 0., scale_y < 0.),
                             },
                         );
                     }
@@ -378,6 +438,8 @@ fn draw_image_impl(
 struct RenderState {
     alpha: f32,
     offset: PointF,
+    /// The scale of the current item relative to the scene
+    scale: (f32, f32),
     clip: RectF,
 }
 
@@ -390,12 +452,17 @@ fn draw_rectangle(&mut self, rect: Pin<&sixtyfps_corelib::items::Rectangle>) {
                 None => return,
             };
 
-            // FIXME: gradients
-            let color = rect.background().color();
-            if color.alpha() == 0 {
+            let background = rect.background();
+            if background.is_transparent() {
                 return;
             }
-            self.new_scene_item(geom, SceneCommand::Rectangle { color });
+            if let Brush::SolidColor(color) = background {
+                self.new_scene_item(geom, SceneCommand::Rectangle { color });
+            } else {
+                let geometry =
+                    self.map_to_scene(RectF::new(PointF::default(), rect.geometry().size));
+                self.new_scene_item(geom, SceneCommand::Gradient { brush: background, geometry });
+            }
         }
     }
 
@@ -502,11 +569,29 @@ fn get_current_clip(&self) -> sixtyfps_corelib::graphics::Rect {
     }
 
     fn translate(&mut self, x: f32, y: f32) {
-        self.current_state.offset.x += x;
-        self.current_state.offset.y += y;
+        let (scale_x, scale_y) = self.current_state.scale;
+        self.current_state.offset.x += x * scale_x;
+        self.current_state.offset.y += y * scale_y;
         self.current_state.clip = self.current_state.clip.translate((-x, -y).into())
     }
 
+    fn scale(&mut self, x_factor: f32, y_factor: f32) {
+        if sixtyfps_corelib::item_rendering::is_degenerate_transform(x_factor, y_factor, 0., 0.) {
+            // Nothing is visible, and the clip can't be mapped back
+            self.current_state.clip = RectF::default();
+            return;
+        }
+        self.current_state.scale.0 *= x_factor;
+        self.current_state.scale.1 *= y_factor;
+        let clip = self.current_state.clip.scale(1. / x_factor, 1. / y_factor);
+        self.current_state.clip = RectF::from_points([clip.origin, clip.origin + clip.size]);
+    }
+
+    /// The scene only contains axis-aligned rectangles, so skewing is not supported and the items
+    /// are drawn without it. The compiler warns about the skew properties when it processes the
+    /// images for this backend.
+    fn skew(&mut self, _x_angle_in_degrees: f32, _y_angle_in_degrees: f32) {}
+
     fn rotate(&mut self, _angle_in_degrees: f32) {
         todo!()
     }
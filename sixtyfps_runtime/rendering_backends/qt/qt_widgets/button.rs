@@ -250,7 +250,10 @@ fn input_event(
                     InputEventResult::EventIgnored
                 }
             }
-            MouseEvent::MouseWheel { .. } => return InputEventResult::EventIgnored,
+            MouseEvent::MouseWheel { .. }
+            | MouseEvent::DragMove { .. }
+            | MouseEvent::Drop { .. }
+            | MouseEvent::TouchPoint { .. } => return InputEventResult::EventIgnored,
         });
         if let MouseEvent::MouseReleased { pos, .. } = event {
             if euclid::rect(0., 0., self.width(), self.height()).contains(pos) {
@@ -70,7 +70,14 @@ fn input_event(
         _window: &WindowRc,
         _self_rc: &sixtyfps_corelib::items::ItemRc,
     ) -> InputEventResult {
-        if !self.enabled() {
+        if !self.enabled()
+            || matches!(
+                event,
+                MouseEvent::DragMove { .. }
+                    | MouseEvent::Drop { .. }
+                    | MouseEvent::TouchPoint { .. }
+            )
+        {
             return InputEventResult::EventIgnored;
         }
         if let MouseEvent::MouseReleased { pos, .. } = event {
@@ -82,7 +82,10 @@ fn input_event(
                     InputEventResult::EventIgnored
                 }
             }
-            MouseEvent::MouseWheel { .. } => return InputEventResult::EventIgnored,
+            MouseEvent::MouseWheel { .. }
+            | MouseEvent::DragMove { .. }
+            | MouseEvent::Drop { .. }
+            | MouseEvent::TouchPoint { .. } => return InputEventResult::EventIgnored,
         });
         if matches!(event, MouseEvent::MouseReleased { .. }) {
             Self::FIELD_OFFSETS.is_open.apply_pin(self).set(true);
@@ -215,6 +215,9 @@ fn input_event(
                     // TODO
                     InputEventResult::EventAccepted
                 }
+                MouseEvent::DragMove { .. }
+                | MouseEvent::Drop { .. }
+                | MouseEvent::TouchPoint { .. } => InputEventResult::EventIgnored,
             };
             self.data.set(data);
             result
@@ -154,7 +154,7 @@ fn input_event(
                 data.pressed = 0;
                 InputEventResult::EventIgnored
             }
-            MouseEvent::MousePressed { pos, button: PointerEventButton::left } => {
+            MouseEvent::MousePressed { pos, button: PointerEventButton::left, .. } => {
                 data.pressed_x = pos.x as f32;
                 data.pressed = 1;
                 data.pressed_val = value;
@@ -189,6 +189,9 @@ fn input_event(
                 debug_assert_ne!(button, PointerEventButton::left);
                 InputEventResult::EventIgnored
             }
+            MouseEvent::DragMove { .. }
+            | MouseEvent::Drop { .. }
+            | MouseEvent::TouchPoint { .. } => InputEventResult::EventIgnored,
         };
         data.active_controls = new_control;
 
@@ -178,6 +178,9 @@ fn input_event(
                 }
                 MouseEvent::MouseMoved { .. } => false,
                 MouseEvent::MouseWheel { .. } => false, // TODO
+                MouseEvent::DragMove { .. }
+                | MouseEvent::Drop { .. }
+                | MouseEvent::TouchPoint { .. } => false,
             };
         data.active_controls = new_control;
         if changed {
@@ -20,8 +20,8 @@
 use sixtyfps_corelib::input::{KeyEvent, KeyEventType, MouseEvent};
 use sixtyfps_corelib::item_rendering::{CachedRenderingData, ItemRenderer};
 use sixtyfps_corelib::items::{
-    self, FillRule, ImageRendering, ItemRef, MouseCursor, PointerEventButton, TextOverflow,
-    TextWrap,
+    self, FillRule, ImageRendering, ItemRef, MouseCursor, PointerEventButton, TextDecoration,
+    TextOverflow, TextWrap,
 };
 use sixtyfps_corelib::layout::Orientation;
 use sixtyfps_corelib::window::{PlatformWindow, PopupWindow, PopupWindowLocation, WindowRc};
@@ -77,6 +77,7 @@ struct TimerHandler : QObject {
 
     struct SixtyFPSWidget : QWidget {
         void *rust_window;
+        QRectF ime_cursor_rect;
 
         SixtyFPSWidget() {
             setMouseTracking(true);
@@ -106,7 +107,7 @@ struct SixtyFPSWidget : QWidget {
             rust!(SFPS_mousePressEvent [rust_window: &QtWindow as "void*", pos: qttypes::QPoint as "QPoint", button: u32 as "int" ] {
                 let pos = Point::new(pos.x as _, pos.y as _);
                 let button = from_qt_button(button);
-                rust_window.mouse_event(MouseEvent::MousePressed{ pos, button })
+                rust_window.mouse_event(MouseEvent::MousePressed{ pos, button, click_count: 0 })
             });
         }
         void mouseReleaseEvent(QMouseEvent *event) override {
@@ -138,10 +139,12 @@ struct SixtyFPSWidget : QWidget {
             if (delta.isNull()) {
                 delta = event->angleDelta();
             }
-            rust!(SFPS_mouseWheelEvent [rust_window: &QtWindow as "void*", pos: qttypes::QPointF as "QPointF", delta: qttypes::QPoint as "QPoint"] {
+            uint modifiers = uint(event->modifiers());
+            rust!(SFPS_mouseWheelEvent [rust_window: &QtWindow as "void*", pos: qttypes::QPointF as "QPointF", delta: qttypes::QPoint as "QPoint", modifiers: u32 as "uint"] {
                 let pos = Point::new(pos.x as _, pos.y as _);
                 let delta = Point::new(delta.x as _, delta.y as _);
-                rust_window.mouse_event(MouseEvent::MouseWheel{pos, delta})
+                let modifiers = qt_keyboard_modifiers(modifiers);
+                rust_window.mouse_event(MouseEvent::MouseWheel{pos, delta, modifiers})
             });
         }
 
@@ -162,6 +165,29 @@ struct SixtyFPSWidget : QWidget {
             });
         }
 
+        void inputMethodEvent(QInputMethodEvent *event) override {
+            QString commit_string = event->commitString();
+            QString preedit_string = event->preeditString();
+            int cursor = preedit_string.size();
+            for (const auto &attribute : event->attributes()) {
+                if (attribute.type == QInputMethodEvent::Cursor) {
+                    cursor = attribute.start;
+                }
+            }
+            // convert to an utf8 pos
+            uintptr_t preedit_cursor = QStringView(preedit_string).left(cursor).toUtf8().size();
+            rust!(SFPS_inputMethodEvent [rust_window: &QtWindow as "void*", commit_string: qttypes::QString as "QString", preedit_string: qttypes::QString as "QString", preedit_cursor: usize as "uintptr_t"] {
+                rust_window.input_method_event(commit_string.clone(), preedit_string.clone(), preedit_cursor);
+            });
+        }
+
+        QVariant inputMethodQuery(Qt::InputMethodQuery query) const override {
+            if (query == Qt::ImCursorRectangle) {
+                return ime_cursor_rect;
+            }
+            return QWidget::inputMethodQuery(query);
+        }
+
         void customEvent(QEvent *event) override {
             if (event->type() == QEvent::User) {
                 rust!(SFPS_updateWindowProps [rust_window: &QtWindow as "void*"]{
@@ -203,8 +229,9 @@ struct SixtyFPSWidget : QWidget {
 
     // Helper function used for the TextInput layouting
     //
+    // if line_height > 0, the lines are that far apart, with the glyphs centered in the line
     // if line_for_y_pos > 0, then the function will return the line at this y position
-    static int do_text_layout(QTextLayout &layout, int flags, const QRectF &rect, int line_for_y_pos = -1) {
+    static int do_text_layout(QTextLayout &layout, int flags, const QRectF &rect, qreal line_height = 0, int line_for_y_pos = -1) {
         QTextOption options;
         options.setWrapMode((flags & Qt::TextWordWrap) ? QTextOption::WordWrap : QTextOption::NoWrap);
         layout.setTextOption(options);
@@ -219,9 +246,14 @@ struct SixtyFPSWidget : QWidget {
             if (!line.isValid())
                 break;
             line.setLineWidth(rect.width());
-            height += leading;
-            line.setPosition(QPointF(0, height));
-            height += line.height();
+            if (line_height > 0) {
+                line.setPosition(QPointF(0, height + (line_height - line.height()) / 2.));
+                height += line_height;
+            } else {
+                height += leading;
+                line.setPosition(QPointF(0, height));
+                height += line.height();
+            }
             if (line_for_y_pos >= 0 && height > line_for_y_pos) {
                 return count;
             }
@@ -235,6 +267,48 @@ struct SixtyFPSWidget : QWidget {
         }
         return -1;
     }
+
+    // Must have the same layout as the QtStyledTextSpan in Rust
+    struct SFPS_StyledTextSpan {
+        int start;
+        int length;
+        bool bold;
+        bool italic;
+        bool underline;
+        bool has_color;
+        QRgb color;
+    };
+
+    // Helper function used for the layouting of the styled text of the Text element: the spans
+    // are applied as formats, and the lines are moved according to the horizontal alignment
+    static void do_styled_text_layout(QTextLayout &layout, const SFPS_StyledTextSpan *spans, size_t spans_len, int flags, const QRectF &rect) {
+        QVector<QTextLayout::FormatRange> formats;
+        for (size_t i = 0; i < spans_len; ++i) {
+            const auto &span = spans[i];
+            QTextCharFormat fmt;
+            if (span.bold)
+                fmt.setFontWeight(QFont::Bold);
+            if (span.italic)
+                fmt.setFontItalic(true);
+            if (span.underline)
+                fmt.setFontUnderline(true);
+            if (span.has_color)
+                fmt.setForeground(QColor::fromRgba(span.color));
+            formats << QTextLayout::FormatRange{ span.start, span.length, fmt };
+        }
+        layout.setFormats(formats);
+        do_text_layout(layout, flags, rect);
+        for (int i = 0; i < layout.lineCount(); ++i) {
+            QTextLine line = layout.lineAt(i);
+            qreal x = 0;
+            if (flags & Qt::AlignHCenter) {
+                x = (rect.width() - line.naturalTextWidth()) / 2.;
+            } else if (flags & Qt::AlignRight) {
+                x = rect.width() - line.naturalTextWidth();
+            }
+            line.setPosition(QPointF(x, line.y()));
+        }
+    }
 }}
 
 cpp_class! {pub unsafe struct QPainterPath as "QPainterPath"}
@@ -286,7 +360,9 @@ pub fn set_fill_rule(&mut self, rule: key_generated::Qt_FillRule) {
     }
 }
 
-fn into_qbrush(brush: sixtyfps_corelib::Brush) -> qttypes::QBrush {
+/// Convert the brush to a QBrush for an item of the given size. Radial and conic gradients are
+/// computed in the coordinates of an item whose top left corner is at (0, 0).
+fn into_qbrush(brush: sixtyfps_corelib::Brush, width: f64, height: f64) -> qttypes::QBrush {
     match brush {
         sixtyfps_corelib::Brush::SolidColor(color) => {
             let color: u32 = color.as_argb_encoded();
@@ -317,6 +393,47 @@ fn into_qbrush(brush: sixtyfps_corelib::Brush) -> qttypes::QBrush {
                 return QBrush(qlg);
             }}
         }
+        sixtyfps_corelib::Brush::RadialGradient(g) => {
+            cpp_class!(unsafe struct QRadialGradient as "QRadialGradient");
+            let mut qrg = cpp! {
+                unsafe [width as "qreal", height as "qreal"] -> QRadialGradient as "QRadialGradient" {
+                    // The stop at position 1 is on the circle that goes through the corners
+                    QRadialGradient qrg(width / 2, height / 2, qSqrt(width * width + height * height) / 2);
+                    return qrg;
+                }
+            };
+            for s in g.stops() {
+                let pos: f32 = s.position;
+                let color: u32 = s.color.as_argb_encoded();
+                cpp! {unsafe [mut qrg as "QRadialGradient", pos as "float", color as "QRgb"] {
+                    qrg.setColorAt(pos, QColor::fromRgba(color));
+                }};
+            }
+            cpp! {unsafe [qrg as "QRadialGradient"] -> qttypes::QBrush as "QBrush" {
+                return QBrush(qrg);
+            }}
+        }
+        sixtyfps_corelib::Brush::ConicGradient(g) => {
+            cpp_class!(unsafe struct QConicalGradient as "QConicalGradient");
+            let mut qcg = cpp! {
+                unsafe [width as "qreal", height as "qreal"] -> QConicalGradient as "QConicalGradient" {
+                    // QConicalGradient goes counter-clockwise, so start at the top and reverse
+                    // the stops positions
+                    QConicalGradient qcg(width / 2, height / 2, 90);
+                    return qcg;
+                }
+            };
+            for s in g.stops() {
+                let pos: f32 = 1. - s.position;
+                let color: u32 = s.color.as_argb_encoded();
+                cpp! {unsafe [mut qcg as "QConicalGradient", pos as "float", color as "QRgb"] {
+                    qcg.setColorAt(pos, QColor::fromRgba(color));
+                }};
+            }
+            cpp! {unsafe [qcg as "QConicalGradient"] -> qttypes::QBrush as "QBrush" {
+                return QBrush(qcg);
+            }}
+        }
         _ => qttypes::QBrush::default(),
     }
 }
@@ -377,11 +494,12 @@ struct QtItemRenderer<'a> {
 
 impl ItemRenderer for QtItemRenderer<'_> {
     fn draw_rectangle(&mut self, rect: Pin<&items::Rectangle>) {
-        let brush: qttypes::QBrush = into_qbrush(rect.background());
-        let rect: qttypes::QRectF = get_geometry!(items::Rectangle, rect);
+        let geometry: qttypes::QRectF = get_geometry!(items::Rectangle, rect);
+        let brush: qttypes::QBrush =
+            into_qbrush(rect.background(), geometry.width, geometry.height);
         let painter: &mut QPainter = &mut *self.painter;
-        cpp! { unsafe [painter as "QPainter*", brush as "QBrush", rect as "QRectF"] {
-            painter->fillRect(rect, brush);
+        cpp! { unsafe [painter as "QPainter*", brush as "QBrush", geometry as "QRectF"] {
+            painter->fillRect(geometry, brush);
         }}
     }
 
@@ -434,11 +552,11 @@ fn draw_clipped_image(&mut self, image: Pin<&items::ClippedImage>) {
 
     fn draw_text(&mut self, text: std::pin::Pin<&items::Text>) {
         let rect: qttypes::QRectF = get_geometry!(items::Text, text);
-        let fill_brush: qttypes::QBrush = into_qbrush(text.color());
+        let fill_brush: qttypes::QBrush = into_qbrush(text.color(), rect.width, rect.height);
         let mut string: qttypes::QString = text.text().as_str().into();
         let font: QFont =
             get_font(text.unresolved_font_request().merge(&self.default_font_properties));
-        let flags = match text.horizontal_alignment() {
+        let flags = match text.visual_horizontal_alignment(&self.window) {
             TextHorizontalAlignment::left => key_generated::Qt_AlignmentFlag_AlignLeft,
             TextHorizontalAlignment::center => key_generated::Qt_AlignmentFlag_AlignHCenter,
             TextHorizontalAlignment::right => key_generated::Qt_AlignmentFlag_AlignRight,
@@ -451,12 +569,73 @@ fn draw_text(&mut self, text: std::pin::Pin<&items::Text>) {
             TextWrap::word_wrap => key_generated::Qt_TextFlag_TextWordWrap,
         };
         let elide = text.overflow() == TextOverflow::elide;
+        let line_height: f32 = text.line_height();
+        let max_lines: i32 = text.max_lines();
         let painter: &mut QPainter = &mut *self.painter;
-        cpp! { unsafe [painter as "QPainter*", rect as "QRectF", fill_brush as "QBrush", mut string as "QString", flags as "int", font as "QFont", elide as "bool"] {
+        if let Some(styled_text) = text.parsed_styled_text() {
+            let string: qttypes::QString = styled_text.text.as_str().into();
+            let spans = qt_styled_text_spans(&styled_text);
+            let spans_ptr = spans.as_ptr();
+            let spans_len = spans.len();
+            cpp! { unsafe [painter as "QPainter*", rect as "QRectF", fill_brush as "QBrush", string as "QString", flags as "int", font as "QFont", spans_ptr as "const SFPS_StyledTextSpan*", spans_len as "size_t"] {
+                QTextLayout layout(QString(string).replace(QChar('\n'), QChar::LineSeparator), font);
+                do_styled_text_layout(layout, spans_ptr, spans_len, flags, rect);
+                painter->setPen(QPen(fill_brush, 0));
+                painter->setBrush(Qt::NoBrush);
+                layout.draw(painter, rect.topLeft());
+            }}
+            return;
+        }
+        cpp! { unsafe [painter as "QPainter*", rect as "QRectF", fill_brush as "QBrush", mut string as "QString", flags as "int", font as "QFont", elide as "bool", line_height as "float", max_lines as "int"] {
             painter->setFont(font);
             painter->setPen(QPen(fill_brush, 0));
             painter->setBrush(Qt::NoBrush);
-            if (!elide) {
+            if (line_height > 0 || max_lines > 0) {
+                // Position the lines manually, to honor the line height and the maximum number of lines
+                string.replace(QChar('\n'), QChar::LineSeparator);
+                QFontMetricsF fm(font);
+                qreal advance = line_height > 0 ? line_height : fm.lineSpacing();
+                QTextLayout layout(string, font);
+                QTextOption options;
+                options.setWrapMode((flags & Qt::TextWordWrap) ? QTextOption::WordWrap : QTextOption::NoWrap);
+                options.setAlignment(Qt::Alignment(flags & Qt::AlignHorizontal_Mask));
+                layout.setTextOption(options);
+                layout.setCacheEnabled(true);
+                layout.beginLayout();
+                QVector<QTextLine> lines;
+                bool truncated = false;
+                while (true) {
+                    auto line = layout.createLine();
+                    if (!line.isValid())
+                        break;
+                    if ((max_lines > 0 && lines.size() >= max_lines)
+                            || (elide && !lines.isEmpty() && (lines.size() + 1) * advance > rect.height())) {
+                        truncated = true;
+                        break;
+                    }
+                    line.setLineWidth(rect.width());
+                    line.setPosition(QPointF(0, lines.size() * advance + (advance - line.height()) / 2.));
+                    lines.append(line);
+                }
+                layout.endLayout();
+                qreal height = lines.size() * advance;
+                qreal y = 0;
+                if (flags & Qt::AlignVCenter) {
+                    y = (rect.height() - height) / 2.;
+                } else if (flags & Qt::AlignBottom) {
+                    y = rect.height() - height;
+                }
+                for (int i = 0; i < lines.size(); ++i) {
+                    const auto &line = lines[i];
+                    if (elide && truncated && i == lines.size() - 1) {
+                        QString to_elide = QStringView(string).mid(line.textStart(), line.textLength()).trimmed() % QStringView(u"…");
+                        QRectF line_rect(rect.x(), rect.y() + y + line.y(), rect.width(), line.height());
+                        painter->drawText(line_rect, flags & Qt::AlignHorizontal_Mask, fm.elidedText(to_elide, Qt::ElideRight, rect.width()));
+                    } else {
+                        line.draw(painter, rect.topLeft() + QPointF(0, y));
+                    }
+                }
+            } else if (!elide) {
                 painter->drawText(rect, flags, string);
             } else if (!(flags & Qt::TextWordWrap)) {
                 QString elided;
@@ -513,17 +692,18 @@ fn draw_text(&mut self, text: std::pin::Pin<&items::Text>) {
 
     fn draw_text_input(&mut self, text_input: std::pin::Pin<&items::TextInput>) {
         let rect: qttypes::QRectF = get_geometry!(items::TextInput, text_input);
-        let fill_brush: qttypes::QBrush = into_qbrush(text_input.color());
+        let fill_brush: qttypes::QBrush = into_qbrush(text_input.color(), rect.width, rect.height);
         let selection_foreground_color: u32 =
             text_input.selection_foreground_color().as_argb_encoded();
         let selection_background_color: u32 =
             text_input.selection_background_color().as_argb_encoded();
 
-        let text = text_input.text();
+        let visual_representation = text_input.visual_representation();
+        let text = &visual_representation.text;
         let mut string: qttypes::QString = text.as_str().into();
         let font: QFont =
             get_font(text_input.unresolved_font_request().merge(&self.default_font_properties));
-        let flags = match text_input.horizontal_alignment() {
+        let flags = match text_input.visual_horizontal_alignment(&self.window) {
             TextHorizontalAlignment::left => key_generated::Qt_AlignmentFlag_AlignLeft,
             TextHorizontalAlignment::center => key_generated::Qt_AlignmentFlag_AlignHCenter,
             TextHorizontalAlignment::right => key_generated::Qt_AlignmentFlag_AlignRight,
@@ -538,8 +718,8 @@ fn draw_text_input(&mut self, text_input: std::pin::Pin<&items::TextInput>) {
 
         // convert byte offsets to offsets in Qt UTF-16 encoded string, as that's
         // what QTextLayout expects.
-        let cursor_position_as_offset: i32 = text_input.cursor_position();
-        let anchor_position_as_offset: i32 = text_input.anchor_position();
+        let cursor_position_as_offset: i32 = visual_representation.cursor_position;
+        let anchor_position_as_offset: i32 = visual_representation.anchor_position;
         let cursor_position: i32 = if cursor_position_as_offset > 0 {
             utf8_byte_offset_to_utf16_units(text.as_str(), cursor_position_as_offset as usize)
                 as i32
@@ -552,6 +732,13 @@ fn draw_text_input(&mut self, text_input: std::pin::Pin<&items::TextInput>) {
         } else {
             0
         };
+        let preedit_start: i32 = utf8_byte_offset_to_utf16_units(
+            text.as_str(),
+            visual_representation.preedit_range.start,
+        ) as i32;
+        let preedit_end: i32 =
+            utf8_byte_offset_to_utf16_units(text.as_str(), visual_representation.preedit_range.end)
+                as i32;
 
         let text_cursor_width: f32 = if text_input.cursor_visible() && text_input.enabled() {
             text_input.text_cursor_width()
@@ -560,10 +747,12 @@ fn draw_text_input(&mut self, text_input: std::pin::Pin<&items::TextInput>) {
         };
 
         let single_line: bool = text_input.single_line();
+        let line_height: f32 = text_input.line_height();
 
         let painter: &mut QPainter = &mut *self.painter;
         cpp! { unsafe [
                 painter as "QPainter*",
+                line_height as "float",
                 rect as "QRectF",
                 fill_brush as "QBrush",
                 selection_foreground_color as "QRgb",
@@ -574,12 +763,14 @@ fn draw_text_input(&mut self, text_input: std::pin::Pin<&items::TextInput>) {
                 font as "QFont",
                 cursor_position as "int",
                 anchor_position as "int",
+                preedit_start as "int",
+                preedit_end as "int",
                 text_cursor_width as "float"] {
             if (!single_line) {
                 string.replace(QChar('\n'), QChar::LineSeparator);
             }
             QTextLayout layout(string, font);
-            do_text_layout(layout, flags, rect);
+            do_text_layout(layout, flags, rect, line_height);
             painter->setPen(QPen(fill_brush, 0));
             QVector<QTextLayout::FormatRange> selections;
             if (anchor_position != cursor_position) {
@@ -592,6 +783,11 @@ fn draw_text_input(&mut self, text_input: std::pin::Pin<&items::TextInput>) {
                     fmt
                 };
             }
+            if (preedit_start != preedit_end) {
+                QTextCharFormat fmt;
+                fmt.setFontUnderline(true);
+                selections << QTextLayout::FormatRange{ preedit_start, preedit_end - preedit_start, fmt };
+            }
             layout.draw(painter, rect.topLeft(), selections);
             if (text_cursor_width > 0) {
                 layout.drawCursor(painter, rect.topLeft(), cursor_position, text_cursor_width);
@@ -606,8 +802,9 @@ fn draw_path(&mut self, path: Pin<&items::Path>) {
         }
         // FIXME: handle width/height
         //let rect: qttypes::QRectF = get_geometry!(pos, items::Path, path);
-        let fill_brush: qttypes::QBrush = into_qbrush(path.fill());
-        let stroke_brush: qttypes::QBrush = into_qbrush(path.stroke());
+        let (width, height) = (path.width() as f64, path.height() as f64);
+        let fill_brush: qttypes::QBrush = into_qbrush(path.fill(), width, height);
+        let stroke_brush: qttypes::QBrush = into_qbrush(path.stroke(), width, height);
         let stroke_width: f32 = path.stroke_width();
         let (offset, path_events) = path.fitted_path_events();
         let pos = qttypes::QPoint { x: offset.x as _, y: offset.y as _ };
@@ -836,6 +1033,22 @@ fn rotate(&mut self, angle_in_degrees: f32) {
         }}
     }
 
+    fn scale(&mut self, x_factor: f32, y_factor: f32) {
+        let painter: &mut QPainter = &mut *self.painter;
+        cpp! { unsafe [painter as "QPainter*", x_factor as "float", y_factor as "float"] {
+            painter->scale(x_factor, y_factor);
+        }}
+    }
+
+    fn skew(&mut self, x_angle_in_degrees: f32, y_angle_in_degrees: f32) {
+        let painter: &mut QPainter = &mut *self.painter;
+        let (sh, sv) =
+            (x_angle_in_degrees.to_radians().tan(), y_angle_in_degrees.to_radians().tan());
+        cpp! { unsafe [painter as "QPainter*", sh as "float", sv as "float"] {
+            painter->shear(sh, sv);
+        }}
+    }
+
     fn apply_opacity(&mut self, opacity: f32) {
         let painter: &mut QPainter = &mut *self.painter;
         cpp! { unsafe [painter as "QPainter*", opacity as "float"] {
@@ -1011,7 +1224,9 @@ fn draw_image_impl(
                 .map_or(QtRenderingCacheItem::Invalid, |mut pixmap: qttypes::QPixmap| {
                     let colorize = colorize_property.map_or(Brush::default(), |c| c.get());
                     if !colorize.is_transparent() {
-                        let brush: qttypes::QBrush = into_qbrush(colorize);
+                        let size = pixmap.size();
+                        let brush: qttypes::QBrush =
+                            into_qbrush(colorize, size.width as _, size.height as _);
                         cpp!(unsafe [mut pixmap as "QPixmap", brush as "QBrush"] {
                             QPainter p(&pixmap);
                             p.setCompositionMode(QPainter::CompositionMode_SourceIn);
@@ -1058,8 +1273,8 @@ fn draw_rectangle_impl(
         mut border_width: f32,
         border_radius: f32,
     ) {
-        let brush: qttypes::QBrush = into_qbrush(brush);
-        let border_color: qttypes::QBrush = into_qbrush(border_color);
+        let brush: qttypes::QBrush = into_qbrush(brush, rect.width, rect.height);
+        let border_color: qttypes::QBrush = into_qbrush(border_color, rect.width, rect.height);
         adjust_rect_and_border_for_inner_drawing(&mut rect, &mut border_width);
         cpp! { unsafe [painter as "QPainter*", brush as "QBrush",  border_color as "QBrush", border_width as "float", border_radius as "float", rect as "QRectF"] {
             painter->setPen(border_width > 0 ? QPen(border_color, border_width) : Qt::NoPen);
@@ -1160,12 +1375,7 @@ fn mouse_event(&self, event: MouseEvent) {
     fn key_event(&self, key: i32, text: qttypes::QString, qt_modifiers: u32, released: bool) {
         sixtyfps_corelib::animations::update_animations();
         let text: String = text.into();
-        let modifiers = sixtyfps_corelib::input::KeyboardModifiers {
-            control: (qt_modifiers & key_generated::Qt_KeyboardModifier_ControlModifier) != 0,
-            alt: (qt_modifiers & key_generated::Qt_KeyboardModifier_AltModifier) != 0,
-            shift: (qt_modifiers & key_generated::Qt_KeyboardModifier_ShiftModifier) != 0,
-            meta: (qt_modifiers & key_generated::Qt_KeyboardModifier_MetaModifier) != 0,
-        };
+        let modifiers = qt_keyboard_modifiers(qt_modifiers);
 
         let text = qt_key_to_string(key as key_generated::Qt_Key, text);
 
@@ -1173,12 +1383,41 @@ fn key_event(&self, key: i32, text: qttypes::QString, qt_modifiers: u32, release
             event_type: if released { KeyEventType::KeyReleased } else { KeyEventType::KeyPressed },
             text,
             modifiers,
+            ..Default::default()
         };
         self.self_weak.upgrade().unwrap().process_key_input(&event);
 
         timer_event();
     }
 
+    fn input_method_event(
+        &self,
+        commit_string: qttypes::QString,
+        preedit_string: qttypes::QString,
+        preedit_cursor: usize,
+    ) {
+        sixtyfps_corelib::animations::update_animations();
+        let runtime_window = self.self_weak.upgrade().unwrap();
+        let commit_string: String = commit_string.into();
+        if !commit_string.is_empty() {
+            runtime_window.clone().process_key_input(&KeyEvent {
+                event_type: KeyEventType::CommitComposition,
+                text: commit_string.into(),
+                ..Default::default()
+            });
+        }
+        let preedit_string: String = preedit_string.into();
+        runtime_window.process_key_input(&KeyEvent {
+            event_type: KeyEventType::UpdateComposition,
+            text: preedit_string.into(),
+            preedit_selection_start: preedit_cursor,
+            preedit_selection_end: preedit_cursor,
+            ..Default::default()
+        });
+
+        timer_event();
+    }
+
     fn default_font_properties(&self) -> FontRequest {
         self.self_weak.upgrade().unwrap().default_font_properties()
     }
@@ -1381,13 +1620,40 @@ fn set_mouse_cursor(&self, cursor: MouseCursor) {
         }};
     }
 
+    fn set_ime_allowed(&self, allowed: bool) {
+        let widget_ptr = self.widget_ptr();
+        cpp! {unsafe [widget_ptr as "QWidget*", allowed as "bool"] {
+            widget_ptr->setAttribute(Qt::WA_InputMethodEnabled, allowed);
+            QGuiApplication::inputMethod()->update(Qt::ImEnabled);
+        }};
+    }
+
+    fn set_ime_cursor_rect(&self, rect: sixtyfps_corelib::graphics::Rect) {
+        let widget_ptr = self.widget_ptr();
+        let rect = qttypes::QRectF {
+            x: rect.origin.x as _,
+            y: rect.origin.y as _,
+            width: rect.width() as _,
+            height: rect.height() as _,
+        };
+        cpp! {unsafe [widget_ptr as "SixtyFPSWidget*", rect as "QRectF"] {
+            widget_ptr->ime_cursor_rect = rect;
+            QGuiApplication::inputMethod()->update(Qt::ImCursorRectangle);
+        }};
+    }
+
     fn text_size(
         &self,
         font_request: sixtyfps_corelib::graphics::FontRequest,
         text: &str,
         max_width: Option<f32>,
     ) -> Size {
-        get_font(font_request.merge(&self.default_font_properties())).text_size(text, max_width)
+        let line_height = font_request.line_height;
+        get_font(font_request.merge(&self.default_font_properties())).text_size(
+            text,
+            max_width,
+            line_height,
+        )
     }
 
     fn text_input_byte_offset_for_position(
@@ -1399,8 +1665,10 @@ fn text_input_byte_offset_for_position(
         let pos = qttypes::QPointF { x: pos.x as _, y: pos.y as _ };
         let font: QFont =
             get_font(text_input.unresolved_font_request().merge(&self.default_font_properties()));
-        let string = qttypes::QString::from(text_input.text().as_str());
-        let flags = match text_input.horizontal_alignment() {
+        let visual_representation = text_input.visual_representation();
+        let string = qttypes::QString::from(visual_representation.text.as_str());
+        let flags = match text_input.visual_horizontal_alignment(&self.self_weak.upgrade().unwrap())
+        {
             TextHorizontalAlignment::left => key_generated::Qt_AlignmentFlag_AlignLeft,
             TextHorizontalAlignment::center => key_generated::Qt_AlignmentFlag_AlignHCenter,
             TextHorizontalAlignment::right => key_generated::Qt_AlignmentFlag_AlignRight,
@@ -1413,14 +1681,15 @@ fn text_input_byte_offset_for_position(
             TextWrap::word_wrap => key_generated::Qt_TextFlag_TextWordWrap,
         };
         let single_line: bool = text_input.single_line();
-        cpp! { unsafe [font as "QFont", string as "QString", pos as "QPointF", flags as "int", rect as "QRectF", single_line as "bool"] -> usize as "size_t" {
+        let line_height: f32 = text_input.line_height();
+        let byte_offset = cpp! { unsafe [font as "QFont", string as "QString", pos as "QPointF", flags as "int", rect as "QRectF", single_line as "bool", line_height as "float"] -> usize as "size_t" {
             // we need to do the \n replacement in a copy because the original need to be kept to know the utf8 offset
             auto copy = string;
             if (!single_line) {
                 copy.replace(QChar('\n'), QChar::LineSeparator);
             }
             QTextLayout layout(copy, font);
-            auto line = do_text_layout(layout, flags, rect, pos.y());
+            auto line = do_text_layout(layout, flags, rect, line_height, pos.y());
             if (line < 0 || layout.lineCount() <= line)
                 return 0;
             QTextLine textLine = layout.lineAt(line);
@@ -1434,7 +1703,50 @@ fn text_input_byte_offset_for_position(
             }
             // convert to an utf8 pos;
             return QStringView(string).left(cur).toUtf8().size();
-        }}
+        }};
+        visual_representation.map_byte_offset_from_visual(byte_offset)
+    }
+
+    fn text_byte_offset_for_position(
+        &self,
+        text: Pin<&sixtyfps_corelib::items::Text>,
+        pos: Point,
+    ) -> Option<usize> {
+        let styled_text = text.parsed_styled_text()?;
+        let rect: qttypes::QRectF = get_geometry!(items::Text, text);
+        let pos = qttypes::QPointF { x: pos.x as _, y: pos.y as _ };
+        let font: QFont =
+            get_font(text.unresolved_font_request().merge(&self.default_font_properties()));
+        let string = qttypes::QString::from(styled_text.text.as_str());
+        let spans = qt_styled_text_spans(&styled_text);
+        let spans_ptr = spans.as_ptr();
+        let spans_len = spans.len();
+        let flags = match text.visual_horizontal_alignment(&self.self_weak.upgrade().unwrap()) {
+            TextHorizontalAlignment::left => key_generated::Qt_AlignmentFlag_AlignLeft,
+            TextHorizontalAlignment::center => key_generated::Qt_AlignmentFlag_AlignHCenter,
+            TextHorizontalAlignment::right => key_generated::Qt_AlignmentFlag_AlignRight,
+        } | match text.vertical_alignment() {
+            TextVerticalAlignment::top => key_generated::Qt_AlignmentFlag_AlignTop,
+            TextVerticalAlignment::center => key_generated::Qt_AlignmentFlag_AlignVCenter,
+            TextVerticalAlignment::bottom => key_generated::Qt_AlignmentFlag_AlignBottom,
+        } | match text.wrap() {
+            TextWrap::no_wrap => 0,
+            TextWrap::word_wrap => key_generated::Qt_TextFlag_TextWordWrap,
+        };
+        let byte_offset = cpp! { unsafe [font as "QFont", string as "QString", pos as "QPointF", flags as "int", rect as "QRectF", spans_ptr as "const SFPS_StyledTextSpan*", spans_len as "size_t"] -> i64 as "qint64" {
+            QTextLayout layout(QString(string).replace(QChar('\n'), QChar::LineSeparator), font);
+            do_styled_text_layout(layout, spans_ptr, spans_len, flags, rect);
+            for (int i = 0; i < layout.lineCount(); ++i) {
+                QTextLine line = layout.lineAt(i);
+                if (line.naturalTextRect().translated(layout.position()).contains(pos)) {
+                    int cur = line.xToCursor(pos.x() - layout.position().x(), QTextLine::CursorOnCharacter);
+                    // convert to an utf8 pos;
+                    return QStringView(string).left(cur).toUtf8().size();
+                }
+            }
+            return -1;
+        }};
+        (byte_offset >= 0).then(|| byte_offset as usize)
     }
 
     fn text_input_position_for_byte_offset(
@@ -1445,10 +1757,15 @@ fn text_input_position_for_byte_offset(
         let rect: qttypes::QRectF = get_geometry!(items::TextInput, text_input);
         let font: QFont =
             get_font(text_input.unresolved_font_request().merge(&self.default_font_properties()));
-        let text = text_input.text();
+        let visual_representation = text_input.visual_representation();
+        let text = &visual_representation.text;
         let mut string = qttypes::QString::from(text.as_str());
-        let offset: u32 = utf8_byte_offset_to_utf16_units(text.as_str(), byte_offset) as _;
-        let flags = match text_input.horizontal_alignment() {
+        let offset: u32 = utf8_byte_offset_to_utf16_units(
+            text.as_str(),
+            visual_representation.map_byte_offset_to_visual(byte_offset),
+        ) as _;
+        let flags = match text_input.visual_horizontal_alignment(&self.self_weak.upgrade().unwrap())
+        {
             TextHorizontalAlignment::left => key_generated::Qt_AlignmentFlag_AlignLeft,
             TextHorizontalAlignment::center => key_generated::Qt_AlignmentFlag_AlignHCenter,
             TextHorizontalAlignment::right => key_generated::Qt_AlignmentFlag_AlignRight,
@@ -1461,18 +1778,24 @@ fn text_input_position_for_byte_offset(
             TextWrap::word_wrap => key_generated::Qt_TextFlag_TextWordWrap,
         };
         let single_line: bool = text_input.single_line();
-        let r = cpp! { unsafe [font as "QFont", mut string as "QString", offset as "int", flags as "int", rect as "QRectF", single_line as "bool"]
+        let line_height: f32 = text_input.line_height();
+        let r = cpp! { unsafe [font as "QFont", mut string as "QString", offset as "int", flags as "int", rect as "QRectF", single_line as "bool", line_height as "float"]
                 -> qttypes::QPointF as "QPointF" {
             if (!single_line) {
                 string.replace(QChar('\n'), QChar::LineSeparator);
             }
             QTextLayout layout(string, font);
-            do_text_layout(layout, flags, rect);
+            do_text_layout(layout, flags, rect, line_height);
 
             QTextLine textLine = layout.lineForTextPosition(offset);
             if (!textLine.isValid())
                 return QPointF();
-            return QPointF(textLine.x() + textLine.cursorToX(offset), textLine.y());
+            // The position is the one of the top of the line, as laid out by do_text_layout, so
+            // that hit testing that position with text_input_byte_offset_for_position finds the same line
+            qreal top = line_height > 0
+                ? textLine.y() - (line_height - textLine.height()) / 2.
+                : textLine.y() - QFontMetrics(font).leading();
+            return QPointF(textLine.x() + textLine.cursorToX(offset), top);
         }};
         return Point::new(r.x as _, r.y as _);
     }
@@ -1487,7 +1810,10 @@ fn get_font(request: FontRequest) -> QFont {
     let pixel_size: f32 = request.pixel_size.unwrap_or(0.);
     let weight: i32 = request.weight.unwrap_or(0);
     let letter_spacing: f32 = request.letter_spacing.unwrap_or_default();
-    cpp!(unsafe [family as "QString", pixel_size as "float", weight as "int", letter_spacing as "float"] -> QFont as "QFont" {
+    let italic: bool = request.italic;
+    let underline: bool = request.text_decoration == TextDecoration::underline;
+    let strikethrough: bool = request.text_decoration == TextDecoration::strikethrough;
+    cpp!(unsafe [family as "QString", pixel_size as "float", weight as "int", letter_spacing as "float", italic as "bool", underline as "bool", strikethrough as "bool"] -> QFont as "QFont" {
         QFont f;
         if (!family.isEmpty())
             f.setFamily(family);
@@ -1501,6 +1827,9 @@ fn get_font(request: FontRequest) -> QFont {
     #endif
         }
         f.setLetterSpacing(QFont::AbsoluteSpacing, letter_spacing);
+        f.setItalic(italic);
+        f.setUnderline(underline);
+        f.setStrikeOut(strikethrough);
         // Mark all font properties as resolved, to avoid inheriting font properties
         // from the widget hierarchy. Later we call QPainter::setFont, which would
         // merge in unset properties (such as bold, etc.) that it retrieved from
@@ -1517,16 +1846,29 @@ fn get_font(request: FontRequest) -> QFont {
 cpp_class! {pub unsafe struct QFont as "QFont"}
 
 impl QFont {
-    fn text_size(&self, text: &str, max_width: Option<f32>) -> sixtyfps_corelib::graphics::Size {
+    /// The size of the text. If `line_height` is set, each line is that high.
+    fn text_size(
+        &self,
+        text: &str,
+        max_width: Option<f32>,
+        line_height: Option<f32>,
+    ) -> sixtyfps_corelib::graphics::Size {
         let string = qttypes::QString::from(text);
         let mut r = qttypes::QRectF::default();
         if let Some(max) = max_width {
             r.height = f32::MAX as _;
             r.width = max as _;
         }
-        let size = cpp! { unsafe [self as "const QFont*", string as "QString", r as "QRectF"]
+        let line_height: f32 = line_height.unwrap_or(0.);
+        let size = cpp! { unsafe [self as "const QFont*", string as "QString", r as "QRectF", line_height as "float"]
                 -> qttypes::QSizeF as "QSizeF"{
-            return QFontMetricsF(*self).boundingRect(r, r.isEmpty() ? 0 : Qt::TextWordWrap , string).size();
+            QFontMetricsF fm(*self);
+            QSizeF size = fm.boundingRect(r, r.isEmpty() ? 0 : Qt::TextWordWrap , string).size();
+            if (line_height > 0) {
+                int lines = qMax(1, qRound(size.height() / fm.lineSpacing()));
+                size.setHeight(lines * line_height);
+            }
+            return size;
         }};
         sixtyfps_corelib::graphics::Size::new(size.width as _, size.height as _)
     }
@@ -1595,6 +1937,15 @@ pub fn qt_key_to_string(key: key_generated::Qt_Key) -> Option<sixtyfps_corelib::
 
 mod key_codes;
 
+fn qt_keyboard_modifiers(qt_modifiers: u32) -> sixtyfps_corelib::input::KeyboardModifiers {
+    sixtyfps_corelib::input::KeyboardModifiers {
+        control: (qt_modifiers & key_generated::Qt_KeyboardModifier_ControlModifier) != 0,
+        alt: (qt_modifiers & key_generated::Qt_KeyboardModifier_AltModifier) != 0,
+        shift: (qt_modifiers & key_generated::Qt_KeyboardModifier_ShiftModifier) != 0,
+        meta: (qt_modifiers & key_generated::Qt_KeyboardModifier_MetaModifier) != 0,
+    }
+}
+
 fn qt_key_to_string(key: key_generated::Qt_Key, event_text: String) -> SharedString {
     // First try to see if we received one of the non-ascii keys that we have
     // a special representation for. If that fails, try to use the provided
@@ -1703,6 +2054,42 @@ pub extern "C" fn sixtyfps_qt_get_widget(
     }
 }
 
+/// The style of a span of a styled text, as passed to `do_styled_text_layout`
+#[repr(C)]
+struct QtStyledTextSpan {
+    start: i32,
+    length: i32,
+    bold: bool,
+    italic: bool,
+    underline: bool,
+    has_color: bool,
+    color: u32,
+}
+
+/// Converts the spans of the styled text to spans with UTF-16 offsets for Qt
+fn qt_styled_text_spans(
+    styled_text: &sixtyfps_corelib::styled_text::StyledText,
+) -> Vec<QtStyledTextSpan> {
+    let text = styled_text.text.as_str();
+    styled_text
+        .spans
+        .iter()
+        .map(|span| {
+            let start = utf8_byte_offset_to_utf16_units(text, span.range.start);
+            let end = utf8_byte_offset_to_utf16_units(text, span.range.end);
+            QtStyledTextSpan {
+                start: start as i32,
+                length: (end - start) as i32,
+                bold: span.style.bold,
+                italic: span.style.italic,
+                underline: span.style.underline,
+                has_color: span.style.color.is_some(),
+                color: span.style.color.map_or(0, |color| color.as_argb_encoded()),
+            }
+        })
+        .collect()
+}
+
 fn utf8_byte_offset_to_utf16_units(str: &str, byte_offset: usize) -> usize {
     let mut current_offset = 0;
     let mut utf16_units = 0;
@@ -0,0 +1,181 @@
+/* LICENSE BEGIN
+    This file is part of the SixtyFPS Project -- https://sixtyfps.io
+    Copyright (c) 2021 Olivier Goffart <olivier.goffart@sixtyfps.io>
+    Copyright (c) 2021 Simon Hausmann <simon.hausmann@sixtyfps.io>
+
+    SPDX-License-Identifier: GPL-3.0-only
+    This file is also available under commercial licensing terms.
+    Please contact info@sixtyfps.io for more information.
+LICENSE END */
+//! The fonts used by the software renderer.
+//!
+//! In order for the screenshots to be the same on every machine, the `bundled-font` feature
+//! replaces the system fonts with the DejaVu Sans font of the GL backend: only that font and
+//! the fonts registered by the application are then available.
+
+use sixtyfps_corelib::graphics::FontRequest;
+use std::cell::RefCell;
+
+pub const DEFAULT_FONT_SIZE: f32 = 12.;
+pub const DEFAULT_FONT_WEIGHT: i32 = 400; // CSS normal
+
+thread_local! {
+    static FONT_DB: RefCell<fontdb::Database> = RefCell::new({
+        let mut font_db = fontdb::Database::new();
+        #[cfg(feature = "bundled-font")]
+        {
+            font_db.load_font_data(include_bytes!("../gl/fonts/DejaVuSans.ttf").to_vec());
+            font_db.set_sans_serif_family("DejaVu Sans");
+        }
+        #[cfg(not(feature = "bundled-font"))]
+        font_db.load_system_fonts();
+        font_db
+    })
+}
+
+pub fn register_font_from_memory(data: &'static [u8]) {
+    FONT_DB.with(|db| db.borrow_mut().load_font_data(data.to_vec()))
+}
+
+pub fn register_font_from_path(path: &std::path::Path) -> Result<(), std::io::Error> {
+    FONT_DB.with(|db| db.borrow_mut().load_font_file(path))
+}
+
+/// A font face, scaled to the requested size.
+pub struct Font<'a> {
+    face: ttf_parser::Face<'a>,
+    /// The factor to convert from font units to pixels
+    scale: f32,
+    letter_spacing: f32,
+}
+
+impl<'a> Font<'a> {
+    fn glyph(&self, c: char) -> ttf_parser::GlyphId {
+        self.face.glyph_index(c).unwrap_or_default()
+    }
+
+    /// The advance of the given character, in pixels
+    pub fn advance(&self, c: char) -> f32 {
+        self.face.glyph_hor_advance(self.glyph(c)).unwrap_or_default() as f32 * self.scale
+            + self.letter_spacing
+    }
+
+    /// The width of a string, in pixels
+    pub fn text_width(&self, text: &str) -> f32 {
+        text.chars().map(|c| self.advance(c)).sum()
+    }
+
+    /// The distance from the top of a line to its baseline, in pixels
+    pub fn ascent(&self) -> f32 {
+        self.face.ascender() as f32 * self.scale
+    }
+
+    /// The height of a line, in pixels
+    pub fn line_height(&self) -> f32 {
+        let face = &self.face;
+        (face.ascender() as f32 - face.descender() as f32 + face.line_gap() as f32) * self.scale
+    }
+
+    /// Appends the outline of the character `c` to the path builder. (x, y) is the position of
+    /// the glyph on the baseline, in pixels.
+    pub fn append_glyph(&self, c: char, x: f32, y: f32, path: &mut tiny_skia::PathBuilder) {
+        struct Builder<'b> {
+            path: &'b mut tiny_skia::PathBuilder,
+            x: f32,
+            y: f32,
+            scale: f32,
+        }
+        // Font units have the y axis pointing upwards
+        impl<'b> ttf_parser::OutlineBuilder for Builder<'b> {
+            fn move_to(&mut self, x: f32, y: f32) {
+                self.path.move_to(self.x + x * self.scale, self.y - y * self.scale);
+            }
+            fn line_to(&mut self, x: f32, y: f32) {
+                self.path.line_to(self.x + x * self.scale, self.y - y * self.scale);
+            }
+            fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+                self.path.quad_to(
+                    self.x + x1 * self.scale,
+                    self.y - y1 * self.scale,
+                    self.x + x * self.scale,
+                    self.y - y * self.scale,
+                );
+            }
+            fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+                self.path.cubic_to(
+                    self.x + x1 * self.scale,
+                    self.y - y1 * self.scale,
+                    self.x + x2 * self.scale,
+                    self.y - y2 * self.scale,
+                    self.x + x * self.scale,
+                    self.y - y * self.scale,
+                );
+            }
+            fn close(&mut self) {
+                self.path.close();
+            }
+        }
+        self.face.outline_glyph(self.glyph(c), &mut Builder { path, x, y, scale: self.scale });
+    }
+
+    /// Splits the text into lines that are not wider than `max_width` when `wrap` is true.
+    pub fn layout_lines<'t>(&self, text: &'t str, max_width: f32, wrap: bool) -> Vec<&'t str> {
+        let mut lines = Vec::new();
+        for paragraph in text.split('\n') {
+            if !wrap {
+                lines.push(paragraph);
+                continue;
+            }
+            let mut line_start = 0;
+            let mut last_break = None;
+            let mut width = 0.;
+            for (pos, c) in paragraph.char_indices() {
+                if c.is_whitespace() {
+                    last_break = Some(pos);
+                }
+                width += self.advance(c);
+                if width > max_width && pos > line_start {
+                    let line_end = match last_break {
+                        Some(b) if b > line_start => b,
+                        _ => pos,
+                    };
+                    lines.push(&paragraph[line_start..line_end]);
+                    line_start = paragraph[line_end..]
+                        .char_indices()
+                        .find(|(_, c)| !c.is_whitespace())
+                        .map_or(paragraph.len(), |(i, _)| line_end + i);
+                    last_break = None;
+                    width = self.text_width(&paragraph[line_start..pos + c.len_utf8()]);
+                }
+            }
+            lines.push(&paragraph[line_start.min(paragraph.len())..]);
+        }
+        lines
+    }
+}
+
+/// Calls `f` with the font that matches best the request. The sizes in the request must be in pixels.
+pub fn with_font<R>(request: &FontRequest, f: impl FnOnce(&Font) -> R) -> R {
+    FONT_DB.with(|db| {
+        let db = db.borrow();
+        let family = request
+            .family
+            .as_ref()
+            .filter(|family| !family.is_empty())
+            .map_or(fontdb::Family::SansSerif, |family| fontdb::Family::Name(family));
+        let query = fontdb::Query {
+            families: &[family, fontdb::Family::SansSerif],
+            weight: fontdb::Weight(request.weight.unwrap_or(DEFAULT_FONT_WEIGHT) as u16),
+            style: if request.italic { fontdb::Style::Italic } else { fontdb::Style::Normal },
+            ..Default::default()
+        };
+        let id = db.query(&query).expect("the bundled font is always available");
+        db.with_face_data(id, |data, index| {
+            let face = ttf_parser::Face::from_slice(data, index).expect("invalid font");
+            let pixel_size = request.pixel_size.unwrap_or(DEFAULT_FONT_SIZE);
+            let scale = pixel_size / face.units_per_em().unwrap_or(1000) as f32;
+            f(&Font { face, scale, letter_spacing: request.letter_spacing.unwrap_or_default() })
+        })
+        .unwrap()
+    })
+}
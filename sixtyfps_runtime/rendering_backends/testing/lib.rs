@@ -18,14 +18,17 @@
 
 use image::GenericImageView;
 use sixtyfps_corelib::component::ComponentRc;
-use sixtyfps_corelib::graphics::{Image, Point, Size};
+use sixtyfps_corelib::graphics::{Image, Point, Rgba8Pixel, SharedPixelBuffer, Size};
 use sixtyfps_corelib::window::{PlatformWindow, Window};
 use sixtyfps_corelib::ImageInner;
 use std::path::Path;
 use std::pin::Pin;
-use std::rc::Rc;
+use std::rc::{Rc, Weak};
 use std::sync::Mutex;
 
+mod fonts;
+mod software_renderer;
+
 #[derive(Default)]
 pub struct TestingBackend {
     clipboard: Mutex<Option<String>>,
@@ -33,26 +36,31 @@ pub struct TestingBackend {
 
 impl sixtyfps_corelib::backend::Backend for TestingBackend {
     fn create_window(&'static self) -> Rc<Window> {
-        Window::new(|_| Rc::new(TestingWindow::default()))
+        Window::new(|window_weak| Rc::new(TestingWindow { self_weak: window_weak.clone() }))
     }
 
+    /// Runs the deterministic event loop, see [`sixtyfps_corelib::tests::run_virtual_event_loop`]
     fn run_event_loop(&'static self, _behavior: sixtyfps_corelib::backend::EventLoopQuitBehavior) {
-        unimplemented!("running an event loop with the testing backend");
+        sixtyfps_corelib::tests::run_virtual_event_loop();
     }
 
-    fn quit_event_loop(&'static self) {}
+    fn quit_event_loop(&'static self) {
+        sixtyfps_corelib::tests::quit_virtual_event_loop();
+    }
 
     fn register_font_from_memory(
         &'static self,
-        _data: &'static [u8],
+        data: &'static [u8],
     ) -> Result<(), Box<dyn std::error::Error>> {
+        fonts::register_font_from_memory(data);
         Ok(())
     }
 
     fn register_font_from_path(
         &'static self,
-        _path: &std::path::Path,
+        path: &std::path::Path,
     ) -> Result<(), Box<dyn std::error::Error>> {
+        fonts::register_font_from_path(path)?;
         Ok(())
     }
 
@@ -64,8 +72,8 @@ fn clipboard_text(&'static self) -> Option<String> {
         self.clipboard.lock().unwrap().clone()
     }
 
-    fn post_event(&'static self, _event: Box<dyn FnOnce() + Send>) {
-        unimplemented!("event with the testing backend");
+    fn post_event(&'static self, event: Box<dyn FnOnce() + Send>) {
+        sixtyfps_corelib::tests::post_virtual_event(event);
     }
 
     fn image_size(&'static self, image: &Image) -> Size {
@@ -94,15 +102,17 @@ fn image_size(&'static self, image: &Image) -> Size {
             ImageInner::StaticTextures { size, .. } => size.cast(),
         }
     }
-}
 
-pub struct TestingWindow {}
-
-impl Default for TestingWindow {
-    fn default() -> Self {
-        Self {}
+    /// The time only advances with [`sixtyfps_corelib::tests::advance_time`]
+    fn duration_since_start(&'static self) -> core::time::Duration {
+        sixtyfps_corelib::tests::virtual_time()
     }
 }
+
+pub struct TestingWindow {
+    self_weak: Weak<Window>,
+}
+
 impl PlatformWindow for TestingWindow {
     fn show(self: Rc<Self>) {
         unimplemented!("showing a testing window")
@@ -137,29 +147,90 @@ fn apply_geometry_constraint(
 
     fn set_mouse_cursor(&self, _cursor: sixtyfps_corelib::items::MouseCursor) {}
 
+    /// The text is measured with fixed metrics: every byte is 10 pixels wide and every line,
+    /// separated by a newline, is 10 pixels high, or `line-height` high when it is set.
+    /// The width is the one of the longest line.
     fn text_size(
         &self,
-        _font_request: sixtyfps_corelib::graphics::FontRequest,
+        font_request: sixtyfps_corelib::graphics::FontRequest,
         text: &str,
         _max_width: Option<f32>,
     ) -> Size {
-        Size::new(text.len() as f32 * 10., 10.)
+        let width = text.split('\n').map(|line| line.len()).max().unwrap_or(0);
+        let line_count = text.split('\n').count();
+        Size::new(width as f32 * 10., line_count as f32 * font_request.line_height.unwrap_or(10.))
     }
 
+    /// Uses the same fixed metrics as `text_size`.
     fn text_input_byte_offset_for_position(
         &self,
-        _text_input: Pin<&sixtyfps_corelib::items::TextInput>,
-        _pos: Point,
+        text_input: Pin<&sixtyfps_corelib::items::TextInput>,
+        pos: Point,
     ) -> usize {
-        0
+        let visual_representation = text_input.visual_representation();
+        let text = &visual_representation.text;
+        let line_height = text_input.unresolved_font_request().line_height.unwrap_or(10.);
+        let line_index = (pos.y / line_height).max(0.) as usize;
+        let mut line_start = 0;
+        for (index, line) in text.split('\n').enumerate() {
+            if index == line_index || line_start + line.len() == text.len() {
+                let mut offset =
+                    line_start + ((pos.x / 10.).round().max(0.) as usize).min(line.len());
+                while !text.is_char_boundary(offset) {
+                    offset -= 1;
+                }
+                return visual_representation.map_byte_offset_from_visual(offset);
+            }
+            line_start += line.len() + 1;
+        }
+        text_input.text().len()
     }
 
     fn text_input_position_for_byte_offset(
         &self,
-        _text_input: Pin<&sixtyfps_corelib::items::TextInput>,
-        _byte_offset: usize,
+        text_input: Pin<&sixtyfps_corelib::items::TextInput>,
+        byte_offset: usize,
     ) -> Point {
-        Default::default()
+        let visual_representation = text_input.visual_representation();
+        let text = &visual_representation.text;
+        let byte_offset = visual_representation.map_byte_offset_to_visual(byte_offset);
+        let before = &text.as_bytes()[..byte_offset.min(text.len())];
+        let line_start = before.iter().rposition(|b| *b == b'\n').map_or(0, |i| i + 1);
+        let line_index = before.iter().filter(|b| **b == b'\n').count();
+        let line_height = text_input.unresolved_font_request().line_height.unwrap_or(10.);
+        Point::new((before.len() - line_start) as f32 * 10., line_index as f32 * line_height)
+    }
+
+    /// Uses the same fixed metrics as `text_size`, and wraps the lines in the same way as
+    /// the layout, ignoring the alignment.
+    fn text_byte_offset_for_position(
+        &self,
+        text: Pin<&sixtyfps_corelib::items::Text>,
+        pos: Point,
+    ) -> Option<usize> {
+        let styled_text = text.parsed_styled_text()?;
+        let line_height = text.unresolved_font_request().line_height.unwrap_or(10.);
+        if pos.x < 0. || pos.y < 0. {
+            return None;
+        }
+        let max_width =
+            (text.wrap() == sixtyfps_corelib::items::TextWrap::word_wrap).then(|| text.width());
+        let lines = styled_text.layout_lines(max_width, |_, word| word.len() as f32 * 10.);
+        let run = lines
+            .get((pos.y / line_height) as usize)?
+            .iter()
+            .find(|run| (run.x..run.x + run.width).contains(&pos.x))?;
+        let mut offset = run.range.start + ((pos.x - run.x) / 10.) as usize;
+        while !styled_text.text.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        Some(offset)
+    }
+
+    /// Renders the window with a software renderer. The text is rendered with the fonts of
+    /// the `fonts` module, but the layout still uses the fixed metrics of `text_size`.
+    fn take_snapshot(&self) -> Option<SharedPixelBuffer<Rgba8Pixel>> {
+        software_renderer::render_window(&self.self_weak.upgrade()?)
     }
 
     fn as_any(&self) -> &dyn std::any::Any {
@@ -173,3 +244,73 @@ fn as_any(&self) -> &dyn std::any::Any {
 pub fn init() {
     sixtyfps_corelib::backend::instance_or_init(|| Box::new(TestingBackend::default()));
 }
+
+/// The environment variable that, when set, makes [`compare_with_reference`] write the
+/// snapshot to the reference file instead of comparing.
+pub const UPDATE_REFERENCE_ENV_VAR: &str = "SIXTYFPS_UPDATE_SCREENSHOTS";
+
+/// Compares a snapshot taken with `take_snapshot` against the reference PNG file at `path`.
+/// Each color channel of each pixel may differ by at most `tolerance`.
+///
+/// If the `SIXTYFPS_UPDATE_SCREENSHOTS` environment variable is set, the snapshot is saved as the
+/// new reference instead. This is also how a missing reference is created: without the variable,
+/// a missing reference is an error.
+pub fn compare_with_reference(
+    snapshot: &SharedPixelBuffer<Rgba8Pixel>,
+    path: &Path,
+    tolerance: u8,
+) -> Result<(), String> {
+    let (width, height) = (snapshot.width() as u32, snapshot.height() as u32);
+    if std::env::var_os(UPDATE_REFERENCE_ENV_VAR).is_some() {
+        return image::save_buffer(
+            path,
+            snapshot.as_bytes(),
+            width,
+            height,
+            image::ColorType::Rgba8,
+        )
+        .map_err(|e| format!("Could not save {}: {}", path.display(), e));
+    }
+
+    if !path.exists() {
+        return Err(format!(
+            "The reference {} does not exist. Run with {} set to create it",
+            path.display(),
+            UPDATE_REFERENCE_ENV_VAR
+        ));
+    }
+    let reference = image::open(path)
+        .map_err(|e| format!("Could not load {}: {}", path.display(), e))?
+        .into_rgba8();
+    if reference.dimensions() != (width, height) {
+        return Err(format!(
+            "The snapshot is {}x{} but the reference {} is {}x{}",
+            width,
+            height,
+            path.display(),
+            reference.width(),
+            reference.height()
+        ));
+    }
+    let different_pixels = reference
+        .pixels()
+        .zip(snapshot.as_slice())
+        .filter(|(expected, actual)| {
+            let actual = [actual.r, actual.g, actual.b, actual.a];
+            expected
+                .0
+                .iter()
+                .zip(actual.iter())
+                .any(|(e, a)| (*e as i16 - *a as i16).abs() > tolerance as i16)
+        })
+        .count();
+    if different_pixels > 0 {
+        return Err(format!(
+            "{} pixels differ from the reference {} by more than {}",
+            different_pixels,
+            path.display(),
+            tolerance
+        ));
+    }
+    Ok(())
+}
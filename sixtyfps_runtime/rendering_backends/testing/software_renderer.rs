@@ -0,0 +1,905 @@
+/* LICENSE BEGIN
+    This file is part of the SixtyFPS Project -- https://sixtyfps.io
+    Copyright (c) 2021 Olivier Goffart <olivier.goffart@sixtyfps.io>
+    Copyright (c) 2021 Simon Hausmann <simon.hausmann@sixtyfps.io>
+
+    SPDX-License-Identifier: GPL-3.0-only
+    This file is also available under commercial licensing terms.
+    Please contact info@sixtyfps.io for more information.
+LICENSE END */
+//! A renderer that rasterizes the items on the CPU into a pixmap, so that the tests can compare
+//! the result with reference images.
+
+use sixtyfps_corelib::graphics::{
+    euclid, Brush, Color, Image, Point, Rect, Rgba8Pixel, SharedImageBuffer, SharedPixelBuffer,
+    Size,
+};
+use sixtyfps_corelib::item_rendering::{
+    is_degenerate_transform, CachedRenderingData, ItemRenderer,
+};
+use sixtyfps_corelib::items::{
+    self, FillRule, ImageFit, ImageRendering, Item, TextDecoration, TextHorizontalAlignment,
+    TextOverflow, TextVerticalAlignment, TextWrap,
+};
+use sixtyfps_corelib::styled_text::StyledText;
+use sixtyfps_corelib::window::{Window, WindowRc};
+use sixtyfps_corelib::ImageInner;
+use std::pin::Pin;
+use std::rc::Rc;
+use tiny_skia::{ClipMask, Paint, PathBuilder, Pixmap, Shader, Transform};
+
+use crate::fonts;
+
+#[derive(Clone)]
+struct RenderState {
+    /// Maps the current local coordinates (in logical pixels) to physical pixels
+    transform: Transform,
+    /// The clip rectangle, in the current local coordinates
+    clip: Rect,
+    /// The mask of the clip in physical pixels. None when nothing was clipped yet.
+    clip_mask: Option<Rc<ClipMask>>,
+    alpha: f32,
+}
+
+pub struct SoftwareRenderer {
+    pixmap: Pixmap,
+    state: RenderState,
+    state_stack: Vec<RenderState>,
+    scale_factor: f32,
+    window: WindowRc,
+}
+
+impl SoftwareRenderer {
+    /// Creates a renderer that renders into a transparent buffer of `width` x `height`
+    /// physical pixels.
+    pub fn new(width: u32, height: u32, scale_factor: f32, window: WindowRc) -> Option<Self> {
+        Some(Self {
+            pixmap: Pixmap::new(width, height)?,
+            state: RenderState {
+                transform: Transform::from_scale(scale_factor, scale_factor),
+                clip: Rect::new(
+                    Point::default(),
+                    Size::new(width as f32 / scale_factor, height as f32 / scale_factor),
+                ),
+                clip_mask: None,
+                alpha: 1.,
+            },
+            state_stack: Vec::new(),
+            scale_factor,
+            window,
+        })
+    }
+
+    /// Fills the whole buffer with the given color.
+    pub fn fill(&mut self, color: Color) {
+        self.pixmap.fill(to_skia_color(color));
+    }
+
+    /// Returns the rendered pixels, with the alpha not premultiplied
+    pub fn into_buffer(self) -> SharedPixelBuffer<Rgba8Pixel> {
+        let mut buffer =
+            SharedPixelBuffer::new(self.pixmap.width() as usize, self.pixmap.height() as usize);
+        for (dst, src) in buffer.make_mut_slice().iter_mut().zip(self.pixmap.pixels()) {
+            let src = src.demultiply();
+            *dst = Rgba8Pixel { r: src.red(), g: src.green(), b: src.blue(), a: src.alpha() };
+        }
+        buffer
+    }
+
+    /// Calls `draw` with a paint that fills with the brush a shape whose bounding box is `bounds`,
+    /// in local coordinates.
+    fn with_brush_paint(
+        &mut self,
+        brush: Brush,
+        bounds: Rect,
+        draw: impl FnOnce(&mut Pixmap, &Paint, Transform, Option<&ClipMask>),
+    ) {
+        if brush.is_transparent() || self.state.clip.is_empty() {
+            return;
+        }
+        let gradient_pixmap;
+        let mut shader = match brush {
+            Brush::SolidColor(color) => Shader::SolidColor(to_skia_color(color)),
+            _ => {
+                // Gradients are evaluated for each physical pixel of the bounding box, so that all
+                // the kinds of gradients are rendered the same way.
+                let width = (bounds.width() * self.scale_factor).ceil().max(1.) as u32;
+                let height = (bounds.height() * self.scale_factor).ceil().max(1.) as u32;
+                gradient_pixmap = match Pixmap::new(width, height) {
+                    Some(mut pixmap) => {
+                        for (i, pixel) in pixmap.pixels_mut().iter_mut().enumerate() {
+                            let point = Point::new(
+                                ((i as u32 % width) as f32 + 0.5) / self.scale_factor,
+                                ((i as u32 / width) as f32 + 0.5) / self.scale_factor,
+                            );
+                            let color = brush.color_at(point, bounds.size);
+                            *pixel = tiny_skia::ColorU8::from_rgba(
+                                color.red(),
+                                color.green(),
+                                color.blue(),
+                                color.alpha(),
+                            )
+                            .premultiply();
+                        }
+                        pixmap
+                    }
+                    None => return,
+                };
+                tiny_skia::Pattern::new(
+                    gradient_pixmap.as_ref(),
+                    tiny_skia::SpreadMode::Pad,
+                    tiny_skia::FilterQuality::Bilinear,
+                    1.,
+                    Transform::from_translate(bounds.origin.x, bounds.origin.y)
+                        .pre_scale(1. / self.scale_factor, 1. / self.scale_factor),
+                )
+            }
+        };
+        shader.apply_opacity(self.state.alpha);
+        let paint = Paint { shader, anti_alias: true, ..Default::default() };
+        draw(&mut self.pixmap, &paint, self.state.transform, self.state.clip_mask.as_deref());
+    }
+
+    fn fill_path(&mut self, path: &tiny_skia::Path, brush: Brush, fill_rule: tiny_skia::FillRule) {
+        let bounds = from_skia_rect(path.bounds());
+        self.with_brush_paint(brush, bounds, |pixmap, paint, transform, clip_mask| {
+            pixmap.fill_path(path, paint, fill_rule, transform, clip_mask);
+        });
+    }
+
+    fn draw_image_impl(
+        &mut self,
+        source: Image,
+        source_clip: Option<tiny_skia::IntRect>,
+        size: Size,
+        image_fit: ImageFit,
+        image_rendering: ImageRendering,
+        colorize: Brush,
+    ) {
+        if self.state.clip.is_empty() {
+            return;
+        }
+        let mut image = match load_image(&source) {
+            Some(image) => image,
+            None => return,
+        };
+        if !colorize.is_transparent() {
+            let color = colorize.color();
+            for pixel in image.pixels_mut() {
+                let alpha = (color.alpha() as u16 * pixel.alpha() as u16 / 255) as u8;
+                *pixel =
+                    tiny_skia::ColorU8::from_rgba(color.red(), color.green(), color.blue(), alpha)
+                        .premultiply();
+            }
+        }
+        let source_clip = match source_clip {
+            Some(clip) => tiny_skia::IntRect::from_ltrb(
+                clip.left().max(0),
+                clip.top().max(0),
+                clip.right().min(image.width() as i32),
+                clip.bottom().min(image.height() as i32),
+            ),
+            None => tiny_skia::IntRect::from_xywh(0, 0, image.width(), image.height()),
+        };
+        let source_clip = match source_clip {
+            Some(clip) => clip,
+            None => return,
+        };
+
+        let scale_x = size.width / source_clip.width() as f32;
+        let scale_y = size.height / source_clip.height() as f32;
+        let (scale_x, scale_y) = match image_fit {
+            ImageFit::fill => (scale_x, scale_y),
+            ImageFit::contain => (scale_x.min(scale_y), scale_x.min(scale_y)),
+            ImageFit::cover => (scale_x.max(scale_y), scale_x.max(scale_y)),
+        };
+        let target_size =
+            Size::new(source_clip.width() as f32 * scale_x, source_clip.height() as f32 * scale_y);
+        let target_origin = Point::new(
+            (size.width - target_size.width) / 2.,
+            (size.height - target_size.height) / 2.,
+        );
+        let target = match Rect::new(target_origin, target_size)
+            .intersection(&Rect::new(Point::default(), size))
+            .and_then(|r| tiny_skia::Rect::from_xywh(r.origin.x, r.origin.y, r.width(), r.height()))
+        {
+            Some(target) => target,
+            None => return,
+        };
+
+        let paint = Paint {
+            shader: tiny_skia::Pattern::new(
+                image.as_ref(),
+                tiny_skia::SpreadMode::Pad,
+                match image_rendering {
+                    ImageRendering::smooth => tiny_skia::FilterQuality::Bilinear,
+                    ImageRendering::pixelated => tiny_skia::FilterQuality::Nearest,
+                },
+                self.state.alpha,
+                Transform::from_translate(target_origin.x, target_origin.y)
+                    .pre_scale(scale_x, scale_y)
+                    .pre_translate(-source_clip.x() as f32, -source_clip.y() as f32),
+            ),
+            anti_alias: true,
+            ..Default::default()
+        };
+        self.pixmap.fill_rect(
+            target,
+            &paint,
+            self.state.transform,
+            self.state.clip_mask.as_deref(),
+        );
+    }
+
+    /// Renders the text laid out in a box of the given size. The characters whose byte offset
+    /// is within `selection` are drawn with the `selection_color`.
+    /// Returns the position of the top of the line and the line height for each byte offset in
+    /// `positions_of`.
+    #[allow(clippy::too_many_arguments)]
+    fn draw_text_impl(
+        &mut self,
+        text: &str,
+        font_request: sixtyfps_corelib::graphics::FontRequest,
+        size: Size,
+        color: Brush,
+        horizontal_alignment: TextHorizontalAlignment,
+        vertical_alignment: TextVerticalAlignment,
+        wrap: TextWrap,
+        overflow: TextOverflow,
+        max_lines: usize,
+        selection: Option<(core::ops::Range<usize>, Color, Color)>,
+        underline: core::ops::Range<usize>,
+        cursor: Option<usize>,
+    ) -> Option<(Point, f32)> {
+        let mut text_path = PathBuilder::new();
+        let mut selected_path = PathBuilder::new();
+        let mut selection_rects = PathBuilder::new();
+        let mut underline_rects = PathBuilder::new();
+        let mut cursor_position = None;
+        let text_decoration = font_request.text_decoration;
+        let line_height = fonts::with_font(&font_request, |font| {
+            let mut lines = font.layout_lines(text, size.width, wrap == TextWrap::word_wrap);
+            let elide_last_line = max_lines > 0 && lines.len() > max_lines;
+            if elide_last_line {
+                lines.truncate(max_lines);
+            }
+            let line_count = lines.len();
+            let line_height = font_request.line_height.unwrap_or_else(|| font.line_height());
+            // The glyphs are centered vertically in the line
+            let glyph_offset = (line_height - font.line_height()) / 2.;
+            let text_height = lines.len() as f32 * line_height;
+            let mut y = match vertical_alignment {
+                TextVerticalAlignment::top => 0.,
+                TextVerticalAlignment::center => (size.height - text_height) / 2.,
+                TextVerticalAlignment::bottom => size.height - text_height,
+            };
+            for (line_index, line) in lines.into_iter().enumerate() {
+                let line_offset = line.as_ptr() as usize - text.as_ptr() as usize;
+                let mut chars = line.char_indices().collect::<Vec<_>>();
+                let mut elided = false;
+                if overflow == TextOverflow::elide {
+                    let ellipsis_width = font.advance('…');
+                    if elide_last_line && line_index + 1 == line_count {
+                        let mut width = ellipsis_width;
+                        let visible = chars
+                            .iter()
+                            .take_while(|(_, c)| {
+                                width += font.advance(*c);
+                                width <= size.width
+                            })
+                            .count();
+                        chars.truncate(visible);
+                        elided = true;
+                    } else if font.text_width(line) > size.width {
+                        let mut width = ellipsis_width;
+                        let visible = chars
+                            .iter()
+                            .take_while(|(_, c)| {
+                                width += font.advance(*c);
+                                width <= size.width
+                            })
+                            .count();
+                        chars.truncate(visible);
+                        elided = true;
+                    }
+                }
+                let line_width = chars.iter().map(|(_, c)| font.advance(*c)).sum::<f32>()
+                    + if elided { font.advance('…') } else { 0. };
+                let mut x = match horizontal_alignment {
+                    TextHorizontalAlignment::left => 0.,
+                    TextHorizontalAlignment::center => (size.width - line_width) / 2.,
+                    TextHorizontalAlignment::right => size.width - line_width,
+                };
+                let baseline = y + glyph_offset + font.ascent();
+                for (pos, c) in chars.iter().copied().chain(if elided {
+                    Some((line.len(), '…'))
+                } else {
+                    None
+                }) {
+                    let offset = line_offset + pos;
+                    if cursor == Some(offset) {
+                        cursor_position = Some(Point::new(x, y));
+                    }
+                    let advance = font.advance(c);
+                    if underline.contains(&offset) || text_decoration == TextDecoration::underline {
+                        underline_rects.push_rect(x, baseline + 1., advance, 1.);
+                    } else if text_decoration == TextDecoration::strikethrough {
+                        underline_rects.push_rect(x, baseline - font.ascent() / 3., advance, 1.);
+                    }
+                    match &selection {
+                        Some((range, ..)) if range.contains(&offset) && !elided => {
+                            selection_rects.push_rect(x, y, advance, line_height);
+                            font.append_glyph(c, x, baseline, &mut selected_path);
+                        }
+                        _ => font.append_glyph(c, x, baseline, &mut text_path),
+                    }
+                    x += advance;
+                }
+                if cursor == Some(line_offset + line.len()) && cursor_position.is_none() {
+                    cursor_position = Some(Point::new(x, y));
+                }
+                y += line_height;
+            }
+            line_height
+        });
+
+        if let Some((_, selection_foreground, selection_background)) = selection {
+            if let Some(path) = selection_rects.finish() {
+                self.fill_path(&path, selection_background.into(), tiny_skia::FillRule::Winding);
+            }
+            if let Some(path) = selected_path.finish() {
+                self.fill_path(&path, selection_foreground.into(), tiny_skia::FillRule::Winding);
+            }
+        }
+        if let Some(path) = underline_rects.finish() {
+            self.fill_path(&path, color.clone(), tiny_skia::FillRule::Winding);
+        }
+        if let Some(path) = text_path.finish() {
+            self.fill_path(&path, color, tiny_skia::FillRule::Winding);
+        }
+        cursor_position.map(|pos| (pos, line_height))
+    }
+
+    /// Draws the `styled-text` of a `Text` element, each run with its own font and color.
+    /// The text is wrapped like in the layout, but not elided.
+    fn draw_styled_text(&mut self, text: Pin<&items::Text>, styled_text: &StyledText) {
+        let font_request =
+            text.unresolved_font_request().merge(&self.window.default_font_properties());
+        let size = text.geometry().size;
+        let (font_line_height, ascent) =
+            fonts::with_font(&font_request, |font| (font.line_height(), font.ascent()));
+        let line_height = font_request.line_height.unwrap_or(font_line_height);
+        let ascent = ascent + (line_height - font_line_height) / 2.;
+
+        let span_font_requests = styled_text
+            .spans
+            .iter()
+            .map(|span| span.style.font_request(font_request.clone()))
+            .collect::<Vec<_>>();
+        let lines = styled_text.layout_lines(
+            (text.wrap() == TextWrap::word_wrap).then(|| size.width),
+            |span_index, word| {
+                fonts::with_font(&span_font_requests[span_index], |font| font.text_width(word))
+            },
+        );
+
+        let text_height = lines.len() as f32 * line_height;
+        let mut y = match text.vertical_alignment() {
+            TextVerticalAlignment::top => 0.,
+            TextVerticalAlignment::center => (size.height - text_height) / 2.,
+            TextVerticalAlignment::bottom => size.height - text_height,
+        };
+        for line in lines {
+            let line_width = line.last().map_or(0., |run| run.x + run.width);
+            let line_x = match text.visual_horizontal_alignment(&self.window) {
+                TextHorizontalAlignment::left => 0.,
+                TextHorizontalAlignment::center => (size.width - line_width) / 2.,
+                TextHorizontalAlignment::right => size.width - line_width,
+            };
+            let baseline = y + ascent;
+            for run in line {
+                let style = &styled_text.spans[run.span_index].style;
+                let x = line_x + run.x;
+                let mut path = PathBuilder::new();
+                fonts::with_font(&span_font_requests[run.span_index], |font| {
+                    let mut glyph_x = x;
+                    for c in styled_text.text[run.range.clone()].chars() {
+                        font.append_glyph(c, glyph_x, baseline, &mut path);
+                        glyph_x += font.advance(c);
+                    }
+                });
+                if style.underline && run.width > 0. {
+                    path.push_rect(x, baseline + 1., run.width, 1.);
+                }
+                if let Some(path) = path.finish() {
+                    let brush = style.color.map_or_else(|| text.color(), Brush::SolidColor);
+                    self.fill_path(&path, brush, tiny_skia::FillRule::Winding);
+                }
+            }
+            y += line_height;
+        }
+    }
+}
+
+impl ItemRenderer for SoftwareRenderer {
+    fn draw_rectangle(&mut self, rect: Pin<&items::Rectangle>) {
+        let geometry = rect.geometry();
+        if let Some(path) = rect_path(0., 0., geometry.width(), geometry.height(), 0.) {
+            self.fill_path(&path, rect.background(), tiny_skia::FillRule::Winding);
+        }
+    }
+
+    fn draw_border_rectangle(&mut self, rect: Pin<&items::BorderRectangle>) {
+        let geometry = rect.geometry();
+        let (width, height) = (geometry.width(), geometry.height());
+        let radius = rect.border_radius().min(width / 2.).min(height / 2.).max(0.);
+        // The border is entirely drawn inside of the rectangle
+        let border_width = rect.border_width().min(width / 2.).min(height / 2.).max(0.);
+        let inner = rect_path(
+            border_width,
+            border_width,
+            width - 2. * border_width,
+            height - 2. * border_width,
+            (radius - border_width).max(0.),
+        );
+        if let Some(inner) = &inner {
+            self.fill_path(inner, rect.background(), tiny_skia::FillRule::Winding);
+        }
+        if border_width > 0. {
+            let mut border = PathBuilder::new();
+            if let Some(outer) = rect_path(0., 0., width, height, radius) {
+                border.push_path(&outer);
+            }
+            if let Some(inner) = &inner {
+                border.push_path(inner);
+            }
+            if let Some(border) = border.finish() {
+                self.fill_path(&border, rect.border_color(), tiny_skia::FillRule::EvenOdd);
+            }
+        }
+    }
+
+    fn draw_image(&mut self, image: Pin<&items::ImageItem>) {
+        let geometry = image.geometry();
+        self.draw_image_impl(
+            image.source(),
+            None,
+            geometry.size,
+            image.image_fit(),
+            image.image_rendering(),
+            Brush::default(),
+        );
+    }
+
+    fn draw_clipped_image(&mut self, image: Pin<&items::ClippedImage>) {
+        let geometry = image.geometry();
+        let source_clip = if image.source_clip_width() > 0 && image.source_clip_height() > 0 {
+            tiny_skia::IntRect::from_xywh(
+                image.source_clip_x(),
+                image.source_clip_y(),
+                image.source_clip_width() as u32,
+                image.source_clip_height() as u32,
+            )
+        } else {
+            None
+        };
+        self.draw_image_impl(
+            image.source(),
+            source_clip,
+            geometry.size,
+            image.image_fit(),
+            image.image_rendering(),
+            image.colorize(),
+        );
+    }
+
+    fn draw_text(&mut self, text: Pin<&items::Text>) {
+        if let Some(styled_text) = text.parsed_styled_text() {
+            return self.draw_styled_text(text, &styled_text);
+        }
+        let font_request =
+            text.unresolved_font_request().merge(&self.window.default_font_properties());
+        self.draw_text_impl(
+            text.text().as_str(),
+            font_request,
+            text.geometry().size,
+            text.color(),
+            text.visual_horizontal_alignment(&self.window),
+            text.vertical_alignment(),
+            text.wrap(),
+            text.overflow(),
+            text.max_lines().max(0) as usize,
+            None,
+            0..0,
+            None,
+        );
+    }
+
+    fn draw_text_input(&mut self, text_input: Pin<&items::TextInput>) {
+        let font_request =
+            text_input.unresolved_font_request().merge(&self.window.default_font_properties());
+        let visual_representation = text_input.visual_representation();
+        let (anchor, cursor) = visual_representation.selection_anchor_and_cursor();
+        let cursor_visible = text_input.cursor_visible() && text_input.enabled();
+        let color = text_input.color();
+        let cursor_position = self.draw_text_impl(
+            visual_representation.text.as_str(),
+            font_request,
+            text_input.geometry().size,
+            color.clone(),
+            text_input.visual_horizontal_alignment(&self.window),
+            text_input.vertical_alignment(),
+            text_input.wrap(),
+            TextOverflow::clip,
+            0,
+            if anchor != cursor {
+                Some((
+                    anchor..cursor,
+                    text_input.selection_foreground_color(),
+                    text_input.selection_background_color(),
+                ))
+            } else {
+                None
+            },
+            visual_representation.preedit_range.clone(),
+            cursor_visible.then(|| visual_representation.cursor_position.max(0) as usize),
+        );
+        if let Some((pos, line_height)) = cursor_position {
+            if let Some(path) =
+                rect_path(pos.x, pos.y, text_input.text_cursor_width(), line_height, 0.)
+            {
+                self.fill_path(&path, color, tiny_skia::FillRule::Winding);
+            }
+        }
+    }
+
+    fn draw_path(&mut self, path: Pin<&items::Path>) {
+        let (offset, path_events) = path.fitted_path_events();
+        let mut builder = PathBuilder::new();
+        for event in path_events.iter() {
+            match event {
+                lyon_path::Event::Begin { at } => {
+                    builder.move_to(at.x + offset.x, at.y + offset.y);
+                }
+                lyon_path::Event::Line { to, .. } => {
+                    builder.line_to(to.x + offset.x, to.y + offset.y);
+                }
+                lyon_path::Event::Quadratic { ctrl, to, .. } => {
+                    builder.quad_to(
+                        ctrl.x + offset.x,
+                        ctrl.y + offset.y,
+                        to.x + offset.x,
+                        to.y + offset.y,
+                    );
+                }
+                lyon_path::Event::Cubic { ctrl1, ctrl2, to, .. } => {
+                    builder.cubic_to(
+                        ctrl1.x + offset.x,
+                        ctrl1.y + offset.y,
+                        ctrl2.x + offset.x,
+                        ctrl2.y + offset.y,
+                        to.x + offset.x,
+                        to.y + offset.y,
+                    );
+                }
+                lyon_path::Event::End { close, .. } => {
+                    if close {
+                        builder.close()
+                    }
+                }
+            }
+        }
+        let skia_path = match builder.finish() {
+            Some(path) => path,
+            None => return,
+        };
+        let fill_rule = match path.fill_rule() {
+            FillRule::nonzero => tiny_skia::FillRule::Winding,
+            FillRule::evenodd => tiny_skia::FillRule::EvenOdd,
+        };
+        self.fill_path(&skia_path, path.fill(), fill_rule);
+
+        let stroke_width = path.stroke_width();
+        if stroke_width > 0. {
+            let bounds =
+                from_skia_rect(skia_path.bounds()).inflate(stroke_width / 2., stroke_width / 2.);
+            let stroke = tiny_skia::Stroke { width: stroke_width, ..Default::default() };
+            self.with_brush_paint(path.stroke(), bounds, |pixmap, paint, transform, clip_mask| {
+                pixmap.stroke_path(&skia_path, paint, &stroke, transform, clip_mask);
+            });
+        }
+    }
+
+    /// The blur is approximated by layering translucent rectangles of increasing size.
+    fn draw_box_shadow(&mut self, box_shadow: Pin<&items::BoxShadow>) {
+        let color = box_shadow.color();
+        if color.alpha() == 0 {
+            return;
+        }
+        let blur = box_shadow.blur().max(0.);
+        let layers = (blur.ceil() as u32).max(1).min(32);
+        let layer_color = Color::from_argb_u8(
+            (color.alpha() as u32 / layers).max(1) as u8,
+            color.red(),
+            color.green(),
+            color.blue(),
+        );
+        for layer in 0..layers {
+            let spread =
+                if layers > 1 { blur * (layer as f32 / (layers - 1) as f32 - 0.5) } else { 0. };
+            if let Some(path) = rect_path(
+                box_shadow.offset_x() - spread,
+                box_shadow.offset_y() - spread,
+                box_shadow.width() + 2. * spread,
+                box_shadow.height() + 2. * spread,
+                (box_shadow.border_radius() + spread).max(0.),
+            ) {
+                self.fill_path(&path, layer_color.into(), tiny_skia::FillRule::Winding);
+            }
+        }
+    }
+
+    fn combine_clip(&mut self, rect: Rect, radius: f32, border_width: f32) {
+        self.state.clip = self.state.clip.intersection(&rect).unwrap_or_default();
+        // The clip is the area inside of the border
+        let path = match rect_path(
+            rect.origin.x + border_width,
+            rect.origin.y + border_width,
+            rect.width() - 2. * border_width,
+            rect.height() - 2. * border_width,
+            (radius - border_width).max(0.),
+        )
+        .and_then(|path| path.transform(self.state.transform))
+        {
+            Some(path) => path,
+            None => {
+                // Nothing is visible anymore
+                self.state.clip = Rect::default();
+                return;
+            }
+        };
+        let mask = match self.state.clip_mask.take() {
+            Some(mask) => {
+                let mut mask = Rc::try_unwrap(mask).unwrap_or_else(|mask| (*mask).clone());
+                mask.intersect_path(&path, tiny_skia::FillRule::Winding, true);
+                mask
+            }
+            None => {
+                let mut mask = ClipMask::new();
+                mask.set_path(
+                    self.pixmap.width(),
+                    self.pixmap.height(),
+                    &path,
+                    tiny_skia::FillRule::Winding,
+                    true,
+                );
+                mask
+            }
+        };
+        self.state.clip_mask = Some(Rc::new(mask));
+    }
+
+    fn get_current_clip(&self) -> Rect {
+        self.state.clip
+    }
+
+    fn translate(&mut self, x: f32, y: f32) {
+        self.state.transform = self.state.transform.pre_translate(x, y);
+        self.state.clip = self.state.clip.translate(euclid::vec2(-x, -y));
+    }
+
+    fn rotate(&mut self, angle_in_degrees: f32) {
+        self.state.transform =
+            self.state.transform.pre_concat(Transform::from_rotate(angle_in_degrees));
+        // The clip becomes the bounding box of the previous clip in the rotated coordinates
+        let mut corners = [
+            tiny_skia::Point::from_xy(self.state.clip.min_x(), self.state.clip.min_y()),
+            tiny_skia::Point::from_xy(self.state.clip.max_x(), self.state.clip.min_y()),
+            tiny_skia::Point::from_xy(self.state.clip.min_x(), self.state.clip.max_y()),
+            tiny_skia::Point::from_xy(self.state.clip.max_x(), self.state.clip.max_y()),
+        ];
+        Transform::from_rotate(-angle_in_degrees).map_points(&mut corners);
+        self.state.clip = Rect::from_points(corners.iter().map(|p| Point::new(p.x, p.y)));
+    }
+
+    fn apply_opacity(&mut self, opacity: f32) {
+        self.state.alpha *= opacity;
+    }
+
+    fn save_state(&mut self) {
+        self.state_stack.push(self.state.clone());
+    }
+
+    fn restore_state(&mut self) {
+        if let Some(state) = self.state_stack.pop() {
+            self.state = state;
+        }
+    }
+
+    fn scale_factor(&self) -> f32 {
+        self.scale_factor
+    }
+
+    fn draw_cached_pixmap(
+        &mut self,
+        _item_cache: &CachedRenderingData,
+        update_fn: &dyn Fn(&mut dyn FnMut(u32, u32, &[u8])),
+    ) {
+        if self.state.clip.is_empty() {
+            return;
+        }
+        // Nothing is cached, the pixmap is rendered again each time.
+        update_fn(&mut |width, height, data| {
+            if let Some(pixmap) = tiny_skia::PixmapRef::from_bytes(data, width, height) {
+                let paint =
+                    tiny_skia::PixmapPaint { opacity: self.state.alpha, ..Default::default() };
+                self.pixmap.draw_pixmap(
+                    0,
+                    0,
+                    pixmap,
+                    &paint,
+                    self.state.transform.pre_scale(1. / self.scale_factor, 1. / self.scale_factor),
+                    self.state.clip_mask.as_deref(),
+                );
+            }
+        });
+    }
+
+    fn scale(&mut self, x_factor: f32, y_factor: f32) {
+        if is_degenerate_transform(x_factor, y_factor, 0., 0.) {
+            // Nothing is visible, and the clip can't be mapped back
+            self.state.clip = Rect::default();
+            return;
+        }
+        self.state.transform = self.state.transform.pre_scale(x_factor, y_factor);
+        let mut corners = [
+            tiny_skia::Point::from_xy(self.state.clip.min_x(), self.state.clip.min_y()),
+            tiny_skia::Point::from_xy(self.state.clip.max_x(), self.state.clip.max_y()),
+        ];
+        Transform::from_scale(1. / x_factor, 1. / y_factor).map_points(&mut corners);
+        self.state.clip = Rect::from_points(corners.iter().map(|p| Point::new(p.x, p.y)));
+    }
+
+    fn skew(&mut self, x_angle_in_degrees: f32, y_angle_in_degrees: f32) {
+        if is_degenerate_transform(1., 1., x_angle_in_degrees, y_angle_in_degrees) {
+            // Nothing is visible, and the clip can't be mapped back
+            self.state.clip = Rect::default();
+            return;
+        }
+        let skew = Transform::from_row(
+            1.,
+            y_angle_in_degrees.to_radians().tan(),
+            x_angle_in_degrees.to_radians().tan(),
+            1.,
+            0.,
+            0.,
+        );
+        self.state.transform = self.state.transform.pre_concat(skew);
+        let mut corners = [
+            tiny_skia::Point::from_xy(self.state.clip.min_x(), self.state.clip.min_y()),
+            tiny_skia::Point::from_xy(self.state.clip.max_x(), self.state.clip.min_y()),
+            tiny_skia::Point::from_xy(self.state.clip.min_x(), self.state.clip.max_y()),
+            tiny_skia::Point::from_xy(self.state.clip.max_x(), self.state.clip.max_y()),
+        ];
+        if let Some(inverse) = skew.invert() {
+            inverse.map_points(&mut corners);
+        }
+        self.state.clip = Rect::from_points(corners.iter().map(|p| Point::new(p.x, p.y)));
+    }
+
+    fn window(&self) -> WindowRc {
+        self.window.clone()
+    }
+
+    fn as_any(&mut self) -> &mut dyn core::any::Any {
+        self
+    }
+}
+
+fn to_skia_color(color: Color) -> tiny_skia::Color {
+    tiny_skia::Color::from_rgba8(color.red(), color.green(), color.blue(), color.alpha())
+}
+
+fn from_skia_rect(rect: tiny_skia::Rect) -> Rect {
+    euclid::rect(rect.x(), rect.y(), rect.width(), rect.height())
+}
+
+/// Returns the path of a rectangle with rounded corners
+fn rect_path(x: f32, y: f32, width: f32, height: f32, radius: f32) -> Option<tiny_skia::Path> {
+    if width <= 0. || height <= 0. {
+        return None;
+    }
+    let radius = radius.min(width / 2.).min(height / 2.);
+    if radius <= 0. {
+        return Some(PathBuilder::from_rect(tiny_skia::Rect::from_xywh(x, y, width, height)?));
+    }
+    // Distance of the control points of the bezier curve that approximates a quarter circle
+    const KAPPA90: f32 = 0.5522847493;
+    let k = radius * (1. - KAPPA90);
+    let (right, bottom) = (x + width, y + height);
+    let mut builder = PathBuilder::new();
+    builder.move_to(x + radius, y);
+    builder.line_to(right - radius, y);
+    builder.cubic_to(right - k, y, right, y + k, right, y + radius);
+    builder.line_to(right, bottom - radius);
+    builder.cubic_to(right, bottom - k, right - k, bottom, right - radius, bottom);
+    builder.line_to(x + radius, bottom);
+    builder.cubic_to(x + k, bottom, x, bottom - k, x, bottom - radius);
+    builder.line_to(x, y + radius);
+    builder.cubic_to(x, y + k, x + k, y, x + radius, y);
+    builder.close();
+    builder.finish()
+}
+
+/// Decodes the image into a pixmap with premultiplied alpha
+fn load_image(image: &Image) -> Option<Pixmap> {
+    let from_rgba = |width: u32, height: u32, pixels: &mut dyn Iterator<Item = [u8; 4]>| {
+        let mut pixmap = Pixmap::new(width, height)?;
+        for (dst, [r, g, b, a]) in pixmap.pixels_mut().iter_mut().zip(pixels) {
+            *dst = tiny_skia::ColorU8::from_rgba(r, g, b, a).premultiply();
+        }
+        Some(pixmap)
+    };
+    let from_dynamic_image = |image: image::DynamicImage| {
+        let image = image.into_rgba8();
+        from_rgba(image.width(), image.height(), &mut image.pixels().map(|p| p.0))
+    };
+    let inner: &ImageInner = image.into();
+    match inner {
+        ImageInner::AbsoluteFilePath(path) => {
+            image::open(std::path::Path::new(path.as_str())).ok().and_then(from_dynamic_image)
+        }
+        ImageInner::EmbeddedData { data, format } => image::load_from_memory_with_format(
+            data.as_slice(),
+            image::ImageFormat::from_extension(std::str::from_utf8(format.as_slice()).ok()?)?,
+        )
+        .ok()
+        .and_then(from_dynamic_image),
+        ImageInner::EmbeddedImage(SharedImageBuffer::RGB8(buffer)) => from_rgba(
+            buffer.width() as u32,
+            buffer.height() as u32,
+            &mut buffer.as_slice().iter().map(|p| [p.r, p.g, p.b, 255]),
+        ),
+        ImageInner::EmbeddedImage(SharedImageBuffer::RGBA8(buffer)) => from_rgba(
+            buffer.width() as u32,
+            buffer.height() as u32,
+            &mut buffer.as_slice().iter().map(|p| [p.r, p.g, p.b, p.a]),
+        ),
+        ImageInner::EmbeddedImage(SharedImageBuffer::RGBA8Premultiplied(buffer)) => {
+            let mut pixmap = Pixmap::new(buffer.width() as u32, buffer.height() as u32)?;
+            pixmap.data_mut().copy_from_slice(buffer.as_bytes());
+            Some(pixmap)
+        }
+        ImageInner::None | ImageInner::StaticTextures { .. } => None,
+    }
+}
+
+/// Renders the content of the window into a buffer with the size of the window item,
+/// in physical pixels.
+pub fn render_window(window: &Rc<Window>) -> Option<SharedPixelBuffer<Rgba8Pixel>> {
+    let component_rc = window.try_component()?;
+    let component = sixtyfps_corelib::component::ComponentRc::borrow_pin(&component_rc);
+    let root_item = component.as_ref().get_item_ref(0);
+    let size = root_item.as_ref().geometry().size;
+    let background = sixtyfps_corelib::items::ItemRef::downcast_pin::<items::WindowItem>(root_item)
+        .map_or(Color::default(), |window_item| window_item.background());
+    let scale_factor = window.scale_factor();
+
+    let mut renderer = SoftwareRenderer::new(
+        (size.width * scale_factor).ceil() as u32,
+        (size.height * scale_factor).ceil() as u32,
+        scale_factor,
+        window.clone(),
+    )?;
+    renderer.fill(background);
+    window.clone().draw_contents(|components| {
+        for (component, origin) in components {
+            sixtyfps_corelib::item_rendering::render_component_items(
+                component,
+                &mut renderer,
+                *origin,
+            );
+        }
+    });
+    Some(renderer.into_buffer())
+}
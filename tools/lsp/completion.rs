@@ -67,6 +67,7 @@ pub(crate) fn completion_at(
                 [
                     ("property", "property <$1> $2;"),
                     ("callback", "callback $1();"),
+                    ("function", "function $1() { $2 }"),
                     ("animate", "animate $1 { $2 }"),
                     ("states", "states [ $1 ]"),
                     ("transitions", "transitions [ $1 ]"),
@@ -268,6 +269,15 @@ fn resolve_element_scope(
                 c.kind = Some(CompletionItemKind::METHOD);
                 c
             }))
+            .chain(element.Function().map(|f| {
+                let mut c = CompletionItem::new_simple(
+                    sixtyfps_compilerlib::parser::identifier_text(&f.DeclaredIdentifier())
+                        .unwrap_or_default(),
+                    "function".into(),
+                );
+                c.kind = Some(CompletionItemKind::FUNCTION);
+                c
+            }))
             .chain(sixtyfps_compilerlib::typeregister::reserved_properties().filter_map(
                 |(k, t)| {
                     if matches!(t, Type::Function { .. }) {
@@ -314,6 +324,7 @@ fn completion_item_from_expression(str: &str, lookup_result: LookupResult) -> Co
                 Expression::BoolLiteral(_) => Some(CompletionItemKind::CONSTANT),
                 Expression::CallbackReference(_) => Some(CompletionItemKind::METHOD),
                 Expression::PropertyReference(_) => Some(CompletionItemKind::PROPERTY),
+                Expression::FunctionReference(_) => Some(CompletionItemKind::FUNCTION),
                 Expression::BuiltinFunctionReference(..) => Some(CompletionItemKind::FUNCTION),
                 Expression::BuiltinMacroReference(..) => Some(CompletionItemKind::FUNCTION),
                 Expression::ElementReference(_) => Some(CompletionItemKind::CLASS),
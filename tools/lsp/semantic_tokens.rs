@@ -30,7 +30,7 @@ macro_rules! declare_legend {
     (@ [], $n:expr) => {};
 }
 // the id of the element
-declare_legend!(LEGEND_TYPES : SemanticTokenType = [TYPE PARAMETER VARIABLE PROPERTY FUNCTION MACRO KEYWORD COMMENT STRING NUMBER OPERATOR]);
+declare_legend!(LEGEND_TYPES : SemanticTokenType = [TYPE PARAMETER VARIABLE PROPERTY FUNCTION MACRO KEYWORD COMMENT STRING NUMBER OPERATOR ENUM_MEMBER]);
 declare_legend!(LEGEND_MODS: SemanticTokenModifier = [DEFINITION DECLARATION]);
 
 pub fn get_semantic_tokens(
@@ -92,6 +92,7 @@ pub fn get_semantic_tokens(
                             None
                         }
                         SyntaxKind::StructDeclaration => Some((self::TYPE, 1 << self::DEFINITION)),
+                        SyntaxKind::EnumDeclaration => Some((self::TYPE, 1 << self::DEFINITION)),
                         _ => None,
                     }
                 }
@@ -100,6 +101,9 @@ pub fn get_semantic_tokens(
                 SyntaxKind::ReturnStatement => Some((self::KEYWORD, 0)),
                 SyntaxKind::AtImageUrl => Some((self::MACRO, 0)),
                 SyntaxKind::AtLinearGradient => Some((self::MACRO, 0)),
+                SyntaxKind::AtRadialGradient => Some((self::MACRO, 0)),
+                SyntaxKind::AtConicGradient => Some((self::MACRO, 0)),
+                SyntaxKind::AtTr => Some((self::MACRO, 0)),
                 SyntaxKind::ConditionalExpression => Some((self::KEYWORD, 0)),
                 SyntaxKind::ObjectMember => Some((self::PROPERTY, 1 << self::DECLARATION)),
                 SyntaxKind::States => Some((self::KEYWORD, 0)),
@@ -134,6 +138,8 @@ pub fn get_semantic_tokens(
                 SyntaxKind::InternalName => Some((self::TYPE, 1 << self::DECLARATION)),
                 SyntaxKind::ObjectTypeMember => Some((self::PROPERTY, 1 << self::DEFINITION)),
                 SyntaxKind::StructDeclaration => Some((self::KEYWORD, 0)),
+                SyntaxKind::EnumDeclaration => Some((self::KEYWORD, 0)),
+                SyntaxKind::EnumValue => Some((self::ENUM_MEMBER, 1 << self::DEFINITION)),
                 _ => None,
             },
             SyntaxKind::PlusEqual
@@ -145,7 +151,9 @@ pub fn get_semantic_tokens(
             | SyntaxKind::EqualEqual
             | SyntaxKind::NotEqual
             | SyntaxKind::OrOr
-            | SyntaxKind::AndAnd => Some((self::OPERATOR, 0)),
+            | SyntaxKind::AndAnd
+            | SyntaxKind::Pipe
+            | SyntaxKind::Percent => Some((self::OPERATOR, 0)),
             SyntaxKind::LAngle | SyntaxKind::RAngle => (token.parent()?.kind()
                 == SyntaxKind::PropertyDeclaration)
                 .then(|| (self::OPERATOR, 0)),
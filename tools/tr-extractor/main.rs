@@ -0,0 +1,159 @@
+/* LICENSE BEGIN
+    This file is part of the SixtyFPS Project -- https://sixtyfps.io
+    Copyright (c) 2021 Olivier Goffart <olivier.goffart@sixtyfps.io>
+    Copyright (c) 2021 Simon Hausmann <simon.hausmann@sixtyfps.io>
+
+    SPDX-License-Identifier: GPL-3.0-only
+    This file is also available under commercial licensing terms.
+    Please contact info@sixtyfps.io for more information.
+LICENSE END */
+//!
+//! Tool to extract the strings marked with `@tr(...)` from .60 files into a gettext `.pot` file
+//!
+//! This is how it can be used:
+//!
+//! ````shell
+//! cargo run --bin sixtyfps-tr-extractor -- -o messages.pot **/*.60
+//! ````
+//!
+//! The resulting file can be used to create or update the `.po` files of each language with
+//! the usual gettext tools, such as `msginit` or `msgmerge`.
+
+use sixtyfps_compilerlib::diagnostics::BuildDiagnostics;
+use sixtyfps_compilerlib::parser::{syntax_nodes, SyntaxKind, SyntaxNode};
+use std::collections::HashMap;
+use std::io::Write;
+use structopt::StructOpt;
+
+#[derive(StructOpt)]
+struct Cli {
+    #[structopt(name = "path to .60 file(s)", parse(from_os_str))]
+    paths: Vec<std::path::PathBuf>,
+
+    /// Write the .pot file to this path instead of printing it to stdout
+    #[structopt(short, long, name = "output", parse(from_os_str))]
+    output: Option<std::path::PathBuf>,
+}
+
+/// A message to translate
+#[derive(Default)]
+struct Message {
+    context: String,
+    text: String,
+    plural: Option<String>,
+    /// `file:line` of each use of the message
+    references: Vec<String>,
+}
+
+#[derive(Default)]
+struct Messages {
+    messages: Vec<Message>,
+    /// Index in `messages` by context and text
+    index: HashMap<(String, String), usize>,
+}
+
+impl Messages {
+    fn add(&mut self, context: String, text: String, plural: Option<String>, reference: String) {
+        let messages = &mut self.messages;
+        let index = *self.index.entry((context.clone(), text.clone())).or_insert_with(|| {
+            messages.push(Message { context, text, plural: None, references: vec![] });
+            messages.len() - 1
+        });
+        let message = &mut self.messages[index];
+        if message.plural.is_none() {
+            message.plural = plural;
+        }
+        message.references.push(reference);
+    }
+}
+
+fn main() -> std::io::Result<()> {
+    let args = Cli::from_args();
+
+    let mut messages = Messages::default();
+    let mut has_error = false;
+    for path in &args.paths {
+        let source = std::fs::read_to_string(path)?;
+        let mut diag = BuildDiagnostics::default();
+        let syntax_node =
+            sixtyfps_compilerlib::parser::parse(source.clone(), Some(path), &mut diag);
+        if diag.has_error() {
+            diag.print();
+            has_error = true;
+            continue;
+        }
+        visit_node(syntax_node, &path.to_string_lossy(), &source, &mut messages);
+    }
+    if has_error {
+        std::process::exit(1);
+    }
+
+    match &args.output {
+        Some(output) => write_pot(&messages, std::fs::File::create(output)?),
+        None => write_pot(&messages, std::io::stdout()),
+    }
+}
+
+fn visit_node(node: SyntaxNode, file_name: &str, source: &str, messages: &mut Messages) {
+    for n in node.node.descendants().filter(|n| n.kind() == SyntaxKind::AtTr) {
+        let tr =
+            syntax_nodes::AtTr::from(SyntaxNode { node: n, source_file: node.source_file.clone() });
+        let unescape = |n: &SyntaxNode| {
+            n.child_text(SyntaxKind::StringLiteral)
+                .and_then(|s| sixtyfps_compilerlib::literals::unescape_string(&s))
+        };
+        let text = match unescape(&tr) {
+            Some(text) => text,
+            None => continue,
+        };
+        let context = tr.TrContext().and_then(|c| unescape(&c)).unwrap_or_default();
+        let plural = tr.TrPlural().and_then(|p| unescape(&p));
+        let offset: usize = tr.node.text_range().start().into();
+        let line = source[..offset].matches('\n').count() + 1;
+        messages.add(context, text, plural, format!("{}:{}", file_name, line));
+    }
+}
+
+fn write_pot(messages: &Messages, mut file: impl Write) -> std::io::Result<()> {
+    writeln!(file, "# SOME DESCRIPTIVE TITLE.")?;
+    writeln!(file, "#, fuzzy")?;
+    writeln!(file, "msgid \"\"")?;
+    writeln!(file, "msgstr \"\"")?;
+    writeln!(file, "\"Content-Type: text/plain; charset=UTF-8\\n\"")?;
+    writeln!(file, "\"Content-Transfer-Encoding: 8bit\\n\"")?;
+    writeln!(file, "\"Plural-Forms: nplurals=2; plural=(n != 1);\\n\"")?;
+    for message in &messages.messages {
+        writeln!(file)?;
+        writeln!(file, "#: {}", message.references.join(" "))?;
+        if !message.context.is_empty() {
+            writeln!(file, "msgctxt {}", escape(&message.context))?;
+        }
+        writeln!(file, "msgid {}", escape(&message.text))?;
+        match &message.plural {
+            Some(plural) => {
+                writeln!(file, "msgid_plural {}", escape(plural))?;
+                writeln!(file, "msgstr[0] \"\"")?;
+                writeln!(file, "msgstr[1] \"\"")?;
+            }
+            None => writeln!(file, "msgstr \"\"")?,
+        }
+    }
+    Ok(())
+}
+
+/// Quote and escape a string for a .po file
+fn escape(s: &str) -> String {
+    let mut result = String::with_capacity(s.len() + 2);
+    result.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\t' => result.push_str("\\t"),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}